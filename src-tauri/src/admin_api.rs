@@ -0,0 +1,185 @@
+//! Optional embedded HTTP admin API, loopback-only, for driving scans and
+//! pulling reports without the Tauri UI (CI pipelines, cron jobs, etc).
+//! Mirrors Garage's `generic_server.rs` admin-server pattern: a small hyper
+//! router gated by a bearer token, delegating every route to the same core
+//! logic the `#[tauri::command]`s call, so the two surfaces can't drift.
+//!
+//! Routes:
+//! - `POST /assets`              -- body = the same text `import_assets` parses
+//! - `GET  /assets`               -- list assets
+//! - `POST /assets/{id}/rescan`   -- trigger an immediate rescan
+//! - `POST /openapi`              -- body = an OpenAPI spec, runs shadow-API detection
+//! - `GET  /report`               -- the Markdown audit report
+//! - `GET  /report.csv`           -- the CSV export
+
+use crate::db::SqliteDatabase;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use std::env;
+use std::net::SocketAddr;
+use tauri::{AppHandle, Manager};
+
+/// Loopback-only embedded admin API config, read from the environment the
+/// same way [`crate::ai::LlmConfig`] reads its `APEX_LLM_*` variables.
+pub struct AdminApiConfig {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,
+}
+
+impl AdminApiConfig {
+    pub fn from_env() -> Self {
+        let enabled = env::var("APEX_ADMIN_API_ENABLED")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        let port = env::var("APEX_ADMIN_API_PORT")
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(8899);
+        let token = env::var("APEX_ADMIN_API_TOKEN").unwrap_or_default();
+
+        Self {
+            enabled,
+            port,
+            token,
+        }
+    }
+}
+
+fn text_response(status: StatusCode, body: String) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header("content-type", "text/plain; charset=utf-8")
+        .body(Body::from(body))
+        .expect("response builder arguments are always valid")
+}
+
+fn json_response<T: serde::Serialize>(status: StatusCode, value: &T) -> Response<Body> {
+    match serde_json::to_string(value) {
+        Ok(body) => Response::builder()
+            .status(status)
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .expect("response builder arguments are always valid"),
+        Err(e) => text_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to serialize response: {}", e),
+        ),
+    }
+}
+
+fn unauthorized() -> Response<Body> {
+    text_response(
+        StatusCode::UNAUTHORIZED,
+        "Missing or invalid bearer token".to_string(),
+    )
+}
+
+fn is_authorized(req: &Request<Body>, token: &str) -> bool {
+    req.headers()
+        .get(hyper::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|presented| presented == token)
+}
+
+async fn read_body_string(req: Request<Body>) -> Result<String, String> {
+    let bytes = hyper::body::to_bytes(req.into_body())
+        .await
+        .map_err(|e| e.to_string())?;
+    String::from_utf8(bytes.to_vec()).map_err(|e| e.to_string())
+}
+
+/// Route one request, having already checked the bearer token.
+async fn route(app: &AppHandle, req: Request<Body>) -> Response<Body> {
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+
+    match (&method, path.as_str()) {
+        (&Method::POST, "/assets") => match read_body_string(req).await {
+            Ok(content) => {
+                match crate::commands::assets::import_assets(app.clone(), content, None).await {
+                    Ok(ids) => json_response(StatusCode::OK, &ids),
+                    Err(e) => text_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+                }
+            }
+            Err(e) => text_response(StatusCode::BAD_REQUEST, e),
+        },
+        (&Method::GET, "/assets") => {
+            let storage = app.state::<Box<dyn crate::db::Storage>>();
+            match crate::commands::assets::get_assets(storage) {
+                Ok(assets) => json_response(StatusCode::OK, &assets),
+                Err(e) => text_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+            }
+        }
+        (&Method::POST, _) if path.starts_with("/assets/") && path.ends_with("/rescan") => {
+            let id_segment = &path["/assets/".len()..path.len() - "/rescan".len()];
+            match id_segment.parse::<i64>() {
+                Ok(id) => match crate::commands::scan::rescan_asset(app.clone(), id).await {
+                    Ok(()) => text_response(StatusCode::NO_CONTENT, String::new()),
+                    Err(e) => text_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+                },
+                Err(_) => text_response(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid asset id: {}", id_segment),
+                ),
+            }
+        }
+        (&Method::POST, "/openapi") => match read_body_string(req).await {
+            Ok(content) => match crate::commands::shadow_api::import_openapi_spec_and_detect_shadow_apis(
+                app.clone(),
+                content,
+            )
+            .await
+            {
+                Ok(report) => json_response(StatusCode::OK, &report),
+                Err(e) => text_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+            },
+            Err(e) => text_response(StatusCode::BAD_REQUEST, e),
+        },
+        (&Method::GET, "/report") => {
+            let db = app.state::<SqliteDatabase>();
+            match crate::commands::export::generate_audit_report(app.clone(), db).await {
+                Ok(report) => text_response(StatusCode::OK, report),
+                Err(e) => text_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+            }
+        }
+        (&Method::GET, "/report.csv") => {
+            let db = app.state::<SqliteDatabase>();
+            match crate::commands::export::export_findings_to_csv(db, None).await {
+                Ok(csv) => Response::builder()
+                    .status(StatusCode::OK)
+                    .header("content-type", "text/csv; charset=utf-8")
+                    .body(Body::from(csv))
+                    .expect("response builder arguments are always valid"),
+                Err(e) => text_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+            }
+        }
+        _ => text_response(StatusCode::NOT_FOUND, "Not Found".to_string()),
+    }
+}
+
+/// Serve the admin API on `addr` until the process exits, rejecting every
+/// request whose bearer token doesn't match `config.token`.
+pub async fn serve(app: AppHandle, addr: SocketAddr, token: String) {
+    let make_svc = make_service_fn(move |_conn| {
+        let app = app.clone();
+        let token = token.clone();
+        async move {
+            Ok::<_, std::convert::Infallible>(service_fn(move |req: Request<Body>| {
+                let app = app.clone();
+                let token = token.clone();
+                async move {
+                    if !is_authorized(&req, &token) {
+                        return Ok::<_, std::convert::Infallible>(unauthorized());
+                    }
+                    Ok::<_, std::convert::Infallible>(route(&app, req).await)
+                }
+            }))
+        }
+    });
+
+    if let Err(e) = Server::bind(&addr).serve(make_svc).await {
+        eprintln!("Admin API server error: {}", e);
+    }
+}
@@ -1,10 +1,11 @@
-use crate::commands::debug::{emit_log, LogLevel};
+use crate::commands::debug::{emit_log, generate_log_id, LogLevel};
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Mutex;
-use tauri::AppHandle;
+use tauri::{AppHandle, Emitter};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -43,13 +44,37 @@ impl std::str::FromStr for ProviderType {
     }
 }
 
+fn default_redaction_enabled() -> bool {
+    true
+}
+
+fn default_num_ctx() -> u32 {
+    4096
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct LlmConfig {
     pub endpoint: String,
+    /// `x-api-key`/`Authorization: Bearer` credential for OpenAI/Anthropic.
+    /// For `ProviderType::Local`, doubles as an optional bearer token for
+    /// Ollama endpoints that aren't on loopback (a reverse-proxied or
+    /// shared-LAN instance) -- see `is_local_endpoint_loopback`.
     pub api_key: String,
     pub model: String,
     #[serde(default)]
     pub provider_type: ProviderType,
+    /// Whether prompts built for this config get run through [`Redactor`]
+    /// before leaving the box. Always bypassed for the Local provider
+    /// regardless of this flag, since nothing leaves the machine.
+    #[serde(default = "default_redaction_enabled")]
+    pub redaction_enabled: bool,
+    /// Context window (in tokens) requested from Ollama via the chat
+    /// request's `options.num_ctx`. Worth raising for long response-body
+    /// snippets in `build_analysis_prompt` that would otherwise overflow
+    /// the default window. Ignored by the OpenAI/Anthropic providers.
+    #[serde(default = "default_num_ctx")]
+    pub num_ctx: u32,
 }
 
 impl Default for LlmConfig {
@@ -59,6 +84,8 @@ impl Default for LlmConfig {
             api_key: String::new(),
             model: "phi3.5".to_string(),
             provider_type: ProviderType::Local,
+            redaction_enabled: true,
+            num_ctx: default_num_ctx(),
         }
     }
 }
@@ -69,6 +96,31 @@ fn get_config_path() -> PathBuf {
     path
 }
 
+/// Note that `var_name` overrode the value loaded from the provider
+/// registry file. `load()` runs before any `AppHandle` exists, so this
+/// can't go through `emit_log` -- it goes to the backend console instead,
+/// same as the other startup-time warnings in this codebase.
+fn warn_env_override(var_name: &str) {
+    eprintln!(
+        "[WARN] [AI] {} overrides the configured value from llm_providers.json",
+        var_name
+    );
+}
+
+/// Apply a string env var override to `file_value`, warning (without
+/// logging either value, since this also covers `api_key`) when it actually
+/// changes the effective config.
+fn env_override(var_name: &str, file_value: String) -> String {
+    match env::var(var_name) {
+        Ok(v) if v != file_value => {
+            warn_env_override(var_name);
+            v
+        }
+        Ok(v) => v,
+        Err(_) => file_value,
+    }
+}
+
 impl LlmConfig {
     pub fn from_env() -> Self {
         let provider_str = env::var("APEX_LLM_PROVIDER").unwrap_or_else(|_| "local".to_string());
@@ -92,27 +144,54 @@ impl LlmConfig {
             api_key: env::var("APEX_LLM_API_KEY").unwrap_or_default(),
             model: env::var("APEX_LLM_MODEL").unwrap_or_else(|_| default_model),
             provider_type,
+            redaction_enabled: env::var("APEX_LLM_REDACTION")
+                .ok()
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(true),
+            num_ctx: env::var("APEX_LLM_NUM_CTX")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_num_ctx),
         }
     }
 
+    /// Resolve the active entry of the [`ProviderRegistry`] (migrating the
+    /// legacy single-config file into it on first run), then layer env var
+    /// overrides on top the same way [`Self::from_env`] does.
     pub fn load() -> Self {
-        let path = get_config_path();
-        if path.exists() {
-            if let Ok(content) = fs::read_to_string(&path) {
-                if let Ok(config) = serde_json::from_str::<LlmConfig>(&content) {
-                    return Self {
-                        endpoint: env::var("APEX_LLM_ENDPOINT").unwrap_or_else(|_| config.endpoint),
-                        api_key: env::var("APEX_LLM_API_KEY").unwrap_or_else(|_| config.api_key),
-                        model: env::var("APEX_LLM_MODEL").unwrap_or_else(|_| config.model),
-                        provider_type: env::var("APEX_LLM_PROVIDER")
-                            .ok()
-                            .and_then(|p| p.parse().ok())
-                            .unwrap_or(config.provider_type),
-                    };
-                }
-            }
+        let config = ProviderRegistry::load()
+            .active_config()
+            .unwrap_or_else(Self::from_env);
+
+        Self {
+            endpoint: env_override("APEX_LLM_ENDPOINT", config.endpoint),
+            api_key: env_override("APEX_LLM_API_KEY", config.api_key),
+            model: env_override("APEX_LLM_MODEL", config.model),
+            provider_type: env::var("APEX_LLM_PROVIDER")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .map(|v: ProviderType| {
+                    if v != config.provider_type {
+                        warn_env_override("APEX_LLM_PROVIDER");
+                    }
+                    v
+                })
+                .unwrap_or(config.provider_type),
+            redaction_enabled: env::var("APEX_LLM_REDACTION")
+                .ok()
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(config.redaction_enabled),
+            num_ctx: env::var("APEX_LLM_NUM_CTX")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .map(|v: u32| {
+                    if v != config.num_ctx {
+                        warn_env_override("APEX_LLM_NUM_CTX");
+                    }
+                    v
+                })
+                .unwrap_or(config.num_ctx),
         }
-        Self::from_env()
     }
 
     pub fn save(&self) -> Result<(), String> {
@@ -127,9 +206,220 @@ impl LlmConfig {
         self.provider_type == ProviderType::Local
     }
 
+    /// Whether `endpoint` is a loopback address. A non-loopback Local
+    /// endpoint is assumed to be a shared/remote Ollama host that needs
+    /// `api_key` sent as a bearer token, same as a cloud provider.
+    fn is_local_endpoint_loopback(&self) -> bool {
+        url::Url::parse(&self.endpoint)
+            .ok()
+            .and_then(|u| {
+                u.host_str()
+                    .map(|h| h == "localhost" || h == "127.0.0.1" || h == "::1")
+            })
+            .unwrap_or(true)
+    }
+
     pub fn is_configured(&self) -> bool {
-        !self.api_key.is_empty() || self.is_local()
+        if self.is_local() {
+            self.is_local_endpoint_loopback() || !self.api_key.is_empty()
+        } else {
+            !self.api_key.is_empty()
+        }
+    }
+}
+
+// -----------------
+// PROVIDER REGISTRY
+// -----------------
+
+/// One saved provider configuration plus a user-facing label, so a local
+/// phi3.5 setup and a cloud OpenAI/Anthropic setup can both be kept on disk
+/// without either overwriting the other's key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderEntry {
+    pub id: String,
+    pub label: String,
+    pub config: LlmConfig,
+}
+
+/// `api_key` withheld, mirroring [`LlmConfigPublic`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderEntryPublic {
+    pub id: String,
+    pub label: String,
+    pub config: LlmConfigPublic,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct ProviderRegistry {
+    providers: Vec<ProviderEntry>,
+    active_id: Option<String>,
+}
+
+fn get_providers_path() -> PathBuf {
+    let mut path = env::current_exe().unwrap_or_default();
+    path.set_file_name("llm_providers.json");
+    path
+}
+
+impl ProviderRegistry {
+    /// Load the registry from disk, migrating the legacy single-config
+    /// file (`llm_config.json`) into a one-entry registry the first time
+    /// this runs on an existing install.
+    fn load() -> Self {
+        let path = get_providers_path();
+        if let Ok(content) = fs::read_to_string(&path) {
+            match serde_json::from_str::<ProviderRegistry>(&content) {
+                Ok(registry) => return registry,
+                // `deny_unknown_fields` on `LlmConfig` means a typo'd or
+                // renamed key lands here instead of silently deserializing
+                // into defaults -- surface it clearly rather than quietly
+                // falling back to the legacy config/env-var defaults.
+                Err(e) => eprintln!(
+                    "[ERROR] [AI] Failed to parse {}: {}. Falling back to defaults.",
+                    path.display(),
+                    e
+                ),
+            }
+        }
+        Self::migrate_legacy()
+    }
+
+    fn migrate_legacy() -> Self {
+        let config = {
+            let legacy_path = get_config_path();
+            fs::read_to_string(&legacy_path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<LlmConfig>(&content).ok())
+                .unwrap_or_else(LlmConfig::from_env)
+        };
+
+        let registry = Self {
+            providers: vec![ProviderEntry {
+                id: "default".to_string(),
+                label: "Default".to_string(),
+                config,
+            }],
+            active_id: Some("default".to_string()),
+        };
+        let _ = registry.save();
+        registry
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let path = get_providers_path();
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize provider registry: {}", e))?;
+        fs::write(&path, content)
+            .map_err(|e| format!("Failed to write provider registry file: {}", e))?;
+        Ok(())
+    }
+
+    fn active_config(&self) -> Option<LlmConfig> {
+        let active_id = self.active_id.as_ref()?;
+        self.providers
+            .iter()
+            .find(|p| &p.id == active_id)
+            .map(|p| p.config.clone())
+    }
+
+    /// Overwrite the active entry's config, creating a "Default" entry if
+    /// the registry somehow has none active (e.g. a fresh, empty file).
+    fn set_active_config(&mut self, config: LlmConfig) {
+        if let Some(active_id) = self.active_id.clone() {
+            if let Some(entry) = self.providers.iter_mut().find(|p| p.id == active_id) {
+                entry.config = config;
+                return;
+            }
+        }
+        let id = "default".to_string();
+        self.providers.push(ProviderEntry {
+            id: id.clone(),
+            label: "Default".to_string(),
+            config,
+        });
+        self.active_id = Some(id);
+    }
+
+    fn to_public(&self) -> Vec<ProviderEntryPublic> {
+        self.providers
+            .iter()
+            .map(|p| ProviderEntryPublic {
+                id: p.id.clone(),
+                label: p.label.clone(),
+                config: LlmConfigPublic::from(&p.config),
+                is_active: self.active_id.as_deref() == Some(p.id.as_str()),
+            })
+            .collect()
+    }
+}
+
+#[tauri::command]
+pub fn list_providers() -> Vec<ProviderEntryPublic> {
+    ProviderRegistry::load().to_public()
+}
+
+#[tauri::command]
+pub fn add_provider(
+    label: String,
+    endpoint: String,
+    api_key: String,
+    model: String,
+    provider_type: String,
+) -> Result<Vec<ProviderEntryPublic>, String> {
+    let mut registry = ProviderRegistry::load();
+
+    let config = LlmConfig {
+        endpoint,
+        api_key,
+        model,
+        provider_type: provider_type.parse().unwrap_or(ProviderType::OpenAI),
+        redaction_enabled: true,
+        num_ctx: default_num_ctx(),
+    };
+
+    let id = generate_log_id();
+    registry.providers.push(ProviderEntry {
+        id: id.clone(),
+        label,
+        config,
+    });
+    if registry.active_id.is_none() {
+        registry.active_id = Some(id);
+    }
+
+    registry.save()?;
+    Ok(registry.to_public())
+}
+
+#[tauri::command]
+pub fn set_active_provider(id: String) -> Result<LlmConfigPublic, String> {
+    let mut registry = ProviderRegistry::load();
+    if !registry.providers.iter().any(|p| p.id == id) {
+        return Err(format!("No provider with id '{}'", id));
+    }
+    registry.active_id = Some(id);
+    registry.save()?;
+
+    Ok(LlmConfigPublic::from(
+        &registry
+            .active_config()
+            .unwrap_or_else(LlmConfig::from_env),
+    ))
+}
+
+#[tauri::command]
+pub fn remove_provider(id: String) -> Result<Vec<ProviderEntryPublic>, String> {
+    let mut registry = ProviderRegistry::load();
+    registry.providers.retain(|p| p.id != id);
+
+    if registry.active_id.as_deref() == Some(id.as_str()) {
+        registry.active_id = registry.providers.first().map(|p| p.id.clone());
     }
+
+    registry.save()?;
+    Ok(registry.to_public())
 }
 
 #[derive(Debug, Deserialize)]
@@ -178,6 +468,8 @@ pub struct LlmConfigPublic {
     pub model: String,
     pub provider_type: ProviderType,
     pub is_configured: bool,
+    pub redaction_enabled: bool,
+    pub num_ctx: u32,
 }
 
 impl From<&LlmConfig> for LlmConfigPublic {
@@ -187,8 +479,282 @@ impl From<&LlmConfig> for LlmConfigPublic {
             model: config.model.clone(),
             provider_type: config.provider_type.clone(),
             is_configured: config.is_configured(),
+            redaction_enabled: config.redaction_enabled,
+            num_ctx: config.num_ctx,
+        }
+    }
+}
+
+// -----------------
+// REDACTION
+// -----------------
+
+/// Reversible placeholder map produced by [`Redactor::redact`]. `rehydrate`
+/// substitutes placeholders back with their original values so an analysis
+/// the model wrote against redacted evidence can still be displayed with
+/// the real values in place.
+pub struct RedactionMap {
+    entries: Vec<(String, String)>,
+}
+
+impl RedactionMap {
+    pub fn rehydrate(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        for (placeholder, original) in &self.entries {
+            out = out.replace(placeholder.as_str(), original.as_str());
+        }
+        out
+    }
+}
+
+/// Scrubs common secret shapes out of prompt text before it leaves the box
+/// for a cloud provider, replacing each match with a stable
+/// `[REDACTED:TYPE:N]` placeholder recorded in the returned [`RedactionMap`].
+pub struct Redactor;
+
+impl Redactor {
+    pub fn redact(text: &str) -> (String, RedactionMap) {
+        let mut map = RedactionMap { entries: Vec::new() };
+        let mut out = text.to_string();
+
+        let jwt_re = regex::Regex::new(r"eyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+").unwrap();
+        out = Self::redact_matches(&out, &jwt_re, "JWT", &mut map);
+
+        let header_re = regex::Regex::new(r"(?mi)^(Authorization|Cookie|Set-Cookie):[ \t]*(.+)$").unwrap();
+        out = Self::redact_header_values(&out, &header_re, &mut map);
+
+        let aws_key_re = regex::Regex::new(r"AKIA[0-9A-Z]{16}").unwrap();
+        out = Self::redact_matches(&out, &aws_key_re, "AWS_KEY", &mut map);
+
+        let email_re = regex::Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap();
+        out = Self::redact_matches(&out, &email_re, "EMAIL", &mut map);
+
+        let credit_card_re = regex::Regex::new(r"\b(?:\d[ -]?){13,19}\b").unwrap();
+        out = Self::redact_matches(&out, &credit_card_re, "CC", &mut map);
+
+        (out, map)
+    }
+
+    fn redact_matches(text: &str, re: &regex::Regex, label: &str, map: &mut RedactionMap) -> String {
+        let mut count = 0;
+        re.replace_all(text, |caps: &regex::Captures| {
+            count += 1;
+            let placeholder = format!("[REDACTED:{}:{}]", label, count);
+            map.entries.push((placeholder.clone(), caps[0].to_string()));
+            placeholder
+        })
+        .into_owned()
+    }
+
+    /// Header lines keep their name (`Authorization: ...`) but swap the
+    /// value for a placeholder, so the model still sees which header
+    /// carried the credential without seeing the credential itself.
+    fn redact_header_values(text: &str, re: &regex::Regex, map: &mut RedactionMap) -> String {
+        let mut count = 0;
+        re.replace_all(text, |caps: &regex::Captures| {
+            count += 1;
+            let placeholder = format!("[REDACTED:HEADER:{}]", count);
+            map.entries.push((placeholder.clone(), caps[2].to_string()));
+            format!("{}: {}", &caps[1], placeholder)
+        })
+        .into_owned()
+    }
+}
+
+/// Run `prompt` through [`Redactor`] unless `config` is the Local provider
+/// or has redaction turned off, since nothing leaves the box in either case.
+fn maybe_redact(config: &LlmConfig, prompt: &str) -> (String, Option<RedactionMap>) {
+    if config.is_local() || !config.redaction_enabled {
+        (prompt.to_string(), None)
+    } else {
+        let (redacted, map) = Redactor::redact(prompt);
+        (redacted, Some(map))
+    }
+}
+
+fn maybe_rehydrate(analysis: String, map: &Option<RedactionMap>) -> String {
+    match map {
+        Some(m) => m.rehydrate(&analysis),
+        None => analysis,
+    }
+}
+
+// -----------------
+// EMBEDDING RETRIEVAL
+// -----------------
+
+/// How many prior findings to surface as "similar previously-seen findings".
+const EMBEDDING_TOP_K: usize = 3;
+/// Cap on stored entries so the index doesn't grow unbounded across scans;
+/// the oldest entry is evicted once a push would exceed it.
+const MAX_EMBEDDING_ENTRIES: usize = 500;
+
+type FindingId = String;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EmbeddingEntry {
+    id: FindingId,
+    summary: String,
+    vector: Vec<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct EmbeddingIndex {
+    entries: Vec<EmbeddingEntry>,
+}
+
+fn get_embedding_index_path() -> PathBuf {
+    let mut path = env::current_exe().unwrap_or_default();
+    path.set_file_name("embedding_index.json");
+    path
+}
+
+impl EmbeddingIndex {
+    fn load() -> Self {
+        let path = get_embedding_index_path();
+        fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let path = get_embedding_index_path();
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize embedding index: {}", e))?;
+        fs::write(&path, content)
+            .map_err(|e| format!("Failed to write embedding index file: {}", e))?;
+        Ok(())
+    }
+
+    fn push(&mut self, entry: EmbeddingEntry) {
+        self.entries.push(entry);
+        if self.entries.len() > MAX_EMBEDDING_ENTRIES {
+            self.entries.remove(0);
         }
     }
+
+    /// Return up to [`EMBEDDING_TOP_K`] entries most similar to `query` by
+    /// cosine similarity, highest first.
+    fn top_k(&self, query: &[f32]) -> Vec<&EmbeddingEntry> {
+        let mut scored: Vec<(f32, &EmbeddingEntry)> = self
+            .entries
+            .iter()
+            .map(|e| (cosine_similarity(query, &e.vector), e))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.into_iter().take(EMBEDDING_TOP_K).map(|(_, e)| e).collect()
+    }
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Embed `text` via the active provider's embedding endpoint. Ollama uses
+/// `/api/embeddings`; OpenAI uses `/v1/embeddings`. Anthropic has no
+/// embeddings API, so this simply errors for that provider -- callers treat
+/// any error here as "skip retrieval for this call".
+async fn embed_text(config: &LlmConfig, text: &str) -> Result<Vec<f32>, String> {
+    fn extract_vector(value: &serde_json::Value) -> Option<Vec<f32>> {
+        value
+            .as_array()
+            .map(|v| v.iter().filter_map(|n| n.as_f64()).map(|n| n as f32).collect())
+    }
+
+    match config.provider_type {
+        ProviderType::Local => {
+            let client = reqwest::Client::new();
+            let url = format!("{}/api/embeddings", ollama_base_url(&config.endpoint));
+            let response = with_ollama_auth(client.post(&url), config)
+                .json(&serde_json::json!({"model": config.model, "prompt": text}))
+                .send()
+                .await
+                .map_err(|e| format!("Failed to request Ollama embedding: {}", e))?;
+
+            let body: serde_json::Value = response
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse Ollama embedding response: {}", e))?;
+
+            extract_vector(&body["embedding"])
+                .ok_or_else(|| "Ollama embedding response missing 'embedding'".to_string())
+        }
+        ProviderType::OpenAI => {
+            let client = reqwest::Client::new();
+            let response = client
+                .post("https://api.openai.com/v1/embeddings")
+                .header("Authorization", format!("Bearer {}", config.api_key))
+                .json(&serde_json::json!({"model": "text-embedding-3-small", "input": text}))
+                .send()
+                .await
+                .map_err(|e| format!("Failed to request OpenAI embedding: {}", e))?;
+
+            let body: serde_json::Value = response
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse OpenAI embedding response: {}", e))?;
+
+            extract_vector(&body["data"][0]["embedding"])
+                .ok_or_else(|| "OpenAI embedding response missing 'data[0].embedding'".to_string())
+        }
+        ProviderType::Anthropic => Err("Anthropic has no embeddings endpoint".to_string()),
+    }
+}
+
+/// Embed `snippet`, retrieve the top-k most similar prior findings from the
+/// on-disk index, and format them as a prompt section -- or an empty string
+/// if the index is empty or the embedding call fails, so retrieval is
+/// always best-effort and never blocks an analysis.
+async fn build_similar_findings_section(config: &LlmConfig, snippet: &str) -> String {
+    let index = EmbeddingIndex::load();
+    if index.entries.is_empty() {
+        return String::new();
+    }
+
+    let Ok(query) = embed_text(config, snippet).await else {
+        return String::new();
+    };
+
+    let similar = index.top_k(&query);
+    if similar.is_empty() {
+        return String::new();
+    }
+
+    let bullets = similar
+        .iter()
+        .map(|e| format!("- {}", e.summary))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!("\n\nSimilar previously-seen findings:\n{}", bullets)
+}
+
+/// Embed and store `snippet`/`summary` in the on-disk index for future
+/// retrieval. Best-effort: an embedding failure here is silently ignored so
+/// it never blocks the analysis it's meant to be recording.
+async fn record_finding_embedding(config: &LlmConfig, snippet: &str, summary: String) {
+    let Ok(vector) = embed_text(config, snippet).await else {
+        return;
+    };
+
+    let mut index = EmbeddingIndex::load();
+    index.push(EmbeddingEntry {
+        id: generate_log_id(),
+        summary,
+        vector,
+    });
+    let _ = index.save();
 }
 
 fn build_analysis_prompt(input: &AnalyzeFindingInput) -> String {
@@ -345,14 +911,672 @@ async fn call_openai_api(config: &LlmConfig, prompt: &str) -> Result<String, Str
         "temperature": 0.7
     });
 
-    let response = client
-        .post(&config.endpoint)
-        .header("Authorization", format!("Bearer {}", config.api_key))
+    let response = client
+        .post(&config.endpoint)
+        .header("Authorization", format!("Bearer {}", config.api_key))
+        .header("Content-Type", "application/json")
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request to LLM: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("LLM API error ({}): {}", status, error_text));
+    }
+
+    let response_json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
+
+    let analysis = response_json["choices"]
+        .as_array()
+        .and_then(|arr| arr.first())
+        .and_then(|choice| choice.get("message"))
+        .and_then(|msg| msg.get("content"))
+        .and_then(|content| content.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| "Failed to extract analysis from LLM response".to_string())?;
+
+    Ok(analysis)
+}
+
+async fn call_ollama_api(config: &LlmConfig, prompt: &str) -> Result<String, String> {
+    let client = reqwest::Client::new();
+
+    let request_body = serde_json::json!({
+        "model": config.model,
+        "messages": [
+            {
+                "role": "system",
+                "content": "You are an EXPERT API SECURITY RESEARCHER. Your tone is professional, technical, and direct."
+            },
+            {
+                "role": "user",
+                "content": prompt
+            }
+        ],
+        "stream": false,
+        "options": {
+            "num_ctx": config.num_ctx,
+            "temperature": 0.1
+        }
+    });
+
+    let response = with_ollama_auth(client.post(&config.endpoint), config)
+        .header("Content-Type", "application/json")
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request to Ollama: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Ollama API error ({}): {}", status, error_text));
+    }
+
+    let response_json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Ollama response: {}", e))?;
+
+    let analysis = response_json["message"]
+        .as_object()
+        .and_then(|msg| msg.get("content"))
+        .and_then(|content| content.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| "Failed to extract analysis from Ollama response".to_string())?;
+
+    Ok(analysis)
+}
+
+async fn call_anthropic_api(config: &LlmConfig, prompt: &str) -> Result<String, String> {
+    let client = reqwest::Client::new();
+
+    let request_body = serde_json::json!({
+        "model": config.model,
+        "system": "You are a helpful security analyst assistant.",
+        "messages": [
+            {
+                "role": "user",
+                "content": prompt
+            }
+        ],
+        "max_tokens": 1000
+    });
+
+    let response = client
+        .post(&config.endpoint)
+        .header("x-api-key", &config.api_key)
+        .header("anthropic-version", "2023-06-01")
+        .header("Content-Type", "application/json")
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request to LLM: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("LLM API error ({}): {}", status, error_text));
+    }
+
+    let response_json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
+
+    let analysis = response_json["content"]
+        .as_array()
+        .map(|blocks| {
+            blocks
+                .iter()
+                .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("text"))
+                .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+                .collect::<Vec<_>>()
+                .join("")
+        })
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| "Failed to extract analysis from LLM response".to_string())?;
+
+    Ok(analysis)
+}
+
+async fn call_llm_api(config: &LlmConfig, prompt: &str) -> Result<String, String> {
+    if config.is_local() {
+        call_ollama_api(config, prompt).await
+    } else if config.provider_type == ProviderType::Anthropic {
+        call_anthropic_api(config, prompt).await
+    } else {
+        call_openai_api(config, prompt).await
+    }
+}
+
+// -----------------
+// AGENTIC TOOL-CALLING LOOP
+// -----------------
+
+/// Hard cap on how many times the model may call a tool before a single
+/// `call_llm_api_with_tools` invocation gives up, so a model stuck in a
+/// probe-probe-probe loop doesn't run forever.
+const MAX_TOOL_STEPS: usize = 5;
+
+/// Tools the analyst model can call mid-conversation to gather real
+/// evidence (re-issue a request, read a single header, diff two bodies)
+/// instead of hallucinating it. Backed by its own `reqwest::Client` so
+/// tool calls aren't tangled up with the scanner's rate limiter.
+pub struct ToolRegistry {
+    client: reqwest::Client,
+}
+
+impl Default for ToolRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// OpenAI/Ollama-style `tools` entries (`{"type": "function", "function": {...}}`).
+    /// [`Self::anthropic_tool_defs`] reshapes these into Anthropic's flatter
+    /// `{"name", "description", "input_schema"}` form rather than
+    /// maintaining the schemas twice.
+    fn definitions() -> Vec<serde_json::Value> {
+        vec![
+            serde_json::json!({
+                "type": "function",
+                "function": {
+                    "name": "http_probe",
+                    "description": "Re-issue a read-only HTTP request against the target and return its status, headers, and a body snippet.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "url": {"type": "string"},
+                            "method": {"type": "string", "description": "GET, HEAD, etc. Defaults to GET."}
+                        },
+                        "required": ["url"]
+                    }
+                }
+            }),
+            serde_json::json!({
+                "type": "function",
+                "function": {
+                    "name": "fetch_header",
+                    "description": "Fetch a single response header's value for a URL.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "url": {"type": "string"},
+                            "name": {"type": "string"}
+                        },
+                        "required": ["url", "name"]
+                    }
+                }
+            }),
+            serde_json::json!({
+                "type": "function",
+                "function": {
+                    "name": "diff_response",
+                    "description": "Line-diff two response bodies and return the changes.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "old": {"type": "string"},
+                            "new": {"type": "string"}
+                        },
+                        "required": ["old", "new"]
+                    }
+                }
+            }),
+            serde_json::json!({
+                "type": "function",
+                "function": {
+                    "name": "may_replay_request",
+                    "description": "Re-issue a state-changing (non-GET) request against the target. Requires explicit user confirmation before running.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "url": {"type": "string"},
+                            "method": {"type": "string"},
+                            "body": {"type": "string"}
+                        },
+                        "required": ["url", "method"]
+                    }
+                }
+            }),
+        ]
+    }
+
+    fn anthropic_tool_defs() -> Vec<serde_json::Value> {
+        Self::definitions()
+            .into_iter()
+            .map(|def| {
+                let f = &def["function"];
+                serde_json::json!({
+                    "name": f["name"],
+                    "description": f["description"],
+                    "input_schema": f["parameters"],
+                })
+            })
+            .collect()
+    }
+
+    /// A `may_`-prefixed tool performs a state-changing action, so the
+    /// executor must get explicit user confirmation before running it
+    /// instead of executing it the moment the model requests it.
+    pub fn requires_confirmation(name: &str) -> bool {
+        name.starts_with("may_")
+    }
+
+    async fn issue_request(
+        &self,
+        url: &str,
+        method: &str,
+        body: Option<&str>,
+    ) -> Result<String, String> {
+        let method_type = match method.to_uppercase().as_str() {
+            "POST" => reqwest::Method::POST,
+            "PUT" => reqwest::Method::PUT,
+            "DELETE" => reqwest::Method::DELETE,
+            "PATCH" => reqwest::Method::PATCH,
+            "HEAD" => reqwest::Method::HEAD,
+            _ => reqwest::Method::GET,
+        };
+
+        let mut request = self.client.request(method_type, url);
+        if let Some(body) = body {
+            request = request.body(body.to_string());
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Tool request failed: {}", e))?;
+
+        let status = response.status();
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(k, v)| format!("{}: {}", k, v.to_str().unwrap_or_default()))
+            .collect::<Vec<String>>()
+            .join("\n");
+        let body_text = response.text().await.unwrap_or_default();
+        let snippet: String = body_text.chars().take(1000).collect();
+
+        Ok(format!(
+            "Status: {}\nHeaders:\n{}\nBody snippet:\n{}",
+            status, headers, snippet
+        ))
+    }
+
+    async fn http_probe(&self, args: &serde_json::Value) -> Result<String, String> {
+        let url = args["url"].as_str().ok_or("http_probe: missing url")?;
+        let method = args["method"].as_str().unwrap_or("GET");
+        self.issue_request(url, method, None).await
+    }
+
+    async fn fetch_header(&self, args: &serde_json::Value) -> Result<String, String> {
+        let url = args["url"].as_str().ok_or("fetch_header: missing url")?;
+        let name = args["name"].as_str().ok_or("fetch_header: missing name")?;
+
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| format!("Tool request failed: {}", e))?;
+
+        Ok(response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("<header not present>")
+            .to_string())
+    }
+
+    fn diff_response(&self, args: &serde_json::Value) -> Result<String, String> {
+        let old = args["old"].as_str().ok_or("diff_response: missing old")?;
+        let new = args["new"].as_str().ok_or("diff_response: missing new")?;
+
+        let diff = similar::TextDiff::from_lines(old, new);
+        let mut out = String::new();
+        for change in diff.iter_all_changes() {
+            let sign = match change.tag() {
+                similar::ChangeTag::Delete => '-',
+                similar::ChangeTag::Insert => '+',
+                similar::ChangeTag::Equal => ' ',
+            };
+            out.push(sign);
+            out.push_str(change.value());
+        }
+        Ok(out)
+    }
+
+    async fn may_replay_request(&self, args: &serde_json::Value) -> Result<String, String> {
+        let url = args["url"].as_str().ok_or("may_replay_request: missing url")?;
+        let method = args["method"]
+            .as_str()
+            .ok_or("may_replay_request: missing method")?;
+        let body = args["body"].as_str();
+        self.issue_request(url, method, body).await
+    }
+
+    /// Run a model-requested tool call by name. Callers must have already
+    /// obtained user confirmation for any `may_`-prefixed tool (see
+    /// [`Self::requires_confirmation`]) before reaching this point.
+    pub async fn execute(&self, name: &str, arguments: &serde_json::Value) -> Result<String, String> {
+        match name {
+            "http_probe" => self.http_probe(arguments).await,
+            "fetch_header" => self.fetch_header(arguments).await,
+            "diff_response" => self.diff_response(arguments),
+            "may_replay_request" => self.may_replay_request(arguments).await,
+            other => Err(format!("Unknown tool: {}", other)),
+        }
+    }
+}
+
+/// Run `tool_calls`/`tool_use` blocks against `registry`, refusing (rather
+/// than skipping) any `may_`-prefixed tool since this loop has no user in
+/// the room to ask for confirmation.
+async fn run_tool_call(
+    registry: &ToolRegistry,
+    name: &str,
+    arguments: &serde_json::Value,
+) -> String {
+    let result = if ToolRegistry::requires_confirmation(name) {
+        Err(format!(
+            "Tool '{}' performs a state-changing action and requires explicit user confirmation, which this unattended analysis loop cannot obtain.",
+            name
+        ))
+    } else {
+        registry.execute(name, arguments).await
+    };
+    result.unwrap_or_else(|e| format!("Tool error: {}", e))
+}
+
+/// OpenAI- and Ollama-compatible tool-calling loop: send `prompt` plus the
+/// tool definitions, and if the response carries a `tool_calls` array,
+/// execute each one and feed the results back as `role: "tool"` messages
+/// until the model returns plain text or [`MAX_TOOL_STEPS`] is hit.
+async fn call_chat_api_with_tools(
+    config: &LlmConfig,
+    prompt: &str,
+    registry: &ToolRegistry,
+) -> Result<String, String> {
+    let client = reqwest::Client::new();
+    let mut messages = vec![
+        serde_json::json!({
+            "role": "system",
+            "content": "You are an EXPERT API SECURITY RESEARCHER. Use the provided tools to verify evidence before concluding instead of guessing."
+        }),
+        serde_json::json!({"role": "user", "content": prompt}),
+    ];
+
+    for _ in 0..MAX_TOOL_STEPS {
+        let request_body = serde_json::json!({
+            "model": config.model,
+            "messages": messages,
+            "tools": ToolRegistry::definitions(),
+            "stream": false
+        });
+
+        let mut request = client
+            .post(&config.endpoint)
+            .header("Content-Type", "application/json");
+        if !config.is_local() {
+            request = request.header("Authorization", format!("Bearer {}", config.api_key));
+        }
+
+        let response = request
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request to LLM: {}", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(format!("LLM API error ({}): {}", status, error_text));
+        }
+
+        let response_json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
+
+        // OpenAI nests the message under `choices[0]`; Ollama returns it
+        // directly as `message`.
+        let message = response_json["choices"][0]["message"]
+            .as_object()
+            .or_else(|| response_json["message"].as_object())
+            .cloned()
+            .ok_or_else(|| "Failed to extract message from LLM response".to_string())?;
+
+        let tool_calls = message
+            .get("tool_calls")
+            .and_then(serde_json::Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        if tool_calls.is_empty() {
+            return message
+                .get("content")
+                .and_then(serde_json::Value::as_str)
+                .map(|s| s.to_string())
+                .ok_or_else(|| "Failed to extract analysis from LLM response".to_string());
+        }
+
+        messages.push(serde_json::Value::Object(message));
+
+        for call in &tool_calls {
+            let tool_call_id = call
+                .get("id")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            let name = call["function"]["name"].as_str().unwrap_or_default();
+            let arguments: serde_json::Value = call["function"]["arguments"]
+                .as_str()
+                .and_then(|s| serde_json::from_str(s).ok())
+                .unwrap_or(serde_json::Value::Null);
+
+            let content = run_tool_call(registry, name, &arguments).await;
+            messages.push(serde_json::json!({
+                "role": "tool",
+                "tool_call_id": tool_call_id,
+                "content": content
+            }));
+        }
+    }
+
+    Err(format!("Exceeded max tool-calling steps ({})", MAX_TOOL_STEPS))
+}
+
+/// As [`call_chat_api_with_tools`], but for Anthropic's Messages API: tool
+/// requests arrive as `tool_use` content blocks instead of a `tool_calls`
+/// array, and results are sent back as a user turn carrying `tool_result`
+/// blocks keyed by `tool_use_id` rather than `role: "tool"` messages.
+async fn call_anthropic_api_with_tools(
+    config: &LlmConfig,
+    prompt: &str,
+    registry: &ToolRegistry,
+) -> Result<String, String> {
+    let client = reqwest::Client::new();
+    let mut messages = vec![serde_json::json!({"role": "user", "content": prompt})];
+
+    for _ in 0..MAX_TOOL_STEPS {
+        let request_body = serde_json::json!({
+            "model": config.model,
+            "system": "You are an EXPERT API SECURITY RESEARCHER. Use the provided tools to verify evidence before concluding instead of guessing.",
+            "messages": messages,
+            "tools": ToolRegistry::anthropic_tool_defs(),
+            "max_tokens": 1000
+        });
+
+        let response = client
+            .post(&config.endpoint)
+            .header("x-api-key", &config.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request to LLM: {}", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(format!("LLM API error ({}): {}", status, error_text));
+        }
+
+        let response_json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
+
+        let content_blocks = response_json["content"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let tool_uses: Vec<&serde_json::Value> = content_blocks
+            .iter()
+            .filter(|b| b.get("type").and_then(serde_json::Value::as_str) == Some("tool_use"))
+            .collect();
+
+        if tool_uses.is_empty() {
+            let text = content_blocks
+                .iter()
+                .filter(|b| b.get("type").and_then(serde_json::Value::as_str) == Some("text"))
+                .filter_map(|b| b.get("text").and_then(serde_json::Value::as_str))
+                .collect::<Vec<_>>()
+                .join("");
+            return if text.is_empty() {
+                Err("Failed to extract analysis from LLM response".to_string())
+            } else {
+                Ok(text)
+            };
+        }
+
+        messages.push(serde_json::json!({"role": "assistant", "content": content_blocks}));
+
+        let mut tool_results = Vec::new();
+        for tool_use in &tool_uses {
+            let id = tool_use["id"].as_str().unwrap_or_default();
+            let name = tool_use["name"].as_str().unwrap_or_default();
+            let arguments = tool_use["input"].clone();
+
+            let content = run_tool_call(registry, name, &arguments).await;
+            tool_results.push(serde_json::json!({
+                "type": "tool_result",
+                "tool_use_id": id,
+                "content": content
+            }));
+        }
+        messages.push(serde_json::json!({"role": "user", "content": tool_results}));
+    }
+
+    Err(format!("Exceeded max tool-calling steps ({})", MAX_TOOL_STEPS))
+}
+
+/// As [`call_llm_api`], but lets the model call tools (see [`ToolRegistry`])
+/// to gather real evidence across up to [`MAX_TOOL_STEPS`] turns before
+/// returning its final analysis. Used by commands whose prompts ask the
+/// model to confirm findings rather than just describe them.
+async fn call_llm_api_with_tools(
+    config: &LlmConfig,
+    prompt: &str,
+    registry: &ToolRegistry,
+) -> Result<String, String> {
+    if config.provider_type == ProviderType::Anthropic {
+        call_anthropic_api_with_tools(config, prompt, registry).await
+    } else {
+        call_chat_api_with_tools(config, prompt, registry).await
+    }
+}
+
+// -----------------
+// STREAMING
+// -----------------
+
+/// One incremental piece of a streaming LLM response, emitted under the
+/// caller-chosen event name so the frontend can render tokens as they
+/// arrive instead of waiting for the whole completion.
+#[derive(Debug, Clone, Serialize)]
+struct StreamChunk {
+    delta: String,
+    done: bool,
+}
+
+fn emit_stream_chunk(app: &AppHandle, event_name: &str, delta: &str, done: bool) {
+    let _ = app.emit(
+        event_name,
+        StreamChunk {
+            delta: delta.to_string(),
+            done,
+        },
+    );
+}
+
+/// Stream a completion from Ollama's `/api/chat` with `"stream": true`,
+/// which responds with newline-delimited JSON objects rather than a single
+/// body. Each object's `message.content` is a token delta; the final
+/// object carries `"done": true`.
+async fn call_ollama_api_streaming(
+    config: &LlmConfig,
+    prompt: &str,
+    app: &AppHandle,
+    event_name: &str,
+) -> Result<String, String> {
+    let client = reqwest::Client::new();
+
+    let request_body = serde_json::json!({
+        "model": config.model,
+        "messages": [
+            {
+                "role": "system",
+                "content": "You are an EXPERT API SECURITY RESEARCHER. Your tone is professional, technical, and direct."
+            },
+            {
+                "role": "user",
+                "content": prompt
+            }
+        ],
+        "stream": true,
+        "options": {
+            "num_ctx": config.num_ctx,
+            "temperature": 0.1
+        }
+    });
+
+    let response = with_ollama_auth(client.post(&config.endpoint), config)
         .header("Content-Type", "application/json")
         .json(&request_body)
         .send()
         .await
-        .map_err(|e| format!("Failed to send request to LLM: {}", e))?;
+        .map_err(|e| format!("Failed to send request to Ollama: {}", e))?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -360,27 +1584,54 @@ async fn call_openai_api(config: &LlmConfig, prompt: &str) -> Result<String, Str
             .text()
             .await
             .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("LLM API error ({}): {}", status, error_text));
+        return Err(format!("Ollama API error ({}): {}", status, error_text));
     }
 
-    let response_json: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
+    let mut full = String::new();
+    let mut buf = String::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Failed to read Ollama stream: {}", e))?;
+        buf.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(newline_pos) = buf.find('\n') {
+            let line = buf[..newline_pos].trim().to_string();
+            buf.drain(..=newline_pos);
+            if line.is_empty() {
+                continue;
+            }
 
-    let analysis = response_json["choices"]
-        .as_array()
-        .and_then(|arr| arr.first())
-        .and_then(|choice| choice.get("message"))
-        .and_then(|msg| msg.get("content"))
-        .and_then(|content| content.as_str())
-        .map(|s| s.to_string())
-        .ok_or_else(|| "Failed to extract analysis from LLM response".to_string())?;
+            let Ok(obj) = serde_json::from_str::<serde_json::Value>(&line) else {
+                continue;
+            };
+            let delta = obj["message"]["content"].as_str().unwrap_or_default();
+            if !delta.is_empty() {
+                full.push_str(delta);
+                emit_stream_chunk(app, event_name, delta, false);
+            }
+            if obj["done"].as_bool() == Some(true) {
+                emit_stream_chunk(app, event_name, "", true);
+            }
+        }
+    }
 
-    Ok(analysis)
+    if full.is_empty() {
+        Err("Failed to extract analysis from Ollama response".to_string())
+    } else {
+        Ok(full)
+    }
 }
 
-async fn call_ollama_api(config: &LlmConfig, prompt: &str) -> Result<String, String> {
+/// Stream a completion from an OpenAI-compatible `/chat/completions`
+/// endpoint with `"stream": true`, which responds as `text/event-stream`:
+/// `data: {json}` lines carrying `choices[0].delta.content`, terminated by
+/// a literal `data: [DONE]` line.
+async fn call_openai_api_streaming(
+    config: &LlmConfig,
+    prompt: &str,
+    app: &AppHandle,
+    event_name: &str,
+) -> Result<String, String> {
     let client = reqwest::Client::new();
 
     let request_body = serde_json::json!({
@@ -388,27 +1639,26 @@ async fn call_ollama_api(config: &LlmConfig, prompt: &str) -> Result<String, Str
         "messages": [
             {
                 "role": "system",
-                "content": "You are an EXPERT API SECURITY RESEARCHER. Your tone is professional, technical, and direct."
+                "content": "You are a helpful security analyst assistant."
             },
             {
                 "role": "user",
                 "content": prompt
             }
         ],
-        "stream": false,
-        "options": {
-            "num_ctx": 4096,
-            "temperature": 0.1
-        }
+        "max_tokens": 1000,
+        "temperature": 0.7,
+        "stream": true
     });
 
     let response = client
         .post(&config.endpoint)
+        .header("Authorization", format!("Bearer {}", config.api_key))
         .header("Content-Type", "application/json")
         .json(&request_body)
         .send()
         .await
-        .map_err(|e| format!("Failed to send request to Ollama: {}", e))?;
+        .map_err(|e| format!("Failed to send request to LLM: {}", e))?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -416,29 +1666,70 @@ async fn call_ollama_api(config: &LlmConfig, prompt: &str) -> Result<String, Str
             .text()
             .await
             .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Ollama API error ({}): {}", status, error_text));
+        return Err(format!("LLM API error ({}): {}", status, error_text));
     }
 
-    let response_json: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse Ollama response: {}", e))?;
+    let mut full = String::new();
+    let mut buf = String::new();
+    let mut stream = response.bytes_stream();
+    'outer: while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Failed to read LLM stream: {}", e))?;
+        buf.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(newline_pos) = buf.find('\n') {
+            let line = buf[..newline_pos].trim().to_string();
+            buf.drain(..=newline_pos);
+            let Some(data) = line.strip_prefix("data:") else {
+                continue;
+            };
+            let data = data.trim();
+            if data == "[DONE]" {
+                emit_stream_chunk(app, event_name, "", true);
+                break 'outer;
+            }
 
-    let analysis = response_json["message"]
-        .as_object()
-        .and_then(|msg| msg.get("content"))
-        .and_then(|content| content.as_str())
-        .map(|s| s.to_string())
-        .ok_or_else(|| "Failed to extract analysis from Ollama response".to_string())?;
+            let Ok(obj) = serde_json::from_str::<serde_json::Value>(data) else {
+                continue;
+            };
+            let delta = obj["choices"]
+                .as_array()
+                .and_then(|arr| arr.first())
+                .and_then(|choice| choice["delta"]["content"].as_str())
+                .unwrap_or_default();
+            if !delta.is_empty() {
+                full.push_str(delta);
+                emit_stream_chunk(app, event_name, delta, false);
+            }
+        }
+    }
 
-    Ok(analysis)
+    if full.is_empty() {
+        Err("Failed to extract analysis from LLM response".to_string())
+    } else {
+        Ok(full)
+    }
 }
 
-async fn call_llm_api(config: &LlmConfig, prompt: &str) -> Result<String, String> {
+/// As [`call_llm_api`], but when `app` is supplied, emits each token delta
+/// under `event_name` as it arrives instead of returning only the final
+/// text. Anthropic has no incremental-format handler yet, so its requests
+/// still fall back to the buffered call regardless of `app`.
+async fn call_llm_api_streaming(
+    config: &LlmConfig,
+    prompt: &str,
+    app: Option<&AppHandle>,
+    event_name: &str,
+) -> Result<String, String> {
+    let Some(app) = app else {
+        return call_llm_api(config, prompt).await;
+    };
+
     if config.is_local() {
-        call_ollama_api(config, prompt).await
+        call_ollama_api_streaming(config, prompt, app, event_name).await
+    } else if config.provider_type == ProviderType::Anthropic {
+        call_anthropic_api(config, prompt).await
     } else {
-        call_openai_api(config, prompt).await
+        call_openai_api_streaming(config, prompt, app, event_name).await
     }
 }
 
@@ -457,13 +1748,14 @@ pub async fn analyze_logic_flaws(
         return Err("LLM not configured for logic audits.".to_string());
     }
 
-    let prompt = build_logic_audit_prompt(&input);
-    let analysis = call_llm_api(&config, &prompt)
+    let (prompt, redaction_map) = maybe_redact(&config, &build_logic_audit_prompt(&input));
+    let registry = ToolRegistry::new();
+    let analysis = call_llm_api_with_tools(&config, &prompt, &registry)
         .await
         .map_err(|e| format!("{}: {}", provider_display, e))?;
 
     Ok(AnalyzeAssetSummaryOutput {
-        summary: analysis,
+        summary: maybe_rehydrate(analysis, &redaction_map),
         provider: provider_display.to_string(),
     })
 }
@@ -478,9 +1770,9 @@ pub async fn analyze_finding(
     let config = LlmConfig::load();
 
     let input = AnalyzeFindingInput {
-        asset_url,
-        finding_type,
-        response_body_snippet,
+        asset_url: asset_url.clone(),
+        finding_type: finding_type.clone(),
+        response_body_snippet: response_body_snippet.clone(),
         context,
     };
 
@@ -494,13 +1786,26 @@ pub async fn analyze_finding(
         return Err("LLM not configured. Please go to Settings and configure a Built-in Local or External API provider.".to_string());
     }
 
-    let prompt = build_analysis_prompt(&input);
-    let analysis = call_llm_api(&config, &prompt)
+    let similar_section = build_similar_findings_section(&config, &response_body_snippet).await;
+    let mut prompt = build_analysis_prompt(&input);
+    prompt.push_str(&similar_section);
+
+    let (prompt, redaction_map) = maybe_redact(&config, &prompt);
+    let registry = ToolRegistry::new();
+    let analysis = call_llm_api_with_tools(&config, &prompt, &registry)
         .await
         .map_err(|e| format!("{}: {}", provider_display, e))?;
 
+    let summary = format!(
+        "[{}] {} — {}",
+        finding_type,
+        asset_url,
+        response_body_snippet.chars().take(160).collect::<String>()
+    );
+    record_finding_embedding(&config, &response_body_snippet, summary).await;
+
     Ok(AnalyzeFindingOutput {
-        analysis,
+        analysis: maybe_rehydrate(analysis, &redaction_map),
         provider: provider_display.to_string(),
     })
 }
@@ -535,13 +1840,13 @@ pub async fn analyze_asset_summary(
         return Err("LLM not configured for summaries.".to_string());
     }
 
-    let prompt = build_asset_summary_prompt(&input);
+    let (prompt, redaction_map) = maybe_redact(&config, &build_asset_summary_prompt(&input));
     let summary = call_llm_api(&config, &prompt)
         .await
         .map_err(|e| format!("{}: {}", provider_display, e))?;
 
     Ok(AnalyzeAssetSummaryOutput {
-        summary,
+        summary: maybe_rehydrate(summary, &redaction_map),
         provider: provider_display.to_string(),
     })
 }
@@ -611,6 +1916,7 @@ OUTPUT FORMAT:
 
 #[tauri::command]
 pub async fn analyze_sequence(
+    app: AppHandle,
     sequence: crate::data::RequestSequence,
     context: Option<String>,
 ) -> Result<SequenceAnalysisOutput, String> {
@@ -626,13 +1932,13 @@ pub async fn analyze_sequence(
     }
 
     let input = SequenceAnalysisInput { sequence, context };
-    let prompt = build_sequence_analysis_prompt(&input);
-    let analysis = call_llm_api(&config, &prompt)
+    let (prompt, redaction_map) = maybe_redact(&config, &build_sequence_analysis_prompt(&input));
+    let analysis = call_llm_api_streaming(&config, &prompt, Some(&app), "ai-stream-analyze-sequence")
         .await
         .map_err(|e| format!("{}: {}", provider_display, e))?;
 
     Ok(SequenceAnalysisOutput {
-        analysis,
+        analysis: maybe_rehydrate(analysis, &redaction_map),
         provider: provider_display.to_string(),
     })
 }
@@ -690,6 +1996,7 @@ OUTPUT FORMAT:
 
 #[tauri::command]
 pub async fn generate_exploit_narrative(
+    app: AppHandle,
     sequence: crate::data::RequestSequence,
 ) -> Result<SequenceAnalysisOutput, String> {
     let config = LlmConfig::load();
@@ -703,13 +2010,18 @@ pub async fn generate_exploit_narrative(
         return Err("LLM not configured.".to_string());
     }
 
-    let prompt = build_exploit_narrative_prompt(&sequence);
-    let analysis = call_llm_api(&config, &prompt)
-        .await
-        .map_err(|e| format!("{}: {}", provider_display, e))?;
+    let (prompt, redaction_map) = maybe_redact(&config, &build_exploit_narrative_prompt(&sequence));
+    let analysis = call_llm_api_streaming(
+        &config,
+        &prompt,
+        Some(&app),
+        "ai-stream-generate-exploit-narrative",
+    )
+    .await
+    .map_err(|e| format!("{}: {}", provider_display, e))?;
 
     Ok(SequenceAnalysisOutput {
-        analysis,
+        analysis: maybe_rehydrate(analysis, &redaction_map),
         provider: provider_display.to_string(),
     })
 }
@@ -746,6 +2058,7 @@ OUTPUT FORMAT:
 
 #[tauri::command]
 pub async fn generate_remediation_diff(
+    app: AppHandle,
     sequence: crate::data::RequestSequence,
 ) -> Result<SequenceAnalysisOutput, String> {
     let config = LlmConfig::load();
@@ -759,17 +2072,310 @@ pub async fn generate_remediation_diff(
         return Err("LLM not configured.".to_string());
     }
 
-    let prompt = build_remediation_diff_prompt(&sequence);
-    let analysis = call_llm_api(&config, &prompt)
-        .await
-        .map_err(|e| format!("{}: {}", provider_display, e))?;
+    let (prompt, redaction_map) = maybe_redact(&config, &build_remediation_diff_prompt(&sequence));
+    let analysis = call_llm_api_streaming(
+        &config,
+        &prompt,
+        Some(&app),
+        "ai-stream-generate-remediation-diff",
+    )
+    .await
+    .map_err(|e| format!("{}: {}", provider_display, e))?;
 
     Ok(SequenceAnalysisOutput {
-        analysis,
+        analysis: maybe_rehydrate(analysis, &redaction_map),
         provider: provider_display.to_string(),
     })
 }
 
+// -----------------
+// VEX EXPORT
+// -----------------
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VexSeverity {
+    Critical,
+    High,
+    Medium,
+    Low,
+    Info,
+}
+
+/// Mirrors CycloneDX's `analysis.state` vocabulary for VEX entries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VexState {
+    Exploitable,
+    NotAffected,
+    FalsePositive,
+}
+
+/// Machine-readable classification for one finding, extracted from a prior
+/// `analyze_finding`/`analyze_logic_flaws` analysis via forced function
+/// calling so the FALSE POSITIVE CHECK becomes a structured signal instead
+/// of prose a downstream scanner can't consume.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VexFinding {
+    pub id: String,
+    pub severity: VexSeverity,
+    pub state: VexState,
+    pub justification: String,
+    pub affected_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VexFindingInput {
+    pub finding_id: String,
+    pub asset_url: String,
+    /// Free-form analysis text already produced for this finding (e.g. the
+    /// `analysis` field of an `AnalyzeFindingOutput`), including its
+    /// FALSE POSITIVE CHECK section.
+    pub analysis: String,
+}
+
+fn vex_finding_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "string"},
+            "severity": {"type": "string", "enum": ["critical", "high", "medium", "low", "info"]},
+            "state": {"type": "string", "enum": ["exploitable", "not_affected", "false_positive"]},
+            "justification": {"type": "string"},
+            "affected_url": {"type": "string"}
+        },
+        "required": ["id", "severity", "state", "justification", "affected_url"]
+    })
+}
+
+fn build_vex_extraction_prompt(input: &VexFindingInput) -> String {
+    format!(
+        r#"Extract a structured VEX (Vulnerability Exploitability eXchange) classification from the following security analysis.
+
+FINDING ID: {}
+AFFECTED URL: {}
+
+ANALYSIS:
+{}
+
+Read the FALSE POSITIVE CHECK section carefully: if it concludes this is a false positive, set state to "false_positive"; if the analysis shows a realistic exploit path, set state to "exploitable"; otherwise set state to "not_affected". Call the report_vex_finding function with your classification."#,
+        input.finding_id, input.asset_url, input.analysis
+    )
+}
+
+fn parse_vex_finding(raw: serde_json::Value, input: &VexFindingInput) -> Result<VexFinding, String> {
+    let mut finding: VexFinding = serde_json::from_value(raw)
+        .map_err(|e| format!("Malformed structured VEX finding: {}", e))?;
+    if finding.id.is_empty() {
+        finding.id = input.finding_id.clone();
+    }
+    if finding.affected_url.is_empty() {
+        finding.affected_url = input.asset_url.clone();
+    }
+    Ok(finding)
+}
+
+/// OpenAI/Ollama-compatible forced function call: `tool_choice` pins the
+/// model to `report_vex_finding` so the response's `tool_calls[0].arguments`
+/// is always the structured object instead of optional prose.
+async fn extract_vex_finding_chat(
+    config: &LlmConfig,
+    input: &VexFindingInput,
+) -> Result<VexFinding, String> {
+    let client = reqwest::Client::new();
+    let prompt = build_vex_extraction_prompt(input);
+
+    let request_body = serde_json::json!({
+        "model": config.model,
+        "messages": [
+            {
+                "role": "system",
+                "content": "You are a security analyst extracting structured VEX data from prose analyses."
+            },
+            {"role": "user", "content": prompt}
+        ],
+        "tools": [{
+            "type": "function",
+            "function": {
+                "name": "report_vex_finding",
+                "description": "Report a structured VEX classification for this finding.",
+                "parameters": vex_finding_schema()
+            }
+        }],
+        "tool_choice": {"type": "function", "function": {"name": "report_vex_finding"}},
+        "stream": false
+    });
+
+    let mut request = client
+        .post(&config.endpoint)
+        .header("Content-Type", "application/json");
+    if !config.is_local() {
+        request = request.header("Authorization", format!("Bearer {}", config.api_key));
+    }
+
+    let response = request
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request to LLM: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("LLM API error ({}): {}", status, error_text));
+    }
+
+    let response_json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
+
+    let message = response_json["choices"][0]["message"]
+        .as_object()
+        .or_else(|| response_json["message"].as_object())
+        .cloned()
+        .ok_or_else(|| "Failed to extract message from LLM response".to_string())?;
+
+    let arguments: serde_json::Value = message
+        .get("tool_calls")
+        .and_then(serde_json::Value::as_array)
+        .and_then(|calls| calls.first())
+        .and_then(|call| call["function"]["arguments"].as_str())
+        .and_then(|s| serde_json::from_str(s).ok())
+        .ok_or_else(|| "Model did not return a structured VEX finding".to_string())?;
+
+    parse_vex_finding(arguments, input)
+}
+
+/// As [`extract_vex_finding_chat`], but for Anthropic's Messages API: a
+/// `tool_choice` of `{"type": "tool", "name": ...}` forces a `tool_use`
+/// block whose already-parsed `input` is the structured object.
+async fn extract_vex_finding_anthropic(
+    config: &LlmConfig,
+    input: &VexFindingInput,
+) -> Result<VexFinding, String> {
+    let client = reqwest::Client::new();
+    let prompt = build_vex_extraction_prompt(input);
+
+    let request_body = serde_json::json!({
+        "model": config.model,
+        "system": "You are a security analyst extracting structured VEX data from prose analyses.",
+        "messages": [{"role": "user", "content": prompt}],
+        "tools": [{
+            "name": "report_vex_finding",
+            "description": "Report a structured VEX classification for this finding.",
+            "input_schema": vex_finding_schema()
+        }],
+        "tool_choice": {"type": "tool", "name": "report_vex_finding"},
+        "max_tokens": 1000
+    });
+
+    let response = client
+        .post(&config.endpoint)
+        .header("x-api-key", &config.api_key)
+        .header("anthropic-version", "2023-06-01")
+        .header("Content-Type", "application/json")
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request to LLM: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("LLM API error ({}): {}", status, error_text));
+    }
+
+    let response_json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
+
+    let arguments = response_json["content"]
+        .as_array()
+        .and_then(|blocks| {
+            blocks
+                .iter()
+                .find(|b| b.get("type").and_then(serde_json::Value::as_str) == Some("tool_use"))
+        })
+        .map(|b| b["input"].clone())
+        .ok_or_else(|| "Model did not return a structured VEX finding".to_string())?;
+
+    parse_vex_finding(arguments, input)
+}
+
+async fn extract_vex_finding(
+    config: &LlmConfig,
+    input: &VexFindingInput,
+) -> Result<VexFinding, String> {
+    if config.provider_type == ProviderType::Anthropic {
+        extract_vex_finding_anthropic(config, input).await
+    } else {
+        extract_vex_finding_chat(config, input).await
+    }
+}
+
+fn vex_severity_str(severity: &VexSeverity) -> &'static str {
+    match severity {
+        VexSeverity::Critical => "critical",
+        VexSeverity::High => "high",
+        VexSeverity::Medium => "medium",
+        VexSeverity::Low => "low",
+        VexSeverity::Info => "info",
+    }
+}
+
+fn vex_state_str(state: &VexState) -> &'static str {
+    match state {
+        VexState::Exploitable => "exploitable",
+        VexState::NotAffected => "not_affected",
+        VexState::FalsePositive => "false_positive",
+    }
+}
+
+/// Serialize `findings` into a CycloneDX 1.5 VEX document: one
+/// `vulnerabilities[]` entry per finding, with its component reference
+/// keyed by `affected_url` and the FP check surfaced as `analysis.state`.
+fn build_cyclonedx_vex(findings: &[VexFinding]) -> serde_json::Value {
+    serde_json::json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "vulnerabilities": findings.iter().map(|f| serde_json::json!({
+            "id": f.id,
+            "ratings": [{"severity": vex_severity_str(&f.severity)}],
+            "affects": [{"ref": f.affected_url}],
+            "analysis": {
+                "state": vex_state_str(&f.state),
+                "justification": f.justification
+            }
+        })).collect::<Vec<_>>()
+    })
+}
+
+#[tauri::command]
+pub async fn export_findings_vex(
+    findings: Vec<VexFindingInput>,
+) -> Result<serde_json::Value, String> {
+    let config = LlmConfig::load();
+    if !config.is_configured() {
+        return Err("LLM not configured.".to_string());
+    }
+
+    let mut vex_findings = Vec::with_capacity(findings.len());
+    for input in &findings {
+        vex_findings.push(extract_vex_finding(&config, input).await?);
+    }
+
+    Ok(build_cyclonedx_vex(&vex_findings))
+}
+
 #[tauri::command]
 pub fn get_llm_config() -> LlmConfigPublic {
     let config = LlmConfig::load();
@@ -782,12 +2388,16 @@ pub fn update_llm_config(
     api_key: String,
     model: String,
     provider_type: String,
+    redaction_enabled: bool,
+    num_ctx: u32,
 ) -> Result<LlmConfigPublic, String> {
     let mut config = LlmConfig::load();
 
     config.endpoint = endpoint;
     config.model = model;
     config.provider_type = provider_type.parse().unwrap_or(ProviderType::OpenAI);
+    config.redaction_enabled = redaction_enabled;
+    config.num_ctx = if num_ctx > 0 { num_ctx } else { default_num_ctx() };
 
     if !api_key.is_empty() {
         config.api_key = api_key;
@@ -806,31 +2416,143 @@ pub fn update_llm_config(
         config.model = "phi3.5".to_string();
     }
 
-    config.save()?;
+    let mut registry = ProviderRegistry::load();
+    registry.set_active_config(config.clone());
+    registry.save()?;
 
     Ok(LlmConfigPublic::from(&config))
 }
 
-pub fn is_model_present(model_name: &str) -> bool {
-    let output = std::process::Command::new("ollama")
-        .args(&["list"])
-        .output();
+/// Derive Ollama's base URL (`scheme://host:port`) from a configured
+/// `/api/chat`-style endpoint, so `/api/tags` and `/api/pull` can be hit
+/// without the caller hard-coding `localhost`.
+fn ollama_base_url(endpoint: &str) -> String {
+    url::Url::parse(endpoint)
+        .map(|u| u.origin().ascii_serialization())
+        .unwrap_or_else(|_| "http://localhost:11434".to_string())
+}
 
-    if let Ok(output) = output {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        stdout.contains(model_name)
+/// Apply `config.api_key` as an `Authorization: Bearer` header to a
+/// request builder bound for Ollama, for reverse-proxied/shared-LAN
+/// instances that require it. A no-op when the key is empty.
+fn with_ollama_auth(request: reqwest::RequestBuilder, config: &LlmConfig) -> reqwest::RequestBuilder {
+    if config.api_key.is_empty() {
+        request
     } else {
-        false
+        request.header("Authorization", format!("Bearer {}", config.api_key))
+    }
+}
+
+/// Check `/api/tags` for `config.model`, which doubles as a liveness check
+/// for the Ollama server itself -- an unreachable server is indistinguishable
+/// from "model not present" here, same as the old CLI-based check was.
+pub async fn is_model_present(config: &LlmConfig) -> bool {
+    let client = reqwest::Client::new();
+    let url = format!("{}/api/tags", ollama_base_url(&config.endpoint));
+
+    let Ok(response) = with_ollama_auth(client.get(&url), config).send().await else {
+        return false;
+    };
+    let Ok(body) = response.json::<serde_json::Value>().await else {
+        return false;
+    };
+
+    body["models"]
+        .as_array()
+        .map(|models| {
+            models.iter().any(|m| {
+                let name = m["name"].as_str().or_else(|| m["model"].as_str());
+                name == Some(config.model.as_str())
+                    || name
+                        .map(|n| n.starts_with(&format!("{}:", config.model)))
+                        .unwrap_or(false)
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Issue `POST /api/pull`, which responds with a newline-delimited JSON
+/// stream of `{status, total, completed}` objects, and forward incremental
+/// percentage updates through `emit_log` as they arrive.
+async fn pull_model_streaming(
+    handle: &AppHandle,
+    config: &LlmConfig,
+    model_name: &str,
+) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    let url = format!("{}/api/pull", ollama_base_url(&config.endpoint));
+
+    let response = with_ollama_auth(client.post(&url), config)
+        .json(&serde_json::json!({"name": model_name}))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to initiate ollama pull: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Ollama pull API error ({}): {}", status, error_text));
+    }
+
+    let mut buf = String::new();
+    let mut last_percent: i64 = -1;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Failed to read ollama pull stream: {}", e))?;
+        buf.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(newline_pos) = buf.find('\n') {
+            let line = buf[..newline_pos].trim().to_string();
+            buf.drain(..=newline_pos);
+            if line.is_empty() {
+                continue;
+            }
+
+            let Ok(obj) = serde_json::from_str::<serde_json::Value>(&line) else {
+                continue;
+            };
+            let status_msg = obj["status"].as_str().unwrap_or_default();
+
+            match (obj["total"].as_u64(), obj["completed"].as_u64()) {
+                (Some(total), Some(completed)) if total > 0 => {
+                    let percent = (completed as f64 / total as f64 * 100.0).round() as i64;
+                    if percent != last_percent {
+                        last_percent = percent;
+                        emit_log(
+                            handle,
+                            LogLevel::Info,
+                            "AI",
+                            &format!("Pulling {}: {}% ({})", model_name, percent, status_msg),
+                            None,
+                        );
+                    }
+                }
+                _ => {
+                    emit_log(
+                        handle,
+                        LogLevel::Info,
+                        "AI",
+                        &format!("Pulling {}: {}", model_name, status_msg),
+                        None,
+                    );
+                }
+            }
+        }
     }
+
+    Ok(())
 }
 
-pub async fn ensure_model_present(handle: AppHandle, model_name: &str) -> Result<(), String> {
-    if is_model_present(model_name) {
+pub async fn ensure_model_present(handle: AppHandle, config: &LlmConfig) -> Result<(), String> {
+    if is_model_present(config).await {
         emit_log(
             &handle,
             LogLevel::Success,
             "AI",
-            &format!("Model {} is ready.", model_name),
+            &format!("Model {} is ready.", config.model),
             None,
         );
         return Ok(());
@@ -840,39 +2562,69 @@ pub async fn ensure_model_present(handle: AppHandle, model_name: &str) -> Result
         &handle,
         LogLevel::Warn,
         "AI",
-        &format!(
-            "Model {} not found. Pulling now... (This may take a few minutes)",
-            model_name
-        ),
+        &format!("Model {} not found. Pulling now...", config.model),
         None,
     );
 
-    let status = std::process::Command::new("ollama")
-        .args(&["pull", model_name])
-        .status()
-        .map_err(|e| format!("Failed to initiate ollama pull: {}", e))?;
+    pull_model_streaming(&handle, config, &config.model).await?;
 
-    if status.success() {
+    if is_model_present(config).await {
         emit_log(
             &handle,
             LogLevel::Success,
             "AI",
-            &format!("Model {} pulled successfully.", model_name),
+            &format!("Model {} pulled successfully.", config.model),
             None,
         );
         Ok(())
     } else {
-        let err = format!("Failed to pull {}. Ensure Ollama is running.", model_name);
+        let err = format!(
+            "Failed to pull {}. Ensure Ollama is reachable at {}.",
+            config.model, config.endpoint
+        );
         emit_log(&handle, LogLevel::Error, "AI", &err, None);
         Err(err)
     }
 }
 
+/// Issue an empty chat request so Ollama loads the model's weights into
+/// memory now rather than on the first real analysis. Ollama has no
+/// separate "load" API -- any request against the model triggers the load,
+/// so an empty prompt is the cheapest way to pay that cost up front.
+async fn preload_model(handle: &AppHandle, config: &LlmConfig) {
+    emit_log(
+        handle,
+        LogLevel::Info,
+        "AI",
+        &format!("Loading model {} into memory...", config.model),
+        None,
+    );
+
+    match call_ollama_api(config, "").await {
+        Ok(_) => emit_log(
+            handle,
+            LogLevel::Success,
+            "AI",
+            &format!("Model {} is loaded and ready.", config.model),
+            None,
+        ),
+        Err(e) => emit_log(
+            handle,
+            LogLevel::Warn,
+            "AI",
+            &format!("Model {} preload failed, will load lazily on first use: {}", config.model, e),
+            None,
+        ),
+    }
+}
+
 pub fn auto_initialize_ai(handle: AppHandle) {
     tauri::async_runtime::spawn(async move {
         let config = LlmConfig::load();
         if config.is_local() {
-            let _ = ensure_model_present(handle, &config.model).await;
+            if ensure_model_present(handle.clone(), &config).await.is_ok() {
+                preload_model(&handle, &config).await;
+            }
         }
     });
 }
@@ -880,13 +2632,13 @@ pub fn auto_initialize_ai(handle: AppHandle) {
 #[tauri::command]
 pub async fn check_local_model_status() -> Result<bool, String> {
     let config = LlmConfig::load();
-    Ok(is_model_present(&config.model))
+    Ok(is_model_present(&config).await)
 }
 
 #[tauri::command]
 pub async fn pull_local_model(app_handle: AppHandle) -> Result<(), String> {
     let config = LlmConfig::load();
-    ensure_model_present(app_handle, &config.model).await
+    ensure_model_present(app_handle, &config).await
 }
 
 #[allow(dead_code)]
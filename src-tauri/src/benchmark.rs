@@ -0,0 +1,146 @@
+//! Reproducible scan-workload benchmarking, in the spirit of a committed
+//! `cargo xtask bench` suite: replay a fixed set of URL/method targets
+//! through `scan_url`, aggregate latency percentiles, and check the
+//! findings each target produced against an expected set. Workloads are
+//! committed JSON files, so throughput and detection regressions show up
+//! as a diff against a known-good report across versions.
+
+use crate::core::rate_limiter::RateLimiter;
+use crate::scanner::scan_url;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Instant;
+
+#[derive(Debug, Deserialize)]
+pub struct BenchmarkTarget {
+    pub url: String,
+    pub method: String,
+    #[serde(default)]
+    pub expected_findings: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BenchmarkWorkload {
+    pub name: String,
+    pub rate_limit_ms: u64,
+    pub iterations: u32,
+    pub targets: Vec<BenchmarkTarget>,
+    /// Optional URL to POST the rendered JSON report to once the run
+    /// finishes, so results can be tracked across versions centrally.
+    #[serde(default)]
+    pub results_server_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TargetReport {
+    pub url: String,
+    pub method: String,
+    pub runs: u32,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub total_findings: usize,
+    pub passed: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BenchmarkReport {
+    pub name: String,
+    pub targets: Vec<TargetReport>,
+}
+
+/// Nearest-rank percentile over an already-sorted slice of millisecond
+/// latencies.
+fn percentile(sorted_ms: &[f64], pct: f64) -> f64 {
+    if sorted_ms.is_empty() {
+        return 0.0;
+    }
+    let rank = (pct / 100.0 * (sorted_ms.len() - 1) as f64).round() as usize;
+    sorted_ms[rank.min(sorted_ms.len() - 1)]
+}
+
+async fn run_target(
+    client: &Client,
+    rate_limiter: &RateLimiter,
+    target: &BenchmarkTarget,
+    iterations: u32,
+) -> TargetReport {
+    let mut latencies_ms = Vec::with_capacity(iterations as usize);
+    let mut found_codes = HashSet::new();
+
+    for _ in 0..iterations {
+        let started = Instant::now();
+        let result = scan_url(client, &target.url, &target.method, rate_limiter).await;
+        latencies_ms.push(started.elapsed().as_secs_f64() * 1000.0);
+        for finding in &result.findings {
+            found_codes.insert(finding.short.clone());
+        }
+    }
+
+    latencies_ms.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let passed = target
+        .expected_findings
+        .iter()
+        .all(|code| found_codes.contains(code));
+
+    TargetReport {
+        url: target.url.clone(),
+        method: target.method.clone(),
+        runs: iterations,
+        p50_ms: percentile(&latencies_ms, 50.0),
+        p95_ms: percentile(&latencies_ms, 95.0),
+        p99_ms: percentile(&latencies_ms, 99.0),
+        total_findings: found_codes.len(),
+        passed,
+    }
+}
+
+pub async fn run_workload(workload: &BenchmarkWorkload) -> BenchmarkReport {
+    let client = Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap_or_default();
+    let rate_limiter = RateLimiter::new(workload.rate_limit_ms);
+
+    let mut targets = Vec::with_capacity(workload.targets.len());
+    for target in &workload.targets {
+        targets.push(run_target(&client, &rate_limiter, target, workload.iterations).await);
+    }
+
+    BenchmarkReport {
+        name: workload.name.clone(),
+        targets,
+    }
+}
+
+/// Load a workload file, replay it through `scan_url`, write the JSON
+/// report next to the workload, and POST it to `results_server_url` if
+/// the workload names one. Returns the report JSON for the caller to
+/// render.
+#[tauri::command]
+pub async fn run_benchmark(path: String) -> Result<String, String> {
+    let contents = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let workload: BenchmarkWorkload = serde_json::from_str(&contents).map_err(|e| e.to_string())?;
+
+    let report = run_workload(&workload).await;
+    let report_json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+
+    let report_path = format!("{}.report.json", path);
+    std::fs::write(&report_path, &report_json).map_err(|e| e.to_string())?;
+
+    if let Some(results_url) = &workload.results_server_url {
+        let client = Client::new();
+        if let Err(e) = client
+            .post(results_url)
+            .body(report_json.clone())
+            .send()
+            .await
+        {
+            eprintln!("Failed to POST benchmark report to results server: {}", e);
+        }
+    }
+
+    Ok(report_json)
+}
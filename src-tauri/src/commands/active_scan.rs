@@ -1,7 +1,7 @@
 use crate::core::active_scanner::{scan_active_target, ActiveScanResult};
 use crate::db::SqliteDatabase;
 use std::collections::HashMap;
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Emitter, Manager};
 
 #[tauri::command]
 pub async fn execute_active_scan(app: AppHandle, id: i64) -> Result<ActiveScanResult, String> {
@@ -18,7 +18,29 @@ pub async fn execute_active_scan(app: AppHandle, id: i64) -> Result<ActiveScanRe
             }
         }
 
-        let result = scan_active_target(id, asset.url.clone(), asset.method.clone(), headers).await;
+        let allow_internal_targets = db
+            .get_setting("allow_internal_scan_targets")
+            .map_err(|e| e.to_string())?
+            .map(|v| v == "true")
+            .unwrap_or(false);
+
+        let concurrency = db
+            .get_setting("active_scan_concurrency")
+            .map_err(|e| e.to_string())?
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(5);
+
+        let result = scan_active_target(
+            id,
+            asset.url.clone(),
+            asset.method.clone(),
+            headers,
+            allow_internal_targets,
+            concurrency,
+            app.clone(),
+        )
+        .await;
+        let _ = app.emit("scan-update", id);
         Ok(result)
     } else {
         Err("Asset not found".to_string())
@@ -1,9 +1,14 @@
-use crate::db::{Asset, ImportOperation, ImportOptions, SqliteDatabase};
+use crate::db::{Asset, ImportOperation, ImportOptions, SqliteDatabase, Storage};
 use crate::scan_url;
+use crate::utils::openapi_parser::parse_openapi_auto;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashSet;
+use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::{mpsc, Mutex, Semaphore};
+use tokio::task::JoinSet;
 use tokio::time::{self, Duration};
 use uuid::Uuid;
 
@@ -78,8 +83,12 @@ pub async fn import_assets(
     Ok(ids)
 }
 
+/// Generic over [`crate::db::Storage`] rather than the concrete
+/// `SqliteDatabase` so it runs unchanged over an in-memory backend in tests,
+/// or a future remote store, as long as whatever's `.manage()`d implements
+/// the trait.
 #[tauri::command]
-pub fn get_assets(state: tauri::State<SqliteDatabase>) -> Result<Vec<Asset>, String> {
+pub fn get_assets(state: tauri::State<Box<dyn Storage>>) -> Result<Vec<Asset>, String> {
     state.get_assets().map_err(|e| e.to_string())
 }
 
@@ -165,121 +174,310 @@ pub async fn enhanced_import_assets(
 
     let import_id = Uuid::new_v4().to_string();
 
-    // Analyze content to extract URLs
-    let urls = analyze_content_for_import(&content);
-    let total_urls = urls.len();
+    // Sniff the pasted/loaded content for a structured capture format
+    // (OpenAPI/Swagger, HAR, Postman) before falling back to loose regex URL
+    // extraction, so method info and relative-path resolution survive the
+    // import instead of every request collapsing to a bare GET.
+    let requests = extract_import_requests(&content);
+    let total_urls = requests.len();
+
+    let db = app.state::<SqliteDatabase>().inner().clone();
+
+    let import_op = ImportOperation {
+        id: 0, // Will be set by database
+        import_id: import_id.clone(),
+        source: source_label.clone(),
+        total_assets: total_urls as i32,
+        successful_assets: 0,
+        failed_assets: 0,
+        duplicate_assets: 0,
+        status: "running".to_string(),
+        options: options.clone(),
+        duration_ms: None,
+        error_message: None,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        updated_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    if let Err(e) = db.record_import_operation(import_op) {
+        return Err(format!("Failed to create import operation: {}", e));
+    }
 
-    // Create import operation record
-    {
-        let db = app.state::<SqliteDatabase>();
-        let import_op = ImportOperation {
-            id: 0, // Will be set by database
-            import_id: import_id.clone(),
-            source: source_label.clone(),
-            total_assets: total_urls as i32,
-            successful_assets: 0,
-            failed_assets: 0,
-            duplicate_assets: 0,
-            status: "running".to_string(),
-            options: options.clone(),
-            duration_ms: None,
-            error_message: None,
-            created_at: chrono::Utc::now().to_rfc3339(),
-            updated_at: chrono::Utc::now().to_rfc3339(),
+    // Enqueue every request as a durable `import_assets` row *before* any
+    // scanning starts. This is the crash-resumable queue: once a row is
+    // written here as `pending`, a restart can find it via
+    // `get_unfinished_import_assets` and re-drive it through the worker
+    // pool even if the in-memory `requests` list this request built never
+    // makes it past this function returning.
+    let mut pending = Vec::new();
+    for req in &requests {
+        match db.add_asset_dedup(
+            &req.url,
+            &source_label,
+            Some(&req.method),
+            options.recursive,
+            false,
+            0,
+        ) {
+            Ok((asset_id, true)) => {
+                if let Err(e) = db.record_import_asset(
+                    &import_id, asset_id, &req.url, &req.method, "pending", None, None,
+                ) {
+                    eprintln!("Failed to enqueue import asset {}: {}", req.url, e);
+                }
+                pending.push((asset_id, req.url.clone(), req.method.clone()));
+            }
+            Ok((asset_id, false)) => {
+                let _ = db.record_import_asset(
+                    &import_id, asset_id, &req.url, &req.method, "duplicate", None, None,
+                );
+            }
+            Err(e) => {
+                eprintln!("Failed to add import asset {}: {}", req.url, e);
+            }
+        }
+    }
+
+    spawn_import_worker_pool(app.clone(), db, import_id.clone(), options, pending, total_urls);
+
+    Ok(import_id)
+}
+
+/// Re-enqueues and resumes a `running` import's still-`pending`/
+/// `in_progress` assets through the worker pool, for an import that was
+/// interrupted (app closed, crash) before it reached a terminal status.
+#[tauri::command]
+pub async fn resume_import(app: AppHandle, import_id: String) -> Result<(), String> {
+    let db = app.state::<SqliteDatabase>().inner().clone();
+    let operation = db
+        .get_import_operation(&import_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Import operation not found".to_string())?;
+
+    let unfinished = db
+        .get_unfinished_import_assets(&import_id)
+        .map_err(|e| e.to_string())?;
+
+    if unfinished.is_empty() {
+        return Ok(());
+    }
+
+    let total_urls = operation.total_assets as usize;
+    let pending: Vec<(i64, String, String)> = unfinished
+        .into_iter()
+        .map(|asset| (asset.asset_id, asset.url, asset.method))
+        .collect();
+
+    db.update_import_operation(&import_id, "running", None, None)
+        .map_err(|e| e.to_string())?;
+
+    spawn_import_worker_pool(app, db, import_id, operation.options, pending, total_urls);
+    Ok(())
+}
+
+/// Flips a `running` import to `cancelled`. Workers check this status
+/// cooperatively before picking up each new URL (see
+/// `spawn_import_worker_pool`) rather than through an in-memory signal, so
+/// an abort requested from a different app session than the one running
+/// the import still takes effect.
+#[tauri::command]
+pub fn abort_import(state: tauri::State<SqliteDatabase>, import_id: String) -> Result<(), String> {
+    state
+        .update_import_operation(&import_id, "cancelled", None, None)
+        .map_err(|e| e.to_string())
+}
+
+/// Scans for import operations a previous run left `running` and resumes
+/// their unfinished assets. Meant to be called once during application
+/// startup, the way this tree's `setup` hook handles the rest of its
+/// recovery-on-launch work.
+pub async fn recover_interrupted_imports(app: AppHandle) {
+    let db = app.state::<SqliteDatabase>().inner().clone();
+    let running = match db.get_running_import_operations() {
+        Ok(ops) => ops,
+        Err(e) => {
+            eprintln!("Failed to scan for interrupted imports: {}", e);
+            return;
+        }
+    };
+
+    for operation in running {
+        let unfinished = match db.get_unfinished_import_assets(&operation.import_id) {
+            Ok(assets) => assets,
+            Err(e) => {
+                eprintln!(
+                    "Failed to load unfinished assets for import {}: {}",
+                    operation.import_id, e
+                );
+                continue;
+            }
         };
 
-        if let Err(e) = db.record_import_operation(import_op) {
-            return Err(format!("Failed to create import operation: {}", e));
+        if unfinished.is_empty() {
+            // Every asset reached a terminal status before the restart;
+            // the operation just never got its own `completed` write.
+            let _ = db.update_import_operation(&operation.import_id, "completed", None, None);
+            continue;
         }
-    }
 
-    let import_id_clone = import_id.clone();
-    let source_label_clone = source_label.clone();
-    let app_handle = app.clone();
+        let pending: Vec<(i64, String, String)> = unfinished
+            .into_iter()
+            .map(|asset| (asset.asset_id, asset.url, asset.method))
+            .collect();
+        let total_urls = operation.total_assets as usize;
+
+        spawn_import_worker_pool(
+            app.clone(),
+            db.clone(),
+            operation.import_id.clone(),
+            operation.options.clone(),
+            pending,
+            total_urls,
+        );
+    }
+}
 
-    // Spawn the import processing task
+/// Drives `pending` (asset_id, url) pairs through a bounded worker pool --
+/// a `Semaphore` capping in-flight scans and a shared token-bucket capping
+/// overall rate -- shared by a fresh `enhanced_import_assets` call,
+/// `resume_import`, and startup recovery, all three only differing in how
+/// they built `pending`.
+fn spawn_import_worker_pool(
+    app_handle: AppHandle,
+    db: SqliteDatabase,
+    import_id: String,
+    options: ImportOptions,
+    pending: Vec<(i64, String, String)>,
+    total_urls: usize,
+) {
     tauri::async_runtime::spawn(async move {
-        let db = app_handle.state::<SqliteDatabase>();
         let start_time = std::time::Instant::now();
-        let mut successful = 0i32;
-        let mut failed = 0i32;
-        let mut duplicates = 0i32;
-
-        for (idx, url) in urls.iter().enumerate() {
-            let process_result =
-                process_import_asset_sync(&db, url, &source_label_clone, options.recursive).await;
-
-            match process_result {
-                Ok(asset_id) => {
-                    // Record the import asset
-                    let _ = db.record_import_asset(
-                        &import_id_clone,
-                        asset_id,
-                        url,
-                        "GET",
-                        "success",
-                        None,
-                        None,
-                    );
-                    successful += 1;
-
-                    // Emit progress event
-                    let _ = app_handle.emit(
-                        "import-progress",
-                        serde_json::json!({
-                            "import_id": import_id_clone,
-                            "current": idx + 1,
-                            "total": total_urls,
-                            "url": url,
-                            "status": "success"
-                        }),
-                    );
+
+        // Bounds how many URLs are in-flight at once; `rate_limit` below
+        // caps the *rate* new scans start, independent of how many workers
+        // are allowed to run concurrently.
+        let semaphore = Arc::new(Semaphore::new(options.concurrency.max(1) as usize));
+
+        // A shared token bucket: one permit minted per `rate_limit` ms,
+        // drained by whichever worker asks for one next. This replaces the
+        // old per-item `sleep`, which forced every scan to wait on the one
+        // before it even though `scan_url` is network-bound and idle most
+        // of that time.
+        let rate_rx = if options.rate_limit > 0 {
+            let (rate_tx, rate_rx) = mpsc::channel::<()>(1);
+            let period = Duration::from_millis(options.rate_limit as u64);
+            tauri::async_runtime::spawn(async move {
+                let mut ticker = time::interval(period);
+                ticker.tick().await; // first tick fires immediately
+                loop {
+                    ticker.tick().await;
+                    if rate_tx.send(()).await.is_err() {
+                        break;
+                    }
                 }
-                Err(e) if e.contains("Duplicate") => {
-                    duplicates += 1;
-                    let _ = app_handle.emit(
-                        "import-progress",
-                        serde_json::json!({
-                            "import_id": import_id_clone,
-                            "current": idx + 1,
-                            "total": total_urls,
-                            "url": url,
-                            "status": "duplicate"
-                        }),
-                    );
+            });
+            Some(Arc::new(Mutex::new(rate_rx)))
+        } else {
+            None
+        };
+
+        let mut join_set = JoinSet::new();
+        for (idx, (asset_id, url, method)) in pending.into_iter().enumerate() {
+            let db = db.clone();
+            let app_handle = app_handle.clone();
+            let import_id = import_id.clone();
+            let semaphore = Arc::clone(&semaphore);
+            let rate_rx = rate_rx.clone();
+
+            join_set.spawn(async move {
+                // Cooperative cancellation: `abort_import` only writes
+                // `cancelled` to the DB, so checking it here is enough to
+                // stop picking up new work, no in-memory signal needed.
+                if matches!(db.get_import_operation(&import_id), Ok(Some(op)) if op.status == "cancelled")
+                {
+                    return;
                 }
-                Err(e) => {
-                    failed += 1;
-                    eprintln!("Failed to import asset {}: {}", url, e);
-                    let _ = app_handle.emit(
-                        "import-progress",
-                        serde_json::json!({
-                            "import_id": import_id_clone,
-                            "current": idx + 1,
-                            "total": total_urls,
-                            "url": url,
-                            "status": "failed",
-                            "error": e
-                        }),
-                    );
+
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore is never closed");
+                if let Some(rate_rx) = &rate_rx {
+                    let _ = rate_rx.lock().await.recv().await;
                 }
-            }
 
-            // Apply rate limiting
-            if options.rate_limit > 0 {
-                time::sleep(Duration::from_millis(options.rate_limit as u64)).await;
-            }
+                let _ = db.update_import_asset_status(&import_id, asset_id, "in_progress", None, None);
+
+                let item_start = std::time::Instant::now();
+                let result = scan_url(&db.client, &url, &method).await;
+                let _ = db.update_scan_result(
+                    asset_id,
+                    &result.status,
+                    result.status_code,
+                    result.risk_score,
+                    result.findings,
+                    &result.response_headers,
+                    &result.response_body,
+                    &result.request_headers,
+                    &result.request_body,
+                );
+                let processing_time_ms = item_start.elapsed().as_millis() as i64;
+                let _ = db.update_import_asset_status(
+                    &import_id,
+                    asset_id,
+                    "success",
+                    None,
+                    Some(processing_time_ms),
+                );
+
+                let _ = app_handle.emit(
+                    "import-progress",
+                    serde_json::json!({
+                        "import_id": import_id,
+                        "current": idx + 1,
+                        "total": total_urls,
+                        "url": url,
+                        "status": "success"
+                    }),
+                );
+            });
         }
 
-        // Update import operation as completed
+        // Wait for every worker before closing out the operation.
+        while join_set.join_next().await.is_some() {}
+
+        // An abort mid-run already wrote `cancelled`; don't clobber it with
+        // `completed` once the in-flight workers drain.
+        let already_cancelled =
+            matches!(db.get_import_operation(&import_id), Ok(Some(op)) if op.status == "cancelled");
+        if already_cancelled {
+            return;
+        }
+
+        // Counts always come from the durable `import_assets` queue rather
+        // than in-memory counters, so a resumed import's totals include
+        // work finished in an earlier run before the crash.
+        let (successful, failed, duplicates) = db
+            .get_import_assets(&import_id)
+            .map(|assets| {
+                assets.iter().fold((0i32, 0i32, 0i32), |(s, f, d), asset| {
+                    match asset.status.as_str() {
+                        "success" => (s + 1, f, d),
+                        "failed" => (s, f + 1, d),
+                        "duplicate" => (s, f, d + 1),
+                        _ => (s, f, d),
+                    }
+                })
+            })
+            .unwrap_or((0, 0, 0));
+
         let duration_ms = start_time.elapsed().as_millis() as i64;
-        let _ = db.update_import_operation(&import_id_clone, "completed", Some(duration_ms), None);
+        let _ = db.update_import_operation(&import_id, "completed", Some(duration_ms), None);
 
-        // Emit completion event
         let _ = app_handle.emit(
             "import-complete",
             serde_json::json!({
-                "import_id": import_id_clone,
+                "import_id": import_id,
                 "total": total_urls,
                 "successful": successful,
                 "failed": failed,
@@ -288,8 +486,182 @@ pub async fn enhanced_import_assets(
             }),
         );
     });
+}
 
-    Ok(import_id)
+/// One request pulled out of a structured import, always with a concrete
+/// method -- unlike the loose regex scanner, which has no way to know one
+/// and defaults every result to GET.
+struct ExtractedRequest {
+    url: String,
+    method: String,
+}
+
+/// Sniffs `content` for a structured capture format (OpenAPI/Swagger, HAR,
+/// Postman collection) before falling back to the loose regex URL scanner.
+/// Recognizing the format lets relative paths -- which `validate_single_url`
+/// already accepts as "common in Swagger/API imports" but which the regex
+/// scanner has no base URL to resolve -- become fully-qualified URLs, and
+/// lets each request keep its real method instead of collapsing to GET.
+fn extract_import_requests(content: &str) -> Vec<ExtractedRequest> {
+    if let Some(requests) = try_extract_openapi(content) {
+        return requests;
+    }
+    if let Some(requests) = try_extract_har(content) {
+        return requests;
+    }
+    if let Some(requests) = try_extract_postman(content) {
+        return requests;
+    }
+
+    analyze_content_for_import(content)
+        .into_iter()
+        .map(|url| ExtractedRequest {
+            url,
+            method: "GET".to_string(),
+        })
+        .collect()
+}
+
+/// Parse `content` as JSON or YAML into a raw [`Value`], the same
+/// auto-detection [`parse_openapi_auto`] itself does, so `servers`/`host`
+/// (not modeled by [`crate::utils::openapi_parser::OpenApiSpec`]) can be
+/// read back out alongside the parsed endpoint list.
+fn parse_raw_document(content: &str) -> Option<Value> {
+    if let Ok(value) = serde_json::from_str::<Value>(content) {
+        return Some(value);
+    }
+    let yaml_value: serde_yaml::Value = serde_yaml::from_str(content).ok()?;
+    serde_json::to_value(yaml_value).ok()
+}
+
+/// Resolve the base URL every documented path gets joined to: an OpenAPI 3
+/// `servers[0].url` (kept as-is, since it may itself carry a path prefix),
+/// or a Swagger 2 `schemes[0]://host` (no path prefix needed -- `basePath`
+/// is already folded into each endpoint's `path` by
+/// [`crate::utils::openapi_parser::parse_openapi_auto`]).
+fn resolve_openapi_base_url(raw: &Value) -> Option<String> {
+    if let Some(url) = raw
+        .get("servers")
+        .and_then(|v| v.as_array())
+        .and_then(|servers| servers.first())
+        .and_then(|s| s.get("url"))
+        .and_then(|u| u.as_str())
+        .filter(|u| !u.is_empty())
+    {
+        return Some(url.trim_end_matches('/').to_string());
+    }
+
+    let host = raw.get("host").and_then(|v| v.as_str())?;
+    let scheme = raw
+        .get("schemes")
+        .and_then(|v| v.as_array())
+        .and_then(|a| a.first())
+        .and_then(|s| s.as_str())
+        .unwrap_or("https");
+    Some(format!("{}://{}", scheme, host))
+}
+
+/// Recognize an OpenAPI 3 / Swagger 2 document and enumerate every
+/// documented `path` x method against its resolved base URL. Returns `None`
+/// (not an empty vec) when the content doesn't parse as a spec at all, so
+/// [`extract_import_requests`] can tell "not this format" apart from "this
+/// format, but it documents nothing".
+fn try_extract_openapi(content: &str) -> Option<Vec<ExtractedRequest>> {
+    let spec = parse_openapi_auto(content).ok()?;
+    if spec.endpoints.is_empty() {
+        return None;
+    }
+
+    let raw = parse_raw_document(content)?;
+    let base = resolve_openapi_base_url(&raw).unwrap_or_default();
+
+    Some(
+        spec.endpoints
+            .iter()
+            .map(|endpoint| ExtractedRequest {
+                url: format!("{}{}", base, endpoint.path),
+                method: endpoint.method.to_uppercase(),
+            })
+            .collect(),
+    )
+}
+
+/// Recognize a HAR (HTTP Archive) capture and pull `method`/`url` out of
+/// every `log.entries[].request`.
+fn try_extract_har(content: &str) -> Option<Vec<ExtractedRequest>> {
+    let value: Value = serde_json::from_str(content).ok()?;
+    let entries = value.pointer("/log/entries")?.as_array()?;
+
+    let requests: Vec<ExtractedRequest> = entries
+        .iter()
+        .filter_map(|entry| {
+            let url = entry.pointer("/request/url")?.as_str()?;
+            let method = entry
+                .pointer("/request/method")
+                .and_then(|v| v.as_str())
+                .unwrap_or("GET");
+            Some(ExtractedRequest {
+                url: url.to_string(),
+                method: method.to_uppercase(),
+            })
+        })
+        .collect();
+
+    if requests.is_empty() {
+        None
+    } else {
+        Some(requests)
+    }
+}
+
+/// Recognize a Postman collection (v2 schema) and walk `item[].request`,
+/// recursing into folders (an `item` entry whose own `item` is an array
+/// rather than a leaf `request`). Requires `info.schema` so an unrelated
+/// JSON document that happens to have a top-level `item` array isn't
+/// misdetected as a collection.
+fn try_extract_postman(content: &str) -> Option<Vec<ExtractedRequest>> {
+    let value: Value = serde_json::from_str(content).ok()?;
+    value.pointer("/info/schema")?.as_str()?;
+    let items = value.get("item")?.as_array()?;
+
+    let mut requests = Vec::new();
+    collect_postman_requests(items, &mut requests);
+
+    if requests.is_empty() {
+        None
+    } else {
+        Some(requests)
+    }
+}
+
+fn collect_postman_requests(items: &[Value], out: &mut Vec<ExtractedRequest>) {
+    for item in items {
+        if let Some(nested) = item.get("item").and_then(|v| v.as_array()) {
+            collect_postman_requests(nested, out);
+            continue;
+        }
+
+        let Some(request) = item.get("request") else {
+            continue;
+        };
+        let method = request
+            .get("method")
+            .and_then(|v| v.as_str())
+            .unwrap_or("GET")
+            .to_uppercase();
+        let url = match request.get("url") {
+            Some(Value::String(raw)) => Some(raw.clone()),
+            Some(Value::Object(_)) => request
+                .pointer("/url/raw")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            _ => None,
+        };
+
+        if let Some(url) = url {
+            out.push(ExtractedRequest { url, method });
+        }
+    }
 }
 
 /// Analyze content to extract URLs using regex patterns
@@ -331,44 +703,6 @@ fn analyze_content_for_import(content: &str) -> Vec<String> {
     urls
 }
 
-/// Process a single import asset with duplicate detection
-async fn process_import_asset_sync(
-    db: &tauri::State<'_, SqliteDatabase>,
-    url: &str,
-    source: &str,
-    recursive: bool,
-) -> Result<i64, String> {
-    // Check for existing asset (duplicate detection)
-    let existing_assets = db.get_assets().map_err(|e| e.to_string())?;
-
-    for asset in existing_assets {
-        if asset.url == url {
-            return Err("Duplicate URL found".to_string());
-        }
-    }
-
-    // Add the asset
-    let asset_id = db
-        .add_asset(url, source, None, recursive)
-        .map_err(|e| e.to_string())?;
-
-    // Scan the asset
-    let result = scan_url(&db.client, url, "GET").await;
-    let _ = db.update_scan_result(
-        asset_id,
-        &result.status,
-        result.status_code,
-        result.risk_score,
-        result.findings,
-        &result.response_headers,
-        &result.response_body,
-        &result.request_headers,
-        &result.request_body,
-    );
-
-    Ok(asset_id)
-}
-
 /// Get the status of an import operation
 #[tauri::command]
 pub fn get_import_status(
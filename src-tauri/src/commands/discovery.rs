@@ -1,7 +1,9 @@
+use crate::core::discovery_telemetry::{hash_body, AssetMetrics, DiscoveryStats, TelemetryStore};
+use crate::core::jobs::JobManager;
 use crate::db::{SqliteDatabase, StagedAsset};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{AppHandle, Emitter, Manager, State};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DiscoveredAsset {
@@ -10,6 +12,10 @@ pub struct DiscoveredAsset {
     pub source: String,
     pub risk_estimate: String,
     pub findings: Vec<String>,
+    #[serde(default)]
+    pub is_fp: bool,
+    #[serde(default)]
+    pub fp_reason: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -19,11 +25,29 @@ struct CrtShEntry {
 
 use futures::stream::{self, StreamExt};
 
+#[derive(Debug, Clone, Serialize)]
+struct JobProgress<'a> {
+    job_id: &'a str,
+    processed: usize,
+    total: usize,
+    latest_asset: Option<&'a str>,
+}
+
+/// Kicks off subdomain discovery as a tracked background job instead of
+/// blocking the caller until crt.sh and every probe finish: returns the new
+/// job id immediately, emits `discovery-progress` as each `probe_asset`
+/// future resolves, and emits `discovery-complete` with the final assets
+/// once done (or cancelled early via `cancel_job`).
 #[tauri::command]
-pub async fn discover_subdomains(domain: String) -> Result<Vec<DiscoveredAsset>, String> {
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(3))
-        .build()
+pub async fn discover_subdomains(
+    app: AppHandle,
+    db: State<'_, SqliteDatabase>,
+    jobs: State<'_, JobManager>,
+    telemetry: State<'_, TelemetryStore>,
+    domain: String,
+) -> Result<String, String> {
+    let client = crate::core::http_client::HttpClientSettings::load(&db)
+        .build_client(std::time::Duration::from_secs(3))
         .map_err(|e| e.to_string())?;
 
     let url = format!("https://crt.sh/?q=%.{}&output=json", domain);
@@ -63,33 +87,79 @@ pub async fn discover_subdomains(domain: String) -> Result<Vec<DiscoveredAsset>,
         })
         .collect();
 
-    let results = stream::iter(target_urls)
-        .map(|url| {
-            let client = &client;
-            async move { probe_asset(client, url).await }
-        })
-        .buffer_unordered(10) // Concurrency limit
-        .collect::<Vec<_>>()
-        .await;
+    let job_id = uuid::Uuid::new_v4().to_string();
+    db.create_job(&job_id, "discover_subdomains", target_urls.len() as i64)
+        .map_err(|e| e.to_string())?;
+    let token = jobs.register(&job_id);
+
+    let db = db.inner().clone();
+    let app_for_task = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let app = app_for_task;
+        let total = target_urls.len();
+        let mut final_assets = Vec::with_capacity(total);
+        let mut processed = 0usize;
+
+        let mut probes = stream::iter(target_urls)
+            .map(|url| {
+                let client = &client;
+                let app = &app;
+                async move {
+                    let telemetry = app.state::<TelemetryStore>();
+                    probe_asset(client, url, &telemetry).await
+                }
+            })
+            .buffer_unordered(10); // Concurrency limit
+
+        let mut cancelled = false;
+        while let Some(asset) = probes.next().await {
+            if token.is_cancelled() {
+                cancelled = true;
+                break;
+            }
 
-    // Assign IDs based on index
-    let final_assets = results
-        .into_iter()
-        .enumerate()
-        .map(|(i, mut asset)| {
-            asset.id = format!("disc_{}", i);
-            asset
-        })
-        .collect();
+            processed += 1;
+            let mut asset = asset;
+            asset.id = format!("disc_{}", processed - 1);
+
+            let _ = app.emit(
+                "discovery-progress",
+                JobProgress {
+                    job_id: &job_id,
+                    processed,
+                    total,
+                    latest_asset: Some(&asset.url),
+                },
+            );
+            let _ = db.update_job_progress(&job_id, processed as i64, Some(&asset.url));
+
+            final_assets.push(asset);
+        }
 
-    Ok(final_assets)
+        if cancelled {
+            app.state::<JobManager>().unregister(&job_id);
+            return;
+        }
+
+        let _ = db.finish_job(&job_id, "completed", None);
+        let _ = app.emit(
+            "discovery-complete",
+            serde_json::json!({ "job_id": job_id.clone(), "assets": final_assets }),
+        );
+        app.state::<JobManager>().unregister(&job_id);
+    });
+
+    Ok(job_id)
 }
 
-async fn probe_asset(client: &Client, url: String) -> DiscoveredAsset {
+async fn probe_asset(client: &Client, url: String, telemetry: &TelemetryStore) -> DiscoveredAsset {
     let static_risk = crate::core::risk::calculate_risk_for_asset(&url, "GET");
     let mut findings = vec!["New Subdomain".to_string()];
     let mut risk_level = static_risk.risk_level;
     let source;
+    let mut is_fp = false;
+    let mut fp_reason = None;
 
     // Active Probing
     match client.head(&url).send().await {
@@ -110,6 +180,37 @@ async fn probe_asset(client: &Client, url: String) -> DiscoveredAsset {
                         findings.push(format!("Server: {}", s));
                     }
                 }
+
+                // Telemetry: a lightweight bounded GET purely to fingerprint
+                // the body (size/content-type/hash) for wildcard/soft-404
+                // false-positive clustering. Probing failures here are
+                // non-fatal to the overall discovery result.
+                let start = std::time::Instant::now();
+                if let Ok(get_resp) = client.get(&url).send().await {
+                    let content_type = get_resp
+                        .headers()
+                        .get("content-type")
+                        .and_then(|v| v.to_str().ok())
+                        .map(|s| s.to_string());
+                    let bounded = crate::utils::bounded_body::read_bounded(get_resp, 64 * 1024).await;
+                    let response_time_ms = start.elapsed().as_millis() as u64;
+                    let body_hash = hash_body(&bounded.text);
+
+                    telemetry.record(AssetMetrics {
+                        url: url.clone(),
+                        source: "cert+probe".to_string(),
+                        size_bytes: bounded.text.len(),
+                        content_type,
+                        response_time_ms,
+                        body_hash,
+                    });
+
+                    if let Some(reason) = telemetry.fp_reason_for(body_hash) {
+                        is_fp = true;
+                        findings.push(format!("Likely false positive: {}", reason));
+                        fp_reason = Some(reason);
+                    }
+                }
             } else if status.as_u16() == 401 || status.as_u16() == 403 {
                 findings.push(format!("Protected ({})", status));
                 // Downgrade risk because it's protected
@@ -142,14 +243,24 @@ async fn probe_asset(client: &Client, url: String) -> DiscoveredAsset {
         source,
         risk_estimate: risk_level,
         findings,
+        is_fp,
+        fp_reason,
     }
 }
 
+#[tauri::command]
+pub async fn get_discovery_stats(telemetry: State<'_, TelemetryStore>) -> Result<DiscoveryStats, String> {
+    Ok(telemetry.stats())
+}
+
 #[tauri::command]
 pub async fn crawl_discovered_assets(
+    db: State<'_, SqliteDatabase>,
     assets: Vec<DiscoveredAsset>,
 ) -> Result<Vec<DiscoveredAsset>, String> {
-    let client = Client::new();
+    let client = crate::core::http_client::HttpClientSettings::load(&db)
+        .build_client(std::time::Duration::from_secs(5))
+        .map_err(|e| e.to_string())?;
     let mut crawled_assets = Vec::new();
 
     let url_re = regex::Regex::new(r#"https?://[^\s"<>]+"#).map_err(|e| e.to_string())?;
@@ -163,7 +274,21 @@ pub async fn crawl_discovered_assets(
             .await;
 
         if let Ok(resp) = resp {
-            let body = resp.text().await.unwrap_or_default();
+            let bounded =
+                crate::utils::bounded_body::read_bounded(resp, crate::utils::bounded_body::DEFAULT_MAX_BODY_BYTES)
+                    .await;
+            let body = bounded.text;
+            if bounded.truncated {
+                crawled_assets.push(DiscoveredAsset {
+                    id: format!("crawl_{}", uuid::Uuid::new_v4()),
+                    url: asset.url.clone(),
+                    source: "crawl".to_string(),
+                    risk_estimate: "Info".to_string(),
+                    findings: vec!["Truncated (limit exceeded)".to_string()],
+                    is_fp: false,
+                    fp_reason: None,
+                });
+            }
 
             // Standard URL method
             for cap in url_re.captures_iter(&body) {
@@ -174,6 +299,8 @@ pub async fn crawl_discovered_assets(
                     source: "crawl".to_string(),
                     risk_estimate: "Info".to_string(),
                     findings: vec!["Extracted from page".to_string()],
+                    is_fp: false,
+                    fp_reason: None,
                 });
             }
 
@@ -190,6 +317,8 @@ pub async fn crawl_discovered_assets(
                             source: "js_analysis".to_string(),
                             risk_estimate: "Medium".to_string(),
                             findings: vec![format!("Found in JS: {}", asset.url)],
+                            is_fp: false,
+                            fp_reason: None,
                         });
                     }
                 }
@@ -227,8 +356,13 @@ pub async fn promote_discovered_assets(
 }
 
 #[tauri::command]
-pub async fn fetch_wayback_urls(domain: String) -> Result<Vec<DiscoveredAsset>, String> {
-    let client = Client::new();
+pub async fn fetch_wayback_urls(
+    db: State<'_, SqliteDatabase>,
+    domain: String,
+) -> Result<Vec<DiscoveredAsset>, String> {
+    let client = crate::core::http_client::HttpClientSettings::load(&db)
+        .build_client(std::time::Duration::from_secs(10))
+        .map_err(|e| e.to_string())?;
     let url = format!("http://web.archive.org/cdx/search/cdx?url=*.{}/*&output=json&fl=original&collapse=urlkey&limit=500", domain);
 
     let response = client
@@ -260,6 +394,8 @@ pub async fn fetch_wayback_urls(domain: String) -> Result<Vec<DiscoveredAsset>,
             source: "wayback".to_string(),
             risk_estimate: "Info".to_string(),
             findings: vec!["Historical Endpoint".to_string()],
+            is_fp: false,
+            fp_reason: None,
         });
     }
 
@@ -267,38 +403,43 @@ pub async fn fetch_wayback_urls(domain: String) -> Result<Vec<DiscoveredAsset>,
 }
 
 #[tauri::command]
-pub async fn scan_ports(domain: String) -> Result<Vec<DiscoveredAsset>, String> {
-    use std::net::{TcpStream, ToSocketAddrs};
-    use std::time::Duration;
-
-    let ports = vec![80, 443, 8000, 8008, 8080, 8443, 8888, 9000];
-    let mut open_ports = Vec::new();
-
-    for port in ports {
-        let target = format!("{}:{}", domain, port);
-        // Default to first resolved address
-        if let Ok(mut addrs) = target.to_socket_addrs() {
-            if let Some(socket_addr) = addrs.next() {
-                if TcpStream::connect_timeout(&socket_addr, Duration::from_millis(500)).is_ok() {
-                    open_ports.push(port);
-                }
-            }
-        }
-    }
+pub async fn scan_ports(
+    domain: String,
+    ports: Option<Vec<u16>>,
+    concurrency: Option<usize>,
+) -> Result<Vec<DiscoveredAsset>, String> {
+    use crate::core::port_scanner::{scan_ports_async, DEFAULT_PORTS};
+    use crate::core::risk::calculate_risk_for_port;
+
+    let ports = ports.unwrap_or_else(|| DEFAULT_PORTS.to_vec());
+    let scanned = scan_ports_async(&domain, &ports, concurrency.unwrap_or(20)).await;
 
     let mut assets = Vec::new();
-    for port in open_ports {
-        let scheme = if port == 443 || port == 8443 {
+    for open in scanned {
+        let scheme = if open.port == 443 || open.port == 8443 {
             "https"
         } else {
             "http"
         };
+
+        let risk = calculate_risk_for_port(open.port, open.banner.as_deref());
+        let mut findings = vec![format!("Open Port: {}", open.port)];
+        if let Some(banner) = &open.banner {
+            findings.push(format!("Banner: {}", banner));
+        }
+        if let Some(cn) = &open.tls_subject {
+            findings.push(format!("TLS Certificate Subject: {}", cn));
+        }
+        findings.extend(risk.risk_factors);
+
         assets.push(DiscoveredAsset {
-            id: format!("port_{}", port),
-            url: format!("{}://{}:{}", scheme, domain, port),
+            id: format!("port_{}", open.port),
+            url: format!("{}://{}:{}", scheme, domain, open.port),
             source: "port_scan".to_string(),
-            risk_estimate: "High".to_string(),
-            findings: vec![format!("Open Port: {}", port)],
+            risk_estimate: risk.risk_level,
+            findings,
+            is_fp: false,
+            fp_reason: None,
         });
     }
 
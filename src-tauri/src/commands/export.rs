@@ -1,5 +1,8 @@
+use crate::data::Severity;
 use crate::db::traits::DatabaseTrait;
 use crate::db::SqliteDatabase;
+use serde_json::json;
+use std::collections::BTreeMap;
 use tauri::State;
 
 #[tauri::command]
@@ -86,30 +89,144 @@ fn export_to_csv_impl(db: &impl DatabaseTrait, scope: Option<String>) -> Result<
         _ => all_assets,
     };
 
-    let mut csv =
-        String::from("URL,Method,Status,Risk Score,FindingsCount,Triage Status,Notes,Source\n");
+    let mut csv = String::from(
+        "URL,Method,Status,Risk Score,FindingsCount,Severities,Triage Status,Notes,Source,Timestamp\n",
+    );
     for asset in assets_to_export {
         let findings_count = asset.findings.len();
-        let safe_url = asset.url.replace(',', ";");
-        let safe_notes = asset.notes.replace(',', ";").replace('\n', " ");
-        let safe_source = asset.source.replace(',', ";");
+        let severities = asset
+            .findings
+            .iter()
+            .map(|f| format!("{:?}", f.severity))
+            .collect::<Vec<_>>()
+            .join(";");
 
         csv.push_str(&format!(
-            "{},{},{},{},{},{},{},{}\n",
-            safe_url,
-            asset.method,
-            asset.status,
+            "{},{},{},{},{},{},{},{},{},{}\n",
+            csv_field(&asset.url),
+            csv_field(&asset.method),
+            csv_field(&asset.status),
             asset.risk_score,
             findings_count,
-            asset.triage_status,
-            safe_notes,
-            safe_source
+            csv_field(&severities),
+            csv_field(&asset.triage_status),
+            csv_field(&asset.notes),
+            csv_field(&asset.source),
+            csv_field(&asset.updated_at),
         ));
     }
 
     Ok(csv)
 }
 
+/// Encode a single CSV field per RFC 4180: wrap in double quotes (doubling
+/// any embedded quote) when the value contains a comma, quote, or newline,
+/// and defang a leading `=`, `+`, `-`, or `@` with a single-quote prefix so
+/// the value can never execute as a formula when the CSV is opened in Excel
+/// or Sheets.
+fn csv_field(value: &str) -> String {
+    let defanged = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if defanged.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", defanged.replace('"', "\"\""))
+    } else {
+        defanged
+    }
+}
+
+/// Map a [`Severity`] to the SARIF `result.level` vocabulary: `error` for
+/// the severities that should fail a CI gate, `warning` for ones worth a
+/// human look, `note` for everything else.
+fn severity_to_sarif_level(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low | Severity::Info => "note",
+    }
+}
+
+#[tauri::command]
+pub async fn export_findings_to_sarif(state: State<'_, SqliteDatabase>) -> Result<String, String> {
+    export_to_sarif_impl(&*state)
+}
+
+/// Serialize every suspect asset's findings into a SARIF 2.1.0 log: one run
+/// with a `tool.driver` named APEX and one rule per distinct `finding.short`
+/// code, and one `result` per finding carrying its `ruleId`, severity-mapped
+/// `level`, the asset URL as an `artifactLocation`, and the request/response
+/// context in `properties` -- so a scan is diffable across runs in standard
+/// SARIF-consuming tooling (CI dashboards, code-scanning viewers).
+fn export_to_sarif_impl(db: &impl DatabaseTrait) -> Result<String, String> {
+    let all_assets = db.get_assets().map_err(|e| e.to_string())?;
+    let suspects: Vec<_> = all_assets
+        .into_iter()
+        .filter(|a| a.triage_status == "Suspect" || a.risk_score > 50)
+        .collect();
+
+    // BTreeMap keeps rule order stable (and dedups) across runs, so two
+    // exports of the same findings diff cleanly.
+    let mut rules: BTreeMap<String, &'static str> = BTreeMap::new();
+    let mut results = Vec::new();
+
+    for asset in &suspects {
+        for finding in &asset.findings {
+            rules
+                .entry(finding.short.clone())
+                .or_insert_with(|| severity_to_sarif_level(&finding.severity));
+
+            results.push(json!({
+                "ruleId": finding.short,
+                "level": severity_to_sarif_level(&finding.severity),
+                "message": { "text": finding.description },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": asset.url }
+                    }
+                }],
+                "properties": {
+                    "method": asset.method,
+                    "statusCode": asset.status_code,
+                    "triageStatus": asset.triage_status,
+                    "requestHeaders": asset.request_headers,
+                    "responseHeaders": asset.response_headers,
+                }
+            }));
+        }
+    }
+
+    let sarif_rules: Vec<_> = rules
+        .into_iter()
+        .map(|(rule_id, _)| {
+            json!({
+                "id": rule_id,
+                "name": rule_id,
+                "shortDescription": { "text": rule_id },
+            })
+        })
+        .collect();
+
+    let sarif = json!({
+        "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "APEX",
+                    "version": "1.0.0",
+                    "rules": sarif_rules,
+                }
+            },
+            "results": results,
+        }]
+    });
+
+    serde_json::to_string_pretty(&sarif).map_err(|e| e.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,6 +272,73 @@ mod tests {
 
         assert!(csv.contains("http://example.com"));
     }
+
+    #[test]
+    fn test_csv_field_quotes_embedded_comma() {
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+    }
+
+    #[test]
+    fn test_csv_field_doubles_embedded_quotes() {
+        assert_eq!(csv_field(r#"say "hi""#), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_csv_field_quotes_multiline_value() {
+        assert_eq!(csv_field("line one\nline two"), "\"line one\nline two\"");
+    }
+
+    #[test]
+    fn test_csv_field_defangs_formula_injection_payload() {
+        assert_eq!(csv_field("=cmd|'/c calc'!A1"), "'=cmd|'/c calc'!A1");
+        assert_eq!(csv_field("+1+1"), "'+1+1");
+        assert_eq!(csv_field("-1+1"), "'-1+1");
+        assert_eq!(csv_field("@SUM(A1:A2)"), "'@SUM(A1:A2)");
+    }
+
+    #[test]
+    fn test_csv_field_leaves_plain_value_untouched() {
+        assert_eq!(csv_field("http://example.com/path"), "http://example.com/path");
+    }
+
+    #[test]
+    fn test_export_csv_escapes_formula_injection_in_notes() {
+        let db = SqliteDatabase::new(":memory:").expect("Failed to create in-memory db");
+        db.add_asset("http://suspect.com", "test", None, false, false, 0)
+            .unwrap();
+        let suspect_id = db.get_assets().unwrap()[0].id;
+        db.update_asset_triage(suspect_id, "Suspect", "=cmd|'/c calc'!A1")
+            .unwrap();
+
+        let csv = export_to_csv_impl(&db, Some("suspects".to_string())).unwrap();
+
+        assert!(csv.contains("'=cmd|'/c calc'!A1"));
+        assert!(!csv.contains("\n=cmd"));
+    }
+
+    #[test]
+    fn test_export_sarif_includes_one_result_per_finding_with_mapped_level() {
+        let db = SqliteDatabase::new(":memory:").expect("Failed to create in-memory db");
+
+        db.add_asset("http://suspect.com", "test", None, false, false, 0)
+            .unwrap();
+        let suspect_id = db.get_assets().unwrap()[0].id;
+        db.update_asset_triage(suspect_id, "Suspect", "Check this")
+            .unwrap();
+
+        let sarif_text = export_to_sarif_impl(&db).unwrap();
+        let sarif: serde_json::Value = serde_json::from_str(&sarif_text).unwrap();
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(sarif["runs"][0]["tool"]["driver"]["name"], "APEX");
+        // No findings were ever recorded against this asset (it was never
+        // scanned), so both the rules and results arrays should be empty.
+        assert!(sarif["runs"][0]["results"].as_array().unwrap().is_empty());
+        assert!(sarif["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+    }
 }
 
 #[tauri::command]
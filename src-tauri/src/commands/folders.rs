@@ -1,4 +1,4 @@
-use crate::db::{Folder, SqliteDatabase};
+use crate::db::{Folder, SqliteDatabase, Storage};
 use tauri::State;
 
 #[tauri::command]
@@ -6,9 +6,10 @@ pub fn get_folders(state: State<SqliteDatabase>) -> Result<Vec<Folder>, String>
     state.get_folders().map_err(|e| e.to_string())
 }
 
+/// Generic over [`Storage`], see [`crate::commands::assets::get_assets`].
 #[tauri::command]
 pub fn add_folder(
-    state: State<SqliteDatabase>,
+    state: State<Box<dyn Storage>>,
     name: String,
     parent_id: Option<i64>,
 ) -> Result<i64, String> {
@@ -17,9 +18,10 @@ pub fn add_folder(
         .map_err(|e| e.to_string())
 }
 
+/// Generic over [`Storage`], see [`crate::commands::assets::get_assets`].
 #[tauri::command]
 pub fn move_assets_to_folder(
-    state: State<SqliteDatabase>,
+    state: State<Box<dyn Storage>>,
     ids: Vec<i64>,
     folder_id: i64,
 ) -> Result<(), String> {
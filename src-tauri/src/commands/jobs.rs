@@ -0,0 +1,26 @@
+use crate::core::jobs::JobManager;
+use crate::db::{Job, SqliteDatabase};
+use tauri::State;
+
+#[tauri::command]
+pub async fn cancel_job(
+    db: State<'_, SqliteDatabase>,
+    jobs: State<'_, JobManager>,
+    job_id: String,
+) -> Result<(), String> {
+    if jobs.cancel(&job_id) {
+        db.finish_job(&job_id, "cancelled", None)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_job(db: State<'_, SqliteDatabase>, job_id: String) -> Result<Option<Job>, String> {
+    db.get_job(&job_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_jobs(db: State<'_, SqliteDatabase>) -> Result<Vec<Job>, String> {
+    db.list_jobs().map_err(|e| e.to_string())
+}
@@ -0,0 +1,17 @@
+pub mod active_scan;
+pub mod assets;
+pub mod crypto;
+pub mod debug;
+pub mod diff;
+pub mod discovery;
+pub mod export;
+pub mod folders;
+pub mod jobs;
+pub mod openapi_import;
+pub mod proxy;
+pub mod repeater;
+pub mod scan;
+pub mod sequence;
+pub mod settings;
+pub mod shadow_api;
+pub mod signatures;
@@ -0,0 +1,361 @@
+use crate::db::{ImportOperation, ImportOptions, SqliteDatabase};
+use crate::scan_url;
+use crate::utils::openapi_parser::{parse_openapi_auto, DocumentedEndpoint, OpenApiSpec};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+use uuid::Uuid;
+
+/// One concrete HTTP request synthesized from a documented OpenAPI/Swagger
+/// operation: path parameters substituted with example/placeholder values,
+/// an example body (if the operation declares one), and an auth header
+/// placeholder resolved from the operation's security requirement.
+struct GeneratedRequest {
+    method: String,
+    url: String,
+    example_body: Option<String>,
+    auth_header: Option<(String, String)>,
+}
+
+/// Import an OpenAPI 3 / Swagger 2 spec (JSON or YAML) as scannable assets.
+///
+/// Walks every documented `path` x method, expands path templates and
+/// declared parameters into a concrete URL, carries over any example body,
+/// and resolves the operation's security requirement to an auth header
+/// placeholder -- then records and scans each generated request through the
+/// same import pipeline `enhanced_import_assets` uses, with `source` fixed
+/// to `"openapi"`.
+#[tauri::command]
+pub async fn import_openapi_assets(app: AppHandle, content: String) -> Result<String, String> {
+    let spec = parse_openapi_auto(&content).map_err(|e| e.to_string())?;
+    let raw = parse_raw_document(&content)?;
+    let requests = build_requests(&spec, &raw);
+    let total = requests.len();
+
+    let import_id = Uuid::new_v4().to_string();
+
+    {
+        let db = app.state::<SqliteDatabase>();
+        let import_op = ImportOperation {
+            id: 0,
+            import_id: import_id.clone(),
+            source: "openapi".to_string(),
+            total_assets: total as i32,
+            successful_assets: 0,
+            failed_assets: 0,
+            duplicate_assets: 0,
+            status: "running".to_string(),
+            options: ImportOptions::default(),
+            duration_ms: None,
+            error_message: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        };
+
+        if let Err(e) = db.record_import_operation(import_op) {
+            return Err(format!("Failed to create import operation: {}", e));
+        }
+    }
+
+    let import_id_clone = import_id.clone();
+    let app_handle = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let db = app_handle.state::<SqliteDatabase>();
+        let start_time = std::time::Instant::now();
+        let mut successful = 0i32;
+        let mut failed = 0i32;
+        let mut duplicates = 0i32;
+
+        for (idx, request) in requests.iter().enumerate() {
+            let process_result = process_generated_request(&db, request).await;
+
+            match process_result {
+                Ok(asset_id) => {
+                    let _ = db.record_import_asset(
+                        &import_id_clone,
+                        asset_id,
+                        &request.url,
+                        &request.method,
+                        "success",
+                        None,
+                        None,
+                    );
+                    successful += 1;
+                }
+                Err(e) if e.contains("Duplicate") => {
+                    duplicates += 1;
+                }
+                Err(e) => {
+                    failed += 1;
+                    eprintln!("Failed to import OpenAPI asset {}: {}", request.url, e);
+                }
+            }
+
+            let _ = app_handle.emit(
+                "import-progress",
+                serde_json::json!({
+                    "import_id": import_id_clone,
+                    "current": idx + 1,
+                    "total": total,
+                    "url": request.url,
+                    "status": "processed"
+                }),
+            );
+        }
+
+        let duration_ms = start_time.elapsed().as_millis() as i64;
+        let _ = db.update_import_operation(&import_id_clone, "completed", Some(duration_ms), None);
+
+        let _ = app_handle.emit(
+            "import-complete",
+            serde_json::json!({
+                "import_id": import_id_clone,
+                "total": total,
+                "successful": successful,
+                "failed": failed,
+                "duplicates": duplicates,
+                "duration_ms": duration_ms
+            }),
+        );
+    });
+
+    Ok(import_id)
+}
+
+/// Add, seed, and scan one generated request -- mirrors
+/// `enhanced_import_assets`'s duplicate-check/add/scan sequence, but also
+/// pre-seeds `request_headers`/`request_body` with the example body and auth
+/// placeholder the spec described, since the scanner itself can't send a
+/// custom body or header. The follow-up scan below overwrites the response
+/// side of the record; the seeded request side is left alone.
+async fn process_generated_request(
+    db: &tauri::State<'_, SqliteDatabase>,
+    request: &GeneratedRequest,
+) -> Result<i64, String> {
+    let existing_assets = db.get_assets().map_err(|e| e.to_string())?;
+    for asset in existing_assets {
+        if asset.url == request.url {
+            return Err("Duplicate URL found".to_string());
+        }
+    }
+
+    let asset_id = db
+        .add_asset(&request.url, "openapi", Some(&request.method), false)
+        .map_err(|e| e.to_string())?;
+
+    let seeded_headers = request
+        .auth_header
+        .as_ref()
+        .map(|(name, value)| format!("{}: {}", name, value))
+        .unwrap_or_default();
+    let seeded_body = request.example_body.clone().unwrap_or_default();
+    let _ = db.update_scan_result(
+        asset_id,
+        "Pending",
+        0,
+        0,
+        Vec::new(),
+        "",
+        "",
+        &seeded_headers,
+        &seeded_body,
+    );
+
+    let result = scan_url(&db.client, &request.url, &request.method).await;
+    let _ = db.update_scan_result(
+        asset_id,
+        &result.status,
+        result.status_code,
+        result.risk_score,
+        result.findings,
+        &result.response_headers,
+        &result.response_body,
+        &result.request_headers,
+        &result.request_body,
+    );
+
+    Ok(asset_id)
+}
+
+/// Parse `content` into a raw [`Value`] so example bodies and security
+/// scheme definitions (not surfaced by [`OpenApiSpec`], which only tracks
+/// what shadow-API matching needs) can be read back out alongside it.
+fn parse_raw_document(content: &str) -> Result<Value, String> {
+    if let Ok(value) = serde_json::from_str::<Value>(content) {
+        return Ok(value);
+    }
+    let yaml_value: serde_yaml::Value = serde_yaml::from_str(content)
+        .map_err(|e| format!("Failed to parse OpenAPI spec as JSON or YAML: {}", e))?;
+    serde_json::to_value(yaml_value).map_err(|e| e.to_string())
+}
+
+/// Build one [`GeneratedRequest`] per documented endpoint.
+fn build_requests(spec: &OpenApiSpec, raw: &Value) -> Vec<GeneratedRequest> {
+    let base_path = raw
+        .get("basePath")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .trim_end_matches('/')
+        .to_string();
+    let security_schemes = extract_security_schemes(raw);
+
+    spec.endpoints
+        .iter()
+        .map(|endpoint| build_request(endpoint, raw, &base_path, &security_schemes))
+        .collect()
+}
+
+fn build_request(
+    endpoint: &DocumentedEndpoint,
+    raw: &Value,
+    base_path: &str,
+    security_schemes: &std::collections::HashMap<String, (String, String)>,
+) -> GeneratedRequest {
+    let raw_path = endpoint.path.strip_prefix(base_path).unwrap_or(&endpoint.path);
+    let operation = raw
+        .get("paths")
+        .and_then(|paths| paths.get(raw_path))
+        .and_then(|path_item| path_item.get(endpoint.method.to_lowercase()));
+
+    let url_path = substitute_path_params(&endpoint.path, operation);
+    let url = format!("http://api.local{}", url_path);
+
+    let example_body = operation.and_then(example_body_for_operation);
+
+    let auth_header = endpoint
+        .security
+        .iter()
+        .find_map(|scheme_name| security_schemes.get(scheme_name))
+        .cloned();
+
+    GeneratedRequest {
+        method: endpoint.method.clone(),
+        url,
+        example_body,
+        auth_header,
+    }
+}
+
+/// Replace every `{name}` path template segment with the operation's
+/// declared example for that parameter, or a type-appropriate placeholder
+/// (`1` for integer/number, `example` otherwise) when no example is given.
+fn substitute_path_params(path_template: &str, operation: Option<&Value>) -> String {
+    let params = operation
+        .and_then(|op| op.get("parameters"))
+        .and_then(|p| p.as_array());
+
+    path_template
+        .split('/')
+        .map(|segment| substitute_segment(segment, params))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn substitute_segment(segment: &str, params: Option<&Vec<Value>>) -> String {
+    let Some(name) = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) else {
+        return segment.to_string();
+    };
+
+    let param = params.and_then(|list| {
+        list.iter()
+            .find(|p| p.get("name").and_then(|n| n.as_str()) == Some(name))
+    });
+
+    if let Some(value) = param.and_then(param_example_value) {
+        return value;
+    }
+
+    let param_type = param
+        .and_then(|p| p.get("schema"))
+        .and_then(|s| s.get("type"))
+        .or_else(|| param.and_then(|p| p.get("type")))
+        .and_then(|t| t.as_str());
+
+    match param_type {
+        Some("integer") | Some("number") => "1".to_string(),
+        _ => "example".to_string(),
+    }
+}
+
+fn param_example_value(param: &Value) -> Option<String> {
+    let example = param
+        .get("example")
+        .or_else(|| param.get("schema").and_then(|s| s.get("example")))?;
+    Some(match example {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    })
+}
+
+/// Pull an example request body out of an OpenAPI 3 `requestBody` or a
+/// Swagger 2 `in: body` parameter's schema, in that order.
+fn example_body_for_operation(operation: &Value) -> Option<String> {
+    if let Some(example) = operation
+        .pointer("/requestBody/content/application~1json/example")
+        .or_else(|| operation.pointer("/requestBody/content/application~1json/schema/example"))
+    {
+        return Some(example.to_string());
+    }
+
+    operation
+        .get("parameters")
+        .and_then(|p| p.as_array())
+        .and_then(|params| {
+            params
+                .iter()
+                .find(|p| p.get("in").and_then(|i| i.as_str()) == Some("body"))
+        })
+        .and_then(|body_param| body_param.pointer("/schema/example"))
+        .map(|example| example.to_string())
+}
+
+/// Read `components.securitySchemes` (OpenAPI 3) or `securityDefinitions`
+/// (Swagger 2) into a `scheme name -> (header name, placeholder value)` map.
+fn extract_security_schemes(raw: &Value) -> std::collections::HashMap<String, (String, String)> {
+    let mut schemes = std::collections::HashMap::new();
+
+    let definitions = raw
+        .pointer("/components/securitySchemes")
+        .or_else(|| raw.get("securityDefinitions"))
+        .and_then(|v| v.as_object());
+
+    let Some(definitions) = definitions else {
+        return schemes;
+    };
+
+    for (name, def) in definitions {
+        if let Some(header) = header_placeholder_for_scheme(def) {
+            schemes.insert(name.clone(), header);
+        }
+    }
+
+    schemes
+}
+
+fn header_placeholder_for_scheme(def: &Value) -> Option<(String, String)> {
+    match def.get("type").and_then(|t| t.as_str())? {
+        "http" | "basic" => {
+            let scheme = def
+                .get("scheme")
+                .and_then(|s| s.as_str())
+                .unwrap_or("bearer")
+                .to_lowercase();
+            if scheme == "basic" {
+                Some(("Authorization".to_string(), "Basic <BASE64_CREDENTIALS>".to_string()))
+            } else {
+                Some(("Authorization".to_string(), "Bearer <TOKEN>".to_string()))
+            }
+        }
+        "apiKey" => {
+            let header_name = def
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or("X-API-Key");
+            Some((header_name.to_string(), "<API_KEY>".to_string()))
+        }
+        "oauth2" | "openIdConnect" => {
+            Some(("Authorization".to_string(), "Bearer <OAUTH_TOKEN>".to_string()))
+        }
+        _ => None,
+    }
+}
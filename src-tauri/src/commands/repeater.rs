@@ -1,8 +1,101 @@
+use crate::core::intruder::{self, AttackMode, IntruderSummary};
+use crate::core::jobs::JobManager;
+use crate::core::repeater_history::{self, HistoryRecord, ResponseDiff};
+use crate::utils::bounded_body::DEFAULT_MAX_BODY_BYTES;
+use futures::StreamExt;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Certificate, Identity};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::time::Instant;
-use tauri::command;
+use std::time::{Duration, Instant};
+use tauri::{command, AppHandle, Emitter, State};
+
+/// TLS knobs for [`send_request`]. All fields are optional so existing
+/// callers that only pass method/url/headers/body keep working unchanged.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct RequestOptions {
+    /// PEM bundle (client certificate followed by its private key) for
+    /// mutual TLS. Mutually exclusive with `client_identity_pkcs12` -- PEM
+    /// is tried first when both are set.
+    #[serde(rename = "clientIdentityPem", default)]
+    pub client_identity_pem: Option<String>,
+    /// Raw PKCS#12 bytes for mutual TLS, paired with `client_identity_password`.
+    #[serde(rename = "clientIdentityPkcs12", default)]
+    pub client_identity_pkcs12: Option<Vec<u8>>,
+    #[serde(rename = "clientIdentityPassword", default)]
+    pub client_identity_password: Option<String>,
+    /// PEM-encoded custom root CA certificates to trust in addition to the
+    /// platform trust store, for auditing servers behind a private CA.
+    #[serde(rename = "rootCaCerts", default)]
+    pub root_ca_certs: Vec<String>,
+    /// Explicit opt-in to skip certificate validation entirely. Previously
+    /// this was the hard-coded, unconditional behavior of `send_request`.
+    #[serde(rename = "acceptInvalidCerts", default)]
+    pub accept_invalid_certs: bool,
+    /// Cap on the response body, in bytes. The body is truncated (not the
+    /// request failed) once the cap is hit, same as `bounded_body::read_bounded`.
+    #[serde(rename = "maxBodyBytes", default)]
+    pub max_body_bytes: Option<usize>,
+}
+
+/// A single field's file payload for a `multipart/form-data` body.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultipartFilePart {
+    pub name: String,
+    pub filename: String,
+    #[serde(rename = "contentType", default)]
+    pub content_type: Option<String>,
+    pub bytes: Vec<u8>,
+}
+
+/// Typed request body for [`send_request`]. Replaces the old raw-`String`
+/// only body, letting the frontend hand over form/multipart data directly
+/// instead of hand-building encodings and boundaries itself.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum RequestBody {
+    Raw { content: String },
+    UrlEncoded { fields: HashMap<String, String> },
+    Multipart {
+        #[serde(default)]
+        fields: HashMap<String, String>,
+        #[serde(default)]
+        files: Vec<MultipartFilePart>,
+    },
+}
+
+impl Default for RequestBody {
+    fn default() -> Self {
+        RequestBody::Raw { content: String::new() }
+    }
+}
+
+fn apply_body(
+    builder: reqwest::RequestBuilder,
+    body: RequestBody,
+) -> Result<reqwest::RequestBuilder, String> {
+    match body {
+        RequestBody::Raw { content } => Ok(builder.body(content)),
+        RequestBody::UrlEncoded { fields } => Ok(builder.form(&fields)),
+        RequestBody::Multipart { fields, files } => {
+            let mut form = reqwest::multipart::Form::new();
+            for (name, value) in fields {
+                form = form.text(name, value);
+            }
+            for file in files {
+                let mut part = reqwest::multipart::Part::bytes(file.bytes).file_name(file.filename);
+                if let Some(content_type) = file.content_type {
+                    part = part
+                        .mime_str(&content_type)
+                        .map_err(|e| format!("Invalid content type for '{}': {}", file.name, e))?;
+                }
+                form = form.part(file.name, part);
+            }
+            Ok(builder.multipart(form))
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RepeaterResponse {
@@ -13,19 +106,75 @@ pub struct RepeaterResponse {
     body: String,
     #[serde(rename = "durationMs")]
     duration_ms: u64,
+    /// Time from sending the request to the first response body chunk
+    /// arriving, `None` for a body-less response.
+    #[serde(rename = "timeToFirstByteMs")]
+    time_to_first_byte_ms: Option<u64>,
+    /// Whether the body was cut short by `max_body_bytes`.
+    truncated: bool,
+}
+
+/// Progress payload emitted on `repeater://progress` as a streamed response
+/// body is read, so the frontend can render a live progress bar.
+#[derive(Debug, Clone, Serialize)]
+struct RepeaterProgress<'a> {
+    #[serde(rename = "requestId")]
+    request_id: Option<&'a str>,
+    #[serde(rename = "bytesReceived")]
+    bytes_received: usize,
+    #[serde(rename = "elapsedMs")]
+    elapsed_ms: u64,
+}
+
+/// Build the client identity from whichever of `client_identity_pem` /
+/// `client_identity_pkcs12` is set, for mutual-TLS-protected targets.
+fn build_identity(options: &RequestOptions) -> Result<Option<Identity>, String> {
+    if let Some(pem) = &options.client_identity_pem {
+        return Identity::from_pem(pem.as_bytes())
+            .map(Some)
+            .map_err(|e| format!("Invalid client identity PEM: {}", e));
+    }
+    if let Some(der) = &options.client_identity_pkcs12 {
+        let password = options.client_identity_password.as_deref().unwrap_or("");
+        return Identity::from_pkcs12_der(der, password)
+            .map(Some)
+            .map_err(|e| format!("Invalid client identity PKCS#12: {}", e));
+    }
+    Ok(None)
+}
+
+fn build_client(options: &RequestOptions) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder()
+        .use_rustls_tls()
+        .danger_accept_invalid_certs(options.accept_invalid_certs);
+
+    if let Some(identity) = build_identity(options)? {
+        builder = builder.identity(identity);
+    }
+
+    for pem in &options.root_ca_certs {
+        let cert = Certificate::from_pem(pem.as_bytes())
+            .map_err(|e| format!("Invalid root CA certificate: {}", e))?;
+        builder = builder.add_root_certificate(cert);
+    }
+
+    builder.build().map_err(|e| e.to_string())
 }
 
 #[command]
 pub async fn send_request(
+    app: AppHandle,
+    jobs: State<'_, JobManager>,
     method: String,
     url: String,
     headers: HashMap<String, String>,
-    body: String,
+    body: RequestBody,
+    options: Option<RequestOptions>,
+    request_id: Option<String>,
 ) -> Result<RepeaterResponse, String> {
-    let client = reqwest::Client::builder()
-        .danger_accept_invalid_certs(true)
-        .build()
-        .map_err(|e| e.to_string())?;
+    let options = options.unwrap_or_default();
+    let max_body_bytes = options.max_body_bytes.unwrap_or(DEFAULT_MAX_BODY_BYTES);
+    let client = build_client(&options)?;
 
     let method = method
         .parse::<reqwest::Method>()
@@ -42,17 +191,10 @@ pub async fn send_request(
     }
 
     let start = Instant::now();
-    let res = client
-        .request(method, &url)
-        .headers(req_headers)
-        .body(body)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    let request = apply_body(client.request(method, &url).headers(req_headers), body)?;
+    let res = request.send().await.map_err(|e| e.to_string())?;
 
-    let duration = start.elapsed().as_millis() as u64;
     let status = res.status();
-
     let mut resp_headers = HashMap::new();
     for (k, v) in res.headers() {
         if let Ok(val) = v.to_str() {
@@ -60,7 +202,51 @@ pub async fn send_request(
         }
     }
 
-    let body_text = res.text().await.map_err(|e| e.to_string())?;
+    // Registering under `request_id` lets the frontend abort mid-stream via
+    // the existing `cancel_job` command -- same cancellation primitive
+    // discovery jobs use, just keyed by a request id instead of a job id.
+    let token = request_id.as_deref().map(|id| jobs.register(id));
+
+    let mut buf: Vec<u8> = Vec::new();
+    let mut truncated = false;
+    let mut time_to_first_byte_ms = None;
+    let mut stream = res.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        if token.as_ref().is_some_and(|t| t.is_cancelled()) {
+            if let Some(id) = &request_id {
+                jobs.unregister(id);
+            }
+            return Err("Request cancelled".to_string());
+        }
+
+        let Ok(chunk) = chunk else { break };
+        if time_to_first_byte_ms.is_none() {
+            time_to_first_byte_ms = Some(start.elapsed().as_millis() as u64);
+        }
+        buf.extend_from_slice(&chunk);
+
+        let _ = app.emit(
+            "repeater://progress",
+            RepeaterProgress {
+                request_id: request_id.as_deref(),
+                bytes_received: buf.len(),
+                elapsed_ms: start.elapsed().as_millis() as u64,
+            },
+        );
+
+        if buf.len() > max_body_bytes {
+            truncated = true;
+            break;
+        }
+    }
+
+    if let Some(id) = &request_id {
+        jobs.unregister(id);
+    }
+
+    let duration = start.elapsed().as_millis() as u64;
+    let body_text = String::from_utf8_lossy(&buf).into_owned();
 
     Ok(RepeaterResponse {
         status: status.as_u16(),
@@ -68,5 +254,177 @@ pub async fn send_request(
         headers: resp_headers,
         body: body_text,
         duration_ms: duration,
+        time_to_first_byte_ms,
+        truncated,
     })
 }
+
+/// Save a previously received response into the content-addressable history
+/// cache, keyed by `(method, url, request_body)` so repeating an identical
+/// request overwrites its own prior entry instead of piling up duplicates.
+#[command]
+pub fn save_response(
+    method: String,
+    url: String,
+    request_body: String,
+    status: u16,
+    headers: HashMap<String, String>,
+    body: String,
+    duration_ms: u64,
+) -> Result<HistoryRecord, String> {
+    repeater_history::save_response(&method, &url, &request_body, status, headers, body, duration_ms)
+}
+
+#[command]
+pub fn list_history() -> Vec<HistoryRecord> {
+    repeater_history::list()
+}
+
+/// Diff two stored history entries by id: header deltas plus a line-level
+/// body diff, same `similar`-crate approach as `diff::compare_responses`.
+#[command]
+pub fn diff_responses(id_a: String, id_b: String) -> Result<ResponseDiff, String> {
+    repeater_history::diff_responses(&id_a, &id_b)
+}
+
+/// Run an Intruder-style batch attack against a `§`-marked request template,
+/// streaming each attempt's result on `intruder://result` as it completes.
+/// Reuses the repeater's TLS/client-build logic so mTLS-protected targets
+/// work the same way they do for a single-shot `send_request`.
+#[command]
+pub async fn run_intruder_attack(
+    app: AppHandle,
+    method: String,
+    url_template: String,
+    headers: HashMap<String, String>,
+    body_template: String,
+    mode: String,
+    payload_lists: Vec<Vec<String>>,
+    concurrency: usize,
+    grep_regex: Option<String>,
+    grep_extract_group: Option<usize>,
+    selected_response_headers: Vec<String>,
+    options: Option<RequestOptions>,
+) -> Result<IntruderSummary, String> {
+    let options = options.unwrap_or_default();
+    let client = build_client(&options)?;
+    let mode: AttackMode = mode.parse()?;
+
+    intruder::run_attack(
+        app,
+        client,
+        method,
+        url_template,
+        headers,
+        body_template,
+        mode,
+        payload_lists,
+        concurrency,
+        grep_regex,
+        grep_extract_group,
+        selected_response_headers,
+    )
+    .await
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PollResult {
+    response: RepeaterResponse,
+    polls: usize,
+    #[serde(rename = "timedOut")]
+    timed_out: bool,
+}
+
+/// Fingerprint a response as status + the sorted values of `header_names`
+/// (the caller's "headers of interest") + a body hash, so two responses
+/// compare equal only when all three agree -- matching the long-poll
+/// convention of diffing on observable state rather than full byte equality.
+fn fingerprint_response(status: u16, headers: &HashMap<String, String>, body: &str, header_names: &[String]) -> String {
+    let mut parts = vec![status.to_string()];
+    let mut names: Vec<&String> = header_names.iter().collect();
+    names.sort();
+    for name in names {
+        if let Some(value) = headers.get(name) {
+            parts.push(format!("{}={}", name, value));
+        }
+    }
+    parts.push(format!("{:x}", Sha256::digest(body.as_bytes())));
+    parts.join("|")
+}
+
+/// Resend the same request on `interval_ms` until the response's fingerprint
+/// (status + `fingerprint_headers` + body hash) differs from the first
+/// response's baseline, or `max_wait_ms` elapses. Useful for watching an
+/// async operation (job queue, token refresh, cache invalidation) for the
+/// moment its observable behavior changes.
+#[command]
+pub async fn poll_until_change(
+    method: String,
+    url: String,
+    headers: HashMap<String, String>,
+    body: RequestBody,
+    options: Option<RequestOptions>,
+    interval_ms: u64,
+    max_wait_ms: u64,
+    fingerprint_headers: Vec<String>,
+) -> Result<PollResult, String> {
+    let options = options.unwrap_or_default();
+    let max_body_bytes = options.max_body_bytes.unwrap_or(DEFAULT_MAX_BODY_BYTES);
+    let client = build_client(&options)?;
+    let method = method.parse::<reqwest::Method>().map_err(|e| e.to_string())?;
+
+    let mut req_headers = HeaderMap::new();
+    for (k, v) in &headers {
+        if let (Ok(k), Ok(v)) = (HeaderName::from_bytes(k.as_bytes()), HeaderValue::from_str(v)) {
+            req_headers.insert(k, v);
+        }
+    }
+
+    let start = Instant::now();
+    let mut baseline: Option<String> = None;
+    let mut polls = 0usize;
+
+    loop {
+        polls += 1;
+        let poll_start = Instant::now();
+        let request = apply_body(
+            client.request(method.clone(), &url).headers(req_headers.clone()),
+            body.clone(),
+        )?;
+        let res = request.send().await.map_err(|e| e.to_string())?;
+
+        let status = res.status();
+        let mut resp_headers = HashMap::new();
+        for (k, v) in res.headers() {
+            if let Ok(val) = v.to_str() {
+                resp_headers.insert(k.to_string(), val.to_string());
+            }
+        }
+        let bounded = crate::utils::bounded_body::read_bounded(res, max_body_bytes).await;
+        let fingerprint = fingerprint_response(status.as_u16(), &resp_headers, &bounded.text, &fingerprint_headers);
+
+        let response = RepeaterResponse {
+            status: status.as_u16(),
+            status_text: status.canonical_reason().unwrap_or("").to_string(),
+            headers: resp_headers,
+            body: bounded.text,
+            duration_ms: poll_start.elapsed().as_millis() as u64,
+            time_to_first_byte_ms: None,
+            truncated: bounded.truncated,
+        };
+
+        match &baseline {
+            None => baseline = Some(fingerprint),
+            Some(b) if *b != fingerprint => {
+                return Ok(PollResult { response, polls, timed_out: false });
+            }
+            _ => {}
+        }
+
+        if start.elapsed().as_millis() as u64 >= max_wait_ms {
+            return Ok(PollResult { response, polls, timed_out: true });
+        }
+
+        tokio::time::sleep(Duration::from_millis(interval_ms)).await;
+    }
+}
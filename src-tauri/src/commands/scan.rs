@@ -15,11 +15,23 @@ pub async fn rescan_asset(app: AppHandle, id: i64) -> Result<(), String> {
     if let Some(asset) = assets.iter().find(|a| a.id == id) {
         let url = asset.url.clone();
         let method = asset.method.clone();
+        let previous_hash = asset.content_hash.clone();
+        let previous_body = asset.response_body.clone();
+        let previous_resp_headers = asset.response_headers.clone();
         let app_handle = app.clone();
 
         tauri::async_runtime::spawn(async move {
             let db_state = app_handle.state::<SqliteDatabase>();
-            let result = scan_url(&db_state.client, &url, &method, &db_state.rate_limiter).await;
+            let mut result = scan_url(&db_state.client, &url, &method, &db_state.rate_limiter).await;
+
+            let drifted = !previous_hash.is_empty() && previous_hash != result.content_hash;
+            if !drifted && !previous_hash.is_empty() {
+                // Identical body as last scan -- avoid re-storing the same
+                // bytes a second time.
+                result.response_body = previous_body;
+                result.response_headers = previous_resp_headers;
+            }
+
             let _ = db_state.update_scan_result(
                 id,
                 &result.status,
@@ -30,7 +42,11 @@ pub async fn rescan_asset(app: AppHandle, id: i64) -> Result<(), String> {
                 &result.response_body,
                 &result.request_headers,
                 &result.request_body,
+                &result.content_hash,
             );
+            if drifted {
+                let _ = app_handle.emit("scan-drift", id);
+            }
             let _ = app_handle.emit("scan-update", id);
         });
     }
@@ -1,4 +1,5 @@
-use crate::core::data::{RequestSequence, SequenceStep};
+use crate::core::sequence_state::SequenceJarStore;
+use crate::data::{RequestSequence, SequenceStep};
 use crate::db::SqliteDatabase;
 use tauri::State;
 
@@ -60,21 +61,79 @@ pub async fn list_sequences(db: State<'_, SqliteDatabase>) -> Result<Vec<Request
 #[tauri::command]
 pub async fn execute_sequence_step(
     db: State<'_, SqliteDatabase>,
+    jars: State<'_, SequenceJarStore>,
     step: SequenceStep,
     context: std::collections::HashMap<String, String>,
 ) -> Result<serde_json::Value, String> {
-    use crate::utils::sequence_engine::{extract_variables, substitute_variables};
+    let outcome = run_step(&db, &jars, &step, &context).await?;
+    Ok(serde_json::json!({
+        "status_code": outcome.status_code,
+        "response_body": outcome.response_body,
+        "response_headers": outcome.response_headers,
+        "updated_context": outcome.updated_context,
+        "final_url": outcome.final_url
+    }))
+}
+
+/// Run an ordered chain of steps, threading each step's `updated_context`
+/// (extracted variables plus auto-harvested CSRF defenses) into the next --
+/// e.g. a login step whose `captures` pull a bearer token out of the
+/// response body, followed by authenticated steps whose `request_headers`
+/// template reads `Authorization: Bearer {{token}}`. Stops and returns the
+/// results gathered so far on the first failed request, since a later step
+/// substituting variables a failed earlier step never produced would only
+/// replay the unresolved `{{name}}` placeholder literally.
+#[tauri::command]
+pub async fn execute_sequence_chain(
+    db: State<'_, SqliteDatabase>,
+    jars: State<'_, SequenceJarStore>,
+    steps: Vec<SequenceStep>,
+    initial_context: std::collections::HashMap<String, String>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let mut context = initial_context;
+    let mut results = Vec::new();
+
+    for step in steps {
+        let outcome = run_step(&db, &jars, &step, &context).await?;
+        context = outcome.updated_context.clone();
+        results.push(serde_json::json!({
+            "status_code": outcome.status_code,
+            "response_body": outcome.response_body,
+            "response_headers": outcome.response_headers,
+            "updated_context": outcome.updated_context,
+            "final_url": outcome.final_url
+        }));
+    }
+
+    Ok(results)
+}
+
+struct StepOutcome {
+    status_code: u16,
+    response_body: String,
+    response_headers: String,
+    updated_context: std::collections::HashMap<String, String>,
+    final_url: String,
+}
+
+async fn run_step(
+    db: &State<'_, SqliteDatabase>,
+    jars: &State<'_, SequenceJarStore>,
+    step: &SequenceStep,
+    context: &std::collections::HashMap<String, String>,
+) -> Result<StepOutcome, String> {
+    use crate::utils::sequence_engine::{capture_csrf_defenses, extract_variables, substitute_variables};
 
     // 1. Substitute variables
-    let final_url = substitute_variables(&step.url, &context);
+    let final_url = substitute_variables(&step.url, context);
     let final_body = step
         .request_body
         .as_ref()
-        .map(|b| substitute_variables(b, &context));
+        .map(|b| substitute_variables(b, context));
     let final_headers_str = step
         .request_headers
         .as_ref()
-        .map(|h| substitute_variables(h, &context));
+        .map(|h| substitute_variables(h, context));
 
     // 2. Setup request
     let method = match step.method.to_uppercase().as_str() {
@@ -85,7 +144,12 @@ pub async fn execute_sequence_step(
         _ => reqwest::Method::GET,
     };
 
-    let mut rb = db.client.request(method, &final_url);
+    let jar = jars.jar_for(&step.sequence_id);
+    let client = crate::core::http_client::HttpClientSettings::load(db)
+        .build_client_with_cookie_jar(std::time::Duration::from_secs(10), jar)
+        .unwrap_or_else(|_| db.client.clone());
+
+    let mut rb = client.request(method, &final_url);
     if let Some(body) = final_body {
         rb = rb.body(body);
     }
@@ -102,7 +166,16 @@ pub async fn execute_sequence_step(
     let resp = rb.send().await.map_err(|e| e.to_string())?;
     let status = resp.status().as_u16();
     let resp_headers_map = resp.headers().clone();
-    let resp_body = resp.text().await.unwrap_or_default();
+    let bounded = crate::utils::bounded_body::read_bounded(
+        resp,
+        crate::utils::bounded_body::DEFAULT_MAX_BODY_BYTES,
+    )
+    .await;
+    let resp_body = if bounded.truncated {
+        format!("{}\n[Truncated (limit exceeded)]", bounded.text)
+    } else {
+        bounded.text
+    };
 
     let mut resp_headers_str = String::new();
     for (k, v) in resp_headers_map.iter() {
@@ -112,16 +185,22 @@ pub async fn execute_sequence_step(
     // 4. Extract new variables
     let new_vars = extract_variables(&step.captures, &resp_body, &resp_headers_str);
 
+    // 5. Auto-harvest CSRF defenses (meta tag, hidden form field, Set-Cookie)
+    // so the next step's variable substitution picks them up without the
+    // user hand-authoring a VariableCapture for them.
+    let csrf_vars = capture_csrf_defenses(&resp_body, &resp_headers_str);
+
     let mut updated_context = context.clone();
+    updated_context.extend(csrf_vars);
     updated_context.extend(new_vars);
 
-    Ok(serde_json::json!({
-        "status_code": status,
-        "response_body": resp_body,
-        "response_headers": resp_headers_str,
-        "updated_context": updated_context,
-        "final_url": final_url
-    }))
+    Ok(StepOutcome {
+        status_code: status,
+        response_body: resp_body,
+        response_headers: resp_headers_str,
+        updated_context,
+        final_url,
+    })
 }
 
 #[tauri::command]
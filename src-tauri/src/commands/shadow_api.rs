@@ -70,7 +70,12 @@ pub async fn import_openapi_spec_and_detect_shadow_apis(
 
         if is_documented {
             documented_count += 1;
-            let _ = db.update_asset_documentation(asset.id, true);
+            let _ = db.update_asset_documentation(
+                asset.id,
+                true,
+                None,
+                Some("openapi_spec_import"),
+            );
         } else {
             shadow_apis.push(ShadowApiAsset {
                 id: asset.id,
@@ -78,7 +83,12 @@ pub async fn import_openapi_spec_and_detect_shadow_apis(
                 method: asset.method.clone(),
                 risk_level: "High".to_string(), // Default risk for shadow APIs
             });
-            let _ = db.update_asset_documentation(asset.id, false);
+            let _ = db.update_asset_documentation(
+                asset.id,
+                false,
+                None,
+                Some("openapi_spec_import"),
+            );
         }
     }
 
@@ -101,7 +111,12 @@ pub async fn clear_documentation_status(app: AppHandle) -> Result<(), String> {
     let assets: Vec<crate::db::Asset> = db.get_assets().map_err(|e| e.to_string())?;
 
     for asset in assets {
-        let _ = db.update_asset_documentation(asset.id, true); // Reset to default true (meaning documented or at least not flag as shadow)
+        let _ = db.update_asset_documentation(
+            asset.id,
+            true,
+            None,
+            Some("clear_documentation_status"),
+        ); // Reset to default true (meaning documented or at least not flag as shadow)
     }
 
     Ok(())
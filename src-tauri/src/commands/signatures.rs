@@ -0,0 +1,75 @@
+use crate::core::detector::tech_stack::builtin_signatures;
+use crate::core::detector::Signature;
+use crate::db::SqliteDatabase;
+use regex::Regex;
+use tauri::State;
+
+/// Setting key under which user-added signatures are stored, serialized as
+/// a JSON array of [`Signature`].
+const CUSTOM_SIGNATURES_SETTING: &str = "custom_signatures";
+
+fn load_custom_signatures(db: &SqliteDatabase) -> Result<Vec<Signature>, String> {
+    match db
+        .get_setting(CUSTOM_SIGNATURES_SETTING)
+        .map_err(|e| e.to_string())?
+    {
+        Some(json) if !json.is_empty() => serde_json::from_str(&json)
+            .map_err(|e| format!("Failed to parse custom_signatures setting: {}", e)),
+        _ => Ok(Vec::new()),
+    }
+}
+
+fn save_custom_signatures(db: &SqliteDatabase, signatures: &[Signature]) -> Result<(), String> {
+    let json = serde_json::to_string(signatures).map_err(|e| e.to_string())?;
+    db.set_setting(CUSTOM_SIGNATURES_SETTING, &json)
+        .map_err(|e| e.to_string())
+}
+
+/// Every signature this installation scans with: the built-in set plus
+/// whatever's been added via [`add_signature`].
+#[tauri::command]
+pub fn list_signatures(state: State<SqliteDatabase>) -> Result<Vec<Signature>, String> {
+    let mut signatures = builtin_signatures();
+    signatures.extend(load_custom_signatures(state.inner())?);
+    Ok(signatures)
+}
+
+/// Add a user-defined signature under the `custom_signatures` setting.
+/// Rejects a pattern that doesn't compile, and an `id` that collides with
+/// an existing signature (built-in or custom), since `delete_signature`
+/// and re-scans both key off `id`.
+#[tauri::command]
+pub fn add_signature(state: State<SqliteDatabase>, signature: Signature) -> Result<(), String> {
+    Regex::new(&signature.pattern).map_err(|e| format!("Invalid pattern: {}", e))?;
+
+    let db = state.inner();
+    let mut custom = load_custom_signatures(db)?;
+    let builtins = builtin_signatures();
+    if builtins.iter().any(|s| s.id == signature.id) || custom.iter().any(|s| s.id == signature.id)
+    {
+        return Err(format!("Signature id '{}' already exists", signature.id));
+    }
+
+    custom.push(signature);
+    save_custom_signatures(db, &custom)
+}
+
+/// Remove a signature previously added via [`add_signature`]. Built-in
+/// signatures aren't stored under `custom_signatures` and can't be deleted
+/// this way.
+#[tauri::command]
+pub fn delete_signature(state: State<SqliteDatabase>, id: String) -> Result<(), String> {
+    let db = state.inner();
+    let mut custom = load_custom_signatures(db)?;
+    let original_len = custom.len();
+    custom.retain(|s| s.id != id);
+
+    if custom.len() == original_len {
+        return Err(format!(
+            "No custom signature with id '{}' found (built-in signatures cannot be deleted)",
+            id
+        ));
+    }
+
+    save_custom_signatures(db, &custom)
+}
@@ -0,0 +1,261 @@
+use crate::core::detector::bola::{id_pattern, json_id_pattern, BolaFinding};
+use crate::core::detector::FindingSeverity;
+use crate::utils::sequence_engine::substitute_variables;
+use std::collections::HashMap;
+
+/// One resolved attacker request carrying the victim's resource id swapped
+/// into the position the same static `id_pattern`/`json_id_pattern` regex
+/// would flag, plus enough context to describe where it came from.
+struct IdorCandidate {
+    location: &'static str,
+    victim_id: String,
+    url: String,
+    body: Option<String>,
+}
+
+/// Cross-account IDOR/BOLA tester: resolve `url_template`/`body_template`
+/// once under the victim's context and once under the attacker's, locate
+/// the candidate resource id in the victim's resolved request (the same
+/// `id_pattern`/`json_id_pattern` [`detect_bola_patterns`] uses), swap it
+/// into the attacker's request in place of the attacker's own id, and
+/// replay that swapped request under the attacker's credentials.
+///
+/// In `dry_run` mode no request is sent -- the candidate substitution is
+/// reported as a `Medium` finding so a user can review what would be probed
+/// before opting into live traffic. Otherwise, a `2xx` attacker response
+/// whose body echoes the victim's resource id is reported as `Critical`:
+/// the attacker was able to read another account's resource by ID alone.
+pub async fn probe_cross_account_idor(
+    client: &reqwest::Client,
+    url_template: &str,
+    method: &str,
+    body_template: Option<&str>,
+    victim_context: &HashMap<String, String>,
+    attacker_context: &HashMap<String, String>,
+    dry_run: bool,
+) -> Vec<BolaFinding> {
+    let candidates = build_candidates(url_template, method, body_template, victim_context, attacker_context);
+
+    if dry_run {
+        return candidates
+            .into_iter()
+            .map(|candidate| dry_run_finding(method, &candidate))
+            .collect();
+    }
+
+    let mut findings = Vec::new();
+    let req_method = reqwest::Method::from_bytes(method.as_bytes()).unwrap_or(reqwest::Method::GET);
+
+    for candidate in candidates {
+        let mut rb = client.request(req_method.clone(), &candidate.url);
+        if let Some(body) = &candidate.body {
+            rb = rb.body(body.clone());
+        }
+
+        let Ok(resp) = rb.send().await else {
+            continue;
+        };
+        let status = resp.status().as_u16();
+        if !(200..300).contains(&status) {
+            continue;
+        }
+        let Ok(text) = resp.text().await else {
+            continue;
+        };
+        if text.contains(&candidate.victim_id) {
+            findings.push(confirmed_finding(status, &candidate));
+        }
+    }
+
+    findings
+}
+
+/// Build every candidate swap: one for a numeric/UUID segment in the URL
+/// path, one for a JSON id field in the body. Either, both, or neither may
+/// apply depending on what the template actually contains.
+fn build_candidates(
+    url_template: &str,
+    method: &str,
+    body_template: Option<&str>,
+    victim_context: &HashMap<String, String>,
+    attacker_context: &HashMap<String, String>,
+) -> Vec<IdorCandidate> {
+    let _ = method;
+    let mut candidates = Vec::new();
+
+    let victim_url = substitute_variables(url_template, victim_context);
+    let attacker_url = substitute_variables(url_template, attacker_context);
+
+    if let Some(victim_id) = id_pattern()
+        .captures(&victim_url)
+        .and_then(|c| c.name("id"))
+        .map(|m| m.as_str().to_string())
+    {
+        if let Some(swapped_url) = swap_first_id(&attacker_url, &victim_id) {
+            candidates.push(IdorCandidate {
+                location: "URL",
+                victim_id,
+                url: swapped_url,
+                body: None,
+            });
+        }
+    }
+
+    if let Some(body_template) = body_template {
+        let victim_body = substitute_variables(body_template, victim_context);
+        let attacker_body = substitute_variables(body_template, attacker_context);
+
+        if let Some(victim_id) = json_id_pattern()
+            .captures(&victim_body)
+            .and_then(|c| c.name("val"))
+            .map(|m| m.as_str().trim_matches(['"', '\'']).to_string())
+        {
+            if let Some(swapped_body) = swap_first_json_id(&attacker_body, &victim_id) {
+                candidates.push(IdorCandidate {
+                    location: "Body",
+                    victim_id,
+                    url: attacker_url.clone(),
+                    body: Some(swapped_body),
+                });
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Replace the first `id_pattern` match in `url` with `replacement`.
+fn swap_first_id(url: &str, replacement: &str) -> Option<String> {
+    let caps = id_pattern().captures(url)?;
+    let m = caps.name("id")?;
+    let mut swapped = url.to_string();
+    swapped.replace_range(m.start()..m.end(), replacement);
+    Some(swapped)
+}
+
+/// Replace the first `json_id_pattern` value match in `body` with
+/// `replacement`, re-quoting it since the pattern's `val` group includes the
+/// surrounding quotes for string-typed ids but not for bare numeric ones.
+fn swap_first_json_id(body: &str, replacement: &str) -> Option<String> {
+    let caps = json_id_pattern().captures(body)?;
+    let m = caps.name("val")?;
+    let is_numeric = replacement.chars().all(|c| c.is_ascii_digit());
+    let replacement_literal = if is_numeric {
+        replacement.to_string()
+    } else {
+        format!("\"{}\"", replacement)
+    };
+    let mut swapped = body.to_string();
+    swapped.replace_range(m.start()..m.end(), &replacement_literal);
+    Some(swapped)
+}
+
+fn dry_run_finding(method: &str, candidate: &IdorCandidate) -> BolaFinding {
+    BolaFinding {
+        finding_type: "Cross-Account BOLA Candidate".to_string(),
+        severity: FindingSeverity::Medium,
+        location: candidate.url.clone(),
+        description: format!(
+            "Dry-run: would replay {} with victim resource id '{}' substituted into the attacker's {} under attacker credentials.",
+            method, candidate.victim_id, candidate.location
+        ),
+        resource_pattern: candidate.victim_id.clone(),
+        is_predictable: false,
+        remediation: "Review whether the server enforces object ownership for this resource before enabling live probing.".to_string(),
+        start_offset: 0,
+        end_offset: 0,
+    }
+}
+
+fn confirmed_finding(status: u16, candidate: &IdorCandidate) -> BolaFinding {
+    BolaFinding {
+        finding_type: "Cross-Account BOLA Confirmed".to_string(),
+        severity: FindingSeverity::Critical,
+        location: candidate.url.clone(),
+        description: format!(
+            "Attacker credentials received a {} response whose body echoed victim resource id '{}' ({} parameter) -- the server did not verify object ownership.",
+            status, candidate.victim_id, candidate.location
+        ),
+        resource_pattern: candidate.victim_id.clone(),
+        is_predictable: true,
+        remediation: "Enforce server-side authorization that checks the authenticated user owns the requested resource, not just that the id exists.".to_string(),
+        start_offset: 0,
+        end_offset: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_build_candidates_swaps_url_path_id() {
+        let victim = context(&[("id", "123"), ("token", "victim-token")]);
+        let attacker = context(&[("id", "456"), ("token", "attacker-token")]);
+
+        let candidates = build_candidates(
+            "https://api.example.com/users/{{id}}/orders",
+            "GET",
+            None,
+            &victim,
+            &attacker,
+        );
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].location, "URL");
+        assert_eq!(candidates[0].victim_id, "123");
+        assert_eq!(candidates[0].url, "https://api.example.com/users/123/orders");
+    }
+
+    #[test]
+    fn test_build_candidates_swaps_json_body_id() {
+        let victim = context(&[("id", "123")]);
+        let attacker = context(&[("id", "456")]);
+
+        let candidates = build_candidates(
+            "https://api.example.com/orders",
+            "POST",
+            Some(r#"{"account_id": {{id}}}"#),
+            &victim,
+            &attacker,
+        );
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].location, "Body");
+        assert_eq!(candidates[0].victim_id, "123");
+        assert_eq!(candidates[0].body.as_deref(), Some(r#"{"account_id": 123}"#));
+    }
+
+    #[test]
+    fn test_dry_run_finding_is_medium_and_reports_no_traffic() {
+        let candidate = IdorCandidate {
+            location: "URL",
+            victim_id: "123".to_string(),
+            url: "https://api.example.com/users/123".to_string(),
+            body: None,
+        };
+        let finding = dry_run_finding("GET", &candidate);
+        assert_eq!(finding.severity, FindingSeverity::Medium);
+        assert!(finding.description.contains("Dry-run"));
+    }
+
+    #[test]
+    fn test_confirmed_finding_is_critical() {
+        let candidate = IdorCandidate {
+            location: "URL",
+            victim_id: "123".to_string(),
+            url: "https://api.example.com/users/123".to_string(),
+            body: None,
+        };
+        let finding = confirmed_finding(200, &candidate);
+        assert_eq!(finding.severity, FindingSeverity::Critical);
+        assert_eq!(finding.finding_type, "Cross-Account BOLA Confirmed");
+    }
+}
@@ -1,7 +1,23 @@
 use super::bola::{generate_bola_variants, BolaFinding};
-use super::sqli::{check_sqli_response, generate_sqli_payloads, SqliFinding};
+use super::sqli::{
+    bodies_differ_significantly, check_sqli_response, dbms_from_error_label,
+    dbms_from_time_based_template, generate_sqli_payloads, generate_time_based_payload_templates,
+    inject_first_query_param, render_time_based_payload, SqliFinding, SqliMode,
+    BOOLEAN_FALSE_PAYLOAD, BOOLEAN_TRUE_PAYLOAD,
+};
+use crate::core::dns_guard::ScopeGuardedResolver;
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+// Time-based blind SQLi: baseline delay, then a confirmation probe at this
+// many additional seconds to verify the response time scales with the
+// injected delay rather than being a one-off network hiccup.
+const TIME_BASED_BASE_DELAY_SECS: u64 = 5;
+const TIME_BASED_CONFIRM_DELAY_SECS: u64 = 8;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActiveScanResult {
@@ -13,11 +29,22 @@ pub struct ActiveScanResult {
     pub sqli_findings: Vec<SqliFinding>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+struct ActiveScanProgress<'a> {
+    asset_id: i64,
+    kind: &'a str,
+    bola_finding: Option<&'a BolaFinding>,
+    sqli_finding: Option<&'a SqliFinding>,
+}
+
 pub async fn scan_active_target(
     asset_id: i64,
     url: String,
     method: String,
     headers: HashMap<String, String>,
+    allow_internal_targets: bool,
+    concurrency: usize,
+    app: AppHandle,
 ) -> ActiveScanResult {
     let mut result = ActiveScanResult {
         asset_id,
@@ -28,7 +55,37 @@ pub async fn scan_active_target(
         sqli_findings: Vec::new(),
     };
 
-    let client = reqwest::Client::new();
+    // Belt-and-suspenders alongside `ScopeGuardedResolver`: an IP-literal
+    // authority (`http://169.254.169.254/`) never goes through `Resolve` on
+    // some connector paths, so check it directly rather than trust that the
+    // custom resolver is always consulted.
+    if !allow_internal_targets {
+        if let Some(ip) = url::Url::parse(&url)
+            .ok()
+            .and_then(|u| u.host_str().and_then(|h| h.parse::<std::net::IpAddr>().ok()))
+        {
+            if crate::core::dns_guard::is_blocked_ip(&ip) {
+                result.log.push(format!(
+                    "Blocked SSRF target: {} is a disallowed literal IP address",
+                    url
+                ));
+                result.status = "Failed".to_string();
+                return result;
+            }
+        }
+    }
+
+    let client = match reqwest::Client::builder()
+        .dns_resolver(Arc::new(ScopeGuardedResolver::new(allow_internal_targets)))
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            result.log.push(format!("Failed to build scanner client: {}", e));
+            result.status = "Failed".to_string();
+            return result;
+        }
+    };
     let req_method = reqwest::Method::from_bytes(method.as_bytes()).unwrap_or(reqwest::Method::GET);
     let mut header_map = reqwest::header::HeaderMap::new();
     for (key, value) in headers {
@@ -39,6 +96,8 @@ pub async fn scan_active_target(
         }
     }
 
+    let concurrency = concurrency.max(1);
+
     // 1. BOLA Check
     result.log.push("Starting BOLA check...".to_string());
     let variants = generate_bola_variants(&url);
@@ -50,69 +109,247 @@ pub async fn scan_active_target(
         result
             .log
             .push(format!("Generated {} BOLA variants.", variants.len()));
-        for variant in variants {
-            match client
-                .request(req_method.clone(), &variant)
-                .headers(header_map.clone())
-                .send()
-                .await
-            {
-                Ok(resp) => {
-                    let status = resp.status().as_u16();
-                    if status >= 200 && status < 300 {
-                        result.bola_findings.push(BolaFinding {
-                            original_url: url.clone(),
-                            tested_url: variant.clone(),
-                            status,
-                            evidence: "Success Status Code (Possible BOLA)".to_string(),
-                        });
+
+        let probes = stream::iter(variants.into_iter().map(|variant| {
+            let client = client.clone();
+            let req_method = req_method.clone();
+            let header_map = header_map.clone();
+            let original_url = url.clone();
+            let app = app.clone();
+            async move {
+                match client
+                    .request(req_method, &variant)
+                    .headers(header_map)
+                    .send()
+                    .await
+                {
+                    Ok(resp) => {
+                        let status = resp.status().as_u16();
+                        if (200..300).contains(&status) {
+                            let finding = BolaFinding {
+                                original_url,
+                                tested_url: variant,
+                                status,
+                                evidence: "Success Status Code (Possible BOLA)".to_string(),
+                            };
+                            let _ = app.emit(
+                                "active-scan-progress",
+                                ActiveScanProgress {
+                                    asset_id,
+                                    kind: "bola",
+                                    bola_finding: Some(&finding),
+                                    sqli_finding: None,
+                                },
+                            );
+                            Ok(Some(finding))
+                        } else {
+                            Ok(None)
+                        }
                     }
-                }
-                Err(e) => {
-                    result
-                        .log
-                        .push(format!("Failed to scan {}: {}", variant, e));
+                    Err(e) => Err(format!("Failed to scan {}: {}", variant, e)),
                 }
             }
+        }))
+        .buffer_unordered(concurrency)
+        .collect::<Vec<_>>()
+        .await;
+
+        for probe in probes {
+            match probe {
+                Ok(Some(finding)) => result.bola_findings.push(finding),
+                Ok(None) => {}
+                Err(e) => result.log.push(e),
+            }
         }
     }
 
     // 2. SQLi Check
     result.log.push("Starting SQLi check...".to_string());
-    if url.contains("?") {
+    if url.contains('?') {
         let payloads = generate_sqli_payloads();
-        for payload in payloads {
-            if let Ok(mut parsed) = url::Url::parse(&url) {
-                let mut pairs: Vec<(String, String)> = parsed.query_pairs().into_owned().collect();
-                if !pairs.is_empty() {
-                    // Inject into first param
-                    let original_val = pairs[0].1.clone();
-                    pairs[0].1 = format!("{}{}", original_val, payload);
-
-                    parsed.query_pairs_mut().clear().extend_pairs(pairs);
-                    let target_url = parsed.to_string();
-
-                    match client
-                        .request(req_method.clone(), &target_url)
-                        .headers(header_map.clone())
-                        .send()
-                        .await
-                    {
-                        Ok(resp) => {
-                            if let Ok(text) = resp.text().await {
-                                if let Some(db_error) = check_sqli_response(&text) {
-                                    result.sqli_findings.push(SqliFinding {
-                                        parameter: "query_param".to_string(),
-                                        payload: payload.clone(),
-                                        evidence: db_error,
-                                        severity: "High".to_string(),
-                                    });
-                                }
+
+        let probes = stream::iter(payloads.into_iter().filter_map(|payload| {
+            let target_url = inject_first_query_param(&url, &payload)?;
+
+            let client = client.clone();
+            let req_method = req_method.clone();
+            let header_map = header_map.clone();
+            let app = app.clone();
+            Some(async move {
+                match client
+                    .request(req_method, &target_url)
+                    .headers(header_map)
+                    .send()
+                    .await
+                {
+                    Ok(resp) => {
+                        if let Ok(text) = resp.text().await {
+                            if let Some(db_error) = check_sqli_response(&text) {
+                                let dbms = dbms_from_error_label(&db_error);
+                                let finding = SqliFinding {
+                                    parameter: "query_param".to_string(),
+                                    payload,
+                                    evidence: db_error,
+                                    severity: "High".to_string(),
+                                    mode: SqliMode::ErrorBased,
+                                    dbms,
+                                };
+                                let _ = app.emit(
+                                    "active-scan-progress",
+                                    ActiveScanProgress {
+                                        asset_id,
+                                        kind: "sqli",
+                                        bola_finding: None,
+                                        sqli_finding: Some(&finding),
+                                    },
+                                );
+                                return Some(finding);
                             }
                         }
-                        Err(_) => {}
+                        None
+                    }
+                    Err(_) => None,
+                }
+            })
+        }))
+        .buffer_unordered(concurrency)
+        .collect::<Vec<_>>()
+        .await;
+
+        result.sqli_findings = probes.into_iter().flatten().collect();
+
+        // 3 & 4. Time-based and boolean-based blind SQLi checks, both keyed
+        // off the same per-parameter baseline (the unmodified query with an
+        // empty payload appended). Run sequentially (not through
+        // buffer_unordered) since concurrent requests would contend for
+        // server resources and corrupt the latency measurements the
+        // time-based check depends on.
+        if let Some(baseline_url) = inject_first_query_param(&url, "") {
+            // 3. Boolean-based: a TRUE and a FALSE payload must both return
+            // 200, and their bodies must diverge from each other (not just
+            // from the baseline) for the parameter to be flagged.
+            result
+                .log
+                .push("Starting boolean-based blind SQLi check...".to_string());
+            if let (Some(true_url), Some(false_url)) = (
+                inject_first_query_param(&url, BOOLEAN_TRUE_PAYLOAD),
+                inject_first_query_param(&url, BOOLEAN_FALSE_PAYLOAD),
+            ) {
+                if let (Ok(true_resp), Ok(false_resp)) = (
+                    fetch_response(&client, req_method.clone(), &header_map, &true_url).await,
+                    fetch_response(&client, req_method.clone(), &header_map, &false_url).await,
+                ) {
+                    if true_resp.0 == 200
+                        && false_resp.0 == 200
+                        && bodies_differ_significantly(&true_resp.1, &false_resp.1)
+                    {
+                        let finding = SqliFinding {
+                            parameter: "query_param".to_string(),
+                            payload: format!(
+                                "{} / {}",
+                                BOOLEAN_TRUE_PAYLOAD, BOOLEAN_FALSE_PAYLOAD
+                            ),
+                            evidence: format!(
+                                "TRUE and FALSE conditions returned 200 with diverging bodies ({} bytes vs {} bytes)",
+                                true_resp.1.len(),
+                                false_resp.1.len()
+                            ),
+                            severity: "High".to_string(),
+                            mode: SqliMode::BooleanBased,
+                            dbms: None,
+                        };
+                        let _ = app.emit(
+                            "active-scan-progress",
+                            ActiveScanProgress {
+                                asset_id,
+                                kind: "sqli",
+                                bola_finding: None,
+                                sqli_finding: Some(&finding),
+                            },
+                        );
+                        result.sqli_findings.push(finding);
+                    }
+                }
+            }
+
+            // 4. Time-based.
+            result
+                .log
+                .push("Starting time-based blind SQLi check...".to_string());
+            match time_request(&client, req_method.clone(), &header_map, &baseline_url).await {
+                Ok(baseline) => {
+                    'templates: for template in generate_time_based_payload_templates() {
+                        let probe_payload =
+                            render_time_based_payload(template, TIME_BASED_BASE_DELAY_SECS);
+                        let Some(probe_url) = inject_first_query_param(&url, &probe_payload)
+                        else {
+                            continue;
+                        };
+                        let Ok(probe_latency) =
+                            time_request(&client, req_method.clone(), &header_map, &probe_url)
+                                .await
+                        else {
+                            continue;
+                        };
+                        let expected_min = baseline
+                            + Duration::from_secs(TIME_BASED_BASE_DELAY_SECS)
+                            - Duration::from_secs(2);
+                        if probe_latency < expected_min {
+                            continue;
+                        }
+
+                        // Confirm with a different delay to rule out a slow
+                        // server rather than an actual injection.
+                        let confirm_payload =
+                            render_time_based_payload(template, TIME_BASED_CONFIRM_DELAY_SECS);
+                        let Some(confirm_url) = inject_first_query_param(&url, &confirm_payload)
+                        else {
+                            continue;
+                        };
+                        let Ok(confirm_latency) =
+                            time_request(&client, req_method.clone(), &header_map, &confirm_url)
+                                .await
+                        else {
+                            continue;
+                        };
+                        let confirm_min = baseline
+                            + Duration::from_secs(TIME_BASED_CONFIRM_DELAY_SECS)
+                            - Duration::from_secs(2);
+                        if confirm_latency < confirm_min {
+                            continue;
+                        }
+
+                        let finding = SqliFinding {
+                            parameter: "query_param".to_string(),
+                            payload: probe_payload,
+                            evidence: format!(
+                                "Response time scaled with injected delay (baseline={:.2}s, {}s delay={:.2}s, {}s delay={:.2}s)",
+                                baseline.as_secs_f32(),
+                                TIME_BASED_BASE_DELAY_SECS,
+                                probe_latency.as_secs_f32(),
+                                TIME_BASED_CONFIRM_DELAY_SECS,
+                                confirm_latency.as_secs_f32(),
+                            ),
+                            severity: "High".to_string(),
+                            mode: SqliMode::TimeBased,
+                            dbms: dbms_from_time_based_template(template),
+                        };
+                        let _ = app.emit(
+                            "active-scan-progress",
+                            ActiveScanProgress {
+                                asset_id,
+                                kind: "sqli",
+                                bola_finding: None,
+                                sqli_finding: Some(&finding),
+                            },
+                        );
+                        result.sqli_findings.push(finding);
+                        break 'templates;
                     }
                 }
+                Err(e) => result
+                    .log
+                    .push(format!("Failed to establish baseline latency: {}", e)),
             }
         }
     } else {
@@ -124,3 +361,36 @@ pub async fn scan_active_target(
     result.status = "Completed".to_string();
     result
 }
+
+async fn time_request(
+    client: &reqwest::Client,
+    method: reqwest::Method,
+    headers: &reqwest::header::HeaderMap,
+    url: &str,
+) -> Result<Duration, reqwest::Error> {
+    let start = Instant::now();
+    client
+        .request(method, url)
+        .headers(headers.clone())
+        .send()
+        .await?;
+    Ok(start.elapsed())
+}
+
+/// Fetch `url` and return its status code and body, for checks (boolean-based
+/// SQLi) that compare response content rather than latency.
+async fn fetch_response(
+    client: &reqwest::Client,
+    method: reqwest::Method,
+    headers: &reqwest::header::HeaderMap,
+    url: &str,
+) -> Result<(u16, String), reqwest::Error> {
+    let resp = client
+        .request(method, url)
+        .headers(headers.clone())
+        .send()
+        .await?;
+    let status = resp.status().as_u16();
+    let body = resp.text().await?;
+    Ok((status, body))
+}
@@ -1,12 +1,27 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+/// Which check surfaced a [`SqliFinding`]. Error-based stays the fast first
+/// pass since it only needs one response; time-based and boolean-based both
+/// need a baseline request to compare against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SqliMode {
+    ErrorBased,
+    TimeBased,
+    BooleanBased,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SqliFinding {
     pub parameter: String,
     pub payload: String,
     pub evidence: String,
     pub severity: String,
+    pub mode: SqliMode,
+    /// Best-effort guess at the backend DBMS, inferred from the error
+    /// pattern matched or the sleep payload template that triggered the
+    /// delay. `None` for boolean-based findings, which carry no DBMS signal.
+    pub dbms: Option<String>,
 }
 
 pub fn generate_sqli_payloads() -> Vec<String> {
@@ -20,6 +35,103 @@ pub fn generate_sqli_payloads() -> Vec<String> {
     ]
 }
 
+/// Delay payload templates for time-based blind detection, keyed by the
+/// `{d}` placeholder that gets substituted with the probe delay (seconds).
+/// Covers MySQL (`SLEEP`), SQL Server (`WAITFOR DELAY`), and PostgreSQL
+/// (`pg_sleep`) — the databases `check_sqli_response` already recognizes by
+/// error string.
+pub fn generate_time_based_payload_templates() -> Vec<&'static str> {
+    vec![
+        "' AND SLEEP({d})-- -",
+        "'; WAITFOR DELAY '0:0:{d}'-- -",
+        "' || pg_sleep({d})-- -",
+    ]
+}
+
+pub fn render_time_based_payload(template: &str, delay_secs: u64) -> String {
+    template.replace("{d}", &delay_secs.to_string())
+}
+
+/// Guess the DBMS a time-based template targets, from the sleep function it
+/// uses -- the same association `generate_time_based_payload_templates`
+/// documents.
+pub fn dbms_from_time_based_template(template: &str) -> Option<String> {
+    if template.contains("SLEEP") {
+        Some("MySQL".to_string())
+    } else if template.contains("WAITFOR") {
+        Some("SQL Server".to_string())
+    } else if template.contains("pg_sleep") {
+        Some("PostgreSQL".to_string())
+    } else {
+        None
+    }
+}
+
+/// Guess the DBMS from the error label `check_sqli_response` matched.
+pub fn dbms_from_error_label(label: &str) -> Option<String> {
+    if label.starts_with("MySQL") {
+        Some("MySQL".to_string())
+    } else if label.starts_with("PostgreSQL") {
+        Some("PostgreSQL".to_string())
+    } else if label.contains("SQL Server") {
+        Some("SQL Server".to_string())
+    } else if label.starts_with("SQLite") {
+        Some("SQLite".to_string())
+    } else if label.contains("Oracle") {
+        Some("Oracle".to_string())
+    } else {
+        None
+    }
+}
+
+/// Boolean-based blind payload pair: a condition that's always true paired
+/// with one that's always false. A vulnerable parameter renders a visibly
+/// different body for each even though both return 200.
+pub const BOOLEAN_TRUE_PAYLOAD: &str = "' AND 1=1-- -";
+pub const BOOLEAN_FALSE_PAYLOAD: &str = "' AND 1=2-- -";
+
+/// True if two response bodies differ enough to suggest the boolean
+/// condition actually changed query results, rather than noise (timestamps,
+/// CSRF tokens) that varies between any two requests. Flags on a length
+/// delta beyond 5%, or -- for bodies close enough in length that a delta
+/// alone isn't conclusive -- a normalized line-level diff ratio beyond 10%.
+pub fn bodies_differ_significantly(true_body: &str, false_body: &str) -> bool {
+    let true_len = true_body.len();
+    let false_len = false_body.len();
+    let max_len = true_len.max(false_len);
+    if max_len == 0 {
+        return false;
+    }
+
+    let len_delta = (true_len as f64 - false_len as f64).abs() / max_len as f64;
+    if len_delta > 0.05 {
+        return true;
+    }
+
+    let true_lines: std::collections::HashSet<&str> = true_body.lines().collect();
+    let false_lines: std::collections::HashSet<&str> = false_body.lines().collect();
+    let total_lines = true_lines.len().max(false_lines.len());
+    if total_lines == 0 {
+        return false;
+    }
+    let differing = true_lines.symmetric_difference(&false_lines).count();
+    (differing as f64 / total_lines as f64) > 0.10
+}
+
+/// Inject `payload` into the first query parameter of `url`, returning the
+/// rewritten URL. Shared by the error-based and time-based SQLi checks.
+pub fn inject_first_query_param(url: &str, payload: &str) -> Option<String> {
+    let mut parsed = url::Url::parse(url).ok()?;
+    let mut pairs: Vec<(String, String)> = parsed.query_pairs().into_owned().collect();
+    if pairs.is_empty() {
+        return None;
+    }
+    let original_val = pairs[0].1.clone();
+    pairs[0].1 = format!("{}{}", original_val, payload);
+    parsed.query_pairs_mut().clear().extend_pairs(pairs);
+    Some(parsed.to_string())
+}
+
 pub fn check_sqli_response(body: &str) -> Option<String> {
     let error_patterns = [
         (Regex::new(r"(?i)SQL syntax.*MySQL").unwrap(), "MySQL Error"),
@@ -1,10 +1,41 @@
 use crate::commands::debug::{emit_log, LogLevel};
-use crate::utils::crypto::CryptoManager;
+use crate::core::llm_backend::{backend_for, stream_ollama_chat, Remediation};
+use crate::core::llm_cache;
+use crate::core::prompt_redaction::{RedactionEntry, RedactionLog, SecretRedactor};
+use crate::core::vex_export::{build_vex_document, write_vex_document};
+use crate::utils::crypto::{CryptoManager, KeySource};
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs;
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Pooled HTTP client shared by every LLM call site. `call_ollama_api` used
+/// to build a fresh `reqwest::Client` per invocation, which discards the
+/// keep-alive connection to the local endpoint between every
+/// `analyze_*`/`generate_*` command — expensive when a scan fires dozens of
+/// per-asset analyses back to back. Managed as Tauri `State` alongside
+/// `CryptoManager` so the whole app reuses one connection pool.
+pub struct LlmClient {
+    client: reqwest::Client,
+}
+
+impl Default for LlmClient {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(60))
+                .build()
+                .unwrap_or_else(|_| reqwest::Client::new()),
+        }
+    }
+}
+
+impl LlmClient {
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "lowercase")]
@@ -46,6 +77,47 @@ pub struct LlmConfig {
     pub model: String,
     #[serde(default)]
     pub provider_type: ProviderType,
+    /// Whether `SecretRedactor` runs on assembled prompts before they reach
+    /// `call_llm_api`. Defaults on: captured traffic routinely contains
+    /// Authorization headers, session cookies, and PII that shouldn't ship
+    /// to whatever endpoint is configured.
+    #[serde(default = "default_redact_secrets")]
+    pub redact_secrets: bool,
+    /// Header names exempt from the Cookie/Set-Cookie redaction pattern,
+    /// for users who need a specific header's value preserved in prompts.
+    #[serde(default)]
+    pub redaction_allow_list: Vec<String>,
+    /// How long a cached `(provider, model, prompt)` analysis stays valid
+    /// before `call_llm_api` re-queries the model. See `core::llm_cache`.
+    #[serde(default = "default_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+    /// Ollama's context window size, sent as `options.num_ctx`. Defaults
+    /// generously so large multi-step `RequestSequence` payloads don't get
+    /// silently truncated at Ollama's default 2048/4096-token window.
+    #[serde(default = "default_num_ctx")]
+    pub num_ctx: u32,
+    #[serde(default = "default_temperature")]
+    pub temperature: f32,
+    /// Cap on completion length. `None` leaves it up to the provider's own
+    /// default rather than forcing a value into the request body.
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+}
+
+fn default_redact_secrets() -> bool {
+    true
+}
+
+fn default_cache_ttl_secs() -> u64 {
+    crate::core::llm_cache::DEFAULT_CACHE_TTL_SECS
+}
+
+fn default_num_ctx() -> u32 {
+    8192
+}
+
+fn default_temperature() -> f32 {
+    0.1
 }
 
 impl Default for LlmConfig {
@@ -55,6 +127,12 @@ impl Default for LlmConfig {
             api_key: String::new(),
             model: "phi3.5".to_string(),
             provider_type: ProviderType::Local,
+            redact_secrets: true,
+            redaction_allow_list: Vec::new(),
+            cache_ttl_secs: default_cache_ttl_secs(),
+            num_ctx: default_num_ctx(),
+            temperature: default_temperature(),
+            max_tokens: None,
         }
     }
 }
@@ -88,6 +166,12 @@ impl LlmConfig {
             api_key: env::var("APEX_LLM_API_KEY").unwrap_or_default(),
             model: env::var("APEX_LLM_MODEL").unwrap_or(default_model),
             provider_type,
+            redact_secrets: true,
+            redaction_allow_list: Vec::new(),
+            cache_ttl_secs: default_cache_ttl_secs(),
+            num_ctx: default_num_ctx(),
+            temperature: default_temperature(),
+            max_tokens: None,
         }
     }
 
@@ -114,6 +198,12 @@ impl LlmConfig {
                             .ok()
                             .and_then(|p| p.parse().ok())
                             .unwrap_or(config.provider_type),
+                        redact_secrets: config.redact_secrets,
+                        redaction_allow_list: config.redaction_allow_list,
+                        cache_ttl_secs: config.cache_ttl_secs,
+                        num_ctx: config.num_ctx,
+                        temperature: config.temperature,
+                        max_tokens: config.max_tokens,
                     };
                 }
             }
@@ -194,6 +284,12 @@ pub struct LlmConfigPublic {
     pub model: String,
     pub provider_type: ProviderType,
     pub is_configured: bool,
+    pub redact_secrets: bool,
+    pub redaction_allow_list: Vec<String>,
+    pub cache_ttl_secs: u64,
+    pub num_ctx: u32,
+    pub temperature: f32,
+    pub max_tokens: Option<u32>,
 }
 
 impl From<&LlmConfig> for LlmConfigPublic {
@@ -203,6 +299,12 @@ impl From<&LlmConfig> for LlmConfigPublic {
             model: config.model.clone(),
             provider_type: config.provider_type.clone(),
             is_configured: config.is_configured(),
+            redact_secrets: config.redact_secrets,
+            redaction_allow_list: config.redaction_allow_list.clone(),
+            cache_ttl_secs: config.cache_ttl_secs,
+            num_ctx: config.num_ctx,
+            temperature: config.temperature,
+            max_tokens: config.max_tokens,
         }
     }
 }
@@ -389,80 +491,46 @@ Output Format (Markdown):
 
 // Removed OpenAI/Anthropic specific implementations to enforce Local-Only policy per user request.
 
-async fn call_ollama_api(config: &LlmConfig, prompt: &str) -> Result<String, String> {
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(60)) // Add timeout
-        .build()
-        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
-
-    let request_body = serde_json::json!({
-        "model": config.model,
-        "messages": [
-            {
-                "role": "system",
-                "content": "You are APEX SECURITY ANALYST, an uncompromising, high-signal security research agent. You prioritize raw technical evidence, impact, and realistic exploitability over theoretical risks. Your tone is direct, expert, and occasionally snarky about common dev mistakes."
-            },
-            {
-                "role": "user",
-                "content": prompt
-            }
-        ],
-        "stream": false,
-        "options": {
-            "num_ctx": 8192, // Increased context window
-            "temperature": 0.1
-        }
-    });
-
-    let response = client
-        .post(&config.endpoint)
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| {
-            format!(
-                "Failed to connect to Ollama at {}. Is it running? Error: {}",
-                config.endpoint, e
-            )
-        })?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Ollama API error ({}): {}", status, error_text));
-    }
-
-    let response_json: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse Ollama response: {}", e))?;
-
-    let analysis = response_json["message"]
-        .as_object()
-        .and_then(|msg| msg.get("content"))
-        .and_then(|content| content.as_str())
-        .map(|s| s.to_string())
-        .ok_or_else(|| "Failed to extract analysis from Ollama response".to_string())?;
-
-    Ok(analysis)
-}
-
-async fn call_llm_api(config: &LlmConfig, prompt: &str) -> Result<String, String> {
+async fn call_llm_api(
+    client: &reqwest::Client,
+    config: &LlmConfig,
+    prompt: &str,
+    redaction_log: &RedactionLog,
+    force_refresh: bool,
+) -> Result<String, String> {
     // Strictly enforce local provider usage
     if !config.is_local() {
         return Err("Only Local AI (Ollama) is supported in this restricted mode.".to_string());
     }
-    call_ollama_api(config, prompt).await
+
+    let mut prompt = prompt.to_string();
+    if config.redact_secrets {
+        let redactor = SecretRedactor::new(&config.redaction_allow_list);
+        let scrubbed = redactor.redact(&mut prompt);
+        redaction_log.record(scrubbed);
+    }
+
+    let provider_key = config.provider_type.to_string();
+    if !force_refresh {
+        if let Some(cached) =
+            llm_cache::get_cached(&provider_key, &config.model, &prompt, config.cache_ttl_secs)
+        {
+            return Ok(cached);
+        }
+    }
+
+    let completion = backend_for(config).chat(client, config, &prompt).await?;
+    let _ = llm_cache::store(&provider_key, &config.model, &prompt, &completion);
+    Ok(completion)
 }
 
 #[tauri::command]
 pub async fn analyze_logic_flaws(
     input: LogicAuditInput,
     crypto: State<'_, CryptoManager>,
+    llm_client: State<'_, LlmClient>,
+    redaction_log: State<'_, RedactionLog>,
+    force_refresh: Option<bool>,
 ) -> Result<AnalyzeAssetSummaryOutput, String> {
     let config = LlmConfig::load(&crypto);
     let provider_display = match config.provider_type {
@@ -476,8 +544,14 @@ pub async fn analyze_logic_flaws(
     }
 
     let prompt = build_logic_audit_prompt(&input);
-    let analysis = call_llm_api(&config, &prompt)
-        .await
+    let analysis = call_llm_api(
+        llm_client.client(),
+        &config,
+        &prompt,
+        &redaction_log,
+        force_refresh.unwrap_or(false),
+    )
+    .await
         .map_err(|e| format!("{}: {}", provider_display, e))?;
 
     Ok(AnalyzeAssetSummaryOutput {
@@ -493,6 +567,9 @@ pub async fn analyze_finding(
     response_body_snippet: String,
     context: Option<String>,
     crypto: State<'_, CryptoManager>,
+    llm_client: State<'_, LlmClient>,
+    redaction_log: State<'_, RedactionLog>,
+    force_refresh: Option<bool>,
 ) -> Result<AnalyzeFindingOutput, String> {
     let config = LlmConfig::load(&crypto);
 
@@ -514,8 +591,14 @@ pub async fn analyze_finding(
     }
 
     let prompt = build_analysis_prompt(&input);
-    let analysis = call_llm_api(&config, &prompt)
-        .await
+    let analysis = call_llm_api(
+        llm_client.client(),
+        &config,
+        &prompt,
+        &redaction_log,
+        force_refresh.unwrap_or(false),
+    )
+    .await
         .map_err(|e| format!("{}: {}", provider_display, e))?;
 
     Ok(AnalyzeFindingOutput {
@@ -524,6 +607,80 @@ pub async fn analyze_finding(
     })
 }
 
+/// Streaming counterpart to `analyze_finding`: forwards each incremental
+/// token to the frontend on `channel_id` as it arrives (via `AppHandle::emit`)
+/// instead of blocking until the whole completion is assembled, then
+/// resolves with the full text like the non-streaming command. Only the
+/// Local (Ollama) provider supports streaming today.
+#[tauri::command]
+pub async fn analyze_finding_stream(
+    channel_id: String,
+    asset_url: String,
+    finding_type: String,
+    response_body_snippet: String,
+    context: Option<String>,
+    app: AppHandle,
+    crypto: State<'_, CryptoManager>,
+    llm_client: State<'_, LlmClient>,
+    redaction_log: State<'_, RedactionLog>,
+) -> Result<AnalyzeFindingOutput, String> {
+    let config = LlmConfig::load(&crypto);
+
+    let input = AnalyzeFindingInput {
+        asset_url,
+        finding_type,
+        response_body_snippet,
+        context,
+    };
+
+    let provider_display = match config.provider_type {
+        ProviderType::OpenAI => "OpenAI",
+        ProviderType::Anthropic => "Anthropic",
+        ProviderType::Local => "Local",
+    };
+
+    if !config.is_configured() {
+        return Err("LLM not configured. Please go to Settings and configure a Built-in Local or External API provider.".to_string());
+    }
+
+    if !config.is_local() {
+        return Err("Streaming is only supported for the Local (Ollama) provider.".to_string());
+    }
+
+    let mut prompt = build_analysis_prompt(&input);
+    if config.redact_secrets {
+        let redactor = SecretRedactor::new(&config.redaction_allow_list);
+        let scrubbed = redactor.redact(&mut prompt);
+        redaction_log.record(scrubbed);
+    }
+
+    let analysis = stream_ollama_chat(llm_client.client(), &config, &prompt, |delta| {
+        let _ = app.emit(&channel_id, delta);
+    })
+    .await
+    .map_err(|e| format!("{}: {}", provider_display, e))?;
+
+    Ok(AnalyzeFindingOutput {
+        analysis,
+        provider: provider_display.to_string(),
+    })
+}
+
+/// Post-process an `analyze_finding`/`analyze_asset_summary` result into a
+/// CycloneDX VEX document (CVSS vector/score and false-positive verdict
+/// parsed out of the Markdown prose) and write it to disk, returning the
+/// file path so findings can be consumed by downstream vulnerability-
+/// management tooling instead of copy-pasted.
+#[tauri::command]
+pub fn export_finding_vex(
+    asset_url: String,
+    finding_type: String,
+    analysis_text: String,
+) -> Result<String, String> {
+    let doc = build_vex_document(&finding_type, &asset_url, &analysis_text);
+    write_vex_document(&doc)
+}
+
 #[tauri::command]
 pub async fn analyze_asset_summary(
     asset_url: String,
@@ -533,6 +690,9 @@ pub async fn analyze_asset_summary(
     headers_snippet: String,
     context: Option<String>,
     crypto: State<'_, CryptoManager>,
+    llm_client: State<'_, LlmClient>,
+    redaction_log: State<'_, RedactionLog>,
+    force_refresh: Option<bool>,
 ) -> Result<AnalyzeAssetSummaryOutput, String> {
     let config = LlmConfig::load(&crypto);
 
@@ -556,8 +716,14 @@ pub async fn analyze_asset_summary(
     }
 
     let prompt = build_asset_summary_prompt(&input);
-    let summary = call_llm_api(&config, &prompt)
-        .await
+    let summary = call_llm_api(
+        llm_client.client(),
+        &config,
+        &prompt,
+        &redaction_log,
+        force_refresh.unwrap_or(false),
+    )
+    .await
         .map_err(|e| format!("{}: {}", provider_display, e))?;
 
     Ok(AnalyzeAssetSummaryOutput {
@@ -575,6 +741,9 @@ pub async fn generate_remediation_guide(
     headers_snippet: String,
     context: Option<String>,
     crypto: State<'_, CryptoManager>,
+    llm_client: State<'_, LlmClient>,
+    redaction_log: State<'_, RedactionLog>,
+    force_refresh: Option<bool>,
 ) -> Result<AnalyzeAssetSummaryOutput, String> {
     let config = LlmConfig::load(&crypto);
 
@@ -598,8 +767,14 @@ pub async fn generate_remediation_guide(
     }
 
     let prompt = build_remediation_guide_prompt(&input);
-    let summary = call_llm_api(&config, &prompt)
-        .await
+    let summary = call_llm_api(
+        llm_client.client(),
+        &config,
+        &prompt,
+        &redaction_log,
+        force_refresh.unwrap_or(false),
+    )
+    .await
         .map_err(|e| format!("{}: {}", provider_display, e))?;
 
     Ok(AnalyzeAssetSummaryOutput {
@@ -619,6 +794,12 @@ pub struct SequenceAnalysisInput {
 pub struct SequenceAnalysisOutput {
     pub analysis: String,
     pub provider: String,
+    /// Parsed `root_cause`/`fix_diff`/`defensive_strategy` fields, populated
+    /// when the active backend supports forced tool calling (today: the
+    /// OpenAI-compatible local backend). `None` means the caller should
+    /// render `analysis` as markdown, same as before this field existed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub structured: Option<Remediation>,
 }
 
 fn build_sequence_analysis_prompt(input: &SequenceAnalysisInput) -> String {
@@ -676,6 +857,9 @@ pub async fn analyze_sequence(
     sequence: crate::core::data::RequestSequence,
     context: Option<String>,
     crypto: State<'_, CryptoManager>,
+    llm_client: State<'_, LlmClient>,
+    redaction_log: State<'_, RedactionLog>,
+    force_refresh: Option<bool>,
 ) -> Result<SequenceAnalysisOutput, String> {
     let config = LlmConfig::load(&crypto);
     let provider_display = match config.provider_type {
@@ -690,13 +874,20 @@ pub async fn analyze_sequence(
 
     let input = SequenceAnalysisInput { sequence, context };
     let prompt = build_sequence_analysis_prompt(&input);
-    let analysis = call_llm_api(&config, &prompt)
-        .await
+    let analysis = call_llm_api(
+        llm_client.client(),
+        &config,
+        &prompt,
+        &redaction_log,
+        force_refresh.unwrap_or(false),
+    )
+    .await
         .map_err(|e| format!("{}: {}", provider_display, e))?;
 
     Ok(SequenceAnalysisOutput {
         analysis,
         provider: provider_display.to_string(),
+        structured: None,
     })
 }
 
@@ -755,6 +946,9 @@ OUTPUT FORMAT:
 pub async fn generate_exploit_narrative(
     sequence: crate::core::data::RequestSequence,
     crypto: State<'_, CryptoManager>,
+    llm_client: State<'_, LlmClient>,
+    redaction_log: State<'_, RedactionLog>,
+    force_refresh: Option<bool>,
 ) -> Result<SequenceAnalysisOutput, String> {
     let config = LlmConfig::load(&crypto);
     let provider_display = match config.provider_type {
@@ -768,13 +962,20 @@ pub async fn generate_exploit_narrative(
     }
 
     let prompt = build_exploit_narrative_prompt(&sequence);
-    let analysis = call_llm_api(&config, &prompt)
-        .await
+    let analysis = call_llm_api(
+        llm_client.client(),
+        &config,
+        &prompt,
+        &redaction_log,
+        force_refresh.unwrap_or(false),
+    )
+    .await
         .map_err(|e| format!("{}: {}", provider_display, e))?;
 
     Ok(SequenceAnalysisOutput {
         analysis,
         provider: provider_display.to_string(),
+        structured: None,
     })
 }
 
@@ -812,8 +1013,11 @@ OUTPUT FORMAT:
 pub async fn generate_remediation_diff(
     sequence: crate::core::data::RequestSequence,
     crypto: State<'_, CryptoManager>,
+    llm_client: State<'_, LlmClient>,
+    redaction_log: State<'_, RedactionLog>,
+    force_refresh: Option<bool>,
 ) -> Result<SequenceAnalysisOutput, String> {
-    let config = LlmConfig::load(&crypto);
+    let config = load_active_llm_config(&crypto);
     let provider_display = match config.provider_type {
         ProviderType::OpenAI => "OpenAI",
         ProviderType::Anthropic => "Anthropic",
@@ -824,29 +1028,452 @@ pub async fn generate_remediation_diff(
         return Err("LLM not configured.".to_string());
     }
 
-    let prompt = build_remediation_diff_prompt(&sequence);
-    let analysis = call_llm_api(&config, &prompt)
+    let mut prompt = build_remediation_diff_prompt(&sequence);
+    if config.redact_secrets {
+        let redactor = SecretRedactor::new(&config.redaction_allow_list);
+        let scrubbed = redactor.redact(&mut prompt);
+        redaction_log.record(scrubbed);
+    }
+
+    // Prefer a forced tool call for a reliable `root_cause`/`fix_diff`/
+    // `defensive_strategy` breakdown over parsing the markdown template,
+    // which local models frequently malform. Backends that don't support
+    // tool calling (plain Ollama) return `None` here and we fall back to
+    // the markdown-prompt path below.
+    let structured = backend_for(&config)
+        .chat_structured_remediation(llm_client.client(), &config, &prompt)
         .await
         .map_err(|e| format!("{}: {}", provider_display, e))?;
 
+    if let Some(remediation) = structured {
+        let analysis = format!(
+            "# 🛠️ REMEDIATION GUIDE\n## 🔍 ROOT CAUSE\n{}\n\n## 💻 SUGGESTED FIX\n```diff\n{}\n```\n\n## 🛡️ DEFENSIVE STRATEGY\n{}\n",
+            remediation.root_cause, remediation.fix_diff, remediation.defensive_strategy
+        );
+        return Ok(SequenceAnalysisOutput {
+            analysis,
+            provider: provider_display.to_string(),
+            structured: Some(remediation),
+        });
+    }
+
+    let analysis = call_llm_api(
+        llm_client.client(),
+        &config,
+        &prompt,
+        &redaction_log,
+        force_refresh.unwrap_or(false),
+    )
+    .await
+        .map_err(|e| format!("{}: {}", provider_display, e))?;
+
     Ok(SequenceAnalysisOutput {
         analysis,
         provider: provider_display.to_string(),
+        structured: None,
     })
 }
 
+/// Streaming counterpart to `generate_remediation_diff`: forwards each
+/// incremental token to the frontend on `channel_id` as it arrives (via
+/// `AppHandle::emit`), same as `analyze_finding_stream`, then resolves with
+/// the full text so the caller ends up with an identical
+/// `SequenceAnalysisOutput` to the non-streaming command. Only the Local
+/// (Ollama) provider supports streaming today.
 #[tauri::command]
-pub fn get_llm_config(crypto: State<'_, CryptoManager>) -> LlmConfigPublic {
+pub async fn generate_remediation_diff_stream(
+    channel_id: String,
+    sequence: crate::core::data::RequestSequence,
+    app: AppHandle,
+    crypto: State<'_, CryptoManager>,
+    llm_client: State<'_, LlmClient>,
+    redaction_log: State<'_, RedactionLog>,
+) -> Result<SequenceAnalysisOutput, String> {
     let config = LlmConfig::load(&crypto);
+    let provider_display = match config.provider_type {
+        ProviderType::OpenAI => "OpenAI",
+        ProviderType::Anthropic => "Anthropic",
+        ProviderType::Local => "Local",
+    };
+
+    if !config.is_configured() {
+        return Err("LLM not configured.".to_string());
+    }
+
+    if !config.is_local() {
+        return Err("Streaming is only supported for the Local (Ollama) provider.".to_string());
+    }
+
+    let mut prompt = build_remediation_diff_prompt(&sequence);
+    if config.redact_secrets {
+        let redactor = SecretRedactor::new(&config.redaction_allow_list);
+        let scrubbed = redactor.redact(&mut prompt);
+        redaction_log.record(scrubbed);
+    }
+
+    let analysis = stream_ollama_chat(llm_client.client(), &config, &prompt, |delta| {
+        let _ = app.emit(&channel_id, delta);
+    })
+    .await
+    .map_err(|e| format!("{}: {}", provider_display, e))?;
+
+    Ok(SequenceAnalysisOutput {
+        analysis,
+        provider: provider_display.to_string(),
+        structured: None,
+    })
+}
+
+fn get_profiles_path() -> PathBuf {
+    let mut path = env::current_exe().unwrap_or_default();
+    path.set_file_name("llm_profiles.json");
+    path
+}
+
+/// A saved, named `LlmConfig` — lets a user keep a local phi3.5 profile for
+/// quick triage and a cloud profile for deep analysis side by side instead of
+/// re-entering endpoint/key/model every time they switch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmProfile {
+    pub id: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub config: LlmConfig,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LlmProfilePublic {
+    pub id: String,
+    pub name: String,
+    pub config: LlmConfigPublic,
+}
+
+impl From<&LlmProfile> for LlmProfilePublic {
+    fn from(profile: &LlmProfile) -> Self {
+        Self {
+            id: profile.id.clone(),
+            name: profile.name.clone(),
+            config: LlmConfigPublic::from(&profile.config),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LlmProfileStore {
+    profiles: Vec<LlmProfile>,
+    active_profile_id: Option<String>,
+}
+
+fn load_profile_store(crypto: &CryptoManager) -> LlmProfileStore {
+    let path = get_profiles_path();
+    let Ok(content) = fs::read_to_string(&path) else {
+        return LlmProfileStore::default();
+    };
+    let Ok(mut store) = serde_json::from_str::<LlmProfileStore>(&content) else {
+        return LlmProfileStore::default();
+    };
+
+    for profile in &mut store.profiles {
+        if let Ok(decrypted) = crypto.decrypt(&profile.config.api_key) {
+            profile.config.api_key = decrypted;
+        }
+        // If decryption fails, use as is (plaintext migration), same as `LlmConfig::load`.
+    }
+    store
+}
+
+fn save_profile_store(store: &LlmProfileStore, crypto: &CryptoManager) -> Result<(), String> {
+    let path = get_profiles_path();
+    let mut store_to_save = LlmProfileStore {
+        profiles: store.profiles.clone(),
+        active_profile_id: store.active_profile_id.clone(),
+    };
+
+    for profile in &mut store_to_save.profiles {
+        if !profile.config.api_key.is_empty() {
+            profile.config.api_key = crypto.encrypt(&profile.config.api_key)?;
+        }
+    }
+
+    let content = serde_json::to_string_pretty(&store_to_save)
+        .map_err(|e| format!("Failed to serialize LLM profiles: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write LLM profiles file: {}", e))
+}
+
+/// Counts from a [`migrate_to_current_key`] pass.
+#[derive(Debug, Default, Serialize)]
+pub struct KeyMigrationReport {
+    pub migrated: u32,
+    pub failed: u32,
+    pub already_current: u32,
+}
+
+/// Re-encrypt every stored LLM API key still under `CryptoManager`'s legacy
+/// hardcoded key with its current primary key, so the legacy key can
+/// eventually be retired. Walks both `llm_config.json` and
+/// `llm_profiles.json`, the only two files with `CryptoManager`-encrypted
+/// fields, and writes each back only if something in it changed. If every
+/// key found decrypts cleanly (none `failed`), the legacy key is retired via
+/// [`CryptoManager::retire_legacy_key`] so it's no longer loaded at startup.
+#[tauri::command]
+pub fn migrate_to_current_key(crypto: State<'_, CryptoManager>) -> Result<KeyMigrationReport, String> {
+    let mut report = KeyMigrationReport::default();
+
+    let config_path = get_config_path();
+    if let Ok(content) = fs::read_to_string(&config_path) {
+        if let Ok(mut config) = serde_json::from_str::<LlmConfig>(&content) {
+            if !config.api_key.is_empty()
+                && migrate_api_key(&crypto, &mut config.api_key, &mut report)
+            {
+                if let Ok(serialized) = serde_json::to_string_pretty(&config) {
+                    fs::write(&config_path, serialized)
+                        .map_err(|e| format!("Failed to write config file: {}", e))?;
+                }
+            }
+        }
+    }
+
+    let profiles_path = get_profiles_path();
+    if let Ok(content) = fs::read_to_string(&profiles_path) {
+        if let Ok(mut store) = serde_json::from_str::<LlmProfileStore>(&content) {
+            let mut changed = false;
+            for profile in &mut store.profiles {
+                if !profile.config.api_key.is_empty()
+                    && migrate_api_key(&crypto, &mut profile.config.api_key, &mut report)
+                {
+                    changed = true;
+                }
+            }
+            if changed {
+                let serialized = serde_json::to_string_pretty(&store)
+                    .map_err(|e| format!("Failed to serialize LLM profiles: {}", e))?;
+                fs::write(&profiles_path, serialized)
+                    .map_err(|e| format!("Failed to write LLM profiles file: {}", e))?;
+            }
+        }
+    }
+
+    if report.failed == 0 {
+        CryptoManager::retire_legacy_key()?;
+    }
+
+    Ok(report)
+}
+
+/// Decrypt `api_key` in place, re-encrypting under the primary key if it was
+/// still under the legacy one. Returns whether the value changed.
+fn migrate_api_key(crypto: &CryptoManager, api_key: &mut String, report: &mut KeyMigrationReport) -> bool {
+    match crypto.decrypt_key_source(api_key) {
+        Ok((_, KeySource::Primary)) => {
+            report.already_current += 1;
+            false
+        }
+        Ok((plaintext, KeySource::Legacy)) => match crypto.encrypt(&plaintext) {
+            Ok(encrypted) => {
+                *api_key = encrypted;
+                report.migrated += 1;
+                true
+            }
+            Err(_) => {
+                report.failed += 1;
+                false
+            }
+        },
+        Err(_) => {
+            report.failed += 1;
+            false
+        }
+    }
+}
+
+/// Resolve the active profile's config, falling back to the single
+/// `LlmConfig::load` path when no profile is active — so existing installs
+/// without any saved profiles keep working unchanged.
+pub fn load_active_llm_config(crypto: &CryptoManager) -> LlmConfig {
+    let store = load_profile_store(crypto);
+    match store.active_profile_id {
+        Some(id) => store
+            .profiles
+            .into_iter()
+            .find(|p| p.id == id)
+            .map(|p| p.config)
+            .unwrap_or_else(|| LlmConfig::load(crypto)),
+        None => LlmConfig::load(crypto),
+    }
+}
+
+#[tauri::command]
+pub fn list_llm_profiles(crypto: State<'_, CryptoManager>) -> Vec<LlmProfilePublic> {
+    let store = load_profile_store(&crypto);
+    store.profiles.iter().map(LlmProfilePublic::from).collect()
+}
+
+#[tauri::command]
+pub fn save_llm_profile(
+    id: Option<String>,
+    name: String,
+    endpoint: String,
+    api_key: String,
+    model: String,
+    provider_type: String,
+    crypto: State<'_, CryptoManager>,
+) -> Result<LlmProfilePublic, String> {
+    let mut store = load_profile_store(&crypto);
+    let provider_type = provider_type.parse().unwrap_or(ProviderType::Local);
+
+    let profile = match id.and_then(|id| store.profiles.iter().position(|p| p.id == id)) {
+        Some(index) => {
+            let existing = &mut store.profiles[index];
+            existing.name = name;
+            existing.config.endpoint = endpoint;
+            if !api_key.is_empty() {
+                existing.config.api_key = api_key;
+            }
+            existing.config.model = model;
+            existing.config.provider_type = provider_type;
+            existing.clone()
+        }
+        None => {
+            let profile = LlmProfile {
+                id: uuid::Uuid::new_v4().to_string(),
+                name,
+                config: LlmConfig {
+                    endpoint,
+                    api_key,
+                    model,
+                    provider_type,
+                    ..Default::default()
+                },
+            };
+            store.profiles.push(profile.clone());
+            profile
+        }
+    };
+
+    save_profile_store(&store, &crypto)?;
+    Ok(LlmProfilePublic::from(&profile))
+}
+
+#[tauri::command]
+pub fn delete_llm_profile(id: String, crypto: State<'_, CryptoManager>) -> Result<(), String> {
+    let mut store = load_profile_store(&crypto);
+    store.profiles.retain(|p| p.id != id);
+    if store.active_profile_id.as_deref() == Some(id.as_str()) {
+        store.active_profile_id = None;
+    }
+    save_profile_store(&store, &crypto)
+}
+
+#[tauri::command]
+pub fn set_active_llm_profile(id: String, crypto: State<'_, CryptoManager>) -> Result<(), String> {
+    let mut store = load_profile_store(&crypto);
+    if !store.profiles.iter().any(|p| p.id == id) {
+        return Err(format!("No LLM profile with id {}", id));
+    }
+    store.active_profile_id = Some(id);
+    save_profile_store(&store, &crypto)
+}
+
+#[tauri::command]
+pub fn get_llm_config(crypto: State<'_, CryptoManager>) -> LlmConfigPublic {
+    let config = load_active_llm_config(&crypto);
     LlmConfigPublic::from(&config)
 }
 
+#[derive(Debug, Serialize)]
+pub struct OllamaModelInfo {
+    pub name: String,
+    pub size: u64,
+    pub digest: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaTagsResponse {
+    #[serde(default)]
+    models: Vec<OllamaTagEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaTagEntry {
+    name: String,
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    digest: String,
+}
+
+/// Derive Ollama's `/api/tags` endpoint from the configured chat endpoint
+/// (e.g. `http://host:11434/api/chat` -> `http://host:11434/api/tags`), so
+/// a custom/remote gateway's host is respected rather than hardcoding
+/// `localhost`.
+fn ollama_tags_url(endpoint: &str) -> String {
+    let trimmed = endpoint.trim_end_matches('/');
+    match trimmed.find("/api/") {
+        Some(idx) => format!("{}/api/tags", &trimmed[..idx]),
+        None => format!("{}/api/tags", trimmed),
+    }
+}
+
+/// Query the locally (or remotely, via a bearer-auth'd gateway) running
+/// Ollama server for the models it actually has pulled, so the frontend can
+/// render a real picker instead of a free-text model field.
+#[tauri::command]
+pub async fn list_local_models(
+    llm_client: State<'_, LlmClient>,
+    crypto: State<'_, CryptoManager>,
+) -> Result<Vec<OllamaModelInfo>, String> {
+    let config = LlmConfig::load(&crypto);
+    let url = ollama_tags_url(&config.endpoint);
+
+    let mut request = llm_client.client().get(&url);
+    if !config.api_key.is_empty() {
+        request = request.bearer_auth(&config.api_key);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Ollama at {}: {}", url, e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Ollama /api/tags error ({})", response.status()));
+    }
+
+    let parsed: OllamaTagsResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Ollama tags response: {}", e))?;
+
+    Ok(parsed
+        .models
+        .into_iter()
+        .map(|m| OllamaModelInfo {
+            name: m.name,
+            size: m.size,
+            digest: m.digest,
+        })
+        .collect())
+}
+
+/// What `SecretRedactor` has scrubbed from recent prompts, so the UI can
+/// show the analyst what was withheld from the model.
+#[tauri::command]
+pub fn get_recent_redactions(redaction_log: State<'_, RedactionLog>) -> Vec<RedactionEntry> {
+    redaction_log.recent(50)
+}
+
 #[tauri::command]
 pub fn update_llm_config(
     endpoint: String,
     api_key: String,
     model: String,
     provider_type: String,
+    redact_secrets: Option<bool>,
+    redaction_allow_list: Option<Vec<String>>,
+    cache_ttl_secs: Option<u64>,
+    num_ctx: Option<u32>,
+    temperature: Option<f32>,
+    max_tokens: Option<u32>,
     crypto: State<'_, CryptoManager>,
 ) -> Result<LlmConfigPublic, String> {
     let mut config = LlmConfig::load(&crypto);
@@ -859,6 +1486,26 @@ pub fn update_llm_config(
         config.api_key = api_key;
     }
 
+    if let Some(cache_ttl_secs) = cache_ttl_secs {
+        config.cache_ttl_secs = cache_ttl_secs;
+    }
+
+    if let Some(redact_secrets) = redact_secrets {
+        config.redact_secrets = redact_secrets;
+    }
+    if let Some(redaction_allow_list) = redaction_allow_list {
+        config.redaction_allow_list = redaction_allow_list;
+    }
+    if let Some(num_ctx) = num_ctx {
+        config.num_ctx = num_ctx;
+    }
+    if let Some(temperature) = temperature {
+        config.temperature = temperature;
+    }
+    if max_tokens.is_some() {
+        config.max_tokens = max_tokens;
+    }
+
     if config.is_local() && config.endpoint.is_empty() {
         config.endpoint = "http://localhost:11434/api/chat".to_string();
     }
@@ -877,15 +1524,30 @@ pub fn update_llm_config(
     Ok(LlmConfigPublic::from(&config))
 }
 
+/// Strip the `:tag` suffix from an Ollama model name (e.g. `phi3.5:latest`
+/// -> `phi3.5`) so name comparisons aren't fooled by an implicit/explicit
+/// tag mismatch.
+fn model_base_name(name: &str) -> &str {
+    name.split(':').next().unwrap_or(name)
+}
+
 pub fn is_model_present(model_name: &str) -> bool {
     let output = std::process::Command::new("ollama").args(["list"]).output();
 
-    if let Ok(output) = output {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        stdout.contains(model_name)
-    } else {
-        false
-    }
+    let Ok(output) = output else {
+        return false;
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let wanted = model_base_name(model_name);
+
+    // Skip the header row; match on the exact (tag-stripped) model name in
+    // the first column rather than a naive substring search, which false-
+    // positives on prefixes like `llama3` matching `llama3.1`.
+    stdout
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().next())
+        .any(|listed| model_base_name(listed) == wanted)
 }
 
 pub async fn ensure_model_present(handle: AppHandle, model_name: &str) -> Result<(), String> {
@@ -932,13 +1594,69 @@ pub async fn ensure_model_present(handle: AppHandle, model_name: &str) -> Result
     }
 }
 
+/// Issue a prompt-less chat request with a long `keep_alive` so Ollama loads
+/// the model's weights into memory ahead of time, instead of cold-loading
+/// them on whatever `analyze_*`/`generate_*` command happens to run first.
+pub async fn preload_model(
+    handle: &AppHandle,
+    client: &reqwest::Client,
+    config: &LlmConfig,
+) -> Result<(), String> {
+    emit_log(
+        handle,
+        LogLevel::Info,
+        "AI",
+        &format!("Warming up model {}...", config.model),
+        None,
+    );
+
+    let request_body = serde_json::json!({
+        "model": config.model,
+        "messages": [],
+        "stream": false,
+        "keep_alive": "30m"
+    });
+
+    let mut request = client
+        .post(&config.endpoint)
+        .header("Content-Type", "application/json");
+    if !config.api_key.is_empty() {
+        request = request.bearer_auth(&config.api_key);
+    }
+
+    let response = request.json(&request_body).send().await.map_err(|e| {
+        format!(
+            "Failed to warm up model {} at {}: {}",
+            config.model, config.endpoint, e
+        )
+    })?;
+
+    if !response.status().is_success() {
+        let err = format!("Model warm-up request failed ({})", response.status());
+        emit_log(handle, LogLevel::Error, "AI", &err, None);
+        return Err(err);
+    }
+
+    emit_log(
+        handle,
+        LogLevel::Success,
+        "AI",
+        &format!("Model {} is warmed up and ready.", config.model),
+        None,
+    );
+    Ok(())
+}
+
 pub fn auto_initialize_ai(handle: AppHandle) {
     tauri::async_runtime::spawn(async move {
         // AppHandle implements Manager, allowing state access
         if let Some(crypto) = handle.try_state::<CryptoManager>() {
             let config = LlmConfig::load(&crypto);
-            if config.is_local() {
-                let _ = ensure_model_present(handle, &config.model).await;
+            if config.is_local() && ensure_model_present(handle.clone(), &config.model).await.is_ok()
+            {
+                if let Some(llm_client) = handle.try_state::<LlmClient>() {
+                    let _ = preload_model(&handle, llm_client.client(), &config).await;
+                }
             }
         }
     });
@@ -959,6 +1677,19 @@ pub async fn pull_local_model(
     ensure_model_present(app_handle, &config.model).await
 }
 
+/// Expose warm-up as its own command so the frontend can trigger it when the
+/// user opens the analysis panel, rather than waiting for their first
+/// `analyze_*`/`generate_*` click to pay the cold-load latency.
+#[tauri::command]
+pub async fn preload_local_model(
+    app_handle: AppHandle,
+    crypto: State<'_, CryptoManager>,
+    llm_client: State<'_, LlmClient>,
+) -> Result<(), String> {
+    let config = LlmConfig::load(&crypto);
+    preload_model(&app_handle, llm_client.client(), &config).await
+}
+
 // Removed dead AppConfig code
 
 #[cfg(test)]
@@ -1028,4 +1759,23 @@ mod tests {
         assert!(prompt.contains("https://example.com"));
         assert!(prompt.contains("SQL_INJECTION"));
     }
+
+    #[test]
+    fn test_model_base_name_strips_tag() {
+        assert_eq!(model_base_name("phi3.5:latest"), "phi3.5");
+        assert_eq!(model_base_name("llama3.1"), "llama3.1");
+        assert_ne!(model_base_name("llama3.1"), model_base_name("llama3"));
+    }
+
+    #[test]
+    fn test_ollama_tags_url_from_chat_endpoint() {
+        assert_eq!(
+            ollama_tags_url("http://localhost:11434/api/chat"),
+            "http://localhost:11434/api/tags"
+        );
+        assert_eq!(
+            ollama_tags_url("https://ollama.mycorp.net/api/chat/"),
+            "https://ollama.mycorp.net/api/tags"
+        );
+    }
 }
@@ -0,0 +1,243 @@
+use crate::core::detector::FindingSeverity;
+use crate::db::advisories::AdvisoryDatabase;
+use serde::{Deserialize, Serialize};
+
+/// A single resolved `(package, version)` pair harvested from a lockfile or
+/// the tech-stack fingerprinter, the unit [`audit_dependencies`] checks
+/// against an [`AdvisoryDatabase`] -- modeled on how `cargo-audit` walks a
+/// resolved `Cargo.lock` rather than re-resolving `Cargo.toml` ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedDependency {
+    pub package: String,
+    pub version: String,
+}
+
+/// A dependency matched against a known-vulnerable advisory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyFinding {
+    pub package: String,
+    pub version: String,
+    pub advisory_id: String,
+    pub severity: FindingSeverity,
+    pub description: String,
+    /// The lowest known-fixed version, when the advisory names one.
+    pub recommendation: Option<String>,
+}
+
+/// Parse an advisory's free-text severity (`"Critical"`/`"High"`/etc, same
+/// strings `Advisory::severity` is populated with) into this module's
+/// [`FindingSeverity`], defaulting to `Medium` for anything unrecognized so
+/// a malformed advisory still surfaces rather than being silently dropped.
+fn parse_advisory_severity(raw: &str) -> FindingSeverity {
+    match raw.to_lowercase().as_str() {
+        "critical" => FindingSeverity::Critical,
+        "high" => FindingSeverity::High,
+        "low" => FindingSeverity::Low,
+        "info" | "informational" => FindingSeverity::Info,
+        _ => FindingSeverity::Medium,
+    }
+}
+
+/// Parse a `Cargo.lock`'s `[[package]]` entries into resolved dependencies.
+/// Only `name`/`version` are read; `source`/`dependencies`/`checksum` are
+/// irrelevant to advisory matching and ignored.
+pub fn parse_cargo_lock(content: &str) -> Vec<ResolvedDependency> {
+    let mut deps = Vec::new();
+    let mut name: Option<String> = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line == "[[package]]" {
+            name = None;
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("name = ") {
+            name = Some(rest.trim_matches('"').to_string());
+        } else if let Some(rest) = line.strip_prefix("version = ") {
+            if let Some(package) = name.take() {
+                deps.push(ResolvedDependency {
+                    package,
+                    version: rest.trim_matches('"').to_string(),
+                });
+            }
+        }
+    }
+
+    deps
+}
+
+/// Parse an npm `package-lock.json`'s `packages`/`dependencies` map (either
+/// lockfile version 2/3's flat `packages` object or the legacy nested
+/// `dependencies` object) into resolved dependencies.
+pub fn parse_package_lock_json(content: &str) -> Vec<ResolvedDependency> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let mut deps = Vec::new();
+
+    if let Some(packages) = value.get("packages").and_then(|v| v.as_object()) {
+        for (path, entry) in packages {
+            if path.is_empty() {
+                continue; // the root project itself, not a dependency
+            }
+            let Some(package) = path.rsplit("node_modules/").next() else {
+                continue;
+            };
+            if let Some(version) = entry.get("version").and_then(|v| v.as_str()) {
+                deps.push(ResolvedDependency {
+                    package: package.to_string(),
+                    version: version.to_string(),
+                });
+            }
+        }
+    } else if let Some(dependencies) = value.get("dependencies").and_then(|v| v.as_object()) {
+        for (package, entry) in dependencies {
+            if let Some(version) = entry.get("version").and_then(|v| v.as_str()) {
+                deps.push(ResolvedDependency {
+                    package: package.to_string(),
+                    version: version.to_string(),
+                });
+            }
+        }
+    }
+
+    deps
+}
+
+/// Match every resolved dependency against `advisories`, producing one
+/// [`DependencyFinding`] per `(dependency, advisory)` hit -- a dependency
+/// affected by two advisories yields two findings, same as `cargo audit`
+/// reporting each vulnerability separately.
+pub fn audit_dependencies(
+    deps: &[ResolvedDependency],
+    advisories: &AdvisoryDatabase,
+) -> Vec<DependencyFinding> {
+    let mut findings = Vec::new();
+
+    for dep in deps {
+        for advisory in advisories.match_package(&dep.package, &dep.version) {
+            let recommendation = advisory
+                .affected
+                .iter()
+                .filter_map(|range| range.fixed.clone())
+                .min();
+
+            findings.push(DependencyFinding {
+                package: dep.package.clone(),
+                version: dep.version.clone(),
+                advisory_id: advisory.id.clone(),
+                severity: parse_advisory_severity(&advisory.severity),
+                description: advisory.summary.clone(),
+                recommendation,
+            });
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::advisories::{AffectedRange, Advisory};
+
+    fn sample_advisories() -> AdvisoryDatabase {
+        AdvisoryDatabase {
+            advisories: vec![Advisory {
+                id: "RUSTSEC-2024-0001".to_string(),
+                package: "serde".to_string(),
+                severity: "High".to_string(),
+                summary: "Unsound deserialization".to_string(),
+                affected: vec![AffectedRange {
+                    introduced: Some("1.0.0".to_string()),
+                    fixed: Some("1.0.188".to_string()),
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_parse_cargo_lock_reads_name_and_version_pairs() {
+        let content = r#"
+[[package]]
+name = "serde"
+version = "1.0.150"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "tokio"
+version = "1.28.0"
+"#;
+        let deps = parse_cargo_lock(content);
+        assert_eq!(
+            deps,
+            vec![
+                ResolvedDependency {
+                    package: "serde".to_string(),
+                    version: "1.0.150".to_string()
+                },
+                ResolvedDependency {
+                    package: "tokio".to_string(),
+                    version: "1.28.0".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_package_lock_json_v3_packages_map() {
+        let content = r#"{
+            "packages": {
+                "": { "name": "app" },
+                "node_modules/lodash": { "version": "4.17.15" }
+            }
+        }"#;
+        let deps = parse_package_lock_json(content);
+        assert_eq!(
+            deps,
+            vec![ResolvedDependency {
+                package: "lodash".to_string(),
+                version: "4.17.15".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_package_lock_json_legacy_dependencies_map() {
+        let content = r#"{
+            "dependencies": {
+                "lodash": { "version": "4.17.15" }
+            }
+        }"#;
+        let deps = parse_package_lock_json(content);
+        assert_eq!(
+            deps,
+            vec![ResolvedDependency {
+                package: "lodash".to_string(),
+                version: "4.17.15".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_audit_dependencies_flags_affected_version() {
+        let deps = vec![ResolvedDependency {
+            package: "serde".to_string(),
+            version: "1.0.100".to_string(),
+        }];
+        let findings = audit_dependencies(&deps, &sample_advisories());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].advisory_id, "RUSTSEC-2024-0001");
+        assert_eq!(findings[0].severity, FindingSeverity::High);
+        assert_eq!(findings[0].recommendation.as_deref(), Some("1.0.188"));
+    }
+
+    #[test]
+    fn test_audit_dependencies_skips_fixed_version() {
+        let deps = vec![ResolvedDependency {
+            package: "serde".to_string(),
+            version: "1.0.188".to_string(),
+        }];
+        assert!(audit_dependencies(&deps, &sample_advisories()).is_empty());
+    }
+}
@@ -15,11 +15,26 @@ pub struct BolaFinding {
     pub end_offset: usize,
 }
 
+/// Regex matching a numeric or UUID-shaped resource id segment in a URL
+/// path. Exposed (not just inlined) so the active cross-account BOLA
+/// prober (`active_scanner::bola_idor`) can locate the same candidate id
+/// token this static detector flags, instead of re-deriving the pattern.
+pub fn id_pattern() -> Regex {
+    Regex::new(r"/(?P<id>[0-9]{1,10}|[0-9a-fA-F-]{36})\b").unwrap()
+}
+
+/// Regex matching a JSON `id`/`user_id`/`account_id`/`owner_id` field whose
+/// value is a short numeric or alphanumeric id. See [`id_pattern`] for why
+/// this is exposed.
+pub fn json_id_pattern() -> Regex {
+    Regex::new(r#"(?i)["'](?:id|user_id|account_id|owner_id)["']\s*:\s*(?P<val>[0-9]{1,10}|["'][A-Za-z0-9-]{10,}["'])"#).unwrap()
+}
+
 pub fn detect_bola_patterns(url: &str, body: &str) -> Vec<BolaFinding> {
     let mut findings = Vec::new();
 
     // 1. URL ID Extraction & Predictability check
-    let id_pattern = Regex::new(r"/(?P<id>[0-9]{1,10}|[0-9a-fA-F-]{36})\b").unwrap();
+    let id_pattern = id_pattern();
     for cap in id_pattern.captures_iter(url) {
         if let Some(m) = cap.name("id") {
             let id_val = m.as_str();
@@ -42,7 +57,7 @@ pub fn detect_bola_patterns(url: &str, body: &str) -> Vec<BolaFinding> {
     }
 
     // 2. Body-based ID detection (JSON)
-    let json_id_pattern = Regex::new(r#"(?i)["'](?:id|user_id|account_id|owner_id)["']\s*:\s*(?P<val>[0-9]{1,10}|["'][A-Za-z0-9-]{10,}["'])"#).unwrap();
+    let json_id_pattern = json_id_pattern();
     for cap in json_id_pattern.captures_iter(body) {
         if let Some(m) = cap.name("val") {
             let val = m.as_str().replace(['"', '\''], "");
@@ -0,0 +1,326 @@
+use crate::core::detector::FindingSeverity;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use x509_parser::certificate::X509Certificate;
+use x509_parser::extensions::{GeneralName, ParsedExtension};
+use x509_parser::parse_x509_certificate;
+use x509_parser::pem::parse_x509_pem;
+use x509_parser::public_key::PublicKey;
+
+/// Certificates expiring within this window are flagged early rather than
+/// waiting for them to lapse outright.
+const EXPIRY_WARNING_WINDOW_SECS: i64 = 30 * 24 * 60 * 60;
+const MIN_RSA_KEY_BITS: usize = 2048;
+
+/// OIDs for SHA-1/MD5-based signature algorithms, all considered broken for
+/// new certificates: sha1WithRSAEncryption, md5WithRSAEncryption,
+/// dsaWithSHA1, ecdsa-with-SHA1.
+const WEAK_SIGNATURE_OIDS: &[&str] = &[
+    "1.2.840.113549.1.1.5",
+    "1.2.840.113549.1.1.4",
+    "1.2.840.10040.4.3",
+    "1.2.840.10045.4.1",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertFinding {
+    pub check: String,
+    pub severity: FindingSeverity,
+    pub description: String,
+    pub recommendation: String,
+}
+
+impl CertFinding {
+    fn new(check: &str, severity: FindingSeverity, description: String, recommendation: &str) -> Self {
+        Self {
+            check: check.to_string(),
+            severity,
+            description,
+            recommendation: recommendation.to_string(),
+        }
+    }
+}
+
+/// Parse a server certificate, PEM or raw DER, and flag weaknesses in it --
+/// the same kind of transport-security posture check `analyze_headers`
+/// already performs for HSTS, but for the certificate itself.
+pub fn analyze_certificate(pem_or_der: &[u8]) -> Vec<CertFinding> {
+    let der = match parse_x509_pem(pem_or_der) {
+        Ok((_, pem)) => pem.contents,
+        Err(_) => pem_or_der.to_vec(),
+    };
+
+    let cert = match parse_x509_certificate(&der) {
+        Ok((_, cert)) => cert,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut findings = Vec::new();
+    findings.extend(check_validity(
+        cert.validity().not_before.timestamp(),
+        cert.validity().not_after.timestamp(),
+        now_unix(),
+    ));
+    findings.extend(check_signature_algorithm(&cert));
+    findings.extend(check_key_strength(&cert));
+    findings.extend(check_self_signed(&cert));
+    findings.extend(check_subject_alt_names(&cert));
+    findings
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Pure timestamp comparison, split out from [`analyze_certificate`] so the
+/// expired / not-yet-valid / expiring-soon thresholds can be unit tested
+/// without needing a real certificate for every case.
+fn check_validity(not_before: i64, not_after: i64, now: i64) -> Vec<CertFinding> {
+    if not_after < now {
+        return vec![CertFinding::new(
+            "Validity",
+            FindingSeverity::Critical,
+            "Certificate has already expired".to_string(),
+            "Renew the certificate immediately",
+        )];
+    }
+    if not_before > now {
+        return vec![CertFinding::new(
+            "Validity",
+            FindingSeverity::High,
+            "Certificate is not yet valid (not_before is in the future)".to_string(),
+            "Check the server's clock and the certificate's issuance date",
+        )];
+    }
+    if not_after - now < EXPIRY_WARNING_WINDOW_SECS {
+        return vec![CertFinding::new(
+            "Validity",
+            FindingSeverity::Medium,
+            "Certificate expires within the next 30 days".to_string(),
+            "Schedule certificate renewal before it expires",
+        )];
+    }
+    Vec::new()
+}
+
+fn check_signature_algorithm(cert: &X509Certificate) -> Vec<CertFinding> {
+    let oid = cert.signature_algorithm.algorithm.to_id_string();
+    if WEAK_SIGNATURE_OIDS.contains(&oid.as_str()) {
+        vec![CertFinding::new(
+            "Signature Algorithm",
+            FindingSeverity::High,
+            format!(
+                "Certificate is signed using a weak SHA-1/MD5-based algorithm (OID {})",
+                oid
+            ),
+            "Re-issue the certificate using SHA-256 or stronger",
+        )]
+    } else {
+        Vec::new()
+    }
+}
+
+fn check_key_strength(cert: &X509Certificate) -> Vec<CertFinding> {
+    match cert.public_key().parsed() {
+        Ok(PublicKey::RSA(rsa)) if rsa.key_size() < MIN_RSA_KEY_BITS => {
+            vec![CertFinding::new(
+                "Key Strength",
+                FindingSeverity::High,
+                format!(
+                    "RSA public key is only {} bits, below the 2048-bit minimum",
+                    rsa.key_size()
+                ),
+                "Re-issue the certificate with a 2048-bit or larger RSA key",
+            )]
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn check_self_signed(cert: &X509Certificate) -> Vec<CertFinding> {
+    if cert.issuer() == cert.subject() {
+        vec![CertFinding::new(
+            "Self-Signed",
+            FindingSeverity::Medium,
+            "Certificate is self-signed (issuer matches subject)".to_string(),
+            "Use a certificate issued by a trusted CA in production",
+        )]
+    } else {
+        Vec::new()
+    }
+}
+
+fn check_subject_alt_names(cert: &X509Certificate) -> Vec<CertFinding> {
+    let Ok(Some(san)) = cert.subject_alternative_name() else {
+        return Vec::new();
+    };
+
+    let mut names = Vec::new();
+    if let ParsedExtension::SubjectAlternativeName(san) = san.parsed_extension() {
+        for name in &san.general_names {
+            match name {
+                GeneralName::DNSName(dns) => names.push(format!("DNS:{}", dns)),
+                GeneralName::IPAddress(ip) => names.push(format!("IP:{:?}", ip)),
+                GeneralName::RFC822Name(email) => names.push(format!("Email:{}", email)),
+                GeneralName::URI(uri) => names.push(format!("URI:{}", uri)),
+                _ => {}
+            }
+        }
+    }
+
+    let mut findings = Vec::new();
+    if let Some(wildcard) = names.iter().find(|n| n.contains("*.")) {
+        findings.push(CertFinding::new(
+            "Subject Alternative Name",
+            FindingSeverity::Low,
+            format!(
+                "Certificate SAN includes a wildcard entry: {}. Compromise of the private key impacts every matching subdomain.",
+                wildcard
+            ),
+            "Prefer exact hostnames over wildcard SANs to limit blast radius",
+        ));
+    }
+    if let Some(email) = names.iter().find(|n| n.starts_with("Email:")) {
+        findings.push(CertFinding::new(
+            "Subject Alternative Name",
+            FindingSeverity::Info,
+            format!("Certificate SAN embeds an email address: {}", email),
+            "Avoid embedding personal email addresses in public certificates",
+        ));
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEAK_KEY_CERT_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIICEjCCAXugAwIBAgIURSHIyHdi5JMFjTP+zxROYdfLwTowDQYJKoZIhvcNAQEL
+BQAwGzEZMBcGA1UEAwwQd2Vhay5leGFtcGxlLmNvbTAeFw0yNjA3MzAwODIzMDZa
+Fw0zNjA3MjcwODIzMDZaMBsxGTAXBgNVBAMMEHdlYWsuZXhhbXBsZS5jb20wgZ8w
+DQYJKoZIhvcNAQEBBQADgY0AMIGJAoGBALvrrrzaNdME8/DUlNMlKiGizvHDFsUr
+KnaEGE9xD9gmrNsOCeedIiKUD0qaTInhOlY/tNDCLfSM7MPuLcgePE+8+v3ox5+2
+yuLtxRPzyfXBoi1vflamyPfl6G1p63CR74RPUkKhG1Tarri+LZGqFW8CAMRPwPdu
+IZXshHXRNIMPAgMBAAGjUzBRMB0GA1UdDgQWBBQVx9pL8oOyWF762r+RSzIuyag2
+zDAfBgNVHSMEGDAWgBQVx9pL8oOyWF762r+RSzIuyag2zDAPBgNVHRMBAf8EBTAD
+AQH/MA0GCSqGSIb3DQEBCwUAA4GBAGsBS0TeK0HqVoAmEEumvP90N8LZgm0j+roR
+ZmW9ZQwN+n2P4bjn4zxpgLRKESrWdTgVf6ux5VsMv/rxi3ANCRVvzJ76ypsar/A1
+qSev4cw02h0ERU9rdMBmt2kbqSk+oruNs5AozqUXFDz2oMzXPw6W88TQh538CXOU
+3H///RdB
+-----END CERTIFICATE-----
+";
+
+    const SHA1_CERT_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIDFzCCAf+gAwIBAgIUbzCQLBVce241r1/3rlhtWJMZMVUwDQYJKoZIhvcNAQEF
+BQAwGzEZMBcGA1UEAwwQc2hhMS5leGFtcGxlLmNvbTAeFw0yNjA3MzAwODIzMDZa
+Fw0zNjA3MjcwODIzMDZaMBsxGTAXBgNVBAMMEHNoYTEuZXhhbXBsZS5jb20wggEi
+MA0GCSqGSIb3DQEBAQUAA4IBDwAwggEKAoIBAQCP5KgxYuDKGVXRo4LXuQMMenrJ
+WAYcbxytWWOiOz9qkJP8sXrEFL37KR6pyfk1UonfjTHvR6oG4Tz182ylG3+2WrLv
+lED3m2D4B9V4NWjhkAY0a2r6GN65P4jAO2gD4j108aW+ZCbTxvB0nNZmaIB92bZl
+vePeuCktKieQvxgITnqa0jC/pHV8sGGNuUc4V3RawLGDUwhUSZIjYtBWpiBbgmTi
+KYEv0bMUA7HVO18vNVJWdBKhmbx08+abMGhz8tkSXiuRcCGkw4pSDgW11I02vp/Z
+DS3ugcsfHwnUJCfkKajPwW9PWEZoDpxorpERKhMMvx8bQFaY0tBg1lPsNPxRAgMB
+AAGjUzBRMB0GA1UdDgQWBBQ/0WVBBT7CPHnAGS4os6oc5H22QzAfBgNVHSMEGDAW
+gBQ/0WVBBT7CPHnAGS4os6oc5H22QzAPBgNVHRMBAf8EBTADAQH/MA0GCSqGSIb3
+DQEBBQUAA4IBAQAaKII9Wdr3124ZUaZIq4Ec1bJn9PAvwDxMcJpVNO45yr+EUVBu
+PTUOHglmZRP7NKs0DPRi4cVlP8+KTMTkAEii1cQFXHSqVse9HQTEdWGOghv25wqr
+iMKk2qVHKpDJ2RC+Vzn3smUrVo3jDDMewFkbbHEAv5bQUfs2yi5Gz1vblVrO1l1I
+6MvSoGbeoRxSLO94xNGX0J17jeWz7K6ZhZbWScuQuTFA/t3u5A0ltK8+BK3BTKso
++yTQtZfeI3SqTfAvay50WBFOoK5KzcFtJ2r6htypwOarU7RcgjOrohG4CL91/vga
+UCK9iwZnqE/2aHaKFR+wF34Wuu0iQeR++kem
+-----END CERTIFICATE-----
+";
+
+    const SAN_CERT_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIDQzCCAiugAwIBAgIUQqcET5tsX3o4FiH7v72v2BJYROEwDQYJKoZIhvcNAQEL
+BQAwGjEYMBYGA1UEAwwPc2FuLmV4YW1wbGUuY29tMB4XDTI2MDczMDA4MjMwNloX
+DTM2MDcyNzA4MjMwNlowGjEYMBYGA1UEAwwPc2FuLmV4YW1wbGUuY29tMIIBIjAN
+BgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEAyjitgW54u6Q78iPS/9XOwrx+YR5Q
+f8KsGZo1vtN7PyftQ2i1fcrRd6A9eTr1y0SgAwQTNXifbzdhfXTxGnv28Je1CeeJ
+OSy5zteXWDvqthSz/W2Y3+gI1P5gON04DiutiHNM3I1ZZrvKlRFpsF99toEKYMyM
+4K5PESA7smhpM/pnufnRnlCwGtmTQ4B74R1cBDxwAEjzztt4Tnusogu2UoDciRZV
+J9M9pAhLiY9FIJ7tYm4l8UFfb01oy1RJvEzOt82TpHZfX3ilo7i5GbjBBJZBEkTV
+MqvHYlcNlxdhXw8mlPLacBpjDfURjA95MXFY0zsZXUolHXBlXmk8J1m1VwIDAQAB
+o4GAMH4wHQYDVR0OBBYEFEEAT1kv0TDTRh0oWaV8xsj3Tx15MB8GA1UdIwQYMBaA
+FEEAT1kv0TDTRh0oWaV8xsj3Tx15MA8GA1UdEwEB/wQFMAMBAf8wKwYDVR0RBCQw
+IoINKi5leGFtcGxlLmNvbYERYWRtaW5AZXhhbXBsZS5jb20wDQYJKoZIhvcNAQEL
+BQADggEBAE2g2YYGL6XAHANe5loVGyR7P+sYZguVPf/ISxcPEXw4nQSBEFzo8H3H
+722aVvM8c6yw0P0I3M1J320kLVU+FwAGWXzbVbKTrDQL64gPFz1FAlYcg2xANLZq
+Wi2e93rclkhdgCXS6svzsC3E9Mlt1XY+jpDLqZ2cFH0cU6Wy0wLaG1AE1KR58Fug
+KG0ganeCfRlnIVOCbnMnCWIcroncxDm2vIf+ri1RJYJru627N9WNqZRDu6yP17N5
+9HAc9KKZDqyfqhVVF/3taJbs/yTQWlUmr+NXWMowWAxx3xhdi6+Np7qYzMOJJTr+
+H6a9iBnrePHztrZRe4ovfmEb1Ou4w5I=
+-----END CERTIFICATE-----
+";
+
+    #[test]
+    fn test_check_validity_flags_expired_as_critical() {
+        let findings = check_validity(1_000, 2_000, 5_000);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, FindingSeverity::Critical);
+    }
+
+    #[test]
+    fn test_check_validity_flags_not_yet_valid_as_high() {
+        let findings = check_validity(5_000, 9_000, 1_000);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, FindingSeverity::High);
+    }
+
+    #[test]
+    fn test_check_validity_flags_expiring_soon_as_medium() {
+        let one_day = 24 * 60 * 60;
+        let findings = check_validity(0, 10_000 + one_day, 10_000);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, FindingSeverity::Medium);
+    }
+
+    #[test]
+    fn test_check_validity_clean_cert_has_no_finding() {
+        let one_year = 365 * 24 * 60 * 60;
+        assert!(check_validity(0, one_year, 1_000).is_empty());
+    }
+
+    #[test]
+    fn test_weak_rsa_key_flagged() {
+        let findings = analyze_certificate(WEAK_KEY_CERT_PEM.as_bytes());
+        assert!(findings
+            .iter()
+            .any(|f| f.check == "Key Strength" && f.severity == FindingSeverity::High));
+    }
+
+    #[test]
+    fn test_sha1_signature_flagged() {
+        let findings = analyze_certificate(SHA1_CERT_PEM.as_bytes());
+        assert!(findings
+            .iter()
+            .any(|f| f.check == "Signature Algorithm" && f.severity == FindingSeverity::High));
+    }
+
+    #[test]
+    fn test_self_signed_flagged() {
+        let findings = analyze_certificate(SAN_CERT_PEM.as_bytes());
+        assert!(findings.iter().any(|f| f.check == "Self-Signed"));
+    }
+
+    #[test]
+    fn test_wildcard_and_email_san_flagged() {
+        let findings = analyze_certificate(SAN_CERT_PEM.as_bytes());
+        assert!(findings
+            .iter()
+            .any(|f| f.check == "Subject Alternative Name" && f.description.contains("wildcard")));
+        assert!(findings
+            .iter()
+            .any(|f| f.check == "Subject Alternative Name" && f.description.contains("email")));
+    }
+
+    #[test]
+    fn test_garbage_input_returns_empty() {
+        assert!(analyze_certificate(b"not a certificate").is_empty());
+    }
+}
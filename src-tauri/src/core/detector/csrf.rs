@@ -0,0 +1,209 @@
+use crate::core::detector::FindingSeverity;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsrfFinding {
+    pub finding_type: String,
+    pub severity: FindingSeverity,
+    pub description: String,
+    /// The unprotected cookie name or form action/field, whichever the
+    /// finding is about.
+    pub subject: String,
+    pub recommendation: String,
+}
+
+/// Hidden input names real frameworks' CSRF middleware expects (Rails'
+/// `authenticity_token`, Django's `csrfmiddlewaretoken`, a generic `csrf`/
+/// `_token` used by most Actix/Express CSRF crates).
+const CSRF_TOKEN_FIELD_NAMES: [&str; 5] = [
+    "csrf",
+    "_token",
+    "authenticity_token",
+    "csrfmiddlewaretoken",
+    "csrf_token",
+];
+
+/// Session-cookie name conventions used to decide whether a `Set-Cookie`
+/// line is worth checking for `SameSite`/CSRF protection at all (as
+/// opposed to an unrelated analytics/preference cookie).
+const SESSION_COOKIE_MARKERS: [&str; 6] = [
+    "session",
+    "sessid",
+    "jsessionid",
+    "phpsessid",
+    "sid",
+    "auth",
+];
+
+fn looks_like_session_cookie(cookie_name: &str) -> bool {
+    let lower = cookie_name.to_lowercase();
+    SESSION_COOKIE_MARKERS.iter().any(|m| lower.contains(m))
+}
+
+/// Detect missing anti-CSRF protections: session cookies without a strict
+/// `SameSite`, HTML forms without a recognized anti-CSRF hidden field, and
+/// the absence of `Origin`/`Referer` as a defense signal. `headers` is the
+/// response header map (as `analyze_headers` takes), `body` the HTML
+/// response body, and `cookies` the raw `Set-Cookie` header values.
+pub fn detect_csrf_weaknesses(
+    headers: &HashMap<String, String>,
+    body: &str,
+    cookies: &[String],
+) -> Vec<CsrfFinding> {
+    let mut findings = Vec::new();
+
+    for cookie in cookies {
+        let name = cookie.split('=').next().unwrap_or(cookie).trim();
+        if !looks_like_session_cookie(name) {
+            continue;
+        }
+
+        let lower = cookie.to_lowercase();
+        let has_strict_samesite = lower.contains("samesite=strict") || lower.contains("samesite=lax");
+
+        if !has_strict_samesite {
+            findings.push(CsrfFinding {
+                finding_type: "Missing SameSite Protection".to_string(),
+                severity: FindingSeverity::High,
+                description: format!(
+                    "Session cookie '{}' is missing SameSite=Strict or SameSite=Lax, so it will be sent on cross-site requests and can be ridden by a forged form/fetch.",
+                    name
+                ),
+                subject: name.to_string(),
+                recommendation: "Set SameSite=Strict (or Lax if cross-site links must carry the session) on this cookie.".to_string(),
+            });
+        }
+    }
+
+    if form_needs_csrf_token(body) {
+        findings.push(CsrfFinding {
+            finding_type: "Missing CSRF Token".to_string(),
+            severity: FindingSeverity::High,
+            description: "A state-changing <form> was found with no recognized anti-CSRF hidden input (csrf/_token/authenticity_token/csrfmiddlewaretoken).".to_string(),
+            subject: "<form>".to_string(),
+            recommendation: "Embed a per-session anti-CSRF token as a hidden form field and validate it server-side on submission.".to_string(),
+        });
+    }
+
+    if !has_origin_or_referer_check(headers) {
+        findings.push(CsrfFinding {
+            finding_type: "No Origin/Referer Validation Signal".to_string(),
+            severity: FindingSeverity::Low,
+            description: "No response header indicates the server validates the Origin/Referer of state-changing requests, the double-submit pattern's usual fallback when a token isn't present.".to_string(),
+            subject: "Origin/Referer".to_string(),
+            recommendation: "Validate that state-changing requests carry an Origin/Referer matching this host, in addition to (or instead of) a CSRF token.".to_string(),
+        });
+    }
+
+    findings
+}
+
+/// Whether `body` contains an HTML `<form>` with no recognized anti-CSRF
+/// hidden input among its fields. A form with a `method="get"` is excluded
+/// since GET forms shouldn't be state-changing in the first place.
+fn form_needs_csrf_token(body: &str) -> bool {
+    let form_pattern = Regex::new(r#"(?is)<form\b([^>]*)>(.*?)</form>"#).unwrap();
+
+    for cap in form_pattern.captures_iter(body) {
+        let attrs = cap.get(1).map(|m| m.as_str()).unwrap_or("").to_lowercase();
+        if attrs.contains("method=\"get\"") || attrs.contains("method='get'") {
+            continue;
+        }
+
+        let contents = cap.get(2).map(|m| m.as_str()).unwrap_or("").to_lowercase();
+        let has_token_field = CSRF_TOKEN_FIELD_NAMES
+            .iter()
+            .any(|name| contents.contains(&format!("name=\"{}\"", name)) || contents.contains(&format!("name='{}'", name)));
+
+        if !has_token_field {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Whether any response header suggests the server checks `Origin`/
+/// `Referer` on state-changing requests (a `Vary: Origin` header, or a
+/// custom header some frameworks emit to advertise the check).
+fn has_origin_or_referer_check(headers: &HashMap<String, String>) -> bool {
+    headers.iter().any(|(key, value)| {
+        key.eq_ignore_ascii_case("vary") && value.to_lowercase().contains("origin")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_csrf_flags_cookie_without_samesite() {
+        let headers = HashMap::new();
+        let cookies = vec!["sessionid=abc123; Path=/; HttpOnly".to_string()];
+        let findings = detect_csrf_weaknesses(&headers, "", &cookies);
+        assert!(findings
+            .iter()
+            .any(|f| f.finding_type == "Missing SameSite Protection"));
+    }
+
+    #[test]
+    fn test_detect_csrf_ignores_non_session_cookie() {
+        let headers = HashMap::new();
+        let cookies = vec!["theme=dark; Path=/".to_string()];
+        let findings = detect_csrf_weaknesses(&headers, "", &cookies);
+        assert!(!findings
+            .iter()
+            .any(|f| f.finding_type == "Missing SameSite Protection"));
+    }
+
+    #[test]
+    fn test_detect_csrf_accepts_strict_samesite_cookie() {
+        let headers = HashMap::new();
+        let cookies = vec!["sessionid=abc123; SameSite=Strict; Secure".to_string()];
+        let findings = detect_csrf_weaknesses(&headers, "", &cookies);
+        assert!(!findings
+            .iter()
+            .any(|f| f.finding_type == "Missing SameSite Protection"));
+    }
+
+    #[test]
+    fn test_detect_csrf_flags_form_without_token() {
+        let body = r#"<form method="post" action="/transfer"><input name="amount"></form>"#;
+        let findings = detect_csrf_weaknesses(&HashMap::new(), body, &[]);
+        assert!(findings.iter().any(|f| f.finding_type == "Missing CSRF Token"));
+    }
+
+    #[test]
+    fn test_detect_csrf_accepts_form_with_token() {
+        let body = r#"<form method="post" action="/transfer"><input name="authenticity_token" value="xyz"><input name="amount"></form>"#;
+        let findings = detect_csrf_weaknesses(&HashMap::new(), body, &[]);
+        assert!(!findings.iter().any(|f| f.finding_type == "Missing CSRF Token"));
+    }
+
+    #[test]
+    fn test_detect_csrf_ignores_get_form() {
+        let body = r#"<form method="get" action="/search"><input name="q"></form>"#;
+        let findings = detect_csrf_weaknesses(&HashMap::new(), body, &[]);
+        assert!(!findings.iter().any(|f| f.finding_type == "Missing CSRF Token"));
+    }
+
+    #[test]
+    fn test_detect_csrf_flags_missing_origin_referer_signal() {
+        let findings = detect_csrf_weaknesses(&HashMap::new(), "", &[]);
+        assert!(findings
+            .iter()
+            .any(|f| f.finding_type == "No Origin/Referer Validation Signal"));
+    }
+
+    #[test]
+    fn test_detect_csrf_accepts_vary_origin_header() {
+        let mut headers = HashMap::new();
+        headers.insert("Vary".to_string(), "Origin".to_string());
+        let findings = detect_csrf_weaknesses(&headers, "", &[]);
+        assert!(!findings
+            .iter()
+            .any(|f| f.finding_type == "No Origin/Referer Validation Signal"));
+    }
+}
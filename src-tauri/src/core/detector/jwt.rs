@@ -0,0 +1,242 @@
+use crate::core::detector::FindingSeverity;
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JwtFinding {
+    pub issue: String,
+    pub severity: FindingSeverity,
+    pub description: String,
+}
+
+/// Weak/default HMAC secrets worth trying offline before giving up --
+/// mirrors the wordlists `jwt_tool`/`hashcat` ship for exactly this attack.
+const WEAK_SECRET_WORDLIST: &[&str] = &[
+    "secret",
+    "changeme",
+    "jwt",
+    "password",
+    "123456",
+    "jwtsecret",
+    "your-256-bit-secret",
+    "supersecret",
+    "admin",
+    "apex",
+    "apexsecurity",
+];
+
+/// Parse and audit a JWT's header/payload for classic token vulnerabilities:
+/// `alg=none` signature stripping, an HS*/RS* key-confusion downgrade, a
+/// missing expiry, and (for HMAC-signed tokens) an offline dictionary crack
+/// of the signing key. Tokens that aren't three dot-separated base64url
+/// segments, or whose header/payload don't decode as JSON, are skipped.
+pub fn detect_jwt_weaknesses(token: &str) -> Vec<JwtFinding> {
+    let mut findings = Vec::new();
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return findings;
+    }
+    let (header_b64, payload_b64, signature_b64) = (parts[0], parts[1], parts[2]);
+
+    let Ok(header_bytes) = general_purpose::URL_SAFE_NO_PAD.decode(header_b64) else {
+        return findings;
+    };
+    let Ok(payload_bytes) = general_purpose::URL_SAFE_NO_PAD.decode(payload_b64) else {
+        return findings;
+    };
+    let Ok(header) = serde_json::from_slice::<serde_json::Value>(&header_bytes) else {
+        return findings;
+    };
+    let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&payload_bytes) else {
+        return findings;
+    };
+
+    let alg = header.get("alg").and_then(|v| v.as_str()).unwrap_or("");
+
+    if alg.eq_ignore_ascii_case("none") {
+        findings.push(JwtFinding {
+            issue: "alg=none".to_string(),
+            severity: FindingSeverity::Critical,
+            description: "Token declares alg=\"none\"; a verifier that honors this accepts an unsigned, fully attacker-forged token.".to_string(),
+        });
+    }
+
+    let references_key_material =
+        header.get("jku").is_some() || header.get("x5u").is_some() || header.get("kid").is_some();
+    if alg.starts_with("HS") && references_key_material {
+        findings.push(JwtFinding {
+            issue: "HS*/RS* key-confusion downgrade".to_string(),
+            severity: FindingSeverity::Critical,
+            description: format!(
+                "Token uses symmetric {} but also carries a jku/x5u/kid header pointing at key material; a verifier that reuses the referenced RSA public key as the HMAC secret is vulnerable to the classic alg-confusion forgery.",
+                alg
+            ),
+        });
+    }
+
+    if payload.get("exp").is_none() && payload.get("nbf").is_none() {
+        findings.push(JwtFinding {
+            issue: "missing exp/nbf claims".to_string(),
+            severity: FindingSeverity::Critical,
+            description: "Token has neither an exp nor an nbf claim, so once issued it never expires and can be replayed indefinitely.".to_string(),
+        });
+    }
+
+    if alg.starts_with("HS") {
+        if let Some(secret) = crack_hmac_secret(header_b64, payload_b64, signature_b64) {
+            findings.push(JwtFinding {
+                issue: "weak HMAC signing key".to_string(),
+                severity: FindingSeverity::Critical,
+                description: format!(
+                    "Token's signature was reproduced using a weak, dictionary-guessable signing key (\"{}\"); anyone can forge arbitrary tokens with it.",
+                    secret
+                ),
+            });
+        }
+    }
+
+    findings
+}
+
+/// Try every candidate in [`WEAK_SECRET_WORDLIST`] as the HMAC signing key,
+/// comparing the recomputed signature against the token's own in constant
+/// time so a timing side-channel can't narrow down the search.
+fn crack_hmac_secret(header_b64: &str, payload_b64: &str, signature_b64: &str) -> Option<&'static str> {
+    let signing_input = format!("{}.{}", header_b64, payload_b64);
+    let target = general_purpose::URL_SAFE_NO_PAD.decode(signature_b64).ok()?;
+
+    WEAK_SECRET_WORDLIST.iter().copied().find(|candidate| {
+        let computed = hmac_sha256(candidate.as_bytes(), signing_input.as_bytes());
+        constant_time_eq(&computed, &target)
+    })
+}
+
+/// Minimal HMAC-SHA256 (RFC 2104) built directly on `sha2::Sha256` rather
+/// than pulling in a dedicated `hmac` crate for this one call site.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    const BLOCK_SIZE: usize = 64;
+    let mut block_key = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        let hashed = Sha256::digest(key);
+        block_key[..hashed.len()].copy_from_slice(&hashed);
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut ipad = [0x36u8; BLOCK_SIZE];
+    let mut opad = [0x5cu8; BLOCK_SIZE];
+    for i in 0..BLOCK_SIZE {
+        ipad[i] ^= block_key[i];
+        opad[i] ^= block_key[i];
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(ipad);
+    inner.update(message);
+    let inner_hash = inner.finalize();
+
+    let mut outer = Sha256::new();
+    outer.update(opad);
+    outer.update(inner_hash);
+    outer.finalize().to_vec()
+}
+
+/// Constant-time byte comparison so a timing side-channel can't leak how
+/// many leading bytes of a guessed signature matched.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_token(header_json: &str, payload_json: &str, secret: &str) -> String {
+        let header_b64 = general_purpose::URL_SAFE_NO_PAD.encode(header_json);
+        let payload_b64 = general_purpose::URL_SAFE_NO_PAD.encode(payload_json);
+        let signing_input = format!("{}.{}", header_b64, payload_b64);
+        let signature = hmac_sha256(secret.as_bytes(), signing_input.as_bytes());
+        let signature_b64 = general_purpose::URL_SAFE_NO_PAD.encode(signature);
+        format!("{}.{}.{}", header_b64, payload_b64, signature_b64)
+    }
+
+    #[test]
+    fn test_detect_jwt_weaknesses_flags_alg_none() {
+        let header = general_purpose::URL_SAFE_NO_PAD.encode(r#"{"alg":"none","typ":"JWT"}"#);
+        let payload = general_purpose::URL_SAFE_NO_PAD.encode(r#"{"sub":"1","exp":9999999999}"#);
+        let token = format!("{}.{}.", header, payload);
+
+        let findings = detect_jwt_weaknesses(&token);
+        assert!(findings.iter().any(|f| f.issue == "alg=none"));
+    }
+
+    #[test]
+    fn test_detect_jwt_weaknesses_flags_missing_exp() {
+        let token = make_token(r#"{"alg":"HS256","typ":"JWT"}"#, r#"{"sub":"1"}"#, "secret");
+        let findings = detect_jwt_weaknesses(&token);
+        assert!(findings.iter().any(|f| f.issue == "missing exp/nbf claims"));
+    }
+
+    #[test]
+    fn test_detect_jwt_weaknesses_accepts_claims_with_expiry() {
+        let token = make_token(
+            r#"{"alg":"HS256","typ":"JWT"}"#,
+            r#"{"sub":"1","exp":9999999999}"#,
+            "a-very-long-and-unguessable-signing-key",
+        );
+        let findings = detect_jwt_weaknesses(&token);
+        assert!(!findings.iter().any(|f| f.issue == "missing exp/nbf claims"));
+    }
+
+    #[test]
+    fn test_detect_jwt_weaknesses_cracks_weak_hmac_secret() {
+        let token = make_token(
+            r#"{"alg":"HS256","typ":"JWT"}"#,
+            r#"{"sub":"1","exp":9999999999}"#,
+            "changeme",
+        );
+        let findings = detect_jwt_weaknesses(&token);
+        let cracked = findings
+            .iter()
+            .find(|f| f.issue == "weak HMAC signing key")
+            .expect("expected a cracked-secret finding");
+        assert!(cracked.description.contains("changeme"));
+    }
+
+    #[test]
+    fn test_detect_jwt_weaknesses_does_not_crack_strong_secret() {
+        let token = make_token(
+            r#"{"alg":"HS256","typ":"JWT"}"#,
+            r#"{"sub":"1","exp":9999999999}"#,
+            "a-very-long-and-unguessable-signing-key",
+        );
+        let findings = detect_jwt_weaknesses(&token);
+        assert!(!findings.iter().any(|f| f.issue == "weak HMAC signing key"));
+    }
+
+    #[test]
+    fn test_detect_jwt_weaknesses_flags_key_confusion_downgrade() {
+        let token = make_token(
+            r#"{"alg":"HS256","typ":"JWT","jku":"https://example.com/keys.json"}"#,
+            r#"{"sub":"1","exp":9999999999}"#,
+            "a-very-long-and-unguessable-signing-key",
+        );
+        let findings = detect_jwt_weaknesses(&token);
+        assert!(findings
+            .iter()
+            .any(|f| f.issue == "HS*/RS* key-confusion downgrade"));
+    }
+
+    #[test]
+    fn test_detect_jwt_weaknesses_skips_malformed_token() {
+        assert!(detect_jwt_weaknesses("not-a-jwt").is_empty());
+    }
+}
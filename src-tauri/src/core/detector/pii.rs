@@ -1,6 +1,8 @@
 use crate::core::detector::FindingSeverity;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecretFinding {
@@ -13,12 +15,31 @@ pub struct SecretFinding {
     pub description: String,
 }
 
-struct SecretPattern {
-    name: &'static str,
-    pattern: &'static str,
+fn default_tag() -> String {
+    "general".to_string()
+}
+
+/// A user-supplied PII rule loaded from an external TOML/JSON ruleset.
+/// Mirrors the shape of the built-in [`PiiPattern`] table, plus a `tag`
+/// (cloud, database, pii, financial, medical, ...) so a whole rule group
+/// can be switched off without touching the binary.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomPiiRule {
+    pub name: String,
+    pub pattern: String,
+    pub severity: FindingSeverity,
+    pub confidence: f64,
+    pub description: String,
+    #[serde(default = "default_tag")]
+    pub tag: String,
+}
+
+struct PiiPattern {
+    name: String,
+    regex: Regex,
     severity: FindingSeverity,
     confidence: f64,
-    description: &'static str,
+    description: String,
 }
 
 fn luhn_check(card_number: &str) -> bool {
@@ -50,6 +71,51 @@ fn luhn_check(card_number: &str) -> bool {
     sum % 10 == 0
 }
 
+/// ISO 7064 mod-97 checksum used by IBAN: strip spaces, move the first four
+/// characters (country code + check digits) to the end, map each letter
+/// A-Z to its two-digit value (A=10 ... Z=35), then reduce the resulting
+/// digit string mod 97 left-to-right so it never has to fit in a single
+/// integer. A valid IBAN leaves remainder 1.
+fn iban_check(s: &str) -> bool {
+    let cleaned: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    if cleaned.len() < 4 {
+        return false;
+    }
+
+    let (head, tail) = cleaned.split_at(4);
+    let rearranged = format!("{}{}", tail, head);
+
+    let mut acc: u64 = 0;
+    for c in rearranged.chars() {
+        let value = match c {
+            '0'..='9' => c.to_digit(10).unwrap() as u64,
+            'A'..='Z' => (c as u64) - ('A' as u64) + 10,
+            'a'..='z' => (c as u64) - ('a' as u64) + 10,
+            _ => return false,
+        };
+        for digit in value.to_string().chars() {
+            acc = (acc * 10 + digit.to_digit(10).unwrap() as u64) % 97;
+        }
+    }
+
+    acc == 1
+}
+
+/// Canadian Social Insurance Number: a plain Luhn checksum over 9 digits,
+/// the same algorithm as credit cards but without the length range check.
+fn sin_check(s: &str) -> bool {
+    let digits: String = s.chars().filter(|c| c.is_ascii_digit()).collect();
+    digits.len() == 9 && luhn_check(&digits)
+}
+
+/// US National Provider Identifier: a Luhn checksum computed over the
+/// 10-digit NPI with the constant prefix `80840` prepended, per the CMS
+/// NPI check-digit specification.
+fn npi_check(s: &str) -> bool {
+    let digits: String = s.chars().filter(|c| c.is_ascii_digit()).collect();
+    digits.len() == 10 && luhn_check(&format!("80840{}", digits))
+}
+
 fn mask_pii(value: &str, pii_type: &str) -> String {
     if pii_type.contains("Email") {
         if let Some(at_pos) = value.find('@') {
@@ -67,108 +133,230 @@ fn mask_pii(value: &str, pii_type: &str) -> String {
     "***".to_string()
 }
 
-fn get_pii_patterns() -> Vec<SecretPattern> {
+/// Rule groups a caller can select/deselect with [`PiiScannerBuilder::select_tags`]
+/// without recompiling: `pii` (SSN/NINO/SIN/passport/email/phone),
+/// `financial` (IBAN/credit cards), `medical` (NPI).
+fn builtin_rules() -> Vec<CustomPiiRule> {
     vec![
-        SecretPattern {
-            name: "US SSN",
-            pattern: r"\b[0-9]{3}-[0-9]{2}-[0-9]{4}\b",
+        CustomPiiRule {
+            name: "US SSN".to_string(),
+            pattern: r"\b[0-9]{3}-[0-9]{2}-[0-9]{4}\b".to_string(),
             severity: FindingSeverity::Critical,
             confidence: 0.90,
-            description: "US Social Security Number detected. Critical PII.",
+            description: "US Social Security Number detected. Critical PII.".to_string(),
+            tag: "pii".to_string(),
         },
-        SecretPattern {
-            name: "US SSN (no dashes)",
-            pattern: r"\b[0-9]{9}\b",
+        CustomPiiRule {
+            name: "US SSN (no dashes)".to_string(),
+            pattern: r"\b[0-9]{9}\b".to_string(),
             severity: FindingSeverity::Medium,
             confidence: 0.30,
-            description: "Potential US SSN (no dashes). Requires context verification.",
+            description: "Potential US SSN (no dashes). Requires context verification.".to_string(),
+            tag: "pii".to_string(),
         },
-        SecretPattern {
-            name: "UK NINO",
-            pattern: r"\b[A-CEGHJ-PR-TW-Z]{1}[A-CEGHJ-NPR-TW-Z]{1}[0-9]{6}[ABCD\s]{1}\b",
+        CustomPiiRule {
+            name: "UK NINO".to_string(),
+            pattern: r"\b[A-CEGHJ-PR-TW-Z]{1}[A-CEGHJ-NPR-TW-Z]{1}[0-9]{6}[ABCD\s]{1}\b".to_string(),
             severity: FindingSeverity::Critical,
             confidence: 0.85,
-            description: "UK National Insurance Number detected.",
+            description: "UK National Insurance Number detected.".to_string(),
+            tag: "pii".to_string(),
         },
-        SecretPattern {
-            name: "Canada SIN",
-            pattern: r"\b[0-9]{3}-[0-9]{3}-[0-9]{3}\b",
+        CustomPiiRule {
+            name: "Canada SIN".to_string(),
+            pattern: r"\b[0-9]{3}-[0-9]{3}-[0-9]{3}\b".to_string(),
             severity: FindingSeverity::Critical,
             confidence: 0.90,
-            description: "Canada Social Insurance Number detected.",
+            description: "Canada Social Insurance Number detected.".to_string(),
+            tag: "pii".to_string(),
         },
-        SecretPattern {
-            name: "IBAN",
-            pattern: r"\b[A-Z]{2}[0-9]{2}[A-Z0-9]{11,30}\b",
+        CustomPiiRule {
+            name: "IBAN".to_string(),
+            pattern: r"\b[A-Z]{2}[0-9]{2}[A-Z0-9]{11,30}\b".to_string(),
             severity: FindingSeverity::High,
             confidence: 0.85,
-            description: "International Bank Account Number (IBAN) detected.",
+            description: "International Bank Account Number (IBAN) detected.".to_string(),
+            tag: "financial".to_string(),
         },
-        SecretPattern {
-            name: "Email Address",
-            pattern: r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Z|a-z]{2,}\b",
+        CustomPiiRule {
+            name: "Email Address".to_string(),
+            pattern: r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Z|a-z]{2,}\b".to_string(),
             severity: FindingSeverity::Medium,
             confidence: 0.95,
-            description: "Email address detected.",
+            description: "Email address detected.".to_string(),
+            tag: "pii".to_string(),
         },
-        SecretPattern {
-            name: "General Phone",
-            pattern: r"\b(?:\+?\d{1,3}[-.\s]?)?\(?\d{3}\)?[-.\s]?\d{3}[-.\s]?\d{4}\b",
+        CustomPiiRule {
+            name: "General Phone".to_string(),
+            pattern: r"\b(?:\+?\d{1,3}[-.\s]?)?\(?\d{3}\)?[-.\s]?\d{3}[-.\s]?\d{4}\b".to_string(),
             severity: FindingSeverity::Medium,
             confidence: 0.80,
-            description: "Phone number detected.",
+            description: "Phone number detected.".to_string(),
+            tag: "pii".to_string(),
         },
-        SecretPattern {
-            name: "Credit Card Number",
-            pattern: r"\b(?:[0-9]{4}[- ]?){3}[0-9]{4}\b",
+        CustomPiiRule {
+            name: "Credit Card Number".to_string(),
+            pattern: r"\b(?:[0-9]{4}[- ]?){3}[0-9]{4}\b".to_string(),
             severity: FindingSeverity::Critical,
             confidence: 0.85,
-            description: "Credit Card Number pattern detected. Verify with Luhn check.",
+            description: "Credit Card Number pattern detected. Verify with Luhn check.".to_string(),
+            tag: "financial".to_string(),
         },
-        SecretPattern {
-            name: "Credit Card (Amex)",
-            pattern: r"\b3[47][0-9]{13}\b",
+        CustomPiiRule {
+            name: "Credit Card (Amex)".to_string(),
+            pattern: r"\b3[47][0-9]{13}\b".to_string(),
             severity: FindingSeverity::Critical,
             confidence: 0.90,
-            description: "American Express Card Number detected.",
+            description: "American Express Card Number detected.".to_string(),
+            tag: "financial".to_string(),
         },
-        SecretPattern {
-            name: "Credit Card (Visa)",
-            pattern: r"\b4[0-9]{12}(?:[0-9]{3})?\b",
+        CustomPiiRule {
+            name: "Credit Card (Visa)".to_string(),
+            pattern: r"\b4[0-9]{12}(?:[0-9]{3})?\b".to_string(),
             severity: FindingSeverity::Critical,
             confidence: 0.85,
-            description: "Visa Card Number detected.",
+            description: "Visa Card Number detected.".to_string(),
+            tag: "financial".to_string(),
         },
-        SecretPattern {
-            name: "Credit Card (MasterCard)",
-            pattern: r"\b5[1-5][0-9]{14}\b",
+        CustomPiiRule {
+            name: "Credit Card (MasterCard)".to_string(),
+            pattern: r"\b5[1-5][0-9]{14}\b".to_string(),
             severity: FindingSeverity::Critical,
             confidence: 0.85,
-            description: "MasterCard Number detected.",
+            description: "MasterCard Number detected.".to_string(),
+            tag: "financial".to_string(),
         },
-        SecretPattern {
-            name: "Passport Number",
-            pattern: r"\b[A-Z0-9]{6,9}\b",
+        CustomPiiRule {
+            name: "US NPI".to_string(),
+            pattern: r"\b[0-9]{10}\b".to_string(),
+            severity: FindingSeverity::High,
+            confidence: 0.40,
+            description: "Potential US National Provider Identifier (NPI). Verified with Luhn check."
+                .to_string(),
+            tag: "medical".to_string(),
+        },
+        CustomPiiRule {
+            name: "Passport Number".to_string(),
+            pattern: r"\b[A-Z0-9]{6,9}\b".to_string(),
             severity: FindingSeverity::High,
             confidence: 0.40,
             description:
-                "Potential Passport Number. High false positive rate without surrounding keywords.",
+                "Potential Passport Number. High false positive rate without surrounding keywords."
+                    .to_string(),
+            tag: "pii".to_string(),
         },
     ]
 }
 
-pub fn detect_pii(content: &str) -> Vec<SecretFinding> {
-    let mut findings = Vec::new();
+/// Builds a [`PiiScanner`] from the built-in rule table, an external
+/// TOML/JSON rule file, or both, the same externalization story
+/// [`crate::core::detector::secrets::SecretScannerBuilder`] gives secret
+/// rules. [`Self::select_tags`] restricts the compiled set to one or more
+/// rule groups (`pii`, `financial`, `medical`, ...) so a noisy group can be
+/// switched off without recompiling.
+pub struct PiiScannerBuilder {
+    rules: Vec<CustomPiiRule>,
+    only_tags: Option<Vec<String>>,
+}
+
+impl PiiScannerBuilder {
+    /// Start from the built-in rule table.
+    pub fn new() -> Self {
+        Self {
+            rules: builtin_rules(),
+            only_tags: None,
+        }
+    }
+
+    /// Start from an empty rule table, discarding the built-in defaults.
+    pub fn without_defaults() -> Self {
+        Self {
+            rules: Vec::new(),
+            only_tags: None,
+        }
+    }
+
+    /// Merge in rules from a JSON file (an array of
+    /// `{ "name", "pattern", "severity", "confidence", "description", "tag" }`
+    /// objects, `tag` optional). A bad regex is reported as `Err` naming the
+    /// offending rule rather than panicking.
+    pub fn load_file(mut self, path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("reading {}: {}", path.display(), e))?;
+        let loaded: Vec<CustomPiiRule> =
+            serde_json::from_str(&text).map_err(|e| format!("parsing {}: {}", path.display(), e))?;
+        for rule in &loaded {
+            Regex::new(&rule.pattern)
+                .map_err(|e| format!("rule '{}': invalid pattern: {}", rule.name, e))?;
+        }
+        self.rules.extend(loaded);
+        Ok(self)
+    }
+
+    /// Restrict the compiled scanner to rules tagged with any of `tags`.
+    pub fn select_tags(mut self, tags: &[&str]) -> Self {
+        self.only_tags = Some(tags.iter().map(|t| t.to_string()).collect());
+        self
+    }
+
+    pub fn build(self) -> Result<PiiScanner, String> {
+        let only_tags = self.only_tags;
+        let mut patterns = Vec::with_capacity(self.rules.len());
+        for rule in self.rules {
+            if let Some(tags) = &only_tags {
+                if !tags.contains(&rule.tag) {
+                    continue;
+                }
+            }
+            let regex = Regex::new(&rule.pattern)
+                .map_err(|e| format!("rule '{}': invalid pattern: {}", rule.name, e))?;
+            patterns.push(PiiPattern {
+                name: rule.name,
+                regex,
+                severity: rule.severity,
+                confidence: rule.confidence,
+                description: rule.description,
+            });
+        }
+        Ok(PiiScanner { patterns })
+    }
+}
+
+impl Default for PiiScannerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-    for pattern in get_pii_patterns() {
-        if let Ok(re) = Regex::new(pattern.pattern) {
-            for cap in re.find_iter(content) {
+/// A compiled set of PII-detection rules, ready to scan content.
+pub struct PiiScanner {
+    patterns: Vec<PiiPattern>,
+}
+
+impl PiiScanner {
+    pub fn scan(&self, content: &str) -> Vec<SecretFinding> {
+        let mut findings = Vec::new();
+
+        for pattern in &self.patterns {
+            for cap in pattern.regex.find_iter(content) {
                 let matched = cap.as_str();
 
                 if pattern.name.contains("Credit Card") && !luhn_check(matched) {
                     continue;
                 }
 
+                if pattern.name == "IBAN" && !iban_check(matched) {
+                    continue;
+                }
+
+                if pattern.name == "Canada SIN" && !sin_check(matched) {
+                    continue;
+                }
+
+                if pattern.name == "US NPI" && !npi_check(matched) {
+                    continue;
+                }
+
                 // Context-aware checks
                 if pattern.name == "US SSN (no dashes)" {
                     let context_start = cap.start().saturating_sub(50);
@@ -194,6 +382,13 @@ pub fn detect_pii(content: &str) -> Vec<SecretFinding> {
                     if !context.contains("sin") && !context.contains("social insurance") {
                         continue;
                     }
+                } else if pattern.name == "US NPI" {
+                    let context_start = cap.start().saturating_sub(50);
+                    let context_end = (cap.end() + 50).min(content.len());
+                    let context = &content[context_start..context_end].to_lowercase();
+                    if !context.contains("npi") && !context.contains("provider identifier") {
+                        continue;
+                    }
                 } else if pattern.name == "Passport Number" {
                     let context_start = cap.start().saturating_sub(50);
                     let context_end = (cap.end() + 50).min(content.len());
@@ -230,19 +425,35 @@ pub fn detect_pii(content: &str) -> Vec<SecretFinding> {
                 }
 
                 findings.push(SecretFinding {
-                    secret_type: pattern.name.to_string(),
+                    secret_type: pattern.name.clone(),
                     severity: pattern.severity.clone(),
-                    matched_value: mask_pii(matched, pattern.name),
+                    matched_value: mask_pii(matched, &pattern.name),
                     start_offset: cap.start(),
                     end_offset: cap.end(),
                     confidence: pattern.confidence,
-                    description: pattern.description.to_string(),
+                    description: pattern.description.clone(),
                 });
             }
         }
+
+        findings
     }
+}
 
-    findings
+static DEFAULT_PII_SCANNER: OnceLock<PiiScanner> = OnceLock::new();
+
+/// The built-in rule set compiles once per process and is reused by every
+/// [`detect_pii`] call instead of recompiling every regex on each scan.
+fn default_scanner() -> &'static PiiScanner {
+    DEFAULT_PII_SCANNER.get_or_init(|| {
+        PiiScannerBuilder::new()
+            .build()
+            .expect("built-in pii rules must compile")
+    })
+}
+
+pub fn detect_pii(content: &str) -> Vec<SecretFinding> {
+    default_scanner().scan(content)
 }
 
 #[cfg(test)]
@@ -291,4 +502,82 @@ mod tests {
         assert!(luhn_check("4242424242424242"));
         assert!(!luhn_check("4242424242424243"));
     }
+
+    #[test]
+    fn test_iban_check_accepts_valid_and_rejects_tampered() {
+        assert!(iban_check("GB94BARC10201530093459"));
+        assert!(!iban_check("GB95BARC10201530093459"));
+    }
+
+    #[test]
+    fn test_detect_pii_rejects_iban_with_bad_checksum() {
+        let content = "IBAN: GB95BARC10201530093459";
+        assert!(detect_pii(content).is_empty());
+    }
+
+    #[test]
+    fn test_detect_pii_accepts_iban_with_valid_checksum() {
+        let content = "IBAN: GB94BARC10201530093459";
+        let findings = detect_pii(content);
+        assert!(findings.iter().any(|f| f.secret_type == "IBAN"));
+    }
+
+    #[test]
+    fn test_sin_check_luhn_over_nine_digits() {
+        assert!(sin_check("046-454-286"));
+        assert!(!sin_check("046-454-287"));
+    }
+
+    #[test]
+    fn test_npi_check_uses_80840_prefix() {
+        assert!(npi_check("1000000004"));
+        assert!(!npi_check("1000000005"));
+    }
+
+    #[test]
+    fn test_detect_pii_rejects_npi_without_context() {
+        let content = "Reference number: 1000000004";
+        assert!(detect_pii(content).is_empty());
+    }
+
+    #[test]
+    fn test_detect_pii_accepts_npi_with_context() {
+        let content = "Provider NPI: 1000000004";
+        let findings = detect_pii(content);
+        assert!(findings.iter().any(|f| f.secret_type == "US NPI"));
+    }
+
+    #[test]
+    fn test_select_tags_restricts_to_financial_group() {
+        let scanner = PiiScannerBuilder::new().select_tags(&["financial"]).build().unwrap();
+        let content = "Contact us at support@example.com, pay with 4242 4242 4242 4242";
+        let findings = scanner.scan(content);
+        assert!(findings.iter().any(|f| f.secret_type.contains("Credit Card")));
+        assert!(!findings.iter().any(|f| f.secret_type == "Email Address"));
+    }
+
+    #[test]
+    fn test_builder_custom_rule_merges_with_defaults() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("apex_custom_pii_rules_test.json");
+        std::fs::write(
+            &path,
+            r#"[{"name":"Internal Employee ID","pattern":"EMP-[0-9]{6}","severity":"Medium","confidence":0.7,"description":"Internal employee identifier.","tag":"pii"}]"#,
+        )
+        .unwrap();
+
+        let scanner = PiiScannerBuilder::new().load_file(&path).unwrap().build().unwrap();
+        let findings = scanner.scan("id=EMP-123456 contact support@example.com");
+        std::fs::remove_file(&path).ok();
+
+        assert!(findings.iter().any(|f| f.secret_type == "Internal Employee ID"));
+        assert!(findings.iter().any(|f| f.secret_type == "Email Address"));
+    }
+
+    #[test]
+    fn test_default_scanner_is_cached_across_calls() {
+        let a = default_scanner() as *const PiiScanner;
+        let b = default_scanner() as *const PiiScanner;
+        assert_eq!(a, b);
+    }
 }
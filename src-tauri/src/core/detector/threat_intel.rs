@@ -0,0 +1,425 @@
+use crate::core::detector::FindingSeverity;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// The kind of observable a threat-intel indicator describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IndicatorType {
+    Domain,
+    Ipv4,
+    Ipv6,
+    Url,
+}
+
+impl IndicatorType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            IndicatorType::Domain => "domain-name",
+            IndicatorType::Ipv4 => "ipv4-addr",
+            IndicatorType::Ipv6 => "ipv6-addr",
+            IndicatorType::Url => "url",
+        }
+    }
+}
+
+/// A single known-bad observable ingested from a STIX bundle or OTX pulse.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Indicator {
+    pub value: String,
+    pub indicator_type: IndicatorType,
+    pub source: String,
+    pub labels: Vec<String>,
+    /// 0-100 confidence, as STIX encodes it. Feeds without a numeric
+    /// confidence (most OTX pulses) default to 50.
+    pub confidence: u8,
+}
+
+/// A response body or URL matched a known-bad indicator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreatIntelFinding {
+    pub matched_value: String,
+    pub indicator_type: String,
+    pub source: String,
+    pub labels: Vec<String>,
+    pub severity: FindingSeverity,
+    pub description: String,
+}
+
+/// STIX 2.x `indicator` SCOs encode their observable as a pattern string
+/// like `[domain-name:value = 'evil.example.com']`. This extracts the
+/// `(type, value)` pair without a full STIX pattern grammar parser, which
+/// is more machinery than this consumer needs.
+fn stix_pattern_regex() -> &'static Regex {
+    static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(domain-name|ipv4-addr|ipv6-addr|url):value\s*=\s*'([^']+)'").unwrap()
+    })
+}
+
+fn stix_type_to_indicator_type(stix_type: &str) -> Option<IndicatorType> {
+    match stix_type {
+        "domain-name" => Some(IndicatorType::Domain),
+        "ipv4-addr" => Some(IndicatorType::Ipv4),
+        "ipv6-addr" => Some(IndicatorType::Ipv6),
+        "url" => Some(IndicatorType::Url),
+        _ => None,
+    }
+}
+
+fn otx_type_to_indicator_type(otx_type: &str) -> Option<IndicatorType> {
+    match otx_type {
+        "domain" | "hostname" => Some(IndicatorType::Domain),
+        "IPv4" => Some(IndicatorType::Ipv4),
+        "IPv6" => Some(IndicatorType::Ipv6),
+        "URL" | "URI" => Some(IndicatorType::Url),
+        _ => None,
+    }
+}
+
+/// Derive a severity from an indicator's labels and confidence: malware/C2/
+/// phishing labels escalate a mid-confidence match, while a plain
+/// low-confidence indicator with no risk label stays Low.
+fn severity_for(indicator: &Indicator) -> FindingSeverity {
+    let high_risk_label = indicator.labels.iter().any(|l| {
+        let l = l.to_lowercase();
+        l.contains("malware")
+            || l.contains("phishing")
+            || l.contains("c2")
+            || l.contains("command-and-control")
+            || l.contains("ransomware")
+    });
+
+    match (high_risk_label, indicator.confidence) {
+        (true, c) if c >= 75 => FindingSeverity::Critical,
+        (true, _) => FindingSeverity::High,
+        (false, c) if c >= 75 => FindingSeverity::High,
+        (false, c) if c >= 40 => FindingSeverity::Medium,
+        (false, _) => FindingSeverity::Low,
+    }
+}
+
+/// Progressively strip the leftmost label of `domain` (`evil.example.com`
+/// -> `example.com` -> `com`), yielding every suffix a domain-name
+/// indicator might be registered under.
+fn domain_suffixes(domain: &str) -> Vec<String> {
+    let labels: Vec<&str> = domain.split('.').collect();
+    (0..labels.len())
+        .map(|i| labels[i..].join("."))
+        .collect()
+}
+
+/// In-memory index of threat-intel indicators, loaded from one or more
+/// STIX 2.x bundles and/or AlienVault OTX pulses. Lookups are O(1) exact
+/// matches for IPs/URLs and a short suffix walk for domains, so scanning a
+/// response body stays cheap even with a large loaded feed.
+#[derive(Debug, Clone, Default)]
+pub struct ThreatIntelMatcher {
+    domains: HashMap<String, Indicator>,
+    ips: HashMap<String, Indicator>,
+    urls: HashMap<String, Indicator>,
+}
+
+impl ThreatIntelMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&mut self, indicator: Indicator) {
+        let key = indicator.value.to_lowercase();
+        match indicator.indicator_type {
+            IndicatorType::Domain => {
+                self.domains.insert(key, indicator);
+            }
+            IndicatorType::Ipv4 | IndicatorType::Ipv6 => {
+                self.ips.insert(key, indicator);
+            }
+            IndicatorType::Url => {
+                self.urls.insert(key, indicator);
+            }
+        }
+    }
+
+    /// Parse a STIX 2.x bundle (`{"type": "bundle", "objects": [...]}`) and
+    /// merge its domain-name/ipv4-addr/ipv6-addr/url indicators into this
+    /// matcher. Returns the number of indicators loaded.
+    pub fn load_stix_bundle(&mut self, json: &str, source: &str) -> Result<usize, String> {
+        let bundle: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let objects = bundle
+            .get("objects")
+            .and_then(Value::as_array)
+            .ok_or("STIX bundle has no \"objects\" array")?;
+
+        let mut loaded = 0;
+        for object in objects {
+            if object.get("type").and_then(Value::as_str) != Some("indicator") {
+                continue;
+            }
+            let Some(pattern) = object.get("pattern").and_then(Value::as_str) else {
+                continue;
+            };
+
+            let labels: Vec<String> = object
+                .get("indicator_types")
+                .or_else(|| object.get("labels"))
+                .and_then(Value::as_array)
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(Value::as_str)
+                        .map(|s| s.to_string())
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            let confidence = object
+                .get("confidence")
+                .and_then(Value::as_u64)
+                .map(|c| c.min(100) as u8)
+                .unwrap_or(50);
+
+            for cap in stix_pattern_regex().captures_iter(pattern) {
+                let Some(indicator_type) = stix_type_to_indicator_type(&cap[1]) else {
+                    continue;
+                };
+                self.insert(Indicator {
+                    value: cap[2].to_string(),
+                    indicator_type,
+                    source: source.to_string(),
+                    labels: labels.clone(),
+                    confidence,
+                });
+                loaded += 1;
+            }
+        }
+
+        Ok(loaded)
+    }
+
+    /// Parse an AlienVault OTX pulse (`{"name": ..., "indicators": [...],
+    /// "tags": [...]}`) and merge its indicators into this matcher. Returns
+    /// the number of indicators loaded.
+    pub fn load_otx_pulse(&mut self, json: &str, source: &str) -> Result<usize, String> {
+        let pulse: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let indicators = pulse
+            .get("indicators")
+            .and_then(Value::as_array)
+            .ok_or("OTX pulse has no \"indicators\" array")?;
+
+        let pulse_labels: Vec<String> = pulse
+            .get("tags")
+            .and_then(Value::as_array)
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(Value::as_str)
+                    .map(|s| s.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut loaded = 0;
+        for entry in indicators {
+            let (Some(value), Some(otx_type)) = (
+                entry.get("indicator").and_then(Value::as_str),
+                entry.get("type").and_then(Value::as_str),
+            ) else {
+                continue;
+            };
+            let Some(indicator_type) = otx_type_to_indicator_type(otx_type) else {
+                continue;
+            };
+
+            self.insert(Indicator {
+                value: value.to_string(),
+                indicator_type,
+                source: source.to_string(),
+                labels: pulse_labels.clone(),
+                confidence: 50,
+            });
+            loaded += 1;
+        }
+
+        Ok(loaded)
+    }
+
+    /// Scan `url` and `body` for any loaded indicator. Domains found in
+    /// `body`/`url` match by exact value or by any registered suffix
+    /// (`evil.example.com` matches an indicator for `example.com`); IPs and
+    /// URLs match exactly.
+    pub fn scan(&self, url: &str, body: &str) -> Vec<ThreatIntelFinding> {
+        let mut findings = Vec::new();
+        let haystacks = [url, body];
+
+        for haystack in haystacks {
+            for candidate in extract_domains(haystack) {
+                for suffix in domain_suffixes(&candidate.to_lowercase()) {
+                    if let Some(indicator) = self.domains.get(&suffix) {
+                        findings.push(to_finding(indicator, &candidate));
+                        break;
+                    }
+                }
+            }
+
+            for candidate in extract_ips(haystack) {
+                if let Some(indicator) = self.ips.get(&candidate.to_lowercase()) {
+                    findings.push(to_finding(indicator, &candidate));
+                }
+            }
+
+            for candidate in extract_urls(haystack) {
+                if let Some(indicator) = self.urls.get(&candidate.to_lowercase()) {
+                    findings.push(to_finding(indicator, &candidate));
+                }
+            }
+        }
+
+        findings
+    }
+}
+
+fn to_finding(indicator: &Indicator, matched_value: &str) -> ThreatIntelFinding {
+    ThreatIntelFinding {
+        matched_value: matched_value.to_string(),
+        indicator_type: indicator.indicator_type.as_str().to_string(),
+        source: indicator.source.clone(),
+        labels: indicator.labels.clone(),
+        severity: severity_for(indicator),
+        description: format!(
+            "Matched known-bad {} indicator from {} ({})",
+            indicator.indicator_type.as_str(),
+            indicator.source,
+            if indicator.labels.is_empty() {
+                "unlabeled".to_string()
+            } else {
+                indicator.labels.join(", ")
+            }
+        ),
+    }
+}
+
+fn extract_domains(text: &str) -> Vec<String> {
+    static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(r"\b(?:[a-zA-Z0-9](?:[a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?\.)+[a-zA-Z]{2,}\b").unwrap()
+    });
+    re.find_iter(text).map(|m| m.as_str().to_string()).collect()
+}
+
+fn extract_ips(text: &str) -> Vec<String> {
+    static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    let re =
+        RE.get_or_init(|| Regex::new(r"\b\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}\b").unwrap());
+    re.find_iter(text).map(|m| m.as_str().to_string()).collect()
+}
+
+fn extract_urls(text: &str) -> Vec<String> {
+    static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r#"\bhttps?://[^\s"'<>]+"#).unwrap());
+    re.find_iter(text).map(|m| m.as_str().to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STIX_BUNDLE: &str = r#"{
+        "type": "bundle",
+        "objects": [
+            {
+                "type": "indicator",
+                "pattern": "[domain-name:value = 'evil.example.com']",
+                "indicator_types": ["malicious-activity", "malware"],
+                "confidence": 85
+            },
+            {
+                "type": "indicator",
+                "pattern": "[ipv4-addr:value = '198.51.100.7']",
+                "labels": ["phishing"],
+                "confidence": 60
+            }
+        ]
+    }"#;
+
+    const OTX_PULSE: &str = r#"{
+        "name": "Example Pulse",
+        "tags": ["botnet"],
+        "indicators": [
+            {"indicator": "bad.otx-example.com", "type": "domain"},
+            {"indicator": "http://bad.otx-example.com/payload", "type": "URL"}
+        ]
+    }"#;
+
+    #[test]
+    fn test_load_stix_bundle_extracts_domain_and_ip_indicators() {
+        let mut matcher = ThreatIntelMatcher::new();
+        let loaded = matcher.load_stix_bundle(STIX_BUNDLE, "test-feed").unwrap();
+        assert_eq!(loaded, 2);
+    }
+
+    #[test]
+    fn test_domain_indicator_matches_via_suffix() {
+        let mut matcher = ThreatIntelMatcher::new();
+        matcher.load_stix_bundle(STIX_BUNDLE, "test-feed").unwrap();
+
+        let findings = matcher.scan(
+            "https://api.example.com/ping",
+            "callback host: cdn.evil.example.com",
+        );
+        assert!(findings.iter().any(|f| f.matched_value == "cdn.evil.example.com"));
+    }
+
+    #[test]
+    fn test_ip_indicator_matches_exactly() {
+        let mut matcher = ThreatIntelMatcher::new();
+        matcher.load_stix_bundle(STIX_BUNDLE, "test-feed").unwrap();
+
+        let findings = matcher.scan("https://api.example.com/ping", "upstream: 198.51.100.7");
+        assert!(findings.iter().any(|f| f.matched_value == "198.51.100.7"));
+        assert_eq!(findings[0].severity, FindingSeverity::High);
+    }
+
+    #[test]
+    fn test_clean_body_has_no_findings() {
+        let mut matcher = ThreatIntelMatcher::new();
+        matcher.load_stix_bundle(STIX_BUNDLE, "test-feed").unwrap();
+
+        let findings = matcher.scan("https://api.example.com/ping", "nothing suspicious here");
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_load_otx_pulse_matches_domain_and_url() {
+        let mut matcher = ThreatIntelMatcher::new();
+        matcher.load_otx_pulse(OTX_PULSE, "otx").unwrap();
+
+        let findings = matcher.scan("", "beacon to bad.otx-example.com observed");
+        assert!(findings.iter().any(|f| f.source == "otx"));
+    }
+
+    #[test]
+    fn test_malware_label_with_high_confidence_is_critical() {
+        let mut matcher = ThreatIntelMatcher::new();
+        matcher.load_stix_bundle(STIX_BUNDLE, "test-feed").unwrap();
+
+        let findings = matcher.scan("", "seen talking to evil.example.com");
+        let finding = findings
+            .iter()
+            .find(|f| f.matched_value == "evil.example.com")
+            .unwrap();
+        assert_eq!(finding.severity, FindingSeverity::Critical);
+    }
+
+    #[test]
+    fn test_refresh_by_loading_a_second_feed_keeps_both() {
+        let mut matcher = ThreatIntelMatcher::new();
+        matcher.load_stix_bundle(STIX_BUNDLE, "feed-a").unwrap();
+        matcher.load_otx_pulse(OTX_PULSE, "feed-b").unwrap();
+
+        let findings = matcher.scan(
+            "",
+            "evil.example.com and bad.otx-example.com both appear here",
+        );
+        assert!(findings.iter().any(|f| f.source == "feed-a"));
+        assert!(findings.iter().any(|f| f.source == "feed-b"));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::db::error::recover_poison;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Per-asset response characteristics recorded during discovery
+/// (`probe_asset`/`crawl_discovered_assets`), used to auto-flag likely false
+/// positives such as wildcard DNS or soft-404 pages that every path on a
+/// host resolves to.
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetMetrics {
+    pub url: String,
+    pub source: String,
+    pub size_bytes: usize,
+    pub content_type: Option<String>,
+    pub response_time_ms: u64,
+    pub body_hash: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateCluster {
+    pub body_hash: u64,
+    pub size_bytes: usize,
+    pub urls: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct DiscoveryStats {
+    pub counts_per_source: HashMap<String, usize>,
+    pub size_distribution: Vec<usize>,
+    pub duplicate_clusters: Vec<DuplicateCluster>,
+}
+
+/// Hash a response body into a normalized fingerprint: the exact bytes
+/// rarely repeat across genuinely distinct pages from the same app, so a
+/// cluster of identical hashes across many discovered URLs is a strong
+/// wildcard/soft-404 signal.
+pub fn hash_body(body: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    body.trim().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Above this many distinct URLs sharing one body hash, the shared response
+/// is treated as a wildcard/soft-404 fingerprint rather than coincidence.
+const DUPLICATE_CLUSTER_THRESHOLD: usize = 3;
+
+#[derive(Default)]
+pub struct TelemetryStore {
+    metrics: Mutex<Vec<AssetMetrics>>,
+}
+
+impl TelemetryStore {
+    pub fn record(&self, metrics: AssetMetrics) {
+        self.metrics.lock().unwrap_or_else(recover_poison).push(metrics);
+    }
+
+    /// Likely-false-positive reason for `body_hash`, if at least
+    /// `DUPLICATE_CLUSTER_THRESHOLD` recorded assets share it.
+    pub fn fp_reason_for(&self, body_hash: u64) -> Option<String> {
+        let metrics = self.metrics.lock().unwrap_or_else(recover_poison);
+        let count = metrics.iter().filter(|m| m.body_hash == body_hash).count();
+        if count >= DUPLICATE_CLUSTER_THRESHOLD {
+            Some("wildcard/soft-404 fingerprint".to_string())
+        } else {
+            None
+        }
+    }
+
+    pub fn stats(&self) -> DiscoveryStats {
+        let metrics = self.metrics.lock().unwrap_or_else(recover_poison);
+
+        let mut counts_per_source = HashMap::new();
+        let mut size_distribution = Vec::new();
+        let mut by_hash: HashMap<u64, Vec<&AssetMetrics>> = HashMap::new();
+
+        for m in metrics.iter() {
+            *counts_per_source.entry(m.source.clone()).or_insert(0) += 1;
+            size_distribution.push(m.size_bytes);
+            by_hash.entry(m.body_hash).or_default().push(m);
+        }
+
+        let mut duplicate_clusters: Vec<DuplicateCluster> = by_hash
+            .into_iter()
+            .filter(|(_, group)| group.len() >= DUPLICATE_CLUSTER_THRESHOLD)
+            .map(|(hash, group)| DuplicateCluster {
+                body_hash: hash,
+                size_bytes: group[0].size_bytes,
+                urls: group.into_iter().map(|m| m.url.clone()).collect(),
+            })
+            .collect();
+        duplicate_clusters.sort_by(|a, b| b.urls.len().cmp(&a.urls.len()));
+
+        DiscoveryStats {
+            counts_per_source,
+            size_distribution,
+            duplicate_clusters,
+        }
+    }
+}
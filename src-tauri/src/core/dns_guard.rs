@@ -0,0 +1,204 @@
+use crate::db::error::recover_poison;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::{Mutex, OnceLock};
+
+/// Hosts `ssrf_guard::guard_and_resolve` has pinned to a specific cleared
+/// address, shared process-wide so the HTTP client that eventually scans a
+/// recursively-discovered asset resolves the *same* address the guard
+/// checked, instead of re-resolving the hostname from scratch -- which is
+/// exactly the window a DNS-rebinding attacker needs (return a safe IP to
+/// the guard, then an internal one once the real scan connects).
+fn pinned_hosts() -> &'static Mutex<HashMap<String, IpAddr>> {
+    static PINNED: OnceLock<Mutex<HashMap<String, IpAddr>>> = OnceLock::new();
+    PINNED.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Bind `host` to `ip` for every future `PinnedHostResolver` lookup. Called
+/// by `ssrf_guard::guard_and_resolve` once it's cleared an address.
+pub fn pin_host(host: &str, ip: IpAddr) {
+    pinned_hosts()
+        .lock()
+        .unwrap_or_else(recover_poison)
+        .insert(host.to_lowercase(), ip);
+}
+
+fn pinned_addr_for(host: &str) -> Option<IpAddr> {
+    pinned_hosts()
+        .lock()
+        .unwrap_or_else(recover_poison)
+        .get(&host.to_lowercase())
+        .copied()
+}
+
+/// `reqwest` DNS resolver that serves a guard-pinned address for any host
+/// `pin_host` has already cleared, falling back to a normal lookup for
+/// everything else. Install this on the client that performs the actual
+/// scan so pinning isn't just advisory.
+#[derive(Clone, Default)]
+pub struct PinnedHostResolver;
+
+impl Resolve for PinnedHostResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            if let Some(ip) = pinned_addr_for(&host) {
+                // Port is filled in by the connector; 0 here is a hostname
+                // lookup-result placeholder, same convention as the lookup below.
+                return Ok(Box::new(std::iter::once(SocketAddr::new(ip, 0))) as Addrs);
+            }
+
+            let resolved: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?
+                .collect();
+            Ok(Box::new(resolved.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Custom resolver for the active scanner's HTTP client. Performs a real DNS
+/// lookup, then rejects the connection if every resolved address falls in
+/// private/loopback/link-local space. This closes the SSRF hole where a
+/// scanned target (or a redirect/recursive discovery hop) resolves to
+/// internal infrastructure via DNS rebinding or a hostname like
+/// `metadata.internal` that simply points at `169.254.169.254`.
+///
+/// `allow_internal` is wired to the `allow_internal_scan_targets` setting so
+/// authorized internal engagements can opt out of the guard.
+#[derive(Clone)]
+pub struct ScopeGuardedResolver {
+    allow_internal: bool,
+}
+
+impl ScopeGuardedResolver {
+    pub fn new(allow_internal: bool) -> Self {
+        Self { allow_internal }
+    }
+}
+
+/// True if `ip` falls in a range that should never be reachable from an
+/// outbound scan: loopback, RFC1918 private space, link-local (including the
+/// cloud metadata endpoint), or the unspecified `0.0.0.0/8` block.
+pub fn is_blocked_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => is_blocked_ipv4(v4),
+        IpAddr::V6(v6) => is_blocked_ipv6(v6),
+    }
+}
+
+fn is_blocked_ipv4(ip: &Ipv4Addr) -> bool {
+    let o = ip.octets();
+    o[0] == 127 // 127.0.0.0/8
+        || o[0] == 10 // 10.0.0.0/8
+        || (o[0] == 172 && (16..=31).contains(&o[1])) // 172.16.0.0/12
+        || (o[0] == 192 && o[1] == 168) // 192.168.0.0/16
+        || (o[0] == 169 && o[1] == 254) // 169.254.0.0/16, incl. 169.254.169.254
+        || o[0] == 0 // 0.0.0.0/8
+}
+
+fn is_blocked_ipv6(ip: &Ipv6Addr) -> bool {
+    if ip.is_loopback() {
+        return true; // ::1
+    }
+    if let Some(v4) = ip.to_ipv4_mapped() {
+        return is_blocked_ipv4(&v4);
+    }
+    let first = ip.segments()[0];
+    (first & 0xfe00) == 0xfc00 // fc00::/7
+        || (first & 0xffc0) == 0xfe80 // fe80::/10
+}
+
+impl Resolve for ScopeGuardedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let allow_internal = self.allow_internal;
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let resolved: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?
+                .collect();
+
+            if allow_internal {
+                return Ok(Box::new(resolved.into_iter()) as Addrs);
+            }
+
+            let safe: Vec<SocketAddr> = resolved
+                .into_iter()
+                .filter(|addr| !is_blocked_ip(&addr.ip()))
+                .collect();
+
+            if safe.is_empty() {
+                return Err(format!(
+                    "blocked SSRF target: {} resolved only to disallowed addresses",
+                    host
+                )
+                .into());
+            }
+
+            Ok(Box::new(safe.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_loopback_and_private_ranges() {
+        let blocked = [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.5",
+            "172.31.255.255",
+            "192.168.1.1",
+            "169.254.169.254",
+            "0.0.0.0",
+            "::1",
+            "fc00::1",
+            "fe80::1",
+        ];
+        for ip in blocked {
+            let parsed: IpAddr = ip.parse().unwrap();
+            assert!(is_blocked_ip(&parsed), "expected {} to be blocked", ip);
+        }
+    }
+
+    #[test]
+    fn allows_public_addresses() {
+        let allowed = ["8.8.8.8", "1.1.1.1", "172.32.0.1", "2606:4700:4700::1111"];
+        for ip in allowed {
+            let parsed: IpAddr = ip.parse().unwrap();
+            assert!(!is_blocked_ip(&parsed), "expected {} to be allowed", ip);
+        }
+    }
+
+    /// End-to-end check that `ScopeGuardedResolver` is actually consulted by
+    /// `reqwest` for an IP-literal authority, not just for hostnames --
+    /// hyper's connector has historically special-cased literal IPs on some
+    /// paths, which would silently bypass `Resolve` entirely. A direct
+    /// request to the cloud metadata address must fail with our own "blocked
+    /// SSRF target" error, not time out or connect.
+    #[tokio::test]
+    async fn blocks_ip_literal_request_end_to_end() {
+        let client = reqwest::Client::builder()
+            .dns_resolver(std::sync::Arc::new(ScopeGuardedResolver::new(false)))
+            .timeout(std::time::Duration::from_secs(5))
+            .build()
+            .unwrap();
+
+        let err = client
+            .get("http://169.254.169.254/")
+            .send()
+            .await
+            .expect_err("request to a blocked IP-literal address must not succeed");
+
+        assert!(
+            err.to_string().contains("blocked SSRF target"),
+            "expected the resolver's rejection, got: {}",
+            err
+        );
+    }
+}
@@ -0,0 +1,315 @@
+use crate::core::detector::Finding;
+use crate::db::SqliteDatabase;
+use crate::error::{Error, Result};
+use crate::utils::crypto::CryptoManager;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Identifies and timestamps one scan's findings, independent of which
+/// [`FindingSink`] persisted them -- the SQLite, in-memory, and encrypted
+/// object-store backends all key off `scan_id`.
+#[derive(Debug, Clone)]
+pub struct ScanMeta {
+    pub scan_id: String,
+    pub url: String,
+    pub created_at: String,
+}
+
+/// Abstracts where a scan's [`Finding`]s end up, the same way
+/// [`crate::core::llm_backend::LlmBackend`] abstracts which LLM server a
+/// prompt is sent to: callers that persist scan results depend on this
+/// trait, not on SQLite directly, so a findings archive can be swapped from
+/// local storage to a remote, encrypted object store without touching scan
+/// logic.
+#[async_trait]
+pub trait FindingSink: Send + Sync {
+    async fn store(&self, meta: &ScanMeta, findings: &[Finding]) -> Result<()>;
+    async fn load(&self, scan_id: &str) -> Result<Vec<Finding>>;
+}
+
+/// Volatile, process-lifetime findings store. Useful for tests and for
+/// short-lived CLI invocations that don't want a SQLite file left behind.
+#[derive(Default)]
+pub struct InMemoryFindingSink {
+    records: Mutex<HashMap<String, Vec<Finding>>>,
+}
+
+impl InMemoryFindingSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl FindingSink for InMemoryFindingSink {
+    async fn store(&self, meta: &ScanMeta, findings: &[Finding]) -> Result<()> {
+        let mut records = self
+            .records
+            .lock()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        records.insert(meta.scan_id.clone(), findings.to_vec());
+        Ok(())
+    }
+
+    async fn load(&self, scan_id: &str) -> Result<Vec<Finding>> {
+        let records = self
+            .records
+            .lock()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        records
+            .get(scan_id)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("no findings archived for scan '{}'", scan_id)))
+    }
+}
+
+/// SQLite-backed archive, separate from the live `assets`/`scan_history`
+/// tables `SqliteDatabase` already maintains -- this one is keyed by an
+/// arbitrary `scan_id` rather than an asset row, so a scan's findings can be
+/// archived (and later replayed) independent of whether the asset that
+/// produced them still exists.
+pub struct SqliteFindingSink {
+    db: SqliteDatabase,
+}
+
+impl SqliteFindingSink {
+    pub fn new(db: SqliteDatabase) -> Result<Self> {
+        {
+            let conn = db.get_conn()?;
+            conn.execute(
+                "CREATE TABLE IF NOT EXISTS finding_archive (
+                    scan_id TEXT PRIMARY KEY,
+                    url TEXT NOT NULL,
+                    created_at TEXT NOT NULL,
+                    findings_json TEXT NOT NULL
+                )",
+                [],
+            )?;
+        }
+        Ok(Self { db })
+    }
+}
+
+#[async_trait]
+impl FindingSink for SqliteFindingSink {
+    async fn store(&self, meta: &ScanMeta, findings: &[Finding]) -> Result<()> {
+        let findings_json = serde_json::to_string(findings)?;
+        let conn = self.db.get_conn()?;
+        conn.execute(
+            "INSERT INTO finding_archive (scan_id, url, created_at, findings_json)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(scan_id) DO UPDATE SET
+                url = excluded.url,
+                created_at = excluded.created_at,
+                findings_json = excluded.findings_json",
+            (&meta.scan_id, &meta.url, &meta.created_at, &findings_json),
+        )?;
+        Ok(())
+    }
+
+    async fn load(&self, scan_id: &str) -> Result<Vec<Finding>> {
+        let conn = self.db.get_conn()?;
+        let findings_json: String = conn
+            .query_row(
+                "SELECT findings_json FROM finding_archive WHERE scan_id = ?1",
+                [scan_id],
+                |row| row.get(0),
+            )
+            .map_err(|_| Error::NotFound(format!("no findings archived for scan '{}'", scan_id)))?;
+        Ok(serde_json::from_str(&findings_json)?)
+    }
+}
+
+/// Raw byte storage for a remote object store (S3-compatible, GCS, etc.).
+/// Kept separate from [`FindingSink`] so the encryption/serialization layer
+/// in [`EncryptedObjectStoreFindingSink`] works against any backend that can
+/// put/get a blob by key, without this crate depending on a specific cloud
+/// SDK.
+#[async_trait]
+pub trait ObjectStoreBackend: Send + Sync {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()>;
+    async fn get(&self, key: &str) -> Result<Vec<u8>>;
+}
+
+/// Archives findings to an [`ObjectStoreBackend`] as AES-256-GCM-encrypted
+/// JSON, using the same [`CryptoManager`] already relied on elsewhere in
+/// this codebase for at-rest secrets -- so findings containing redacted
+/// secrets/PII can be shipped to remote storage without the plaintext ever
+/// leaving the process.
+pub struct EncryptedObjectStoreFindingSink<B: ObjectStoreBackend> {
+    backend: B,
+    crypto: CryptoManager,
+}
+
+impl<B: ObjectStoreBackend> EncryptedObjectStoreFindingSink<B> {
+    pub fn new(backend: B, crypto: CryptoManager) -> Self {
+        Self { backend, crypto }
+    }
+
+    fn object_key(scan_id: &str) -> String {
+        format!("findings/{}.json.enc", scan_id)
+    }
+}
+
+#[async_trait]
+impl<B: ObjectStoreBackend> FindingSink for EncryptedObjectStoreFindingSink<B> {
+    async fn store(&self, meta: &ScanMeta, findings: &[Finding]) -> Result<()> {
+        let payload = serde_json::json!({
+            "scan_id": meta.scan_id,
+            "url": meta.url,
+            "created_at": meta.created_at,
+            "findings": findings,
+        });
+        let plaintext = serde_json::to_string(&payload)?;
+        let encrypted = self
+            .crypto
+            .encrypt(&plaintext)
+            .map_err(Error::Crypto)?;
+        self.backend
+            .put(&Self::object_key(&meta.scan_id), encrypted.into_bytes())
+            .await
+    }
+
+    async fn load(&self, scan_id: &str) -> Result<Vec<Finding>> {
+        let encrypted = self.backend.get(&Self::object_key(scan_id)).await?;
+        let encrypted_str =
+            String::from_utf8(encrypted).map_err(|e| Error::Crypto(e.to_string()))?;
+        let plaintext = self
+            .crypto
+            .decrypt(&encrypted_str)
+            .map_err(Error::Crypto)?;
+        let payload: serde_json::Value = serde_json::from_str(&plaintext)?;
+        let findings = payload
+            .get("findings")
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("no findings archived for scan '{}'", scan_id)))?;
+        Ok(serde_json::from_value(findings)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{Badge, Severity};
+
+    fn sample_findings() -> Vec<Finding> {
+        vec![Finding::new(
+            Badge {
+                emoji: "🔑".to_string(),
+                short: "Secret".to_string(),
+                severity: Severity::Critical,
+                description: "Example finding".to_string(),
+                owasp_category: None,
+                evidence: None,
+                start: None,
+                end: None,
+                is_fp: false,
+                fp_reason: None,
+            },
+            0,
+            10,
+        )]
+    }
+
+    fn sample_meta() -> ScanMeta {
+        ScanMeta {
+            scan_id: "scan-1".to_string(),
+            url: "https://api.example.com/users".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_sink_round_trips_findings() {
+        let sink = InMemoryFindingSink::new();
+        let meta = sample_meta();
+        let findings = sample_findings();
+
+        sink.store(&meta, &findings).await.unwrap();
+        let loaded = sink.load(&meta.scan_id).await.unwrap();
+
+        assert_eq!(loaded.len(), findings.len());
+        assert_eq!(loaded[0].badge.short, "Secret");
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_sink_missing_scan_is_not_found() {
+        let sink = InMemoryFindingSink::new();
+        let err = sink.load("does-not-exist").await.unwrap_err();
+        assert!(matches!(err, Error::NotFound(_)));
+    }
+
+    struct InMemoryObjectStore {
+        objects: Mutex<HashMap<String, Vec<u8>>>,
+    }
+
+    impl InMemoryObjectStore {
+        fn new() -> Self {
+            Self {
+                objects: Mutex::new(HashMap::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ObjectStoreBackend for InMemoryObjectStore {
+        async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+            self.objects
+                .lock()
+                .map_err(|e| Error::Internal(e.to_string()))?
+                .insert(key.to_string(), bytes);
+            Ok(())
+        }
+
+        async fn get(&self, key: &str) -> Result<Vec<u8>> {
+            self.objects
+                .lock()
+                .map_err(|e| Error::Internal(e.to_string()))?
+                .get(key)
+                .cloned()
+                .ok_or_else(|| Error::Proxy(format!("no object at key '{}'", key)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_object_store_sink_round_trips_findings() {
+        let sink = EncryptedObjectStoreFindingSink::new(InMemoryObjectStore::new(), CryptoManager::new());
+        let meta = sample_meta();
+        let findings = sample_findings();
+
+        sink.store(&meta, &findings).await.unwrap();
+        let loaded = sink.load(&meta.scan_id).await.unwrap();
+
+        assert_eq!(loaded.len(), findings.len());
+        assert_eq!(loaded[0].badge.short, "Secret");
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_object_store_sink_stores_ciphertext_not_plaintext() {
+        let object_store = InMemoryObjectStore::new();
+        let crypto = CryptoManager::new();
+
+        let findings = sample_findings();
+        let meta = sample_meta();
+        let payload = serde_json::json!({
+            "scan_id": meta.scan_id,
+            "url": meta.url,
+            "created_at": meta.created_at,
+            "findings": findings,
+        });
+        let plaintext = serde_json::to_string(&payload).unwrap();
+        let encrypted = crypto.encrypt(&plaintext).unwrap();
+        object_store
+            .put(&EncryptedObjectStoreFindingSink::<InMemoryObjectStore>::object_key(&meta.scan_id), encrypted.into_bytes())
+            .await
+            .unwrap();
+
+        let stored = object_store
+            .get(&EncryptedObjectStoreFindingSink::<InMemoryObjectStore>::object_key(&meta.scan_id))
+            .await
+            .unwrap();
+        let stored_text = String::from_utf8(stored).unwrap();
+        assert!(!stored_text.contains("Example finding"));
+    }
+}
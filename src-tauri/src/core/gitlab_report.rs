@@ -0,0 +1,288 @@
+use crate::core::detector::bola::BolaFinding;
+use crate::core::detector::headers::HeaderFinding;
+use crate::core::detector::pii::SecretFinding as PiiFinding;
+use crate::core::detector::secrets::SecretFinding;
+use crate::core::detector::tech_stack::ErrorFinding;
+use crate::core::detector::FindingSeverity;
+use serde::Serialize;
+
+/// GitLab secret-detection/SAST report root
+/// (<https://docs.gitlab.com/ee/user/application_security/sast/#reports-json-format>),
+/// the schema version this module targets is pinned in [`build_gitlab_report`].
+#[derive(Debug, Serialize)]
+pub struct GitlabReport {
+    pub version: String,
+    pub scan: GitlabScan,
+    pub vulnerabilities: Vec<GitlabVulnerability>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GitlabScan {
+    pub scanner: GitlabScanner,
+    pub start_time: String,
+    pub end_time: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GitlabScanner {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GitlabVulnerability {
+    pub id: String,
+    pub category: String,
+    pub name: String,
+    pub description: String,
+    pub severity: String,
+    pub scanner: GitlabScanner,
+    pub location: GitlabLocation,
+    pub identifiers: Vec<GitlabIdentifier>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GitlabLocation {
+    pub file: String,
+    pub start_line: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GitlabIdentifier {
+    #[serde(rename = "type")]
+    pub identifier_type: String,
+    pub name: String,
+    pub value: String,
+}
+
+/// Map this module's `FindingSeverity` onto GitLab's severity enum, which
+/// uses the same five bands under the same names.
+fn gitlab_severity(severity: &FindingSeverity) -> &'static str {
+    match severity {
+        FindingSeverity::Critical => "Critical",
+        FindingSeverity::High => "High",
+        FindingSeverity::Medium => "Medium",
+        FindingSeverity::Low => "Low",
+        FindingSeverity::Info => "Info",
+    }
+}
+
+/// 1-based line number of `offset` within `body`, treating the scanned
+/// response body as the report's "file" so GitLab's line-oriented viewer
+/// can still point at roughly the right spot.
+fn start_line_of(body: &str, offset: usize) -> usize {
+    let offset = offset.min(body.len());
+    body[..offset].bytes().filter(|&b| b == b'\n').count() + 1
+}
+
+fn scanner() -> GitlabScanner {
+    GitlabScanner {
+        id: "apex-security-auditor".to_string(),
+        name: "Apex Security Auditor".to_string(),
+    }
+}
+
+fn cwe_identifier(cwe: &str, name: &str) -> GitlabIdentifier {
+    GitlabIdentifier {
+        identifier_type: "cwe".to_string(),
+        name: name.to_string(),
+        value: cwe.to_string(),
+    }
+}
+
+/// Build a GitLab `gl-secret-detection-report.json`/SAST-shaped report from
+/// every detector's raw findings against one HTTP response. `target_url` is
+/// used as every vulnerability's `location.file` since one report covers a
+/// single scanned response, not a file tree; `start_time`/`end_time` are
+/// passed in rather than generated here so the caller controls formatting
+/// (GitLab expects ISO 8601).
+#[allow(clippy::too_many_arguments)]
+pub fn build_gitlab_report(
+    target_url: &str,
+    body: &str,
+    secrets: &[SecretFinding],
+    pii: &[PiiFinding],
+    header_findings: &[HeaderFinding],
+    bola_findings: &[BolaFinding],
+    error_findings: &[ErrorFinding],
+    start_time: &str,
+    end_time: &str,
+) -> GitlabReport {
+    let mut vulnerabilities = Vec::new();
+
+    for f in secrets {
+        vulnerabilities.push(GitlabVulnerability {
+            id: uuid::Uuid::new_v4().to_string(),
+            category: "secret_detection".to_string(),
+            name: f.secret_type.clone(),
+            description: f.description.clone(),
+            severity: gitlab_severity(&f.severity).to_string(),
+            scanner: scanner(),
+            location: GitlabLocation {
+                file: target_url.to_string(),
+                start_line: start_line_of(body, f.start_offset),
+            },
+            identifiers: vec![cwe_identifier("CWE-312", "Cleartext Storage of Sensitive Information")],
+        });
+    }
+
+    for f in pii {
+        vulnerabilities.push(GitlabVulnerability {
+            id: uuid::Uuid::new_v4().to_string(),
+            category: "secret_detection".to_string(),
+            name: f.secret_type.clone(),
+            description: f.description.clone(),
+            severity: gitlab_severity(&f.severity).to_string(),
+            scanner: scanner(),
+            location: GitlabLocation {
+                file: target_url.to_string(),
+                start_line: start_line_of(body, f.start_offset),
+            },
+            identifiers: vec![cwe_identifier("CWE-312", "Cleartext Storage of Sensitive Information")],
+        });
+    }
+
+    for f in header_findings {
+        vulnerabilities.push(GitlabVulnerability {
+            id: uuid::Uuid::new_v4().to_string(),
+            category: "sast".to_string(),
+            name: format!("Missing or weak header: {}", f.header_name),
+            description: f.description.clone(),
+            severity: gitlab_severity(&f.severity).to_string(),
+            scanner: scanner(),
+            location: GitlabLocation {
+                file: target_url.to_string(),
+                start_line: start_line_of(body, f.start_offset),
+            },
+            identifiers: vec![cwe_identifier(
+                "CWE-693",
+                "Protection Mechanism Failure",
+            )],
+        });
+    }
+
+    for f in bola_findings {
+        vulnerabilities.push(GitlabVulnerability {
+            id: uuid::Uuid::new_v4().to_string(),
+            category: "sast".to_string(),
+            name: f.finding_type.clone(),
+            description: f.description.clone(),
+            severity: gitlab_severity(&f.severity).to_string(),
+            scanner: scanner(),
+            location: GitlabLocation {
+                file: target_url.to_string(),
+                start_line: start_line_of(body, f.start_offset),
+            },
+            identifiers: vec![cwe_identifier(
+                "CWE-639",
+                "Authorization Bypass Through User-Controlled Key",
+            )],
+        });
+    }
+
+    for f in error_findings {
+        vulnerabilities.push(GitlabVulnerability {
+            id: uuid::Uuid::new_v4().to_string(),
+            category: "sast".to_string(),
+            name: format!("{} disclosure", f.technology),
+            description: f.description.clone(),
+            severity: gitlab_severity(&f.severity).to_string(),
+            scanner: scanner(),
+            location: GitlabLocation {
+                file: target_url.to_string(),
+                start_line: start_line_of(body, f.start_offset),
+            },
+            identifiers: vec![cwe_identifier("CWE-200", "Exposure of Sensitive Information")],
+        });
+    }
+
+    GitlabReport {
+        version: "15.0.0".to_string(),
+        scan: GitlabScan {
+            scanner: scanner(),
+            start_time: start_time.to_string(),
+            end_time: end_time.to_string(),
+            status: "success".to_string(),
+        },
+        vulnerabilities,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_line_of_counts_preceding_newlines() {
+        let body = "line1\nline2\nline3";
+        assert_eq!(start_line_of(body, 0), 1);
+        assert_eq!(start_line_of(body, 6), 2);
+        assert_eq!(start_line_of(body, 12), 3);
+    }
+
+    #[test]
+    fn test_build_gitlab_report_tags_secret_with_cwe_312() {
+        let secrets = vec![SecretFinding {
+            secret_type: "AWS Access Key".to_string(),
+            severity: FindingSeverity::Critical,
+            matched_value: "AKIA***".to_string(),
+            start_offset: 6,
+            end_offset: 20,
+            confidence: 0.9,
+            description: "AWS access key detected".to_string(),
+        }];
+
+        let report = build_gitlab_report(
+            "https://api.example.com/debug",
+            "prefix\nAKIAEXAMPLEKEY123456",
+            &secrets,
+            &[],
+            &[],
+            &[],
+            &[],
+            "2026-01-01T00:00:00",
+            "2026-01-01T00:00:05",
+        );
+
+        assert_eq!(report.version, "15.0.0");
+        assert_eq!(report.vulnerabilities.len(), 1);
+        let vuln = &report.vulnerabilities[0];
+        assert_eq!(vuln.category, "secret_detection");
+        assert_eq!(vuln.location.file, "https://api.example.com/debug");
+        assert_eq!(vuln.location.start_line, 2);
+        assert_eq!(vuln.identifiers[0].value, "CWE-312");
+    }
+
+    #[test]
+    fn test_build_gitlab_report_tags_header_finding_with_cwe_693() {
+        let header_findings = vec![HeaderFinding {
+            header_name: "Strict-Transport-Security".to_string(),
+            current_value: None,
+            is_missing: true,
+            is_weak: false,
+            severity: FindingSeverity::Medium,
+            cvss_score: 5.0,
+            cvss_vector: "CVSS:3.1/AV:N".to_string(),
+            description: "HSTS header missing".to_string(),
+            recommendation: "add Strict-Transport-Security".to_string(),
+            start_offset: 0,
+            end_offset: 0,
+        }];
+
+        let report = build_gitlab_report(
+            "https://api.example.com/",
+            "",
+            &[],
+            &[],
+            &header_findings,
+            &[],
+            &[],
+            "2026-01-01T00:00:00",
+            "2026-01-01T00:00:05",
+        );
+
+        assert_eq!(report.vulnerabilities[0].category, "sast");
+        assert_eq!(report.vulnerabilities[0].identifiers[0].value, "CWE-693");
+    }
+}
@@ -0,0 +1,81 @@
+use crate::db::SqliteDatabase;
+use reqwest::cookie::Jar;
+use reqwest::{Certificate, Client, ClientBuilder, Identity, Proxy};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Global outbound-HTTP configuration, loaded from `settings` so every
+/// discovery/replay client can be routed through an intercepting proxy
+/// (Burp/ZAP) and trust the same custom CA or client certificate. Values are
+/// read fresh on each client build rather than cached, matching how other
+/// settings (e.g. `active_scan_concurrency`) are read lazily elsewhere.
+#[derive(Debug, Clone, Default)]
+pub struct HttpClientSettings {
+    /// `http://host:port` or `socks5://host:port` upstream proxy.
+    pub proxy_url: Option<String>,
+    /// Accept self-signed/invalid certs, for testing internal hosts.
+    pub accept_invalid_certs: bool,
+    /// PEM-encoded custom root CA to additionally trust.
+    pub root_ca_pem: Option<String>,
+    /// PEM-encoded client certificate + key for mTLS targets.
+    pub client_identity_pem: Option<String>,
+}
+
+impl HttpClientSettings {
+    pub fn load(db: &SqliteDatabase) -> Self {
+        let get = |key: &str| db.get_setting(key).ok().flatten();
+        Self {
+            proxy_url: get("http_proxy_url"),
+            accept_invalid_certs: get("http_accept_invalid_certs")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            root_ca_pem: get("http_root_ca_pem"),
+            client_identity_pem: get("http_client_identity_pem"),
+        }
+    }
+
+    /// Build a `reqwest::Client` honoring these settings plus `timeout`.
+    /// Used by every recon/replay call site instead of a bare
+    /// `Client::builder()`/`Client::new()` so they all share the same proxy
+    /// and TLS trust configuration.
+    pub fn build_client(&self, timeout: Duration) -> reqwest::Result<Client> {
+        self.apply(Client::builder().timeout(timeout)).build()
+    }
+
+    /// Same as `build_client`, but with a per-caller cookie jar attached
+    /// (e.g. the sequence engine's per-`RequestSequence` jar).
+    pub fn build_client_with_cookie_jar(
+        &self,
+        timeout: Duration,
+        jar: Arc<Jar>,
+    ) -> reqwest::Result<Client> {
+        self.apply(Client::builder().timeout(timeout).cookie_provider(jar))
+            .build()
+    }
+
+    fn apply(&self, mut builder: ClientBuilder) -> ClientBuilder {
+        if let Some(proxy_url) = &self.proxy_url {
+            if let Ok(proxy) = Proxy::all(proxy_url) {
+                builder = builder.proxy(proxy);
+            }
+        }
+
+        if self.accept_invalid_certs {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        if let Some(pem) = &self.root_ca_pem {
+            if let Ok(cert) = Certificate::from_pem(pem.as_bytes()) {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+
+        if let Some(pem) = &self.client_identity_pem {
+            if let Ok(identity) = Identity::from_pem(pem.as_bytes()) {
+                builder = builder.identity(identity);
+            }
+        }
+
+        builder
+    }
+}
@@ -0,0 +1,319 @@
+use futures::stream::{self, StreamExt};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter};
+
+/// How payload lists are combined across a template's insertion points.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AttackMode {
+    /// One payload list, fired at each position in turn while the others
+    /// keep their original template value.
+    Sniper,
+    /// One payload list per position, stepped in lockstep (shortest list
+    /// bounds the attempt count).
+    Pitchfork,
+    /// One payload list per position, every combination of all lists.
+    Cartesian,
+}
+
+impl std::str::FromStr for AttackMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sniper" => Ok(AttackMode::Sniper),
+            "pitchfork" => Ok(AttackMode::Pitchfork),
+            "cartesian" | "clusterbomb" => Ok(AttackMode::Cartesian),
+            other => Err(format!("Unknown attack mode: {}", other)),
+        }
+    }
+}
+
+/// A template string with `§`-delimited insertion points split out into the
+/// literal text around them, so rendering an attempt is just interleaving
+/// `literals` with caller-supplied values.
+#[derive(Debug, Clone)]
+struct TemplateParts {
+    literals: Vec<String>,
+}
+
+impl TemplateParts {
+    fn parse(template: &str) -> Result<Self, String> {
+        let segments: Vec<&str> = template.split('§').collect();
+        if segments.len() % 2 == 0 {
+            return Err("Unbalanced § marker in request template".to_string());
+        }
+        Ok(Self {
+            literals: segments.iter().map(|s| s.to_string()).collect(),
+        })
+    }
+
+    fn placeholder_count(&self) -> usize {
+        self.literals.len() - 1
+    }
+
+    /// The original marker text itself, used as the position's value when
+    /// sniper mode leaves it untouched.
+    fn original_values(template: &str) -> Vec<String> {
+        template
+            .split('§')
+            .skip(1)
+            .step_by(2)
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    fn render(&self, values: &[String]) -> String {
+        let mut out = String::new();
+        for (i, literal) in self.literals.iter().enumerate() {
+            out.push_str(literal);
+            if let Some(v) = values.get(i) {
+                out.push_str(v);
+            }
+        }
+        out
+    }
+}
+
+/// A request template's insertion points span both the URL and the body;
+/// attempts are numbered across both in traversal order (URL first).
+#[derive(Clone)]
+struct RequestTemplate {
+    url: TemplateParts,
+    body: TemplateParts,
+    url_original: Vec<String>,
+    body_original: Vec<String>,
+}
+
+impl RequestTemplate {
+    fn parse(url_template: &str, body_template: &str) -> Result<Self, String> {
+        Ok(Self {
+            url: TemplateParts::parse(url_template)?,
+            body: TemplateParts::parse(body_template)?,
+            url_original: TemplateParts::original_values(url_template),
+            body_original: TemplateParts::original_values(body_template),
+        })
+    }
+
+    fn position_count(&self) -> usize {
+        self.url.placeholder_count() + self.body.placeholder_count()
+    }
+
+    fn original_values(&self) -> Vec<String> {
+        self.url_original
+            .iter()
+            .chain(self.body_original.iter())
+            .cloned()
+            .collect()
+    }
+
+    fn render(&self, values: &[String]) -> (String, String) {
+        let (url_values, body_values) = values.split_at(self.url.placeholder_count());
+        (self.url.render(url_values), self.body.render(body_values))
+    }
+}
+
+/// Build the list of per-attempt value vectors for `mode` given each
+/// position's original value and the caller's payload lists.
+fn build_attempts(
+    mode: AttackMode,
+    original_values: &[String],
+    payload_lists: &[Vec<String>],
+) -> Result<Vec<Vec<String>>, String> {
+    let positions = original_values.len();
+    if positions == 0 {
+        return Err("Template has no § insertion points".to_string());
+    }
+
+    match mode {
+        AttackMode::Sniper => {
+            let payloads = payload_lists
+                .first()
+                .ok_or_else(|| "Sniper mode requires one payload list".to_string())?;
+            let mut attempts = Vec::new();
+            for pos in 0..positions {
+                for payload in payloads {
+                    let mut attempt = original_values.to_vec();
+                    attempt[pos] = payload.clone();
+                    attempts.push(attempt);
+                }
+            }
+            Ok(attempts)
+        }
+        AttackMode::Pitchfork => {
+            if payload_lists.len() != positions {
+                return Err(format!(
+                    "Pitchfork mode requires one payload list per position ({} positions, {} lists)",
+                    positions,
+                    payload_lists.len()
+                ));
+            }
+            let len = payload_lists.iter().map(|l| l.len()).min().unwrap_or(0);
+            Ok((0..len)
+                .map(|i| payload_lists.iter().map(|l| l[i].clone()).collect())
+                .collect())
+        }
+        AttackMode::Cartesian => {
+            if payload_lists.len() != positions {
+                return Err(format!(
+                    "Cartesian mode requires one payload list per position ({} positions, {} lists)",
+                    positions,
+                    payload_lists.len()
+                ));
+            }
+            let mut attempts: Vec<Vec<String>> = vec![Vec::new()];
+            for list in payload_lists {
+                let mut next = Vec::with_capacity(attempts.len() * list.len());
+                for attempt in &attempts {
+                    for payload in list {
+                        let mut extended = attempt.clone();
+                        extended.push(payload.clone());
+                        next.push(extended);
+                    }
+                }
+                attempts = next;
+            }
+            Ok(attempts)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IntruderSummary {
+    #[serde(rename = "totalAttempts")]
+    total_attempts: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct IntruderEventPayload {
+    #[serde(rename = "attemptIndex")]
+    attempt_index: usize,
+    payloads: Vec<String>,
+    status: u16,
+    #[serde(rename = "bodyLength")]
+    body_length: usize,
+    #[serde(rename = "durationMs")]
+    duration_ms: u64,
+    #[serde(rename = "selectedHeaderValues")]
+    selected_header_values: HashMap<String, String>,
+    #[serde(rename = "grepMatch")]
+    grep_match: Option<bool>,
+    #[serde(rename = "grepExtract")]
+    grep_extract: Option<String>,
+    error: Option<String>,
+}
+
+/// Run an Intruder-style batch attack: render the template for every
+/// attempt, fire them through a bounded worker pool, and emit each result
+/// on `intruder://result` as it completes so the frontend can populate a
+/// results table incrementally instead of waiting on the whole batch.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_attack(
+    app: AppHandle,
+    client: reqwest::Client,
+    method: String,
+    url_template: String,
+    headers: HashMap<String, String>,
+    body_template: String,
+    mode: AttackMode,
+    payload_lists: Vec<Vec<String>>,
+    concurrency: usize,
+    grep_regex: Option<String>,
+    grep_extract_group: Option<usize>,
+    selected_response_headers: Vec<String>,
+) -> Result<IntruderSummary, String> {
+    let template = RequestTemplate::parse(&url_template, &body_template)?;
+    if template.position_count() == 0 {
+        return Err("Template has no § insertion points".to_string());
+    }
+    let attempts = build_attempts(mode, &template.original_values(), &payload_lists)?;
+    let total_attempts = attempts.len();
+
+    let grep_regex = grep_regex
+        .map(|pattern| regex::Regex::new(&pattern).map_err(|e| format!("Invalid grep regex: {}", e)))
+        .transpose()?;
+
+    let req_method = method.parse::<reqwest::Method>().map_err(|e| e.to_string())?;
+    let mut header_map = HeaderMap::new();
+    for (k, v) in &headers {
+        if let (Ok(name), Ok(val)) = (HeaderName::from_bytes(k.as_bytes()), HeaderValue::from_str(v)) {
+            header_map.insert(name, val);
+        }
+    }
+
+    stream::iter(attempts.into_iter().enumerate().map(|(attempt_index, payloads)| {
+        let client = client.clone();
+        let req_method = req_method.clone();
+        let header_map = header_map.clone();
+        let template = template.clone();
+        let app = app.clone();
+        let grep_regex = grep_regex.clone();
+        let selected_response_headers = selected_response_headers.clone();
+
+        async move {
+            let (url, body) = template.render(&payloads);
+            let start = Instant::now();
+            let outcome = client
+                .request(req_method, &url)
+                .headers(header_map)
+                .body(body)
+                .send()
+                .await;
+
+            let payload = match outcome {
+                Ok(resp) => {
+                    let status = resp.status().as_u16();
+                    let selected_header_values = selected_response_headers
+                        .iter()
+                        .filter_map(|name| {
+                            resp.headers()
+                                .get(name)
+                                .and_then(|v| v.to_str().ok())
+                                .map(|v| (name.clone(), v.to_string()))
+                        })
+                        .collect();
+                    let response_body = resp.text().await.unwrap_or_default();
+                    let grep_match = grep_regex.as_ref().map(|re| re.is_match(&response_body));
+                    let grep_extract = grep_regex.as_ref().and_then(|re| {
+                        re.captures(&response_body)
+                            .and_then(|caps| caps.get(grep_extract_group.unwrap_or(1)))
+                            .map(|m| m.as_str().to_string())
+                    });
+
+                    IntruderEventPayload {
+                        attempt_index,
+                        payloads,
+                        status,
+                        body_length: response_body.len(),
+                        duration_ms: start.elapsed().as_millis() as u64,
+                        selected_header_values,
+                        grep_match,
+                        grep_extract,
+                        error: None,
+                    }
+                }
+                Err(e) => IntruderEventPayload {
+                    attempt_index,
+                    payloads,
+                    status: 0,
+                    body_length: 0,
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    selected_header_values: HashMap::new(),
+                    grep_match: None,
+                    grep_extract: None,
+                    error: Some(e.to_string()),
+                },
+            };
+
+            let _ = app.emit("intruder://result", payload);
+        }
+    }))
+    .buffer_unordered(concurrency.max(1))
+    .collect::<Vec<_>>()
+    .await;
+
+    Ok(IntruderSummary { total_attempts })
+}
@@ -0,0 +1,38 @@
+use crate::db::error::recover_poison;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+/// Tracks cancellation tokens for in-flight background discovery jobs,
+/// keyed by job id. Job status/progress itself lives in SQLite (see
+/// `db::jobs`) so it survives restarts and can be listed/inspected; this
+/// store only needs to live as long as the process since a cancelled job
+/// is simply one nobody is polling anymore.
+#[derive(Default)]
+pub struct JobManager {
+    tokens: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl JobManager {
+    pub fn register(&self, job_id: &str) -> CancellationToken {
+        let token = CancellationToken::new();
+        self.tokens
+            .lock()
+            .unwrap_or_else(recover_poison)
+            .insert(job_id.to_string(), token.clone());
+        token
+    }
+
+    pub fn cancel(&self, job_id: &str) -> bool {
+        if let Some(token) = self.tokens.lock().unwrap_or_else(recover_poison).get(job_id) {
+            token.cancel();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn unregister(&self, job_id: &str) {
+        self.tokens.lock().unwrap_or_else(recover_poison).remove(job_id);
+    }
+}
@@ -0,0 +1,396 @@
+use crate::core::ai::LlmConfig;
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+
+/// A structured `generate_remediation_diff` result, requested via forced
+/// tool/function calling on backends that support it, instead of parsing
+/// `build_remediation_diff_prompt`'s markdown template (which local models
+/// frequently malform).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Remediation {
+    pub root_cause: String,
+    pub fix_diff: String,
+    pub defensive_strategy: String,
+}
+
+/// A wire format for talking to a local (or local-compatible) LLM server.
+/// `call_llm_api` selects one via `backend_for` so the `analyze_*`/
+/// `generate_*` commands don't need to know which server is actually
+/// listening on `config.endpoint`.
+#[async_trait]
+pub trait LlmBackend: Send + Sync {
+    async fn chat(
+        &self,
+        client: &reqwest::Client,
+        config: &LlmConfig,
+        prompt: &str,
+    ) -> Result<String, String>;
+
+    /// Request a structured remediation breakdown via forced tool calling.
+    /// Returns `Ok(None)` for backends that don't support tool calling (the
+    /// default, and plain Ollama's actual behavior) so the caller falls
+    /// back to the markdown-prompt path.
+    async fn chat_structured_remediation(
+        &self,
+        _client: &reqwest::Client,
+        _config: &LlmConfig,
+        _prompt: &str,
+    ) -> Result<Option<Remediation>, String> {
+        Ok(None)
+    }
+}
+
+/// Build Ollama's `options` object from `config`, so `num_ctx`/`temperature`/
+/// `max_tokens` (Ollama's `num_predict`) are driven by user settings instead
+/// of being hardcoded per call site.
+fn ollama_options(config: &LlmConfig) -> serde_json::Value {
+    let mut options = serde_json::json!({
+        "num_ctx": config.num_ctx,
+        "temperature": config.temperature,
+    });
+    if let Some(max_tokens) = config.max_tokens {
+        options["num_predict"] = serde_json::json!(max_tokens);
+    }
+    options
+}
+
+/// Ollama's native `/api/chat` format: `{model, messages, stream, options}`,
+/// response nested under `message.content`. `config.api_key`, when set, is
+/// sent as a bearer token — Ollama itself ignores it, but this lets the
+/// Local provider target an authenticated gateway in front of a remote
+/// Ollama-compatible server (e.g. `https://ollama.mycorp.net/api/chat`)
+/// rather than only bare `localhost`.
+pub struct OllamaBackend;
+
+#[async_trait]
+impl LlmBackend for OllamaBackend {
+    async fn chat(
+        &self,
+        client: &reqwest::Client,
+        config: &LlmConfig,
+        prompt: &str,
+    ) -> Result<String, String> {
+        let request_body = serde_json::json!({
+            "model": config.model,
+            "messages": [
+                {
+                    "role": "system",
+                    "content": "You are APEX SECURITY ANALYST, an uncompromising, high-signal security research agent. You prioritize raw technical evidence, impact, and realistic exploitability over theoretical risks. Your tone is direct, expert, and occasionally snarky about common dev mistakes."
+                },
+                {
+                    "role": "user",
+                    "content": prompt
+                }
+            ],
+            "stream": false,
+            "options": ollama_options(config)
+        });
+
+        let mut request = client
+            .post(&config.endpoint)
+            .header("Content-Type", "application/json");
+        if !config.api_key.is_empty() {
+            request = request.bearer_auth(&config.api_key);
+        }
+
+        let response = request
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to connect to Ollama at {}. Is it running? Error: {}",
+                    config.endpoint, e
+                )
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(format!("Ollama API error ({}): {}", status, error_text));
+        }
+
+        let response_json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Ollama response: {}", e))?;
+
+        response_json["message"]
+            .as_object()
+            .and_then(|msg| msg.get("content"))
+            .and_then(|content| content.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| "Failed to extract analysis from Ollama response".to_string())
+    }
+}
+
+/// Streaming counterpart to `OllamaBackend::chat`: sets `"stream": true` and
+/// reads the response as a sequence of NDJSON chunks, invoking `on_delta`
+/// with each incremental `message.content` as it arrives so a caller can
+/// forward tokens live (e.g. to the frontend via `AppHandle::emit`) while
+/// still returning the fully assembled text once Ollama reports `"done"`.
+pub async fn stream_ollama_chat(
+    client: &reqwest::Client,
+    config: &LlmConfig,
+    prompt: &str,
+    mut on_delta: impl FnMut(&str),
+) -> Result<String, String> {
+    let request_body = serde_json::json!({
+        "model": config.model,
+        "messages": [
+            {
+                "role": "system",
+                "content": "You are APEX SECURITY ANALYST, an uncompromising, high-signal security research agent. You prioritize raw technical evidence, impact, and realistic exploitability over theoretical risks. Your tone is direct, expert, and occasionally snarky about common dev mistakes."
+            },
+            {
+                "role": "user",
+                "content": prompt
+            }
+        ],
+        "stream": true,
+        "options": ollama_options(config)
+    });
+
+    let mut request = client
+        .post(&config.endpoint)
+        .header("Content-Type", "application/json");
+    if !config.api_key.is_empty() {
+        request = request.bearer_auth(&config.api_key);
+    }
+
+    let response = request
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to connect to Ollama at {}. Is it running? Error: {}",
+                config.endpoint, e
+            )
+        })?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Ollama API error ({}): {}", status, error_text));
+    }
+
+    let mut full = String::new();
+    let mut buf: Vec<u8> = Vec::new();
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Stream read error: {}", e))?;
+        buf.extend_from_slice(&chunk);
+
+        while let Some(newline_pos) = buf.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buf.drain(..=newline_pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let Ok(parsed) = serde_json::from_str::<serde_json::Value>(line) else {
+                continue;
+            };
+
+            if let Some(content) = parsed["message"]["content"].as_str() {
+                if !content.is_empty() {
+                    on_delta(content);
+                    full.push_str(content);
+                }
+            }
+
+            if parsed["done"].as_bool().unwrap_or(false) {
+                return Ok(full);
+            }
+        }
+    }
+
+    Ok(full)
+}
+
+/// OpenAI-compatible `/v1/chat/completions` format served by LM Studio,
+/// llama.cpp's server, and vLLM: `{model, messages, temperature, stream}`,
+/// response nested under `choices[0].message.content` (or, for a streamed
+/// chunk, `choices[0].delta.content`).
+pub struct OpenAiCompatBackend;
+
+#[async_trait]
+impl LlmBackend for OpenAiCompatBackend {
+    async fn chat(
+        &self,
+        client: &reqwest::Client,
+        config: &LlmConfig,
+        prompt: &str,
+    ) -> Result<String, String> {
+        let mut request_body = serde_json::json!({
+            "model": config.model,
+            "messages": [
+                {
+                    "role": "system",
+                    "content": "You are APEX SECURITY ANALYST, an uncompromising, high-signal security research agent. You prioritize raw technical evidence, impact, and realistic exploitability over theoretical risks. Your tone is direct, expert, and occasionally snarky about common dev mistakes."
+                },
+                {
+                    "role": "user",
+                    "content": prompt
+                }
+            ],
+            "temperature": config.temperature,
+            "stream": false
+        });
+        if let Some(max_tokens) = config.max_tokens {
+            request_body["max_tokens"] = serde_json::json!(max_tokens);
+        }
+
+        let mut request = client
+            .post(&config.endpoint)
+            .header("Content-Type", "application/json");
+        if !config.api_key.is_empty() {
+            request = request.bearer_auth(&config.api_key);
+        }
+
+        let response = request
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to connect to local OpenAI-compatible server at {}. Error: {}",
+                    config.endpoint, e
+                )
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(format!(
+                "OpenAI-compatible API error ({}): {}",
+                status, error_text
+            ));
+        }
+
+        let response_json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse OpenAI-compatible response: {}", e))?;
+
+        let choice = response_json["choices"]
+            .get(0)
+            .ok_or_else(|| "Response had no choices".to_string())?;
+
+        choice["message"]
+            .get("content")
+            .or_else(|| choice["delta"].get("content"))
+            .and_then(|content| content.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| "Failed to extract analysis from OpenAI-compatible response".to_string())
+    }
+
+    async fn chat_structured_remediation(
+        &self,
+        client: &reqwest::Client,
+        config: &LlmConfig,
+        prompt: &str,
+    ) -> Result<Option<Remediation>, String> {
+        let tool = serde_json::json!({
+            "type": "function",
+            "function": {
+                "name": "emit_remediation",
+                "description": "Emit a structured remediation breakdown for the vulnerability.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "root_cause": { "type": "string" },
+                        "fix_diff": { "type": "string", "description": "A unified diff-style code fix, vulnerable lines removed and secure lines added." },
+                        "defensive_strategy": { "type": "string" }
+                    },
+                    "required": ["root_cause", "fix_diff", "defensive_strategy"]
+                }
+            }
+        });
+
+        let mut request_body = serde_json::json!({
+            "model": config.model,
+            "messages": [
+                {
+                    "role": "system",
+                    "content": "You are APEX SECURITY ANALYST, an uncompromising, high-signal security research agent. You prioritize raw technical evidence, impact, and realistic exploitability over theoretical risks. Your tone is direct, expert, and occasionally snarky about common dev mistakes."
+                },
+                {
+                    "role": "user",
+                    "content": prompt
+                }
+            ],
+            "temperature": config.temperature,
+            "stream": false,
+            "tools": [tool],
+            "tool_choice": { "type": "function", "function": { "name": "emit_remediation" } }
+        });
+        if let Some(max_tokens) = config.max_tokens {
+            request_body["max_tokens"] = serde_json::json!(max_tokens);
+        }
+
+        let mut request = client
+            .post(&config.endpoint)
+            .header("Content-Type", "application/json");
+        if !config.api_key.is_empty() {
+            request = request.bearer_auth(&config.api_key);
+        }
+
+        let response = request
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to connect to local OpenAI-compatible server at {}. Error: {}",
+                    config.endpoint, e
+                )
+            })?;
+
+        // A server that doesn't support tool calling (or chokes on this
+        // particular schema) is treated as "no structured output available"
+        // rather than a hard error — the caller falls back to the
+        // markdown-prompt path.
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let Ok(response_json) = response.json::<serde_json::Value>().await else {
+            return Ok(None);
+        };
+
+        let Some(arguments) =
+            response_json["choices"][0]["message"]["tool_calls"][0]["function"]["arguments"]
+                .as_str()
+        else {
+            return Ok(None);
+        };
+
+        Ok(serde_json::from_str::<Remediation>(arguments).ok())
+    }
+}
+
+/// Select a backend for `config.endpoint`: paths ending in
+/// `/chat/completions` (LM Studio, llama.cpp server, vLLM) speak the
+/// OpenAI-compatible format; everything else — including Ollama's native
+/// `/api/chat` — uses the Ollama backend.
+pub fn backend_for(config: &LlmConfig) -> Box<dyn LlmBackend> {
+    if config.endpoint.trim_end_matches('/').ends_with("/chat/completions") {
+        Box::new(OpenAiCompatBackend)
+    } else {
+        Box::new(OllamaBackend)
+    }
+}
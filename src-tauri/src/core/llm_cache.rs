@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default time a cached analysis stays valid before a re-run falls back to
+/// a fresh LLM call. Re-opening the same asset or re-running a scan within
+/// this window returns the stored completion instead of re-querying the
+/// model.
+pub const DEFAULT_CACHE_TTL_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    completion: String,
+    provider: String,
+    timestamp: u64,
+}
+
+fn cache_dir() -> PathBuf {
+    let mut path = env::current_exe().unwrap_or_default();
+    path.set_file_name("llm_cache");
+    path
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Content-address a cache entry by `(provider, model, prompt)` so switching
+/// models or providers never returns another backend's completion.
+fn cache_key(provider: &str, model: &str, prompt: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(provider.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(model.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(prompt.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn cache_path(provider: &str, model: &str, prompt: &str) -> PathBuf {
+    cache_dir().join(format!("{}.json", cache_key(provider, model, prompt)))
+}
+
+/// Look up a cached completion for `(provider, model, prompt)`, returning
+/// `None` on a miss or if the stored entry is older than `ttl_secs`.
+pub fn get_cached(provider: &str, model: &str, prompt: &str, ttl_secs: u64) -> Option<String> {
+    let path = cache_path(provider, model, prompt);
+    let content = fs::read_to_string(&path).ok()?;
+    let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+
+    if now_secs().saturating_sub(entry.timestamp) > ttl_secs {
+        return None;
+    }
+
+    Some(entry.completion)
+}
+
+/// Store a completion for `(provider, model, prompt)`, overwriting any
+/// existing entry for the same content hash.
+pub fn store(provider: &str, model: &str, prompt: &str, completion: &str) -> Result<(), String> {
+    let dir = cache_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create LLM cache dir: {}", e))?;
+
+    let entry = CacheEntry {
+        completion: completion.to_string(),
+        provider: provider.to_string(),
+        timestamp: now_secs(),
+    };
+    let content = serde_json::to_string(&entry)
+        .map_err(|e| format!("Failed to serialize cache entry: {}", e))?;
+
+    fs::write(cache_path(provider, model, prompt), content)
+        .map_err(|e| format!("Failed to write LLM cache entry: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_differs_by_model() {
+        let a = cache_key("local", "phi3.5", "same prompt");
+        let b = cache_key("local", "llama3", "same prompt");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_cache_key_differs_by_provider() {
+        let a = cache_key("local", "phi3.5", "same prompt");
+        let b = cache_key("openai", "phi3.5", "same prompt");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_cache_key_stable() {
+        let a = cache_key("local", "phi3.5", "same prompt");
+        let b = cache_key("local", "phi3.5", "same prompt");
+        assert_eq!(a, b);
+    }
+}
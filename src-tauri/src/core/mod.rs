@@ -1,9 +1,26 @@
 pub mod ai;
 pub mod data;
+pub mod deps_audit;
 pub mod detector;
 pub mod detectors;
+pub mod discovery_telemetry;
+pub mod dns_guard;
+pub mod finding_sink;
+pub mod gitlab_report;
+pub mod http_client;
+pub mod intruder;
+pub mod jobs;
+pub mod llm_backend;
+pub mod llm_cache;
+pub mod port_scanner;
+pub mod prompt_redaction;
 pub mod rate_limiter;
+pub mod report;
+pub mod repeater_history;
 pub mod risk;
+pub mod sequence_state;
+pub mod tls_audit;
+pub mod vex_export;
 
 pub mod active_scanner;
 pub mod scanner;
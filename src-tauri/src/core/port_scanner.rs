@@ -0,0 +1,96 @@
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(800);
+const BANNER_TIMEOUT: Duration = Duration::from_millis(800);
+const BANNER_READ_BYTES: usize = 512;
+
+pub const DEFAULT_PORTS: &[u16] = &[
+    21, 22, 23, 25, 80, 443, 445, 1433, 1521, 3306, 3389, 5432, 5900, 6379, 8000, 8008, 8080,
+    8443, 8888, 9000, 9200, 27017,
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScannedPort {
+    pub port: u16,
+    pub banner: Option<String>,
+    pub tls_subject: Option<String>,
+}
+
+/// Concurrently probe `host` across `ports`, bounded by `concurrency`. For
+/// each open port, perform a lightweight banner grab: for plain ports, send
+/// a minimal `HEAD / HTTP/1.0` probe and read whatever comes back; for
+/// 443/8443, complete a TLS handshake and capture the presented
+/// certificate's subject CN instead.
+pub async fn scan_ports_async(host: &str, ports: &[u16], concurrency: usize) -> Vec<ScannedPort> {
+    let concurrency = concurrency.max(1);
+    let host = host.to_string();
+
+    stream::iter(ports.to_vec())
+        .map(|port| {
+            let host = host.clone();
+            async move { probe_port(&host, port).await }
+        })
+        .buffer_unordered(concurrency)
+        .filter_map(|result| async move { result })
+        .collect::<Vec<_>>()
+        .await
+}
+
+async fn probe_port(host: &str, port: u16) -> Option<ScannedPort> {
+    let addr = format!("{}:{}", host, port);
+    let stream = timeout(CONNECT_TIMEOUT, TcpStream::connect(&addr))
+        .await
+        .ok()?
+        .ok()?;
+
+    if port == 443 || port == 8443 {
+        let tls_subject = grab_tls_subject(stream, host).await;
+        return Some(ScannedPort {
+            port,
+            banner: None,
+            tls_subject,
+        });
+    }
+
+    let banner = grab_banner(stream).await;
+    Some(ScannedPort {
+        port,
+        banner,
+        tls_subject: None,
+    })
+}
+
+async fn grab_banner(mut stream: TcpStream) -> Option<String> {
+    let probe = b"HEAD / HTTP/1.0\r\n\r\n";
+    let _ = timeout(BANNER_TIMEOUT, stream.write_all(probe)).await;
+
+    let mut buf = vec![0u8; BANNER_READ_BYTES];
+    let n = timeout(BANNER_TIMEOUT, stream.read(&mut buf)).await.ok()??;
+    if n == 0 {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&buf[..n]).trim().to_string())
+}
+
+async fn grab_tls_subject(stream: TcpStream, host: &str) -> Option<String> {
+    let connector = native_tls::TlsConnector::builder()
+        .danger_accept_invalid_certs(true)
+        .danger_accept_invalid_hostnames(true)
+        .build()
+        .ok()?;
+    let connector = tokio_native_tls::TlsConnector::from(connector);
+    let tls_stream = timeout(BANNER_TIMEOUT, connector.connect(host, stream))
+        .await
+        .ok()?
+        .ok()?;
+
+    let peer_cert = tls_stream.get_ref().peer_certificate().ok().flatten()?;
+    let cert_der = peer_cert.to_der().ok()?;
+    let (_, cert) = x509_parser::parse_x509_certificate(&cert_der).ok()?;
+    Some(cert.subject().to_string())
+}
@@ -1,37 +1,160 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::time::{sleep, Duration, Instant};
 
+/// Requests/sec a host's bucket can be throttled down to by multiplicative
+/// decrease — a floor so a misbehaving host can still recover instead of
+/// being starved forever.
+const MIN_RATE: f64 = 0.2;
+
+/// Requests/sec ceiling a host's bucket can climb back to by additive
+/// increase.
+const MAX_RATE: f64 = 20.0;
+
+/// Additive-increase step applied per request while responses stay 2xx.
+const INCREASE_STEP: f64 = 0.1;
+
+/// Multiplicative-decrease factor applied on a 429/503 response.
+const DECREASE_FACTOR: f64 = 0.5;
+
+/// Burst ceiling: the most tokens a bucket can bank up.
+const BURST_CEILING: f64 = 5.0;
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    rate: f64,
+    last_refill: Instant,
+    /// Set by `on_response` when a `Retry-After` header names an instant to
+    /// hard-block the bucket until, regardless of token balance.
+    blocked_until: Option<Instant>,
+}
+
+impl Bucket {
+    fn new(rate: f64) -> Self {
+        Self {
+            tokens: BURST_CEILING,
+            rate,
+            last_refill: Instant::now(),
+            blocked_until: None,
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(BURST_CEILING);
+        self.last_refill = now;
+    }
+}
+
+fn host_key(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()))
+        .unwrap_or_else(|| url.to_string())
+}
+
+/// Per-host adaptive token-bucket rate limiter. Each host gets its own
+/// bucket so one slow or throttling API can't starve requests to every
+/// other host. `wait()` spends a token (sleeping to refill one if the
+/// bucket is empty); `on_response()` feeds a reply's status code (and any
+/// `Retry-After` it named) back in, applying multiplicative decrease on
+/// 429/503 and additive increase on sustained 2xx, per host.
 #[derive(Debug)]
 pub struct RateLimiter {
-    last_request_time: Mutex<Instant>,
-    min_interval: Duration,
+    buckets: Mutex<HashMap<String, Bucket>>,
+    initial_rate: f64,
 }
 
 impl RateLimiter {
     pub fn new(rate_limit_ms: u64) -> Self {
         Self {
-            last_request_time: Mutex::new(Instant::now() - Duration::from_millis(rate_limit_ms)),
-            min_interval: Duration::from_millis(rate_limit_ms),
+            buckets: Mutex::new(HashMap::new()),
+            initial_rate: Self::rate_from_interval(rate_limit_ms),
+        }
+    }
+
+    fn rate_from_interval(rate_limit_ms: u64) -> f64 {
+        if rate_limit_ms == 0 {
+            MAX_RATE
+        } else {
+            (1000.0 / rate_limit_ms as f64).clamp(MIN_RATE, MAX_RATE)
         }
     }
 
     pub fn update_rate_limit(&mut self, rate_limit_ms: u64) {
-        self.min_interval = Duration::from_millis(rate_limit_ms);
+        self.initial_rate = Self::rate_from_interval(rate_limit_ms);
     }
 
-    /// Waiting for the rate limit to pass before proceeding
-    pub async fn wait(&self) {
-        let mut last_time = self.last_request_time.lock().await;
-        let now = Instant::now();
-        let elapsed = now.duration_since(*last_time);
+    /// Wait for `url`'s host to have a token available, then spend it.
+    pub async fn wait(&self, url: &str) {
+        let key = host_key(url);
+        let initial_rate = self.initial_rate;
+
+        let wait_duration = {
+            let mut buckets = self.buckets.lock().await;
+            let bucket = buckets
+                .entry(key.clone())
+                .or_insert_with(|| Bucket::new(initial_rate));
 
-        if elapsed < self.min_interval {
-            let wait_duration = self.min_interval - elapsed;
+            if let Some(blocked_until) = bucket.blocked_until {
+                let now = Instant::now();
+                if now < blocked_until {
+                    Some(blocked_until - now)
+                } else {
+                    bucket.blocked_until = None;
+                    bucket.last_refill = Instant::now();
+                    None
+                }
+            } else {
+                bucket.refill();
+                if bucket.tokens < 1.0 {
+                    Some(Duration::from_secs_f64(
+                        (1.0 - bucket.tokens) / bucket.rate,
+                    ))
+                } else {
+                    None
+                }
+            }
+        };
+
+        if let Some(wait_duration) = wait_duration {
             sleep(wait_duration).await;
-            *last_time = Instant::now();
-        } else {
-            *last_time = now;
+            crate::metrics::observe_rate_limiter_wait(wait_duration.as_secs_f64());
+        }
+
+        let mut buckets = self.buckets.lock().await;
+        if let Some(bucket) = buckets.get_mut(&key) {
+            bucket.refill();
+            bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+        }
+    }
+
+    /// Feed a response's status code (and optional `Retry-After` duration)
+    /// back into `url`'s host bucket: multiplicative decrease (and a hard
+    /// block, if the server named one) on 429/503, additive increase on
+    /// 2xx.
+    pub async fn on_response(&self, url: &str, status_code: u16, retry_after: Option<Duration>) {
+        let key = host_key(url);
+        let initial_rate = self.initial_rate;
+        let mut buckets = self.buckets.lock().await;
+        let bucket = buckets
+            .entry(key)
+            .or_insert_with(|| Bucket::new(initial_rate));
+
+        match status_code {
+            429 | 503 => {
+                bucket.rate = (bucket.rate * DECREASE_FACTOR).max(MIN_RATE);
+                if let Some(retry_after) = retry_after {
+                    bucket.blocked_until = Some(Instant::now() + retry_after);
+                }
+            }
+            200..=299 => {
+                bucket.rate = (bucket.rate + INCREASE_STEP).min(MAX_RATE);
+            }
+            _ => {}
         }
     }
 }
@@ -0,0 +1,213 @@
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Identifies a cached request by its observable shape rather than a
+/// caller-supplied id, so replaying the exact same request/body pair always
+/// lands on the same history entry instead of accumulating duplicates.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    pub method: String,
+    pub url: String,
+    pub body_hash: String,
+}
+
+impl CacheKey {
+    pub fn new(method: &str, url: &str, body: &str) -> Self {
+        Self {
+            method: method.to_uppercase(),
+            url: url.to_string(),
+            body_hash: format!("{:x}", Sha256::digest(body.as_bytes())),
+        }
+    }
+
+    /// Content address this key is stored under -- a hex SHA-256 digest of
+    /// the key's own canonical form, so `CacheKey::new` is the only thing a
+    /// caller needs to recompute an id for a request it already has.
+    fn content_id(&self) -> String {
+        format!(
+            "{:x}",
+            Sha256::digest(format!("{}\n{}\n{}", self.method, self.url, self.body_hash).as_bytes())
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    pub id: String,
+    pub key: CacheKey,
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+    pub duration_ms: u64,
+    /// SSRI-style digest (`sha256-<base64>`) of `body`, recomputed and
+    /// checked against this field on every [`load`], so a record tampered
+    /// with on disk is caught rather than silently served.
+    pub integrity: String,
+    pub saved_at: String,
+}
+
+fn integrity_of(body: &str) -> String {
+    format!(
+        "sha256-{}",
+        general_purpose::STANDARD.encode(Sha256::digest(body.as_bytes()))
+    )
+}
+
+fn history_dir() -> PathBuf {
+    let mut path = std::env::current_exe().unwrap_or_default();
+    path.set_file_name("repeater_history");
+    path
+}
+
+fn record_path(id: &str) -> PathBuf {
+    history_dir().join(format!("{}.json", id))
+}
+
+/// Persist a request/response as a content-addressable history entry,
+/// returning its id. Saving the same `(method, url, body)` again overwrites
+/// the prior entry for that key rather than growing the history unbounded.
+pub fn save_response(
+    method: &str,
+    url: &str,
+    request_body: &str,
+    status: u16,
+    headers: HashMap<String, String>,
+    body: String,
+    duration_ms: u64,
+) -> Result<HistoryRecord, String> {
+    let key = CacheKey::new(method, url, request_body);
+    let id = key.content_id();
+
+    let record = HistoryRecord {
+        id: id.clone(),
+        key,
+        status,
+        headers,
+        integrity: integrity_of(&body),
+        body,
+        duration_ms,
+        saved_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let dir = history_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create history dir: {}", e))?;
+    let content = serde_json::to_string_pretty(&record)
+        .map_err(|e| format!("Failed to serialize history record: {}", e))?;
+    std::fs::write(record_path(&id), content)
+        .map_err(|e| format!("Failed to write history record: {}", e))?;
+
+    Ok(record)
+}
+
+/// Load a single record by id, verifying its stored `integrity` digest
+/// against the body actually read back. A mismatch means the file was
+/// altered or corrupted on disk after saving -- the entry is evicted
+/// (deleted) on the spot rather than left to fail the same way forever,
+/// since there's nowhere to re-fetch it from.
+pub fn load(id: &str) -> Result<HistoryRecord, String> {
+    let path = record_path(id);
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("History entry '{}' not found: {}", id, e))?;
+    let record: HistoryRecord = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse history entry '{}': {}", id, e))?;
+
+    if integrity_of(&record.body) != record.integrity {
+        let expected = record.integrity.clone();
+        let actual = integrity_of(&record.body);
+        let _ = std::fs::remove_file(&path);
+        return Err(format!(
+            "History entry '{}' failed integrity verification (expected {}, got {}) -- evicted",
+            id, expected, actual
+        ));
+    }
+
+    Ok(record)
+}
+
+/// List every stored record, skipping (rather than failing on) any entry
+/// that fails to parse or verify -- a single corrupted file shouldn't hide
+/// the rest of the history from the caller.
+pub fn list() -> Vec<HistoryRecord> {
+    let dir = history_dir();
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut records: Vec<HistoryRecord> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.path().file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .filter_map(|id| load(&id).ok())
+        .collect();
+
+    records.sort_by(|a, b| b.saved_at.cmp(&a.saved_at));
+    records
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResponseDiff {
+    pub status_a: u16,
+    pub status_b: u16,
+    pub header_diff: Vec<HeaderDelta>,
+    pub body_diff: Vec<BodyDiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HeaderDelta {
+    pub name: String,
+    pub value_a: Option<String>,
+    pub value_b: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BodyDiffLine {
+    pub content: String,
+    pub tag: String, // "Equal", "Delete", "Insert"
+}
+
+/// Compare two stored records: headers present/changed/removed on either
+/// side, plus a line-level body diff via the same `similar` crate
+/// `compare_responses` already uses for ad-hoc response comparison.
+pub fn diff_responses(id_a: &str, id_b: &str) -> Result<ResponseDiff, String> {
+    let a = load(id_a)?;
+    let b = load(id_b)?;
+
+    let mut names: Vec<&String> = a.headers.keys().chain(b.headers.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    let header_diff: Vec<HeaderDelta> = names
+        .into_iter()
+        .filter(|name| a.headers.get(*name) != b.headers.get(*name))
+        .map(|name| HeaderDelta {
+            name: name.clone(),
+            value_a: a.headers.get(name).cloned(),
+            value_b: b.headers.get(name).cloned(),
+        })
+        .collect();
+
+    let text_diff = similar::TextDiff::from_lines(&a.body, &b.body);
+    let body_diff = text_diff
+        .iter_all_changes()
+        .map(|change| {
+            let tag = match change.tag() {
+                similar::ChangeTag::Delete => "Delete",
+                similar::ChangeTag::Insert => "Insert",
+                similar::ChangeTag::Equal => "Equal",
+            };
+            BodyDiffLine {
+                content: change.value().to_string(),
+                tag: tag.to_string(),
+            }
+        })
+        .collect();
+
+    Ok(ResponseDiff {
+        status_a: a.status,
+        status_b: b.status,
+        header_diff,
+        body_diff,
+    })
+}
@@ -0,0 +1,243 @@
+use crate::db::advisories::VulnerabilityMatch;
+use crate::db::Asset;
+use std::collections::BTreeMap;
+use url::Url;
+
+fn host_of(url_str: &str) -> String {
+    Url::parse(url_str)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()))
+        .unwrap_or_else(|| url_str.to_string())
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Map an SGR (Select Graphic Rendition) code to the CSS declaration that
+/// preserves its meaning in HTML. Unrecognized codes are dropped rather than
+/// erroring, since a scanner's ANSI output can include cursor/reset codes
+/// this report doesn't need to render.
+fn ansi_code_to_css(code: &str) -> Option<&'static str> {
+    match code {
+        "1" => Some("font-weight:bold"),
+        "31" => Some("color:#d32f2f"),
+        "32" => Some("color:#388e3c"),
+        "33" => Some("color:#f9a825"),
+        "34" => Some("color:#1976d2"),
+        "35" => Some("color:#8e24aa"),
+        "36" => Some("color:#0097a7"),
+        "91" => Some("color:#ff1744"),
+        "92" => Some("color:#69f0ae"),
+        "93" => Some("color:#ffd740"),
+        _ => None,
+    }
+}
+
+/// Convert a scanner's ANSI-colored severity output (e.g.
+/// `"\x1b[31mCritical\x1b[0m"`) into HTML-escaped text wrapped in styled
+/// `<span>` elements, so terminal-colored severity levels survive into a
+/// browser-viewable report instead of being stripped or shown as raw escape
+/// codes.
+pub fn ansi_to_html(text: &str) -> String {
+    let mut out = String::new();
+    let mut open_spans = 0usize;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' && chars.peek() == Some(&'[') {
+            chars.next();
+            let mut code = String::new();
+            while let Some(&d) = chars.peek() {
+                if d == 'm' {
+                    chars.next();
+                    break;
+                }
+                code.push(d);
+                chars.next();
+            }
+
+            if code.is_empty() || code == "0" {
+                while open_spans > 0 {
+                    out.push_str("</span>");
+                    open_spans -= 1;
+                }
+            } else {
+                let styles: Vec<&str> = code.split(';').filter_map(ansi_code_to_css).collect();
+                if !styles.is_empty() {
+                    out.push_str(&format!("<span style=\"{}\">", styles.join(";")));
+                    open_spans += 1;
+                }
+            }
+        } else {
+            match c {
+                '&' => out.push_str("&amp;"),
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                '\n' => out.push_str("<br>\n"),
+                _ => out.push(c),
+            }
+        }
+    }
+
+    while open_spans > 0 {
+        out.push_str("</span>");
+        open_spans -= 1;
+    }
+
+    out
+}
+
+/// Build a concise, aggregate Markdown summary of `assets` and
+/// `vulnerabilities` grouped by host -- tables of discovered URLs, methods,
+/// recursion depth, and flagged vulnerabilities -- suitable for pasting
+/// directly into a GitHub issue.
+pub fn build_markdown_report(assets: &[Asset], vulnerabilities: &[VulnerabilityMatch]) -> String {
+    let mut grouped: BTreeMap<String, Vec<&Asset>> = BTreeMap::new();
+    for asset in assets {
+        grouped.entry(host_of(&asset.url)).or_default().push(asset);
+    }
+
+    let mut report = String::from("# Asset & Vulnerability Summary\n\n");
+    report.push_str(&format!(
+        "*{} asset(s) across {} host(s), {} vulnerability match(es).*\n\n",
+        assets.len(),
+        grouped.len(),
+        vulnerabilities.len()
+    ));
+
+    for (host, host_assets) in &grouped {
+        report.push_str(&format!("## {}\n\n", host));
+        report.push_str("| URL | Method | Depth | Recursive | Vulnerabilities |\n");
+        report.push_str("|---|---|---|---|---|\n");
+
+        for asset in host_assets {
+            let vulns: Vec<&str> = vulnerabilities
+                .iter()
+                .filter(|v| v.asset_id == asset.id)
+                .map(|v| v.advisory_id.as_str())
+                .collect();
+            let vulns_cell = if vulns.is_empty() {
+                "-".to_string()
+            } else {
+                vulns.join(", ")
+            };
+
+            report.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                asset.url, asset.method, asset.depth, asset.recursive, vulns_cell
+            ));
+        }
+        report.push('\n');
+    }
+
+    report
+}
+
+/// Build a verbose, per-target HTML report: every asset with its findings
+/// and matched vulnerabilities, preserving any ANSI-colored severity output
+/// via `ansi_to_html`.
+pub fn build_html_report(
+    target: &str,
+    assets: &[Asset],
+    vulnerabilities: &[VulnerabilityMatch],
+) -> String {
+    let mut body = String::new();
+    body.push_str(&format!("<h1>Audit Report: {}</h1>\n", html_escape(target)));
+
+    for asset in assets {
+        body.push_str("<div class=\"asset\">\n");
+        body.push_str(&format!("<h2>{}</h2>\n", html_escape(&asset.url)));
+        body.push_str(&format!(
+            "<p>Method: {} | Depth: {} | Risk Score: {}</p>\n",
+            html_escape(&asset.method),
+            asset.depth,
+            asset.risk_score
+        ));
+
+        if !asset.findings.is_empty() {
+            body.push_str("<ul class=\"findings\">\n");
+            for finding in &asset.findings {
+                body.push_str(&format!("<li>{}</li>\n", ansi_to_html(&finding.description)));
+            }
+            body.push_str("</ul>\n");
+        }
+
+        let vulns: Vec<&VulnerabilityMatch> = vulnerabilities
+            .iter()
+            .filter(|v| v.asset_id == asset.id)
+            .collect();
+        if !vulns.is_empty() {
+            body.push_str("<ul class=\"vulnerabilities\">\n");
+            for vuln in vulns {
+                body.push_str(&format!(
+                    "<li>{} ({}): {}</li>\n",
+                    html_escape(&vuln.advisory_id),
+                    html_escape(&vuln.severity),
+                    html_escape(&vuln.summary)
+                ));
+            }
+            body.push_str("</ul>\n");
+        }
+
+        body.push_str("</div>\n");
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Audit Report: {}</title></head>\n<body>\n{}\n</body>\n</html>\n",
+        html_escape(target),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ansi_to_html_wraps_color_span() {
+        let html = ansi_to_html("\u{1b}[31mCritical\u{1b}[0m");
+        assert_eq!(html, "<span style=\"color:#d32f2f\">Critical</span>");
+    }
+
+    #[test]
+    fn test_ansi_to_html_escapes_plain_text() {
+        assert_eq!(ansi_to_html("a < b & c"), "a &lt; b &amp; c");
+    }
+
+    #[test]
+    fn test_markdown_report_groups_by_host() {
+        let assets = vec![
+            Asset {
+                id: 1,
+                url: "http://a.com/x".to_string(),
+                method: "GET".to_string(),
+                status: "Safe".to_string(),
+                status_code: 200,
+                risk_score: 0,
+                findings: vec![],
+                folder_id: 1,
+                response_headers: String::new(),
+                response_body: String::new(),
+                request_headers: String::new(),
+                request_body: String::new(),
+                created_at: String::new(),
+                updated_at: String::new(),
+                notes: String::new(),
+                triage_status: "Unreviewed".to_string(),
+                is_documented: true,
+                source: "Discovery".to_string(),
+                recursive: false,
+                is_workbench: false,
+                depth: 0,
+                content_hash: String::new(),
+            },
+        ];
+
+        let report = build_markdown_report(&assets, &[]);
+        assert!(report.contains("## a.com"));
+        assert!(report.contains("http://a.com/x"));
+    }
+}
@@ -86,3 +86,60 @@ pub fn calculate_risk_for_asset(url: &str, method: &str) -> RiskAssessment {
         risk_factors: factors,
     }
 }
+
+/// Risk for an open TCP port found by the port scanner, based on the
+/// detected service rather than a blanket "High" for every open port.
+/// Well-known web ports are treated as expected exposure (Low/Medium);
+/// unidentified or administrative services (databases, RDP, SSH, etc.)
+/// are scored higher since they're rarely meant to be internet-facing.
+pub fn calculate_risk_for_port(port: u16, banner: Option<&str>) -> RiskAssessment {
+    let mut score;
+    let mut factors = Vec::new();
+
+    let banner_lower = banner.unwrap_or_default().to_lowercase();
+
+    let admin_ports = [21, 22, 23, 25, 135, 139, 445, 1433, 1521, 3306, 3389, 5432, 5900, 6379, 9200, 27017];
+    let web_ports = [80, 443, 8000, 8008, 8080, 8443, 8888, 9000];
+
+    if admin_ports.contains(&port) {
+        score = 60;
+        factors.push(format!("Administrative/database service exposed on port {}", port));
+    } else if web_ports.contains(&port) {
+        score = 20;
+        factors.push(format!("Web service exposed on port {}", port));
+    } else {
+        score = 40;
+        factors.push(format!("Unrecognized service exposed on port {}", port));
+    }
+
+    let sensitive_banner_keywords = ["mysql", "postgres", "redis", "mongodb", "rdp", "ssh", "ftp"];
+    for kw in sensitive_banner_keywords {
+        if banner_lower.contains(kw) {
+            score += 20;
+            factors.push(format!("Banner indicates {} service", kw));
+            break;
+        }
+    }
+
+    if score > 100 {
+        score = 100;
+    }
+
+    let risk_level = if score >= 80 {
+        "Critical".to_string()
+    } else if score >= 50 {
+        "High".to_string()
+    } else if score >= 30 {
+        "Medium".to_string()
+    } else if score > 0 {
+        "Low".to_string()
+    } else {
+        "Info".to_string()
+    };
+
+    RiskAssessment {
+        risk_score: score,
+        risk_level,
+        risk_factors: factors,
+    }
+}
@@ -0,0 +1,21 @@
+use crate::db::error::recover_poison;
+use reqwest::cookie::Jar;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Per-`RequestSequence` cookie jars, keyed by sequence id, so that an
+/// authenticated multi-step flow (login -> action) keeps the session cookie
+/// a server issues on step 1 available to every later step.
+#[derive(Default)]
+pub struct SequenceJarStore {
+    jars: Mutex<HashMap<String, Arc<Jar>>>,
+}
+
+impl SequenceJarStore {
+    pub fn jar_for(&self, sequence_id: &str) -> Arc<Jar> {
+        let mut jars = self.jars.lock().unwrap_or_else(recover_poison);
+        jars.entry(sequence_id.to_string())
+            .or_insert_with(|| Arc::new(Jar::default()))
+            .clone()
+    }
+}
@@ -0,0 +1,166 @@
+use crate::db::{Badge, Severity};
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Path to the `testssl.sh` binary, overridable via the
+/// `TESTSSL_BIN` environment variable for installs that don't put it on
+/// `PATH` (e.g. a vendored copy shipped next to the app bundle).
+fn testssl_binary() -> String {
+    std::env::var("TESTSSL_BIN").unwrap_or_else(|_| "testssl.sh".to_string())
+}
+
+const SCAN_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// One row of testssl.sh's `--jsonfile` output. testssl emits far more
+/// fields than we use; only the ones needed to produce a `Badge` are
+/// deserialized, everything else is ignored.
+#[derive(Debug, Deserialize)]
+struct TestsslFinding {
+    id: String,
+    severity: String,
+    finding: String,
+}
+
+/// Run `testssl.sh --jsonfile <tmp> <host>` and convert its findings into
+/// the same `Badge` vocabulary the body/header detectors use, so TLS
+/// posture issues show up in the same findings list as everything else.
+///
+/// Returns an empty `Vec` (never an `Err`) if the binary is missing, the
+/// scan fails to start, or the process times out -- a missing `testssl.sh`
+/// install shouldn't block the rest of a scan.
+pub fn scan_tls_posture(host: &str) -> Vec<Badge> {
+    let Some(json) = run_testssl(host) else {
+        return Vec::new();
+    };
+
+    let Ok(findings) = serde_json::from_str::<Vec<TestsslFinding>>(&json) else {
+        return Vec::new();
+    };
+
+    findings.iter().filter_map(finding_to_badge).collect()
+}
+
+fn run_testssl(host: &str) -> Option<String> {
+    let out_path: PathBuf =
+        std::env::temp_dir().join(format!("testssl-{}.json", Uuid::new_v4()));
+
+    let mut child = Command::new(testssl_binary())
+        .arg("--quiet")
+        .arg("--jsonfile")
+        .arg(&out_path)
+        .arg(host)
+        .spawn()
+        .ok()?;
+
+    let start = std::time::Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().ok()? {
+            break status;
+        }
+        if start.elapsed() > SCAN_TIMEOUT {
+            let _ = child.kill();
+            let _ = std::fs::remove_file(&out_path);
+            return None;
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    };
+
+    if !status.success() {
+        let _ = std::fs::remove_file(&out_path);
+        return None;
+    }
+
+    let contents = std::fs::read_to_string(&out_path).ok();
+    let _ = std::fs::remove_file(&out_path);
+    contents
+}
+
+/// Map a testssl.sh finding onto our `Badge` vocabulary. Only findings
+/// whose `id` matches a known posture problem are surfaced -- testssl
+/// reports dozens of informational rows (protocol support, cipher lists)
+/// we don't want flooding the findings list.
+fn finding_to_badge(finding: &TestsslFinding) -> Option<Badge> {
+    let severity = map_severity(&finding.severity, &finding.id)?;
+
+    let (emoji, short) = match finding.id.as_str() {
+        id if id.contains("expired") => ("🔐", "Expired TLS Certificate"),
+        id if id.contains("self_signed") => ("🔐", "Self-Signed TLS Certificate"),
+        id if id.starts_with("heartbleed") => ("💔", "Heartbleed"),
+        id if id.starts_with("ROBOT") => ("🤖", "ROBOT Vulnerability"),
+        id if id.starts_with("BEAST") => ("🐾", "BEAST Vulnerability"),
+        id if id.starts_with("CRIME") => ("🎭", "CRIME Vulnerability"),
+        id if id.contains("SSLv2") || id.contains("SSLv3") => ("🧓", "Obsolete SSL Protocol"),
+        id if id.contains("TLS1") && id.contains("TLS1_1") => ("🧓", "Obsolete TLS Protocol"),
+        id if id.contains("RC4") => ("🔓", "Weak Cipher (RC4)"),
+        id if id.contains("export") => ("🔓", "Export-Grade Cipher"),
+        id if id.contains("ocsp_stapling") => ("📋", "Missing OCSP Stapling"),
+        _ => return None,
+    };
+
+    Some(Badge::new(emoji, short, severity, &finding.finding))
+}
+
+fn map_severity(raw: &str, id: &str) -> Option<Severity> {
+    match raw.to_uppercase().as_str() {
+        "CRITICAL" | "FATAL" => Some(Severity::Critical),
+        "HIGH" => Some(Severity::High),
+        "MEDIUM" => Some(Severity::Medium),
+        "LOW" => Some(Severity::Low),
+        "OK" => {
+            // testssl reports a clean OCSP/stapling check as "OK"; we only
+            // care about the absence, which the opposite finding id covers.
+            let _ = id;
+            None
+        }
+        _ => Some(Severity::Info),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expired_cert_maps_to_critical_badge() {
+        let finding = TestsslFinding {
+            id: "cert_expiration_status".to_string(),
+            severity: "CRITICAL".to_string(),
+            finding: "certificate expired 4 days ago".to_string(),
+        };
+        let badge = finding_to_badge(&finding).expect("expected a badge");
+        assert_eq!(badge.severity, Severity::Critical);
+        assert_eq!(badge.short, "Expired TLS Certificate");
+    }
+
+    #[test]
+    fn test_heartbleed_maps_to_badge() {
+        let finding = TestsslFinding {
+            id: "heartbleed".to_string(),
+            severity: "HIGH".to_string(),
+            finding: "VULNERABLE".to_string(),
+        };
+        let badge = finding_to_badge(&finding).expect("expected a badge");
+        assert_eq!(badge.short, "Heartbleed");
+    }
+
+    #[test]
+    fn test_unknown_finding_id_is_dropped() {
+        let finding = TestsslFinding {
+            id: "cipher_list_some_unrelated_row".to_string(),
+            severity: "INFO".to_string(),
+            finding: "TLS_AES_128_GCM_SHA256".to_string(),
+        };
+        assert!(finding_to_badge(&finding).is_none());
+    }
+
+    #[test]
+    fn test_missing_binary_returns_empty() {
+        std::env::set_var("TESTSSL_BIN", "/nonexistent/testssl.sh");
+        let badges = scan_tls_posture("example.com");
+        assert!(badges.is_empty());
+        std::env::remove_var("TESTSSL_BIN");
+    }
+}
@@ -0,0 +1,222 @@
+use regex::Regex;
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A CycloneDX VEX (Vulnerability Exploitability eXchange) document, the
+/// machine-readable counterpart to the Markdown an `analyze_finding`/
+/// `analyze_asset_summary` command returns — so a CVSS vector and
+/// false-positive verdict that's otherwise trapped in prose can feed
+/// downstream vulnerability-management tooling.
+#[derive(Debug, Serialize)]
+pub struct VexDocument {
+    #[serde(rename = "bomFormat")]
+    pub bom_format: String,
+    #[serde(rename = "specVersion")]
+    pub spec_version: String,
+    pub version: u32,
+    pub vulnerabilities: Vec<VexVulnerability>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VexVulnerability {
+    pub id: String,
+    pub ratings: Vec<VexRating>,
+    pub affects: Vec<VexAffects>,
+    pub analysis: VexAnalysis,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VexRating {
+    pub method: String,
+    pub vector: String,
+    pub score: f64,
+    pub severity: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VexAffects {
+    #[serde(rename = "ref")]
+    pub reference: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VexAnalysis {
+    pub state: String,
+}
+
+/// CVSS v3.1 severity bands (same thresholds used by `core::risk`'s
+/// risk-level scoring, just on the 0-10 CVSS scale instead of 0-100).
+fn severity_for_score(score: f64) -> &'static str {
+    if score >= 9.0 {
+        "critical"
+    } else if score >= 7.0 {
+        "high"
+    } else if score >= 4.0 {
+        "medium"
+    } else if score > 0.0 {
+        "low"
+    } else {
+        "none"
+    }
+}
+
+/// Pull the CVSS v3.1 vector and its leading numeric score out of the
+/// analysis prose, matching the `"7.5 High - CVSS:3.1/AV:N/AC:L/..."`
+/// format the prompts ask the model to produce. Falls back to a bare
+/// vector match (score 0.0) if the leading score isn't present, and to
+/// empty/zero when no CVSS vector appears at all.
+fn parse_cvss(text: &str) -> (f64, String) {
+    let scored_re =
+        Regex::new(r"(\d+(?:\.\d+)?)\s*(?:Low|Medium|High|Critical)?\s*-\s*(CVSS:3\.1/[A-Z:/]+)")
+            .unwrap();
+    if let Some(caps) = scored_re.captures(text) {
+        let score: f64 = caps[1].parse().unwrap_or(0.0);
+        return (score, caps[2].to_string());
+    }
+
+    let vector_re = Regex::new(r"CVSS:3\.1/[A-Z:/]+").unwrap();
+    if let Some(m) = vector_re.find(text) {
+        return (0.0, m.as_str().to_string());
+    }
+
+    (0.0, String::new())
+}
+
+/// Derive a VEX `analysis.state` from the prompt's "FALSE POSITIVE CHECK"
+/// section. Text that affirmatively calls the finding a false positive maps
+/// to `not_affected`; otherwise `exploitable` if the analysis reads as
+/// confirmed, else `in_triage` for anything ambiguous enough to need a human
+/// look.
+fn classify_analysis_state(text: &str) -> &'static str {
+    let lower = text.to_lowercase();
+    let mentions_false_positive = lower.contains("false positive");
+
+    if mentions_false_positive {
+        let negated = lower.contains("not a false positive")
+            || lower.contains("unlikely to be a false positive")
+            || lower.contains("not likely a false positive");
+        if negated {
+            "exploitable"
+        } else {
+            "not_affected"
+        }
+    } else if lower.contains("confirmed") || lower.contains("exploitable") {
+        "exploitable"
+    } else {
+        "in_triage"
+    }
+}
+
+/// Build a single-vulnerability VEX document from an `analyze_finding`-style
+/// result.
+pub fn build_vex_document(finding_type: &str, asset_url: &str, analysis_text: &str) -> VexDocument {
+    let (score, vector) = parse_cvss(analysis_text);
+    let state = classify_analysis_state(analysis_text);
+
+    VexDocument {
+        bom_format: "CycloneDX".to_string(),
+        spec_version: "1.5".to_string(),
+        version: 1,
+        vulnerabilities: vec![VexVulnerability {
+            id: finding_type.to_string(),
+            ratings: vec![VexRating {
+                method: "CVSSv31".to_string(),
+                vector,
+                score,
+                severity: severity_for_score(score).to_string(),
+            }],
+            affects: vec![VexAffects {
+                reference: asset_url.to_string(),
+            }],
+            analysis: VexAnalysis {
+                state: state.to_string(),
+            },
+        }],
+    }
+}
+
+fn vex_export_dir() -> PathBuf {
+    let mut path = env::current_exe().unwrap_or_default();
+    path.set_file_name("vex_exports");
+    path
+}
+
+/// Serialize `doc` and write it to disk next to `llm_config.json`, returning
+/// the path so the caller/UI can surface it to the analyst.
+pub fn write_vex_document(doc: &VexDocument) -> Result<String, String> {
+    let dir = vex_export_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create VEX export dir: {}", e))?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let safe_id = doc
+        .vulnerabilities
+        .first()
+        .map(|v| v.id.replace(|c: char| !c.is_alphanumeric(), "_"))
+        .unwrap_or_else(|| "finding".to_string());
+    let file_path = dir.join(format!("vex-{}-{}.json", safe_id, timestamp));
+
+    let content = serde_json::to_string_pretty(doc)
+        .map_err(|e| format!("Failed to serialize VEX document: {}", e))?;
+    fs::write(&file_path, content).map_err(|e| format!("Failed to write VEX document: {}", e))?;
+
+    Ok(file_path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cvss_with_score() {
+        let text = "CVSS v3.1 EVALUATION: 7.5 High - CVSS:3.1/AV:N/AC:L/PR:N/UI:N/I:H/A:N";
+        let (score, vector) = parse_cvss(text);
+        assert_eq!(score, 7.5);
+        assert_eq!(vector, "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/I:H/A:N");
+    }
+
+    #[test]
+    fn test_parse_cvss_missing() {
+        let (score, vector) = parse_cvss("No CVSS data in this text.");
+        assert_eq!(score, 0.0);
+        assert!(vector.is_empty());
+    }
+
+    #[test]
+    fn test_severity_bands() {
+        assert_eq!(severity_for_score(9.5), "critical");
+        assert_eq!(severity_for_score(7.5), "high");
+        assert_eq!(severity_for_score(5.0), "medium");
+        assert_eq!(severity_for_score(1.0), "low");
+        assert_eq!(severity_for_score(0.0), "none");
+    }
+
+    #[test]
+    fn test_classify_false_positive() {
+        let text = "FALSE POSITIVE CHECK: This is likely a false positive given the 404 response.";
+        assert_eq!(classify_analysis_state(text), "not_affected");
+    }
+
+    #[test]
+    fn test_classify_confirmed_exploitable() {
+        let text = "This finding is confirmed exploitable via direct request replay.";
+        assert_eq!(classify_analysis_state(text), "exploitable");
+    }
+
+    #[test]
+    fn test_build_vex_document_shape() {
+        let doc = build_vex_document(
+            "SQL_INJECTION",
+            "https://example.com/api",
+            "7.5 High - CVSS:3.1/AV:N/AC:L/PR:N/UI:N/I:H/A:N. Confirmed exploitable.",
+        );
+        assert_eq!(doc.bom_format, "CycloneDX");
+        assert_eq!(doc.vulnerabilities.len(), 1);
+        assert_eq!(doc.vulnerabilities[0].analysis.state, "exploitable");
+    }
+}
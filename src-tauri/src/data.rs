@@ -90,3 +90,45 @@ pub struct RequestSequence {
     pub created_at: String,
     pub context_summary: Option<String>, // LLM-generated summary of the flow so far
 }
+
+/// Digest used by [`AuthProfile::HmacSigned`] -- HMAC-SHA-256 covers most
+/// exchange-style signing schemes; HMAC-SHA-512 is offered for APIs that
+/// require the stronger digest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AuthDigest {
+    Sha256,
+    Sha512,
+}
+
+/// How a scan authenticates its requests for a given scope. Variants cover
+/// the credential shapes seen in practice: a static header (bearer token or
+/// API key), a cookie jar from a prior login, or a per-request HMAC
+/// signature for APIs that require one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AuthProfile {
+    StaticHeader {
+        header_name: String,
+        header_value: String,
+    },
+    CookieJar {
+        cookies: Vec<(String, String)>,
+    },
+    HmacSigned {
+        /// Base64-encoded signing secret.
+        secret_key_base64: String,
+        digest: AuthDigest,
+        signature_header: String,
+        nonce_header: String,
+    },
+}
+
+/// An [`AuthProfile`] bound to a URL scope -- applied to a scan whenever the
+/// target URL starts with `scope_prefix` (e.g. `https://api.example.com/admin`
+/// or just a host `https://api.example.com`), so a single scan can carry
+/// credentials for several authenticated surfaces at once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScopedAuthProfile {
+    pub id: i64,
+    pub scope_prefix: String,
+    pub profile: AuthProfile,
+}
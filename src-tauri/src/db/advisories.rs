@@ -0,0 +1,275 @@
+use crate::db::SqliteDatabase;
+use crate::error::{Error, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// A single affected-version range, OSV's `introduced`/`fixed` event pair:
+/// vulnerable from `introduced` (inclusive) up to, but not including,
+/// `fixed`. An absent `fixed` means every version from `introduced` onward
+/// is affected.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AffectedRange {
+    #[serde(default)]
+    pub introduced: Option<String>,
+    #[serde(default)]
+    pub fixed: Option<String>,
+}
+
+/// A single advisory, OSV-shaped: an id, the affected package, and the
+/// version ranges it applies to.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Advisory {
+    pub id: String,
+    pub package: String,
+    pub severity: String,
+    #[serde(default)]
+    pub summary: String,
+    pub affected: Vec<AffectedRange>,
+}
+
+/// A loaded set of advisories, matched against the asset store's detected
+/// `name@version` fingerprints the same way `cargo-audit` matches a
+/// `Cargo.lock` against the RustSec advisory DB.
+#[derive(Debug, Default)]
+pub struct AdvisoryDatabase {
+    pub advisories: Vec<Advisory>,
+}
+
+/// A matched advisory/asset pair, as returned by `SqliteDatabase::vulnerabilities`.
+#[derive(Debug, Clone, Serialize)]
+pub struct VulnerabilityMatch {
+    pub advisory_id: String,
+    pub severity: String,
+    pub summary: String,
+    pub asset_id: i64,
+    pub asset_url: String,
+    pub package: String,
+    pub version: String,
+}
+
+impl AdvisoryDatabase {
+    /// Load every `*.json` file in `dir` as a single OSV-style advisory.
+    pub fn load_from_dir(dir: &Path) -> Result<Self> {
+        let mut advisories = Vec::new();
+
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+
+            let content = fs::read_to_string(&path)?;
+            match serde_json::from_str::<Advisory>(&content) {
+                Ok(advisory) => advisories.push(advisory),
+                Err(e) => {
+                    eprintln!("Advisories: skipping malformed advisory {:?}: {}", path, e);
+                }
+            }
+        }
+
+        Ok(Self { advisories })
+    }
+
+    /// Shallow-clone `repo_url` into a scratch directory and load every
+    /// advisory from it, analogous to how the RustSec advisory DB itself is
+    /// distributed as a git repo of one TOML/JSON file per advisory.
+    pub fn fetch_from_repo(repo_url: &str) -> Result<Self> {
+        let scratch = std::env::temp_dir().join(format!(
+            "apex-advisories-{}",
+            uuid::Uuid::new_v4()
+        ));
+
+        let status = std::process::Command::new("git")
+            .args([
+                "clone",
+                "--depth",
+                "1",
+                repo_url,
+                &scratch.to_string_lossy(),
+            ])
+            .status()
+            .map_err(|e| Error::Internal(format!("Failed to run git: {}", e)))?;
+
+        if !status.success() {
+            return Err(Error::Internal(format!(
+                "git clone of advisory repo {} failed",
+                repo_url
+            )));
+        }
+
+        let result = Self::load_from_dir(&scratch);
+        let _ = fs::remove_dir_all(&scratch);
+        result
+    }
+
+    /// Find every advisory affecting `package`@`version`, e.g. for matching
+    /// a resolved `Cargo.lock`/`package-lock.json` entry directly rather
+    /// than a passively-fingerprinted asset -- see `core::deps_audit`.
+    pub fn match_package(&self, package: &str, version: &str) -> Vec<&Advisory> {
+        let Some(parsed_version) = parse_version(version) else {
+            return Vec::new();
+        };
+        self.advisories
+            .iter()
+            .filter(|advisory| {
+                advisory.package.eq_ignore_ascii_case(package)
+                    && Self::matches(advisory, &parsed_version)
+            })
+            .collect()
+    }
+
+    /// Whether `version` falls inside any of `advisory.affected` ranges.
+    fn matches(advisory: &Advisory, version: &(u64, u64, u64)) -> bool {
+        advisory.affected.iter().any(|range| {
+            let introduced = range
+                .introduced
+                .as_deref()
+                .and_then(parse_version)
+                .unwrap_or((0, 0, 0));
+            let above_introduced = *version >= introduced;
+
+            let below_fixed = match range.fixed.as_deref().and_then(parse_version) {
+                Some(fixed) => *version < fixed,
+                None => true,
+            };
+
+            above_introduced && below_fixed
+        })
+    }
+}
+
+/// Parse a bare `major.minor.patch` (or `major.minor`) version string into a
+/// comparable tuple. Missing components default to 0.
+fn parse_version(raw: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = raw.trim().splitn(3, '.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+    let patch = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+/// Pull a `name@version` (or `name/version`) fingerprint out of detected
+/// finding evidence or a `Server:` response header, e.g. `jquery@1.11.0` or
+/// `Server: nginx/1.18.0`.
+pub fn extract_name_version(text: &str) -> Option<(String, String)> {
+    static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(r"([A-Za-z][A-Za-z0-9_.\-]*)[@/]v?(\d+(?:\.\d+){1,2})").unwrap()
+    });
+    let caps = re.captures(text)?;
+    Some((caps[1].to_string(), caps[2].to_string()))
+}
+
+impl SqliteDatabase {
+    /// Match every asset's detected `name@version` fingerprint (pulled from
+    /// its findings' evidence and its recorded `Server:` header) against
+    /// `advisories`, returning one `VulnerabilityMatch` per hit -- the
+    /// passive-fingerprinting counterpart to `cargo audit`'s lockfile scan.
+    pub fn vulnerabilities(
+        &self,
+        advisories: &AdvisoryDatabase,
+    ) -> Result<Vec<VulnerabilityMatch>> {
+        let assets = self.get_assets()?;
+        let mut matches = Vec::new();
+
+        for asset in &assets {
+            let mut fingerprints: Vec<(String, String)> = Vec::new();
+            for finding in &asset.findings {
+                if let Some(evidence) = &finding.evidence {
+                    if let Some(fp) = extract_name_version(evidence) {
+                        fingerprints.push(fp);
+                    }
+                }
+            }
+            if let Some(fp) = extract_name_version(&asset.response_headers) {
+                fingerprints.push(fp);
+            }
+
+            for (package, version) in fingerprints {
+                let Some(parsed_version) = parse_version(&version) else {
+                    continue;
+                };
+
+                for advisory in &advisories.advisories {
+                    if !advisory.package.eq_ignore_ascii_case(&package) {
+                        continue;
+                    }
+                    if AdvisoryDatabase::matches(advisory, &parsed_version) {
+                        matches.push(VulnerabilityMatch {
+                            advisory_id: advisory.id.clone(),
+                            severity: advisory.severity.clone(),
+                            summary: advisory.summary.clone(),
+                            asset_id: asset.id,
+                            asset_url: asset.url.clone(),
+                            package: package.clone(),
+                            version: version.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_name_version_at_sign() {
+        assert_eq!(
+            extract_name_version("jquery@1.11.0 detected in page"),
+            Some(("jquery".to_string(), "1.11.0".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_extract_name_version_server_header() {
+        assert_eq!(
+            extract_name_version("Server: nginx/1.18.0"),
+            Some(("nginx".to_string(), "1.18.0".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_matches_affected_range() {
+        let advisory = Advisory {
+            id: "GHSA-TEST-0001".to_string(),
+            package: "jquery".to_string(),
+            severity: "High".to_string(),
+            summary: "XSS in jquery".to_string(),
+            affected: vec![AffectedRange {
+                introduced: Some("1.0.0".to_string()),
+                fixed: Some("1.12.0".to_string()),
+            }],
+        };
+
+        assert!(AdvisoryDatabase::matches(&advisory, &(1, 11, 0)));
+        assert!(!AdvisoryDatabase::matches(&advisory, &(1, 12, 0)));
+    }
+
+    #[test]
+    fn test_match_package_finds_affected_and_skips_fixed_version() {
+        let db = AdvisoryDatabase {
+            advisories: vec![Advisory {
+                id: "GHSA-TEST-0002".to_string(),
+                package: "tokio".to_string(),
+                severity: "Critical".to_string(),
+                summary: "DoS in tokio".to_string(),
+                affected: vec![AffectedRange {
+                    introduced: Some("1.0.0".to_string()),
+                    fixed: Some("1.18.3".to_string()),
+                }],
+            }],
+        };
+
+        assert_eq!(db.match_package("tokio", "1.8.0").len(), 1);
+        assert!(db.match_package("tokio", "1.18.3").is_empty());
+        assert!(db.match_package("serde", "1.8.0").is_empty());
+    }
+}
@@ -1,5 +1,6 @@
-use crate::db::{Asset, Badge, ScanHistoryEntry, Severity, SqliteDatabase};
+use crate::db::{row_extract, Asset, Badge, ScanHistoryEntry, SqliteDatabase};
 use crate::error::Result;
+use rusqlite::OptionalExtension;
 
 impl SqliteDatabase {
     pub fn add_asset(
@@ -11,10 +12,28 @@ impl SqliteDatabase {
         is_workbench: bool,
         depth: i32,
     ) -> Result<i64> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let (id, _newly_inserted) =
+            self.add_asset_dedup(url, source, method, recursive, is_workbench, depth)?;
+        Ok(id)
+    }
+
+    /// Same as [`Self::add_asset`], but also reports whether `url` was
+    /// newly inserted. Uses `INSERT ... ON CONFLICT(url) DO NOTHING
+    /// RETURNING id` against the `assets.url` unique index instead of a
+    /// separate `SELECT`-then-`INSERT`, so the duplicate check is O(1) and
+    /// race-free when the importer's worker pool adds several URLs
+    /// concurrently -- a plain `SELECT` first would let two workers both
+    /// see "not found" and race to insert.
+    pub fn add_asset_dedup(
+        &self,
+        url: &str,
+        source: &str,
+        method: Option<&str>,
+        recursive: bool,
+        is_workbench: bool,
+        depth: i32,
+    ) -> Result<(i64, bool)> {
+        let conn = self.get_conn()?;
         let method_val = method.unwrap_or("GET");
 
         println!(
@@ -22,46 +41,57 @@ impl SqliteDatabase {
             url, method_val, source
         );
 
-        conn.execute(
-            "INSERT OR IGNORE INTO assets (url, method, status, source, recursive, is_workbench, depth) VALUES (?1, ?2, 'Pending', ?3, ?4, ?5, ?6)",
-            (url, method_val, source, recursive, is_workbench, depth),
-        )?;
-
-        let (id, current_recursive, current_source): (i64, bool, String) = conn
+        let inserted_id: Option<i64> = conn
             .query_row(
-                "SELECT id, recursive, source FROM assets WHERE url = ?1 AND method = ?2",
-                (url, method_val),
-                |row| {
-                    Ok((
-                        row.get(0)?,
-                        row.get::<_, bool>(1).unwrap_or(false),
-                        row.get::<_, String>(2)
-                            .unwrap_or_else(|_| "User".to_string()),
-                    ))
-                },
+                "INSERT INTO assets (url, method, status, source, recursive, is_workbench, depth) VALUES (?1, ?2, 'Pending', ?3, ?4, ?5, ?6) ON CONFLICT(url) DO NOTHING RETURNING id",
+                (url, method_val, source, recursive, is_workbench, depth),
+                |row| row.get(0),
             )
-            .map_err(|e| {
-                println!("[DB] Failed to retrieve asset after insert/ignore: {}", e);
-                e
-            })?;
+            .optional()?;
+
+        let (id, newly_inserted, current_recursive, current_source) = match inserted_id {
+            Some(id) => (id, true, recursive, source.to_string()),
+            None => {
+                let (id, current_recursive, current_source): (i64, bool, String) = conn
+                    .query_row(
+                        "SELECT id, recursive, source FROM assets WHERE url = ?1",
+                        [url],
+                        |row| {
+                            Ok((
+                                row.get(0)?,
+                                row.get::<_, bool>(1).unwrap_or(false),
+                                row.get::<_, String>(2)
+                                    .unwrap_or_else(|_| "User".to_string()),
+                            ))
+                        },
+                    )
+                    .map_err(|e| {
+                        println!("[DB] Failed to retrieve asset after conflict: {}", e);
+                        e
+                    })?;
+                (id, false, current_recursive, current_source)
+            }
+        };
 
         println!(
-            "[DB] Asset ID: {}, Current Source: {}, Target Source: {}",
-            id, current_source, source
+            "[DB] Asset ID: {}, Current Source: {}, Target Source: {}, Newly Inserted: {}",
+            id, current_source, source, newly_inserted
         );
 
-        if recursive && !current_recursive {
-            let _ = conn.execute("UPDATE assets SET recursive = 1 WHERE id = ?1", [id]);
-        }
+        if !newly_inserted {
+            if recursive && !current_recursive {
+                let _ = conn.execute("UPDATE assets SET recursive = 1 WHERE id = ?1", [id]);
+            }
 
-        // If newly added or existing, update source if provided source is not "Recursive"
-        // This allows upgrading "Recursive" assets to "Import" or "Workbench"
-        if source != "Recursive" && source != current_source {
-            println!(
-                "[DB] Updating source for asset {} from {} to {}",
-                id, current_source, source
-            );
-            let _ = conn.execute("UPDATE assets SET source = ?1 WHERE id = ?2", (source, id));
+            // If newly added or existing, update source if provided source is not "Recursive"
+            // This allows upgrading "Recursive" assets to "Import" or "Workbench"
+            if source != "Recursive" && source != current_source {
+                println!(
+                    "[DB] Updating source for asset {} from {} to {}",
+                    id, current_source, source
+                );
+                let _ = conn.execute("UPDATE assets SET source = ?1 WHERE id = ?2", (source, id));
+            }
         }
 
         // If is_workbench is requested, force it!
@@ -70,43 +100,14 @@ impl SqliteDatabase {
             let _ = conn.execute("UPDATE assets SET is_workbench = 1 WHERE id = ?1", [id]);
         }
 
-        Ok(id)
+        Ok((id, newly_inserted))
     }
 
     pub fn get_assets(&self) -> Result<Vec<Asset>> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
-        let mut stmt = conn.prepare("SELECT id, url, method, status, status_code, risk_score, findings, folder_id, response_headers, response_body, request_headers, request_body, created_at, updated_at, notes, triage_status, is_documented, source, recursive, is_workbench, depth FROM assets ORDER BY id DESC")?;
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare("SELECT id, url, method, status, status_code, risk_score, findings, folder_id, response_headers, response_body, request_headers, request_body, created_at, updated_at, notes, triage_status, is_documented, source, recursive, is_workbench, depth, content_hash FROM assets ORDER BY id DESC")?;
 
-        let asset_iter = stmt.query_map([], |row| {
-            let findings_json: String = row.get(6)?;
-            let findings: Vec<Badge> = serde_json::from_str(&findings_json).unwrap_or_default();
-            Ok(Asset {
-                id: row.get(0)?,
-                url: row.get(1)?,
-                method: row.get(2)?,
-                status: row.get(3)?,
-                status_code: row.get(4)?,
-                risk_score: row.get(5)?,
-                findings,
-                folder_id: row.get(7)?,
-                response_headers: row.get(8)?,
-                response_body: row.get(9)?,
-                request_headers: row.get(10)?,
-                request_body: row.get(11)?,
-                created_at: row.get(12)?,
-                updated_at: row.get(13)?,
-                notes: row.get(14).unwrap_or_else(|_| "".to_string()),
-                triage_status: row.get(15).unwrap_or_else(|_| "Unreviewed".to_string()),
-                is_documented: row.get(16).unwrap_or(true),
-                source: row.get(17).unwrap_or_else(|_| "User".to_string()),
-                recursive: row.get(18).unwrap_or(false),
-                is_workbench: row.get(19).unwrap_or(false),
-                depth: row.get(20).unwrap_or(0),
-            })
-        })?;
+        let asset_iter = stmt.query_map([], row_extract::<Asset>)?;
 
         let mut assets = Vec::new();
         for asset in asset_iter {
@@ -121,6 +122,7 @@ impl SqliteDatabase {
         Ok(assets)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn update_scan_result(
         &self,
         id: i64,
@@ -132,49 +134,60 @@ impl SqliteDatabase {
         resp_body: &str,
         req_headers: &str,
         req_body: &str,
+        content_hash: &str,
     ) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
-
-        let current_asset: std::result::Result<(i32, i32, String, String, String), rusqlite::Error> = conn.query_row(
-            "SELECT status_code, risk_score, findings, response_headers, response_body FROM assets WHERE id = ?1",
-            [id],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
-        );
+        self.with_transaction(|tx| {
+            let current_asset: std::result::Result<(i32, i32, String, String, String), rusqlite::Error> = tx.query_row(
+                "SELECT status_code, risk_score, findings, response_headers, response_body FROM assets WHERE id = ?1",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+            );
 
-        if let Ok((old_code, old_risk, old_findings, old_headers, old_body)) = current_asset {
-            if old_code != 0 || !old_body.is_empty() {
-                let _ = conn.execute(
-                    "INSERT INTO scan_history (asset_id, status_code, risk_score, findings, response_headers, response_body) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                    (id, old_code, old_risk, old_findings, old_headers, old_body),
-                );
+            if let Ok((old_code, old_risk, old_findings, old_headers, old_body)) = current_asset {
+                if old_code != 0 || !old_body.is_empty() {
+                    let _ = tx.execute(
+                        "INSERT INTO scan_history (asset_id, status_code, risk_score, findings, response_headers, response_body) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        (id, old_code, old_risk, old_findings, old_headers, old_body),
+                    );
+                }
             }
-        }
 
-        let findings_json = serde_json::to_string(&findings).unwrap_or_else(|_| "[]".to_string());
-        conn.execute(
-            "UPDATE assets SET status = ?1, status_code = ?2, risk_score = ?3, findings = ?4, response_headers = ?5, response_body = ?6, request_headers = ?7, request_body = ?8, updated_at = CURRENT_TIMESTAMP WHERE id = ?9",
-            (status, status_code, risk_score, findings_json, resp_headers, resp_body, req_headers, req_body, id),
-        )?;
-        Ok(())
+            let findings_json = serde_json::to_string(&findings).unwrap_or_else(|_| "[]".to_string());
+            tx.execute(
+                "UPDATE assets SET status = ?1, status_code = ?2, risk_score = ?3, findings = ?4, response_headers = ?5, response_body = ?6, request_headers = ?7, request_body = ?8, content_hash = ?9, updated_at = CURRENT_TIMESTAMP WHERE id = ?10",
+                (status, status_code, risk_score, findings_json, resp_headers, resp_body, req_headers, req_body, content_hash, id),
+            )?;
+
+            // Keep the normalized `findings` table in lockstep with this
+            // scan's results; the triggers on it then re-derive risk_score,
+            // so future FP toggles (which only touch this table) stay correct.
+            tx.execute("DELETE FROM findings WHERE asset_id = ?1", [id])?;
+            for badge in &findings {
+                tx.execute(
+                    "INSERT INTO findings (asset_id, short_name, severity, evidence, is_false_positive, reason) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    (
+                        id,
+                        &badge.short,
+                        format!("{:?}", badge.severity),
+                        &badge.evidence,
+                        badge.is_fp,
+                        &badge.fp_reason,
+                    ),
+                )?;
+            }
+
+            Ok(())
+        })
     }
 
     pub fn delete_asset(&self, id: i64) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
         conn.execute("DELETE FROM assets WHERE id = ?1", [id])?;
         Ok(())
     }
 
     pub fn get_asset_history(&self, asset_id: i64) -> Result<Vec<ScanHistoryEntry>> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
         let mut stmt = conn.prepare("SELECT id, asset_id, timestamp, status_code, risk_score, findings, response_headers, response_body FROM scan_history WHERE asset_id = ?1 ORDER BY id DESC LIMIT 50")?;
 
         let history_iter = stmt.query_map([asset_id], |row| {
@@ -199,10 +212,7 @@ impl SqliteDatabase {
         Ok(history)
     }
     pub fn get_authorized_domains(&self) -> Result<std::collections::HashSet<String>> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
         let mut stmt =
             conn.prepare("SELECT DISTINCT url FROM assets WHERE source != 'Recursive'")?;
         let url_iter = stmt.query_map([], |row| row.get::<_, String>(0))?;
@@ -227,10 +237,7 @@ impl SqliteDatabase {
     }
 
     pub fn update_asset_triage(&self, id: i64, triage_status: &str, notes: &str) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
         conn.execute(
             "UPDATE assets SET triage_status = ?1, notes = ?2, updated_at = CURRENT_TIMESTAMP WHERE id = ?3",
             (triage_status, notes, id),
@@ -238,6 +245,11 @@ impl SqliteDatabase {
         Ok(())
     }
 
+    /// Flips `is_false_positive` on the matching row in the normalized
+    /// `findings` table; the `trg_findings_risk_after_update` trigger then
+    /// re-derives `assets.risk_score` from the remaining active findings.
+    /// Also mirrors the flag into the legacy `assets.findings` JSON blob so
+    /// API responses built from it (`Asset.findings`) stay consistent.
     pub fn update_finding_fp(
         &self,
         asset_id: i64,
@@ -246,72 +258,53 @@ impl SqliteDatabase {
         is_fp: bool,
         reason: Option<&str>,
     ) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
-
-        let findings_json: String = conn.query_row(
-            "SELECT findings FROM assets WHERE id = ?1",
-            [asset_id],
-            |row| row.get(0),
-        )?;
-
-        let mut findings: Vec<Badge> = serde_json::from_str(&findings_json).unwrap_or_default();
+        self.with_transaction(|tx| {
+            let rows_affected = tx.execute(
+                "UPDATE findings SET is_false_positive = ?1, reason = ?2 WHERE asset_id = ?3 AND short_name = ?4 AND evidence IS ?5",
+                (is_fp, reason, asset_id, short_name, evidence),
+            )?;
 
-        let mut updated = false;
-        for f in &mut findings {
-            if f.short == short_name && f.evidence.as_deref() == evidence {
-                f.is_fp = is_fp;
-                f.fp_reason = reason.map(|s| s.to_string());
-                updated = true;
+            if rows_affected == 0 {
+                return Err(crate::error::Error::NotFound(
+                    "Finding not found".to_string(),
+                ));
             }
-        }
 
-        if !updated {
-            return Err(crate::error::Error::NotFound(
-                "Finding not found".to_string(),
-            ));
-        }
-
-        let new_findings_json =
-            serde_json::to_string(&findings).unwrap_or_else(|_| "[]".to_string());
-        conn.execute(
-            "UPDATE assets SET findings = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
-            (new_findings_json, asset_id),
-        )?;
+            let findings_json: String = tx.query_row(
+                "SELECT findings FROM assets WHERE id = ?1",
+                [asset_id],
+                |row| row.get(0),
+            )?;
+            let mut findings: Vec<Badge> = serde_json::from_str(&findings_json).unwrap_or_default();
+            for f in &mut findings {
+                if f.short == short_name && f.evidence.as_deref() == evidence {
+                    f.is_fp = is_fp;
+                    f.fp_reason = reason.map(|s| s.to_string());
+                }
+            }
+            let new_findings_json =
+                serde_json::to_string(&findings).unwrap_or_else(|_| "[]".to_string());
+            tx.execute(
+                "UPDATE assets SET findings = ?1 WHERE id = ?2",
+                (new_findings_json, asset_id),
+            )?;
 
-        Ok(())
+            Ok(())
+        })
     }
 
+    /// The `findings` table triggers keep `assets.risk_score` current as of
+    /// every FP toggle, so this only has to derive the coarser `status`
+    /// bucket from that already-correct score.
     pub fn recalculate_asset_risk_score(&self, asset_id: i64) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
-        let findings_json: String = conn.query_row(
-            "SELECT findings FROM assets WHERE id = ?1",
+        let risk_score: i32 = conn.query_row(
+            "SELECT risk_score FROM assets WHERE id = ?1",
             [asset_id],
             |row| row.get(0),
         )?;
 
-        let findings: Vec<Badge> = serde_json::from_str(&findings_json).unwrap_or_default();
-
-        let mut risk_score = 0;
-        for f in &findings {
-            if f.is_fp {
-                continue;
-            }
-            risk_score += match f.severity {
-                Severity::Critical => 100,
-                Severity::High => 50,
-                Severity::Medium => 25,
-                Severity::Low => 10,
-                Severity::Info => 0,
-            };
-        }
-
         let final_status = if risk_score >= 100 {
             "Critical"
         } else if risk_score >= 50 {
@@ -323,18 +316,15 @@ impl SqliteDatabase {
         };
 
         conn.execute(
-            "UPDATE assets SET risk_score = ?1, status = ?2, updated_at = CURRENT_TIMESTAMP WHERE id = ?3",
-            (risk_score, final_status, asset_id),
+            "UPDATE assets SET status = ?1 WHERE id = ?2",
+            (final_status, asset_id),
         )?;
 
         Ok(())
     }
 
     pub fn update_asset_source(&self, id: i64, new_source: &str) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
         conn.execute(
             "UPDATE assets SET source = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
             (new_source, id),
@@ -343,46 +333,17 @@ impl SqliteDatabase {
     }
 
     pub fn get_pending_scans(&self, limit: i32) -> Result<Vec<Asset>> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         let mut stmt = conn.prepare("
-            SELECT id, url, method, status, status_code, risk_score, findings, folder_id, response_headers, response_body, request_headers, request_body, created_at, updated_at, notes, triage_status, is_documented, source, recursive, is_workbench, depth 
+            SELECT id, url, method, status, status_code, risk_score, findings, folder_id, response_headers, response_body, request_headers, request_body, created_at, updated_at, notes, triage_status, is_documented, source, recursive, is_workbench, depth, content_hash
             FROM assets 
             WHERE status = 'Pending'
             ORDER BY created_at ASC
             LIMIT ?1
         ")?;
 
-        let asset_iter = stmt.query_map([limit], |row| {
-            let findings_json: String = row.get(6)?;
-            let findings: Vec<Badge> = serde_json::from_str(&findings_json).unwrap_or_default();
-            Ok(Asset {
-                id: row.get(0)?,
-                url: row.get(1)?,
-                method: row.get(2)?,
-                status: row.get(3)?,
-                status_code: row.get(4)?,
-                risk_score: row.get(5)?,
-                findings,
-                folder_id: row.get(7)?,
-                response_headers: row.get(8)?,
-                response_body: row.get(9)?,
-                request_headers: row.get(10)?,
-                request_body: row.get(11)?,
-                created_at: row.get(12)?,
-                updated_at: row.get(13)?,
-                notes: row.get(14).unwrap_or_else(|_| "".to_string()),
-                triage_status: row.get(15).unwrap_or_else(|_| "Unreviewed".to_string()),
-                is_documented: row.get(16).unwrap_or(true),
-                source: row.get(17).unwrap_or_else(|_| "User".to_string()),
-                recursive: row.get(18).unwrap_or(false),
-                is_workbench: row.get(19).unwrap_or(false),
-                depth: row.get(20).unwrap_or(0),
-            })
-        })?;
+        let asset_iter = stmt.query_map([limit], row_extract::<Asset>)?;
 
         let mut assets = Vec::new();
         for asset in asset_iter {
@@ -392,13 +353,10 @@ impl SqliteDatabase {
     }
 
     pub fn get_stale_assets(&self, limit: i32, minutes_stale: i32) -> Result<Vec<Asset>> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         let mut stmt = conn.prepare("
-            SELECT id, url, method, status, status_code, risk_score, findings, folder_id, response_headers, response_body, request_headers, request_body, created_at, updated_at, notes, triage_status, is_documented, source, recursive, is_workbench, depth 
+            SELECT id, url, method, status, status_code, risk_score, findings, folder_id, response_headers, response_body, request_headers, request_body, created_at, updated_at, notes, triage_status, is_documented, source, recursive, is_workbench, depth, content_hash
             FROM assets 
             WHERE datetime(updated_at, '+' || ?1 || ' minutes') < datetime('now')
             OR status = 'Pending'
@@ -406,33 +364,7 @@ impl SqliteDatabase {
             LIMIT ?2
         ")?;
 
-        let asset_iter = stmt.query_map((minutes_stale, limit), |row| {
-            let findings_json: String = row.get(6)?;
-            let findings: Vec<Badge> = serde_json::from_str(&findings_json).unwrap_or_default();
-            Ok(Asset {
-                id: row.get(0)?,
-                url: row.get(1)?,
-                method: row.get(2)?,
-                status: row.get(3)?,
-                status_code: row.get(4)?,
-                risk_score: row.get(5)?,
-                findings,
-                folder_id: row.get(7)?,
-                response_headers: row.get(8)?,
-                response_body: row.get(9)?,
-                request_headers: row.get(10)?,
-                request_body: row.get(11)?,
-                created_at: row.get(12)?,
-                updated_at: row.get(13)?,
-                notes: row.get(14).unwrap_or_else(|_| "".to_string()),
-                triage_status: row.get(15).unwrap_or_else(|_| "Unreviewed".to_string()),
-                is_documented: row.get(16).unwrap_or(true),
-                source: row.get(17).unwrap_or_else(|_| "User".to_string()),
-                recursive: row.get(18).unwrap_or(false),
-                is_workbench: row.get(19).unwrap_or(false),
-                depth: row.get(20).unwrap_or(0),
-            })
-        })?;
+        let asset_iter = stmt.query_map((minutes_stale, limit), row_extract::<Asset>)?;
 
         let mut assets = Vec::new();
         for asset in asset_iter {
@@ -442,20 +374,14 @@ impl SqliteDatabase {
     }
 
     pub fn clear_all_assets(&self) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
         conn.execute("DELETE FROM assets", [])?;
         Ok(())
     }
 
     pub fn purge_recursive_assets(&self) -> Result<usize> {
         let authorized_domains = self.get_authorized_domains()?;
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         let mut stmt = conn.prepare("SELECT id, url FROM assets WHERE source = 'Recursive'")?;
         let asset_iter = stmt.query_map([], |row| {
@@ -487,10 +413,7 @@ impl SqliteDatabase {
     }
 
     pub fn sanitize_urls(&self) -> Result<usize> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         let mut stmt = conn.prepare("SELECT id, url FROM assets")?;
         let asset_iter = stmt.query_map([], |row| {
@@ -538,23 +461,39 @@ impl SqliteDatabase {
         Ok(count)
     }
 
-    pub fn update_asset_documentation(&self, id: i64, is_documented: bool) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
-        conn.execute(
-            "UPDATE assets SET is_documented = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
-            (is_documented, id),
-        )?;
-        Ok(())
+    pub fn update_asset_documentation(
+        &self,
+        id: i64,
+        is_documented: bool,
+        actor: Option<&str>,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        self.with_transaction(|tx| {
+            let previous: bool = tx
+                .query_row(
+                    "SELECT is_documented FROM assets WHERE id = ?1",
+                    [id],
+                    |row| row.get(0),
+                )
+                .unwrap_or(true);
+
+            tx.execute(
+                "UPDATE assets SET is_documented = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+                (is_documented, id),
+            )?;
+
+            if previous != is_documented {
+                tx.execute(
+                    "INSERT INTO asset_documentation_history (asset_id, previous_value, new_value, actor, reason) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    (id, previous, is_documented, actor, reason),
+                )?;
+            }
+            Ok(())
+        })
     }
 
     pub fn update_asset_workbench_status(&self, id: i64, is_workbench: bool) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
         conn.execute(
             "UPDATE assets SET is_workbench = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
             (is_workbench, id),
@@ -562,26 +501,63 @@ impl SqliteDatabase {
         Ok(())
     }
 
-    pub fn batch_mark_shadow_apis(&self, asset_ids: &[i64]) -> Result<usize> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
-
-        let mut count = 0;
-        for asset_id in asset_ids {
-            let rows_affected = conn.execute(
+    pub fn batch_mark_shadow_apis(
+        &self,
+        asset_ids: &[i64],
+        actor: Option<&str>,
+        reason: Option<&str>,
+    ) -> Result<usize> {
+        self.with_transaction(|tx| {
+            let mut update_stmt = tx.prepare(
                 "UPDATE assets SET is_documented = 0, updated_at = CURRENT_TIMESTAMP WHERE id = ?1 AND is_documented = 1",
-                [asset_id],
             )?;
-            count += rows_affected;
-        }
+            let mut history_stmt = tx.prepare(
+                "INSERT INTO asset_documentation_history (asset_id, previous_value, new_value, actor, reason) VALUES (?1, 1, 0, ?2, ?3)",
+            )?;
+            let mut count = 0;
+            for asset_id in asset_ids {
+                let rows_affected = update_stmt.execute([asset_id])?;
+                if rows_affected > 0 {
+                    history_stmt.execute((asset_id, actor, reason))?;
+                }
+                count += rows_affected;
+            }
+            Ok(count)
+        })
+    }
 
-        Ok(count)
+    /// Ordered `is_documented` change log for `asset_id`, newest first, so
+    /// auditors can reconstruct its documentation/Shadow-API lifecycle.
+    pub fn documentation_history(
+        &self,
+        asset_id: i64,
+    ) -> Result<Vec<crate::db::DocumentationHistoryEntry>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, asset_id, previous_value, new_value, actor, reason, changed_at FROM asset_documentation_history WHERE asset_id = ?1 ORDER BY id DESC",
+        )?;
+
+        let rows = stmt.query_map([asset_id], |row| {
+            Ok(crate::db::DocumentationHistoryEntry {
+                id: row.get(0)?,
+                asset_id: row.get(1)?,
+                previous_value: row.get(2)?,
+                new_value: row.get(3)?,
+                actor: row.get(4)?,
+                reason: row.get(5)?,
+                changed_at: row.get(6)?,
+            })
+        })?;
+
+        let mut history = Vec::new();
+        for entry in rows {
+            history.push(entry?);
+        }
+        Ok(history)
     }
 
     pub fn asset_exists_by_url_method(&self, url: &str, method: &str) -> bool {
-        let conn = match self.conn.lock() {
+        let conn = match self.get_conn() {
             Ok(c) => c,
             Err(_) => return false,
         };
@@ -596,7 +572,7 @@ impl SqliteDatabase {
     }
 
     pub fn is_asset_recently_scanned(&self, url: &str, method: &str, minutes: i32) -> bool {
-        let conn = match self.conn.lock() {
+        let conn = match self.get_conn() {
             Ok(c) => c,
             Err(_) => return false,
         };
@@ -0,0 +1,61 @@
+use crate::data::{AuthProfile, ScopedAuthProfile};
+use crate::db::SqliteDatabase;
+use crate::error::{Error, Result};
+
+impl SqliteDatabase {
+    pub fn save_auth_profile(&self, scope_prefix: &str, profile: &AuthProfile) -> Result<i64> {
+        let conn = self.get_conn()?;
+
+        let profile_json = serde_json::to_string(profile)?;
+        conn.execute(
+            "INSERT INTO auth_profiles (scope_prefix, profile_json) VALUES (?1, ?2)",
+            (scope_prefix, &profile_json),
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn list_auth_profiles(&self) -> Result<Vec<ScopedAuthProfile>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, scope_prefix, profile_json FROM auth_profiles ORDER BY LENGTH(scope_prefix) DESC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let id: i64 = row.get(0)?;
+            let scope_prefix: String = row.get(1)?;
+            let profile_json: String = row.get(2)?;
+            Ok((id, scope_prefix, profile_json))
+        })?;
+
+        let mut profiles = Vec::new();
+        for row in rows {
+            let (id, scope_prefix, profile_json) = row?;
+            let profile: AuthProfile = serde_json::from_str(&profile_json)?;
+            profiles.push(ScopedAuthProfile {
+                id,
+                scope_prefix,
+                profile,
+            });
+        }
+        Ok(profiles)
+    }
+
+    /// Find the best-matching profile for `url`: the longest `scope_prefix`
+    /// that `url` starts with, so a narrower path-scoped profile (e.g.
+    /// `https://api.example.com/admin`) wins over a broader host-scoped one
+    /// (`https://api.example.com`) when both apply.
+    pub fn find_auth_profile_for_url(&self, url: &str) -> Result<Option<ScopedAuthProfile>> {
+        Ok(self
+            .list_auth_profiles()?
+            .into_iter()
+            .filter(|p| url.starts_with(&p.scope_prefix))
+            .max_by_key(|p| p.scope_prefix.len()))
+    }
+
+    pub fn delete_auth_profile(&self, id: i64) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute("DELETE FROM auth_profiles WHERE id = ?1", [id])?;
+        Ok(())
+    }
+}
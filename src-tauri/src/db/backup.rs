@@ -0,0 +1,93 @@
+use crate::db::SqliteDatabase;
+use crate::error::{Error, Result};
+use rusqlite::backup::{Backup, Progress};
+use rusqlite::Connection;
+use std::time::Duration;
+
+impl SqliteDatabase {
+    /// Pages copied per `Backup::step`; small enough that a long backup of a
+    /// response-body-heavy database yields frequent progress callbacks and
+    /// doesn't hold the source connection's page lock for too long at once.
+    const BACKUP_PAGES_PER_STEP: i32 = 100;
+
+    /// Writes a consistent point-in-time copy of the live database to
+    /// `path` using SQLite's online backup API, which is safe to run
+    /// against a WAL-mode database that's still being written to -- unlike
+    /// a plain file copy, which can snapshot the main file and WAL out of
+    /// sync and produce a corrupt copy.
+    pub fn backup_to(&self, path: &str) -> Result<()> {
+        self.backup_to_with_progress(path, |_: Progress| {})
+    }
+
+    /// Same as [`Self::backup_to`], but reports `Progress` (pages remaining
+    /// / total pages) after each step so callers can surface a progress bar
+    /// for large databases.
+    pub fn backup_to_with_progress(
+        &self,
+        path: &str,
+        mut on_progress: impl FnMut(Progress),
+    ) -> Result<()> {
+        // Held for the whole backup so the source database can't be closed
+        // out from under it; other pooled connections remain free to serve
+        // concurrent scans in the meantime.
+        let src = self.get_conn()?;
+        let mut dst = Connection::open(path)
+            .map_err(|e| Error::Internal(format!("failed to open backup target: {}", e)))?;
+
+        let backup = Backup::new(&src, &mut dst)?;
+        backup.run_to_completion(
+            Self::BACKUP_PAGES_PER_STEP,
+            Duration::from_millis(10),
+            Some(&mut on_progress),
+        )?;
+        Ok(())
+    }
+
+    /// Streams the backup to an arbitrary writer instead of a file path, for
+    /// callers (e.g. a Tauri command piping bytes to a save dialog) that
+    /// want the finished database as a byte stream rather than a path on
+    /// disk. SQLite's backup API only writes to a `Connection`, so this
+    /// still backs up to a throwaway temp file first and then copies it
+    /// through.
+    pub fn backup_to_writer<W: std::io::Write>(
+        &self,
+        mut writer: W,
+        on_progress: impl FnMut(Progress),
+    ) -> Result<()> {
+        let tmp_path =
+            std::env::temp_dir().join(format!("apex-backup-{}.db", uuid::Uuid::new_v4()));
+        let tmp_path_str = tmp_path
+            .to_str()
+            .ok_or_else(|| Error::Internal("temp backup path is not valid UTF-8".to_string()))?;
+
+        self.backup_to_with_progress(tmp_path_str, on_progress)?;
+
+        let result = (|| {
+            let mut file = std::fs::File::open(&tmp_path)
+                .map_err(|e| Error::Internal(format!("failed to reopen backup file: {}", e)))?;
+            std::io::copy(&mut file, &mut writer)
+                .map_err(|e| Error::Internal(format!("failed to stream backup: {}", e)))?;
+            Ok(())
+        })();
+
+        let _ = std::fs::remove_file(&tmp_path);
+        result
+    }
+
+    /// Restores the database at `path` into this pool's connections via the
+    /// same online backup API used by [`Self::backup_to`], overwriting the
+    /// live database in place. Runs `run_migrations` against the source
+    /// first so an older evidence-database export is brought up to the
+    /// current schema before it's swapped in, rather than restoring a copy
+    /// the rest of the app doesn't know how to read.
+    pub fn restore_from(&self, path: &str) -> Result<()> {
+        let src = Connection::open(path)
+            .map_err(|e| Error::Internal(format!("failed to open restore source: {}", e)))?;
+        Self::run_migrations(&src)?;
+
+        let mut dst = self.get_conn()?;
+        let backup = Backup::new(&src, &mut dst)?;
+        backup.run_to_completion(Self::BACKUP_PAGES_PER_STEP, Duration::from_millis(10), None)?;
+        Ok(())
+    }
+}
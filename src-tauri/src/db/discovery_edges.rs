@@ -0,0 +1,77 @@
+use crate::db::SqliteDatabase;
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// A persisted parent -> child discovery edge: how a crawl reached
+/// `child_url` (directly from a seed when `parent_url` is `None`), so
+/// reports can show each endpoint's path through the crawl tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveryEdge {
+    pub id: i64,
+    pub parent_url: Option<String>,
+    pub child_url: String,
+    pub depth: i64,
+}
+
+impl SqliteDatabase {
+    pub fn save_discovery_edge(
+        &self,
+        parent_url: Option<&str>,
+        child_url: &str,
+        depth: usize,
+    ) -> Result<i64> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "INSERT INTO discovery_edges (parent_url, child_url, depth) VALUES (?1, ?2, ?3)",
+            (parent_url, child_url, depth as i64),
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn list_discovery_edges(&self) -> Result<Vec<DiscoveryEdge>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt =
+            conn.prepare("SELECT id, parent_url, child_url, depth FROM discovery_edges ORDER BY id")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(DiscoveryEdge {
+                id: row.get(0)?,
+                parent_url: row.get(1)?,
+                child_url: row.get(2)?,
+                depth: row.get(3)?,
+            })
+        })?;
+
+        let mut edges = Vec::new();
+        for row in rows {
+            edges.push(row?);
+        }
+        Ok(edges)
+    }
+
+    /// Every edge whose `child_url` was reached from `parent_url`, i.e. one
+    /// level of the crawl tree rooted at `parent_url`.
+    pub fn list_discovery_edges_for_parent(&self, parent_url: &str) -> Result<Vec<DiscoveryEdge>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, parent_url, child_url, depth FROM discovery_edges WHERE parent_url = ?1 ORDER BY id",
+        )?;
+        let rows = stmt.query_map([parent_url], |row| {
+            Ok(DiscoveryEdge {
+                id: row.get(0)?,
+                parent_url: row.get(1)?,
+                child_url: row.get(2)?,
+                depth: row.get(3)?,
+            })
+        })?;
+
+        let mut edges = Vec::new();
+        for row in rows {
+            edges.push(row?);
+        }
+        Ok(edges)
+    }
+}
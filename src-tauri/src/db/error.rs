@@ -0,0 +1,57 @@
+use thiserror::Error;
+
+/// Error surfaced by the connection-pool layer itself, kept distinct from
+/// [`crate::error::Error`] so a pool failure isn't reported through the same
+/// `Internal`/`Database` variants every unrelated SQLite error takes.
+///
+/// Before the r2d2 migration, every store method locked a single shared
+/// `Mutex<Connection>` and mapped a poisoned guard -- a panic that happened
+/// to occur while holding the lock -- to a bogus
+/// `rusqlite::Error::InvalidPath("Poisoned Mutex")`, which permanently
+/// bricked the database for the rest of the process. `r2d2::Pool` doesn't
+/// share that failure mode: each checkout is an independent connection, so
+/// a panic on one never taints the others. `Poisoned` is kept here for the
+/// rare case a caller still holds a raw lock around a connection (e.g. a
+/// future in-process cache) and needs a typed way to report it rather than
+/// reinventing the `InvalidPath` hack.
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("sqlite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("connection pool exhausted: {0}")]
+    PoolExhausted(String),
+
+    #[error("a guard around a pooled connection was poisoned by a panic")]
+    Poisoned,
+}
+
+impl<T> From<std::sync::PoisonError<T>> for StoreError {
+    fn from(_: std::sync::PoisonError<T>) -> Self {
+        StoreError::Poisoned
+    }
+}
+
+impl From<StoreError> for crate::error::Error {
+    fn from(e: StoreError) -> Self {
+        match e {
+            StoreError::Sqlite(e) => crate::error::Error::Database(e),
+            StoreError::PoolExhausted(msg) => {
+                crate::error::Error::Internal(format!("connection pool exhausted: {}", msg))
+            }
+            StoreError::Poisoned => {
+                crate::error::Error::Internal("connection pool poisoned".to_string())
+            }
+        }
+    }
+}
+
+/// Recovers a poisoned `std::sync::Mutex` guard instead of propagating the
+/// poison, logging the fact so it's visible without killing the caller's
+/// request -- the same `into_inner`/`get_mut` semantics the standard
+/// library's own poisoning tests exercise, applied at the call site rather
+/// than left to infect every lock user with `InvalidPath` mapping.
+pub(crate) fn recover_poison<T>(poisoned: std::sync::PoisonError<T>) -> T {
+    eprintln!("[DB] Recovering a poisoned lock guard");
+    poisoned.into_inner()
+}
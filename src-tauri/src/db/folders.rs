@@ -1,12 +1,9 @@
-use crate::db::{Folder, SqliteDatabase};
+use crate::db::{row_extract, Folder, SqliteDatabase};
 use crate::error::Result;
 
 impl SqliteDatabase {
     pub fn add_folder(&self, name: &str, parent_id: Option<i64>) -> Result<i64> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
         conn.execute(
             "INSERT INTO folders (name, parent_id) VALUES (?1, ?2)",
             (name, parent_id),
@@ -15,20 +12,10 @@ impl SqliteDatabase {
     }
 
     pub fn get_folders(&self) -> Result<Vec<Folder>> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
         let mut stmt =
             conn.prepare("SELECT id, name, parent_id, created_at FROM folders ORDER BY id ASC")?;
-        let folder_iter = stmt.query_map([], |row| {
-            Ok(Folder {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                parent_id: row.get(2)?,
-                created_at: row.get(3)?,
-            })
-        })?;
+        let folder_iter = stmt.query_map([], row_extract::<Folder>)?;
         let mut folders = Vec::new();
         for f in folder_iter {
             folders.push(f?);
@@ -37,24 +24,17 @@ impl SqliteDatabase {
     }
 
     pub fn move_assets_to_folder(&self, asset_ids: Vec<i64>, folder_id: i64) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
-        for id in asset_ids {
-            conn.execute(
-                "UPDATE assets SET folder_id = ?1 WHERE id = ?2",
-                (folder_id, id),
-            )?;
-        }
-        Ok(())
+        self.with_transaction(|tx| {
+            let mut stmt = tx.prepare("UPDATE assets SET folder_id = ?1 WHERE id = ?2")?;
+            for id in asset_ids {
+                stmt.execute((folder_id, id))?;
+            }
+            Ok(())
+        })
     }
 
     pub fn delete_folder(&self, id: i64) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
         // Move assets to default folder (1) before deleting
         conn.execute("UPDATE assets SET folder_id = 1 WHERE folder_id = ?1", [id])?;
         conn.execute("DELETE FROM folders WHERE id = ?1 AND id != 1", [id])?;
@@ -0,0 +1,124 @@
+use crate::db::{Asset, Badge, Folder, ImportAsset, ImportOperation, ImportOptions};
+use rusqlite::types::FromSql;
+use rusqlite::Row;
+
+/// Maps a `rusqlite::Row` into a domain struct by column position, so a
+/// query site reads as `stmt.query_map([], row_extract::<Asset>)` instead of
+/// repeating the same `row.get(N)` list (and column order) at every call
+/// site that selects the same columns.
+pub trait FromRow: Sized {
+    fn from_row(row: &Row) -> rusqlite::Result<Self>;
+}
+
+/// Free-function adapter so `FromRow::from_row` can be passed directly
+/// where `rusqlite` wants an `Fn(&Row) -> rusqlite::Result<T>`.
+pub fn row_extract<T: FromRow>(row: &Row) -> rusqlite::Result<T> {
+    T::from_row(row)
+}
+
+macro_rules! impl_from_row_for_tuple {
+    ($($idx:tt => $t:ident),+) => {
+        impl<$($t: FromSql),+> FromRow for ($($t,)+) {
+            fn from_row(row: &Row) -> rusqlite::Result<Self> {
+                Ok(($(row.get($idx)?,)+))
+            }
+        }
+    };
+}
+
+impl_from_row_for_tuple!(0 => A);
+impl_from_row_for_tuple!(0 => A, 1 => B);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F);
+
+impl FromRow for Folder {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Folder {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            parent_id: row.get(2)?,
+            created_at: row.get(3)?,
+        })
+    }
+}
+
+/// Matches the column order every `assets` `SELECT` in this module uses:
+/// `id, url, method, status, status_code, risk_score, findings, folder_id,
+/// response_headers, response_body, request_headers, request_body,
+/// created_at, updated_at, notes, triage_status, is_documented, source,
+/// recursive, is_workbench, depth, content_hash`.
+impl FromRow for Asset {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let findings_json: String = row.get(6)?;
+        let findings: Vec<Badge> = serde_json::from_str(&findings_json).unwrap_or_default();
+        Ok(Asset {
+            id: row.get(0)?,
+            url: row.get(1)?,
+            method: row.get(2)?,
+            status: row.get(3)?,
+            status_code: row.get(4)?,
+            risk_score: row.get(5)?,
+            findings,
+            folder_id: row.get(7)?,
+            response_headers: row.get(8)?,
+            response_body: row.get(9)?,
+            request_headers: row.get(10)?,
+            request_body: row.get(11)?,
+            created_at: row.get(12)?,
+            updated_at: row.get(13)?,
+            notes: row.get(14).unwrap_or_else(|_| "".to_string()),
+            triage_status: row.get(15).unwrap_or_else(|_| "Unreviewed".to_string()),
+            is_documented: row.get(16).unwrap_or(true),
+            source: row.get(17).unwrap_or_else(|_| "User".to_string()),
+            recursive: row.get(18).unwrap_or(false),
+            is_workbench: row.get(19).unwrap_or(false),
+            depth: row.get(20).unwrap_or(0),
+            content_hash: row.get(21).unwrap_or_else(|_| "".to_string()),
+        })
+    }
+}
+
+/// Matches `id, import_id, source, total_assets, successful_assets,
+/// failed_assets, duplicate_assets, status, options, duration_ms,
+/// error_message, created_at, updated_at`.
+impl FromRow for ImportOperation {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let options_json: String = row.get(8)?;
+        let options: ImportOptions = serde_json::from_str(&options_json).unwrap_or_default();
+        Ok(ImportOperation {
+            id: row.get(0)?,
+            import_id: row.get(1)?,
+            source: row.get(2)?,
+            total_assets: row.get(3)?,
+            successful_assets: row.get(4)?,
+            failed_assets: row.get(5)?,
+            duplicate_assets: row.get(6)?,
+            status: row.get(7)?,
+            options,
+            duration_ms: row.get(9)?,
+            error_message: row.get(10)?,
+            created_at: row.get(11)?,
+            updated_at: row.get(12)?,
+        })
+    }
+}
+
+/// Matches `id, import_id, asset_id, url, method, status, error_message,
+/// processing_time_ms, created_at`.
+impl FromRow for ImportAsset {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(ImportAsset {
+            id: row.get(0)?,
+            import_id: row.get(1)?,
+            asset_id: row.get(2)?,
+            url: row.get(3)?,
+            method: row.get(4)?,
+            status: row.get(5)?,
+            error_message: row.get(6)?,
+            processing_time_ms: row.get(7).unwrap_or(0),
+            created_at: row.get(8)?,
+        })
+    }
+}
@@ -1,12 +1,9 @@
-use crate::db::{ImportAsset, ImportOperation, ImportOptions, SqliteDatabase};
+use crate::db::{row_extract, ImportAsset, ImportOperation, SqliteDatabase};
 use crate::error::Result;
 
 impl SqliteDatabase {
     pub fn record_import_operation(&self, operation: ImportOperation) -> Result<i64> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         let options_json = serde_json::to_string(&operation.options)
             .map_err(crate::error::Error::Serialization)?;
@@ -40,10 +37,7 @@ impl SqliteDatabase {
         duration_ms: Option<i64>,
         error_message: Option<&str>,
     ) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         match (duration_ms, error_message) {
             (Some(duration), Some(error)) => {
@@ -86,10 +80,7 @@ impl SqliteDatabase {
         error_message: Option<&str>,
         processing_time_ms: Option<i64>,
     ) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         conn.execute(
             "INSERT INTO import_assets (import_id, asset_id, url, method, status, error_message, processing_time_ms) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
@@ -107,10 +98,7 @@ impl SqliteDatabase {
     }
 
     pub fn get_import_history(&self, limit: usize, offset: usize) -> Result<Vec<ImportOperation>> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         let mut stmt = conn.prepare(
             "SELECT id, import_id, source, total_assets, successful_assets, failed_assets, duplicate_assets, status, options, duration_ms, error_message, created_at, updated_at 
@@ -119,26 +107,7 @@ impl SqliteDatabase {
              LIMIT ?1 OFFSET ?2"
         )?;
 
-        let import_iter = stmt.query_map((limit, offset), |row| {
-            let options_json: String = row.get(8)?;
-            let options: ImportOptions = serde_json::from_str(&options_json).unwrap_or_default();
-
-            Ok(ImportOperation {
-                id: row.get(0)?,
-                import_id: row.get(1)?,
-                source: row.get(2)?,
-                total_assets: row.get(3)?,
-                successful_assets: row.get(4)?,
-                failed_assets: row.get(5)?,
-                duplicate_assets: row.get(6)?,
-                status: row.get(7)?,
-                options,
-                duration_ms: row.get(9)?,
-                error_message: row.get(10)?,
-                created_at: row.get(11)?,
-                updated_at: row.get(12)?,
-            })
-        })?;
+        let import_iter = stmt.query_map((limit, offset), row_extract::<ImportOperation>)?;
 
         let mut operations = Vec::new();
         for operation in import_iter {
@@ -148,31 +117,16 @@ impl SqliteDatabase {
     }
 
     pub fn get_import_assets(&self, import_id: &str) -> Result<Vec<ImportAsset>> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         let mut stmt = conn.prepare(
-            "SELECT id, import_id, asset_id, url, method, status, error_message, processing_time_ms, created_at 
-             FROM import_assets 
-             WHERE import_id = ?1 
+            "SELECT id, import_id, asset_id, url, method, status, error_message, processing_time_ms, created_at
+             FROM import_assets
+             WHERE import_id = ?1
              ORDER BY created_at ASC"
         )?;
 
-        let asset_iter = stmt.query_map([import_id], |row| {
-            Ok(ImportAsset {
-                id: row.get(0)?,
-                import_id: row.get(1)?,
-                asset_id: row.get(2)?,
-                url: row.get(3)?,
-                method: row.get(4)?,
-                status: row.get(5)?,
-                error_message: row.get(6)?,
-                processing_time_ms: row.get(7).unwrap_or(0),
-                created_at: row.get(8)?,
-            })
-        })?;
+        let asset_iter = stmt.query_map([import_id], row_extract::<ImportAsset>)?;
 
         let mut assets = Vec::new();
         for asset in asset_iter {
@@ -181,11 +135,83 @@ impl SqliteDatabase {
         Ok(assets)
     }
 
+    /// The still-unfinished half of an import's queue: rows enqueued by
+    /// `record_import_asset` at `pending`/`in_progress` that never reached
+    /// a terminal `success`/`failed`/`duplicate` status, either because the
+    /// worker pool hasn't gotten to them yet or because the app was closed
+    /// mid-import. `resume_import` and startup recovery both re-drive this
+    /// exact set through the worker pool instead of the original in-memory
+    /// URL list, which doesn't survive a restart.
+    pub fn get_unfinished_import_assets(&self, import_id: &str) -> Result<Vec<ImportAsset>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, import_id, asset_id, url, method, status, error_message, processing_time_ms, created_at
+             FROM import_assets
+             WHERE import_id = ?1 AND status IN ('pending', 'in_progress')
+             ORDER BY created_at ASC, id ASC",
+        )?;
+
+        let asset_iter = stmt.query_map([import_id], row_extract::<ImportAsset>)?;
+
+        let mut assets = Vec::new();
+        for asset in asset_iter {
+            assets.push(asset?);
+        }
+        Ok(assets)
+    }
+
+    /// Import operations stranded in `running` -- the app closed (or
+    /// crashed) before the worker pool finished and wrote `completed`/
+    /// `cancelled`/`failed` back. Read at startup to find what needs
+    /// resuming.
+    pub fn get_running_import_operations(&self) -> Result<Vec<ImportOperation>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, import_id, source, total_assets, successful_assets, failed_assets, duplicate_assets, status, options, duration_ms, error_message, created_at, updated_at
+             FROM import_operations
+             WHERE status = 'running'",
+        )?;
+
+        let op_iter = stmt.query_map([], row_extract::<ImportOperation>)?;
+
+        let mut ops = Vec::new();
+        for op in op_iter {
+            ops.push(op?);
+        }
+        Ok(ops)
+    }
+
+    /// Transitions an already-`record_import_asset`-ed row to its next
+    /// state (`in_progress` when a worker picks it up, `success`/`failed`
+    /// once the scan finishes) instead of inserting a new row, so a given
+    /// URL has exactly one `import_assets` row across its whole
+    /// pending -> in_progress -> success/failed lifecycle.
+    pub fn update_import_asset_status(
+        &self,
+        import_id: &str,
+        asset_id: i64,
+        status: &str,
+        error_message: Option<&str>,
+        processing_time_ms: Option<i64>,
+    ) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE import_assets SET status = ?1, error_message = ?2, processing_time_ms = ?3 WHERE import_id = ?4 AND asset_id = ?5",
+            (
+                status,
+                error_message,
+                processing_time_ms,
+                import_id,
+                asset_id,
+            ),
+        )?;
+        Ok(())
+    }
+
     pub fn clear_import_history(&self) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         conn.execute("DELETE FROM import_assets", [])?;
         conn.execute("DELETE FROM import_operations", [])?;
@@ -194,10 +220,7 @@ impl SqliteDatabase {
     }
 
     pub fn get_import_operation(&self, import_id: &str) -> Result<Option<ImportOperation>> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         let mut stmt = conn.prepare(
             "SELECT id, import_id, source, total_assets, successful_assets, failed_assets, duplicate_assets, status, options, duration_ms, error_message, created_at, updated_at 
@@ -205,26 +228,7 @@ impl SqliteDatabase {
              WHERE import_id = ?1"
         )?;
 
-        let mut import_iter = stmt.query_map([import_id], |row| {
-            let options_json: String = row.get(8)?;
-            let options: ImportOptions = serde_json::from_str(&options_json).unwrap_or_default();
-
-            Ok(ImportOperation {
-                id: row.get(0)?,
-                import_id: row.get(1)?,
-                source: row.get(2)?,
-                total_assets: row.get(3)?,
-                successful_assets: row.get(4)?,
-                failed_assets: row.get(5)?,
-                duplicate_assets: row.get(6)?,
-                status: row.get(7)?,
-                options,
-                duration_ms: row.get(9)?,
-                error_message: row.get(10)?,
-                created_at: row.get(11)?,
-                updated_at: row.get(12)?,
-            })
-        })?;
+        let mut import_iter = stmt.query_map([import_id], row_extract::<ImportOperation>)?;
 
         if let Some(op) = import_iter.next() {
             Ok(Some(op?))
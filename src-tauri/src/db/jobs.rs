@@ -0,0 +1,97 @@
+use crate::db::{Job, SqliteDatabase};
+use crate::error::Result;
+
+impl SqliteDatabase {
+    pub fn create_job(&self, id: &str, job_type: &str, total: i64) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "INSERT INTO jobs (id, job_type, status, processed, total) VALUES (?1, ?2, 'running', 0, ?3)",
+            (id, job_type, total),
+        )?;
+        Ok(())
+    }
+
+    pub fn update_job_progress(
+        &self,
+        id: &str,
+        processed: i64,
+        latest_asset: Option<&str>,
+    ) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "UPDATE jobs SET processed = ?1, latest_asset = ?2, updated_at = CURRENT_TIMESTAMP WHERE id = ?3",
+            (processed, latest_asset, id),
+        )?;
+        Ok(())
+    }
+
+    pub fn finish_job(&self, id: &str, status: &str, error_message: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "UPDATE jobs SET status = ?1, error_message = ?2, updated_at = CURRENT_TIMESTAMP WHERE id = ?3",
+            (status, error_message, id),
+        )?;
+        Ok(())
+    }
+
+    pub fn get_job(&self, id: &str) -> Result<Option<Job>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, job_type, status, processed, total, latest_asset, error_message, created_at, updated_at
+             FROM jobs WHERE id = ?1",
+        )?;
+
+        let mut rows = stmt.query_map([id], |row| {
+            Ok(Job {
+                id: row.get(0)?,
+                job_type: row.get(1)?,
+                status: row.get(2)?,
+                processed: row.get(3)?,
+                total: row.get(4)?,
+                latest_asset: row.get(5)?,
+                error_message: row.get(6)?,
+                created_at: row.get(7)?,
+                updated_at: row.get(8)?,
+            })
+        })?;
+
+        if let Some(job) = rows.next() {
+            Ok(Some(job?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn list_jobs(&self) -> Result<Vec<Job>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, job_type, status, processed, total, latest_asset, error_message, created_at, updated_at
+             FROM jobs ORDER BY created_at DESC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(Job {
+                id: row.get(0)?,
+                job_type: row.get(1)?,
+                status: row.get(2)?,
+                processed: row.get(3)?,
+                total: row.get(4)?,
+                latest_asset: row.get(5)?,
+                error_message: row.get(6)?,
+                created_at: row.get(7)?,
+                updated_at: row.get(8)?,
+            })
+        })?;
+
+        let mut jobs = Vec::new();
+        for job in rows {
+            jobs.push(job?);
+        }
+        Ok(jobs)
+    }
+}
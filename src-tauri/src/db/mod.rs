@@ -1,14 +1,32 @@
-use crate::error::Result;
+use crate::error::{Error, Result};
+use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+use tokio::sync::broadcast;
 
+pub mod advisories;
 pub mod assets;
+pub mod auth_profiles;
+pub mod backup;
+pub mod discovery_edges;
+pub mod encryption;
+pub mod error;
 pub mod folders;
+pub mod from_row;
 pub mod imports;
+pub mod jobs;
+pub mod notify;
+pub mod query;
 pub mod sequences;
+pub mod storage;
 pub mod traits;
 
+pub use error::StoreError;
+pub use from_row::{row_extract, FromRow};
+pub use notify::{ChangeOp, DbChange};
+pub use storage::{InMemoryStorage, Storage};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ImportOperation {
     pub id: i64,
@@ -46,6 +64,16 @@ pub struct ImportOptions {
     pub batch_mode: bool,
     pub rate_limit: i32,
     pub auto_triage: bool,
+    /// How many URLs `enhanced_import_assets` scans at once. `rate_limit`
+    /// still caps overall throughput via a shared token bucket, so raising
+    /// this doesn't exceed it -- it just lets idle network wait time on one
+    /// URL overlap with another instead of serializing the whole import.
+    #[serde(default = "default_import_concurrency")]
+    pub concurrency: i32,
+}
+
+fn default_import_concurrency() -> i32 {
+    4
 }
 
 impl Default for ImportOptions {
@@ -56,12 +84,26 @@ impl Default for ImportOptions {
             batch_mode: true,
             rate_limit: 10,
             auto_triage: false,
+            concurrency: default_import_concurrency(),
         }
     }
 }
 
 pub use crate::data::{Badge, Severity};
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Job {
+    pub id: String,
+    pub job_type: String,
+    pub status: String, // queued, running, completed, cancelled, failed
+    pub processed: i64,
+    pub total: i64,
+    pub latest_asset: Option<String>,
+    pub error_message: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Folder {
     pub id: i64,
@@ -91,6 +133,12 @@ pub struct Asset {
     pub is_documented: bool,
     pub source: String,
     pub recursive: bool,
+    pub is_workbench: bool,
+    pub depth: i32,
+    /// Hex SHA-256 digest of `response_body` as of the last scan, used to
+    /// detect drift between rescans in O(1) instead of diffing the full
+    /// body every time. Empty for assets scanned before this column existed.
+    pub content_hash: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -105,50 +153,301 @@ pub struct ScanHistoryEntry {
     pub response_body: String,
 }
 
+/// One `is_documented` transition recorded by `update_asset_documentation`/
+/// `batch_mark_shadow_apis`, so an auditor can reconstruct why an asset was
+/// (or wasn't) flagged as a Shadow API at a given point in time.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DocumentationHistoryEntry {
+    pub id: i64,
+    pub asset_id: i64,
+    pub previous_value: bool,
+    pub new_value: bool,
+    pub actor: Option<String>,
+    pub reason: Option<String>,
+    pub changed_at: String,
+}
+
+/// Per-connection PRAGMAs applied to every connection the pool hands out,
+/// before `init_tables`/`run_migrations` ever run against it. SQLite
+/// ignores `FOREIGN KEY` clauses and waits indefinitely on a locked database
+/// unless a connection opts in, which otherwise silently defeats the
+/// `ON DELETE CASCADE` clauses on `scan_history`/`import_assets`/
+/// `sequence_steps`.
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionOptions {
+    /// How long a writer backs off and retries on `SQLITE_BUSY` before
+    /// giving up, instead of erroring immediately.
+    pub busy_timeout_ms: u32,
+    /// Switch to WAL journaling so `get_assets`/`get_stale_assets` reads
+    /// aren't blocked behind an in-progress `update_scan_result` write.
+    pub enable_wal: bool,
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            busy_timeout_ms: 5000,
+            enable_wal: true,
+        }
+    }
+}
+
+impl ConnectionOptions {
+    fn apply(&self, conn: &Connection) -> rusqlite::Result<()> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        conn.busy_timeout(std::time::Duration::from_millis(
+            self.busy_timeout_ms as u64,
+        ))?;
+        if self.enable_wal {
+            conn.pragma_update(None, "journal_mode", "WAL")?;
+            conn.pragma_update(None, "synchronous", "NORMAL")?;
+        }
+        Ok(())
+    }
+
+    /// Overrides `defaults` with whatever `db.busy_timeout_ms`/
+    /// `db.enable_wal` rows are present in the `settings` table, so the
+    /// PRAGMA tuning can be changed without recompiling. Unset or
+    /// unparsable settings fall back to `defaults` unchanged.
+    fn from_settings(conn: &Connection, defaults: ConnectionOptions) -> Self {
+        Self {
+            busy_timeout_ms: read_setting_value(conn, "db.busy_timeout_ms")
+                .unwrap_or(defaults.busy_timeout_ms),
+            enable_wal: read_setting_value(conn, "db.enable_wal").unwrap_or(defaults.enable_wal),
+        }
+    }
+}
+
+/// Reads and parses a single `settings` row, used to tune the pool/PRAGMA
+/// knobs at startup without a dedicated settings struct for each one.
+fn read_setting_value<T: std::str::FromStr>(conn: &Connection, key: &str) -> Option<T> {
+    conn.query_row(
+        "SELECT value FROM settings WHERE key = ?1",
+        [key],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|v| v.parse().ok())
+}
+
+/// Applies [`ConnectionOptions`] to every connection r2d2 hands out of the
+/// pool, so a connection opened to replace a broken one (rather than a
+/// poisoned mutex guard, which this pool sidesteps entirely) is configured
+/// identically to the rest.
+#[derive(Debug)]
+struct ConnectionCustomizer {
+    options: ConnectionOptions,
+    /// Hex-encoded SQLCipher key, set only for databases opened via
+    /// [`SqliteDatabase::new_encrypted`]. Must be applied via `PRAGMA key`
+    /// before any other statement touches a freshly-acquired connection,
+    /// so it runs ahead of `options.apply`.
+    encryption_key_hex: Option<String>,
+    /// Cloned into every pooled connection's update/commit/rollback hooks
+    /// so a write made through *any* connection in the pool reaches every
+    /// `subscribe()`r, not just the one that opened it.
+    change_tx: broadcast::Sender<DbChange>,
+}
+
+impl r2d2::CustomizeConnection<Connection, rusqlite::Error> for ConnectionCustomizer {
+    fn on_acquire(&self, conn: &mut Connection) -> std::result::Result<(), rusqlite::Error> {
+        if let Some(key_hex) = &self.encryption_key_hex {
+            conn.pragma_update(None, "key", format!("\"x'{}'\"", key_hex))?;
+        }
+        self.options.apply(conn)?;
+
+        // Buffered per-connection: the update hook only records candidate
+        // changes, and the commit hook is what actually publishes them, so
+        // a rolled-back transaction's writes never reach a subscriber.
+        let pending: Arc<std::sync::Mutex<Vec<DbChange>>> =
+            Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let pending_for_update = Arc::clone(&pending);
+        conn.update_hook(Some(
+            move |action: rusqlite::hooks::Action, _db: &str, table: &str, rowid: i64| {
+                if !notify::WATCHED_TABLES.contains(&table) {
+                    return;
+                }
+                let op = match action {
+                    rusqlite::hooks::Action::SQLITE_INSERT => ChangeOp::Insert,
+                    rusqlite::hooks::Action::SQLITE_UPDATE => ChangeOp::Update,
+                    rusqlite::hooks::Action::SQLITE_DELETE => ChangeOp::Delete,
+                    _ => return,
+                };
+                if let Ok(mut pending) = pending_for_update.lock() {
+                    pending.push(DbChange {
+                        table: table.to_string(),
+                        op,
+                        rowid,
+                    });
+                }
+            },
+        ));
+
+        let pending_for_commit = Arc::clone(&pending);
+        let change_tx = self.change_tx.clone();
+        conn.commit_hook(Some(move || {
+            if let Ok(mut pending) = pending_for_commit.lock() {
+                for change in pending.drain(..) {
+                    // No subscribers is the common case outside an open UI
+                    // session; a send error just means nobody's listening.
+                    let _ = change_tx.send(change);
+                }
+            }
+            false
+        }));
+
+        conn.rollback_hook(Some(move || {
+            if let Ok(mut pending) = pending.lock() {
+                pending.clear();
+            }
+        }));
+
+        Ok(())
+    }
+}
+
 pub struct SqliteDatabase {
-    pub(crate) conn: Arc<Mutex<Connection>>,
+    pool: r2d2::Pool<SqliteConnectionManager>,
     pub client: reqwest::Client,
     pub rate_limiter: crate::core::rate_limiter::SharedRateLimiter,
+    change_tx: broadcast::Sender<DbChange>,
 }
 
 impl Clone for SqliteDatabase {
     fn clone(&self) -> Self {
         Self {
-            conn: self.conn.clone(),
+            pool: self.pool.clone(),
             client: self.client.clone(),
             rate_limiter: self.rate_limiter.clone(),
+            change_tx: self.change_tx.clone(),
         }
     }
 }
 
 impl SqliteDatabase {
     pub fn new(path: &str) -> Result<Self> {
-        let conn_raw = Connection::open(path)?;
-        let conn = Arc::new(Mutex::new(conn_raw));
-        let conn_lock = conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        Self::new_with_options(path, ConnectionOptions::default())
+    }
+
+    pub fn new_with_options(path: &str, options: ConnectionOptions) -> Result<Self> {
+        Self::open(path, options, None)
+    }
+
+    /// Shared bootstrap/pool-construction path for the plaintext (`new`,
+    /// `new_with_options`) and SQLCipher (`new_encrypted`,
+    /// `new_encrypted_with_options`) constructors. `key_hex`, when set, is
+    /// issued as `PRAGMA key` on the bootstrap connection before anything
+    /// else touches it, and installed on the pool's customizer so every
+    /// later pooled connection unlocks itself the same way.
+    fn open(path: &str, options: ConnectionOptions, key_hex: Option<String>) -> Result<Self> {
+        let manager = SqliteConnectionManager::file(path);
+
+        // Bootstrap on a single direct connection (not yet pooled) so
+        // `init_tables`/`run_migrations` can create the `settings` table
+        // before it's read for the pool-size/PRAGMA overrides below.
+        let (tuned_options, pool_size) = {
+            use r2d2::ManageConnection;
+            let bootstrap = manager
+                .connect()
+                .map_err(|e| Error::Internal(format!("connection pool error: {}", e)))?;
+            if let Some(key_hex) = &key_hex {
+                bootstrap.pragma_update(None, "key", format!("\"x'{}'\"", key_hex))?;
+                Self::verify_encryption_key(&bootstrap)?;
+            }
+            options.apply(&bootstrap)?;
+            Self::init_tables(&bootstrap)?;
+            Self::run_migrations(&bootstrap)?;
+            (
+                ConnectionOptions::from_settings(&bootstrap, options),
+                Self::read_setting_u32(&bootstrap, "db.pool_size", 8),
+            )
+        };
+
+        let (change_tx, _) = broadcast::channel(notify::CHANGE_CHANNEL_CAPACITY);
 
-        Self::init_tables(&conn_lock)?;
-        Self::run_migrations(&conn_lock)?;
+        let mut builder = r2d2::Pool::builder()
+            .max_size(pool_size)
+            .connection_customizer(Box::new(ConnectionCustomizer {
+                options: tuned_options,
+                encryption_key_hex: key_hex,
+                change_tx: change_tx.clone(),
+            }));
+        if path == ":memory:" {
+            // Every pooled connection to ":memory:" would otherwise be its
+            // own empty database, so pin the pool to the one connection.
+            builder = builder.max_size(1);
+        }
+        let pool = builder
+            .build(manager)
+            .map_err(|e| Error::Internal(format!("connection pool error: {}", e)))?;
 
         let client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(10))
             .danger_accept_invalid_certs(true)
+            // Honor hosts `ssrf_guard::guard_and_resolve` has already pinned,
+            // so a recursively-discovered asset is scanned at the exact
+            // address the guard cleared rather than a freshly re-resolved
+            // (and potentially rebound) one.
+            .dns_resolver(Arc::new(crate::core::dns_guard::PinnedHostResolver::default()))
             .build()
             .unwrap_or_default();
 
         let rate_limiter = Arc::new(crate::core::rate_limiter::RateLimiter::new(100)); // Default 100ms
 
-        drop(conn_lock);
-
         Ok(SqliteDatabase {
-            conn,
+            pool,
             client,
             rate_limiter,
+            change_tx,
         })
     }
 
+    /// SQLCipher accepts a wrong key silently and only fails the first time
+    /// a query actually touches the (garbage-looking) page data, surfacing
+    /// a generic "file is not a database" error at that point. Running a
+    /// real read here turns that into a clean, immediate failure from the
+    /// constructor instead of a confusing error on some later unrelated
+    /// query.
+    fn verify_encryption_key(conn: &Connection) -> Result<()> {
+        conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| {
+            row.get::<_, i64>(0)
+        })
+        .map_err(|_| Error::Internal("incorrect encryption passphrase".to_string()))?;
+        Ok(())
+    }
+
+    /// Checks out a connection from the pool. Returns the typed
+    /// [`StoreError`] rather than masking a pool timeout as a filesystem
+    /// error; `?` converts it into [`crate::error::Error`] for the public
+    /// `Result` methods built on top of this.
+    pub(crate) fn get_conn(
+        &self,
+    ) -> std::result::Result<r2d2::PooledConnection<SqliteConnectionManager>, StoreError> {
+        self.pool
+            .get()
+            .map_err(|e| StoreError::PoolExhausted(e.to_string()))
+    }
+
+    /// Runs `f` against a single connection checked out of the pool, wrapped
+    /// in a transaction that's committed on `Ok` and rolled back on `Err` --
+    /// the one place batch mutators (`batch_mark_shadow_apis`,
+    /// `move_assets_to_folder`, ...) need to reach for instead of looping
+    /// `conn.execute` calls that can leave a partial update committed.
+    pub(crate) fn with_transaction<T>(
+        &self,
+        f: impl FnOnce(&rusqlite::Transaction) -> Result<T>,
+    ) -> Result<T> {
+        let mut conn = self.get_conn()?;
+        let tx = conn.transaction()?;
+        let result = f(&tx)?;
+        tx.commit()?;
+        Ok(result)
+    }
+
+    fn read_setting_u32(conn: &Connection, key: &str, default: u32) -> u32 {
+        read_setting_value(conn, key).unwrap_or(default)
+    }
+
     fn init_tables(conn: &Connection) -> Result<()> {
         conn.execute(
             "CREATE TABLE IF NOT EXISTS folders (
@@ -262,6 +561,25 @@ impl SqliteDatabase {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS jobs (
+                id TEXT PRIMARY KEY,
+                job_type TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'queued',
+                processed INTEGER NOT NULL DEFAULT 0,
+                total INTEGER NOT NULL DEFAULT 0,
+                latest_asset TEXT,
+                error_message TEXT,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_jobs_status ON jobs(status)",
+            [],
+        )?;
+
         conn.execute(
             "CREATE TABLE IF NOT EXISTS sequences (
                 id TEXT PRIMARY KEY,
@@ -291,6 +609,175 @@ impl SqliteDatabase {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS auth_profiles (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                scope_prefix TEXT NOT NULL,
+                profile_json TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_auth_profiles_scope_prefix ON auth_profiles(scope_prefix)",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS discovery_edges (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                parent_url TEXT,
+                child_url TEXT NOT NULL,
+                depth INTEGER NOT NULL DEFAULT 0,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_discovery_edges_child_url ON discovery_edges(child_url)",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS asset_documentation_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                asset_id INTEGER NOT NULL,
+                previous_value BOOLEAN NOT NULL,
+                new_value BOOLEAN NOT NULL,
+                actor TEXT,
+                reason TEXT,
+                changed_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY(asset_id) REFERENCES assets(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_asset_documentation_history_asset_id ON asset_documentation_history(asset_id)",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS findings (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                asset_id INTEGER NOT NULL,
+                short_name TEXT NOT NULL,
+                severity TEXT NOT NULL,
+                evidence TEXT,
+                is_false_positive BOOLEAN NOT NULL DEFAULT 0,
+                reason TEXT,
+                FOREIGN KEY(asset_id) REFERENCES assets(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_findings_asset_id ON findings(asset_id)",
+            [],
+        )?;
+
+        // Keep `assets.risk_score` and `assets.updated_at` derived from the
+        // normalized `findings` table instead of recomputed in Rust, so
+        // every write path (scan results, FP toggles, manual edits) stays
+        // in sync for free. Severity weights mirror
+        // `recalculate_asset_risk_score`'s old Rust-side scoring.
+        conn.execute(
+            "CREATE TRIGGER IF NOT EXISTS trg_findings_risk_after_insert
+             AFTER INSERT ON findings
+             BEGIN
+                 UPDATE assets SET
+                     risk_score = (
+                         SELECT COALESCE(SUM(CASE severity
+                             WHEN 'Critical' THEN 100
+                             WHEN 'High' THEN 50
+                             WHEN 'Medium' THEN 25
+                             WHEN 'Low' THEN 10
+                             ELSE 0 END), 0)
+                         FROM findings
+                         WHERE asset_id = NEW.asset_id AND is_false_positive = 0
+                     ),
+                     updated_at = CURRENT_TIMESTAMP
+                 WHERE id = NEW.asset_id;
+             END",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TRIGGER IF NOT EXISTS trg_findings_risk_after_update
+             AFTER UPDATE ON findings
+             BEGIN
+                 UPDATE assets SET
+                     risk_score = (
+                         SELECT COALESCE(SUM(CASE severity
+                             WHEN 'Critical' THEN 100
+                             WHEN 'High' THEN 50
+                             WHEN 'Medium' THEN 25
+                             WHEN 'Low' THEN 10
+                             ELSE 0 END), 0)
+                         FROM findings
+                         WHERE asset_id = NEW.asset_id AND is_false_positive = 0
+                     ),
+                     updated_at = CURRENT_TIMESTAMP
+                 WHERE id = NEW.asset_id;
+             END",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TRIGGER IF NOT EXISTS trg_findings_risk_after_delete
+             AFTER DELETE ON findings
+             BEGIN
+                 UPDATE assets SET
+                     risk_score = (
+                         SELECT COALESCE(SUM(CASE severity
+                             WHEN 'Critical' THEN 100
+                             WHEN 'High' THEN 50
+                             WHEN 'Medium' THEN 25
+                             WHEN 'Low' THEN 10
+                             ELSE 0 END), 0)
+                         FROM findings
+                         WHERE asset_id = OLD.asset_id AND is_false_positive = 0
+                     ),
+                     updated_at = CURRENT_TIMESTAMP
+                 WHERE id = OLD.asset_id;
+             END",
+            [],
+        )?;
+
+        // Generic `updated_at` freshness trigger: catches any mutation that
+        // forgot to bump it by hand. `recursive_triggers` defaults off, so
+        // the trigger's own UPDATE doesn't re-fire itself, and the `WHEN`
+        // guard keeps it a no-op once a statement already set updated_at.
+        conn.execute(
+            "CREATE TRIGGER IF NOT EXISTS trg_assets_touch_updated_at
+             AFTER UPDATE ON assets
+             FOR EACH ROW
+             WHEN NEW.updated_at = OLD.updated_at
+             BEGIN
+                 UPDATE assets SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+             END",
+            [],
+        )?;
+
+        // One place for the UI's triage summary to read an asset's
+        // risk/documentation picture without pulling every finding into
+        // Rust to recompute it.
+        conn.execute(
+            "CREATE VIEW IF NOT EXISTS asset_effective_risk AS
+             SELECT
+                 a.id AS asset_id,
+                 a.url AS url,
+                 a.risk_score AS risk_score,
+                 a.triage_status AS triage_status,
+                 a.is_documented AS is_documented,
+                 COUNT(CASE WHEN f.is_false_positive = 0 THEN 1 END) AS active_finding_count,
+                 COUNT(CASE WHEN f.severity = 'Critical' AND f.is_false_positive = 0 THEN 1 END) AS critical_count,
+                 COUNT(CASE WHEN f.severity = 'High' AND f.is_false_positive = 0 THEN 1 END) AS high_count,
+                 COUNT(CASE WHEN f.severity = 'Medium' AND f.is_false_positive = 0 THEN 1 END) AS medium_count,
+                 COUNT(CASE WHEN f.severity = 'Low' AND f.is_false_positive = 0 THEN 1 END) AS low_count,
+                 COUNT(CASE WHEN f.severity = 'Info' AND f.is_false_positive = 0 THEN 1 END) AS info_count
+             FROM assets a
+             LEFT JOIN findings f ON f.asset_id = a.id
+             GROUP BY a.id",
+            [],
+        )?;
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_assets_url ON assets(url)",
             [],
@@ -330,6 +817,7 @@ impl SqliteDatabase {
             ("assets", "is_documented", "BOOLEAN NOT NULL DEFAULT 1"),
             ("assets", "source", "TEXT DEFAULT 'User'"),
             ("assets", "recursive", "BOOLEAN DEFAULT 0"),
+            ("assets", "content_hash", "TEXT DEFAULT ''"),
             ("sequence_steps", "captures", "TEXT DEFAULT '[]'"),
         ];
 
@@ -356,14 +844,75 @@ impl SqliteDatabase {
                 }
             }
         }
+
+        Self::migrate_findings_backfill(conn)?;
+        Self::migrate_dedupe_asset_urls(conn)?;
+
+        Ok(())
+    }
+
+    /// `assets.url` is declared `UNIQUE` in `init_tables`, but `CREATE
+    /// TABLE IF NOT EXISTS` is a no-op against a database created before
+    /// that constraint was added, so an upgraded install can still have
+    /// duplicate URLs on disk even though `add_asset_dedup`'s `ON
+    /// CONFLICT(url)` assumes there aren't any. Keeps the lowest `id` per
+    /// URL (cascading the delete through `scan_history`/`findings`/
+    /// `import_assets`) and then backstops the column-level constraint
+    /// with an explicit index, which is a no-op everywhere the inline
+    /// `UNIQUE` already applied.
+    fn migrate_dedupe_asset_urls(conn: &Connection) -> Result<()> {
+        conn.execute(
+            "DELETE FROM assets WHERE id NOT IN (
+                SELECT MIN(id) FROM assets GROUP BY url
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_assets_url_unique ON assets(url)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// One-time backfill of the normalized `findings` table from the
+    /// pre-existing `assets.findings` JSON blob. Guarded on the table
+    /// already having rows rather than a schema-version flag, matching the
+    /// column-probe style above; a freshly created database has nothing to
+    /// backfill from, and a previously-backfilled one already has rows.
+    fn migrate_findings_backfill(conn: &Connection) -> Result<()> {
+        let already_seeded: i64 =
+            conn.query_row("SELECT COUNT(*) FROM findings", [], |row| row.get(0))?;
+        if already_seeded > 0 {
+            return Ok(());
+        }
+
+        let mut stmt = conn.prepare("SELECT id, findings FROM assets")?;
+        let rows: Vec<(i64, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        drop(stmt);
+
+        for (asset_id, findings_json) in rows {
+            let badges: Vec<Badge> = serde_json::from_str(&findings_json).unwrap_or_default();
+            for badge in badges {
+                conn.execute(
+                    "INSERT INTO findings (asset_id, short_name, severity, evidence, is_false_positive, reason) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    (
+                        asset_id,
+                        &badge.short,
+                        format!("{:?}", badge.severity),
+                        &badge.evidence,
+                        badge.is_fp,
+                        &badge.fp_reason,
+                    ),
+                )?;
+            }
+        }
         Ok(())
     }
 
     pub fn get_setting(&self, key: &str) -> Result<Option<String>> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
         let mut stmt = conn.prepare("SELECT value FROM settings WHERE key = ?")?;
         let mut rows = stmt.query([key])?;
         if let Some(row) = rows.next()? {
@@ -374,10 +923,7 @@ impl SqliteDatabase {
     }
 
     pub fn set_setting(&self, key: &str, value: &str) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
         conn.execute(
             "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
             [key, value],
@@ -410,6 +956,7 @@ impl traits::DatabaseTrait for SqliteDatabase {
         resp_body: &str,
         req_headers: &str,
         req_body: &str,
+        content_hash: &str,
     ) -> Result<()> {
         SqliteDatabase::update_scan_result(
             self,
@@ -422,6 +969,7 @@ impl traits::DatabaseTrait for SqliteDatabase {
             resp_body,
             req_headers,
             req_body,
+            content_hash,
         )
     }
     fn delete_asset(&self, id: i64) -> Result<()> {
@@ -464,11 +1012,25 @@ impl traits::DatabaseTrait for SqliteDatabase {
     fn sanitize_urls(&self) -> Result<usize> {
         SqliteDatabase::sanitize_urls(self)
     }
-    fn update_asset_documentation(&self, id: i64, is_documented: bool) -> Result<()> {
-        SqliteDatabase::update_asset_documentation(self, id, is_documented)
+    fn update_asset_documentation(
+        &self,
+        id: i64,
+        is_documented: bool,
+        actor: Option<&str>,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        SqliteDatabase::update_asset_documentation(self, id, is_documented, actor, reason)
+    }
+    fn batch_mark_shadow_apis(
+        &self,
+        asset_ids: &[i64],
+        actor: Option<&str>,
+        reason: Option<&str>,
+    ) -> Result<usize> {
+        SqliteDatabase::batch_mark_shadow_apis(self, asset_ids, actor, reason)
     }
-    fn batch_mark_shadow_apis(&self, asset_ids: &[i64]) -> Result<usize> {
-        SqliteDatabase::batch_mark_shadow_apis(self, asset_ids)
+    fn documentation_history(&self, asset_id: i64) -> Result<Vec<DocumentationHistoryEntry>> {
+        SqliteDatabase::documentation_history(self, asset_id)
     }
 
     fn add_folder(&self, name: &str, parent_id: Option<i64>) -> Result<i64> {
@@ -0,0 +1,43 @@
+use crate::db::SqliteDatabase;
+use tokio::sync::broadcast;
+
+/// The write that touched a row, mirroring `rusqlite::hooks::Action`'s
+/// insert/update/delete without exposing that type (and its `SQLITE_*`
+/// naming) to subscribers outside the `db` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeOp {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// One row-level change, emitted only after the transaction that made it
+/// commits -- a subscriber never sees a change from a transaction that
+/// later rolled back.
+#[derive(Debug, Clone)]
+pub struct DbChange {
+    pub table: String,
+    pub op: ChangeOp,
+    pub rowid: i64,
+}
+
+/// Broadcast channel capacity: enough that a burst of scan writes between
+/// two UI polls doesn't drop events, without holding an unbounded backlog
+/// for a subscriber that's fallen behind (it gets `Lagged` instead).
+pub(super) const CHANGE_CHANNEL_CAPACITY: usize = 1024;
+
+/// Tables a UI actually wants to hear about. `scan_history` and the rest
+/// churn constantly during a scan and would just add noise to a "refresh
+/// the view" signal.
+pub(super) const WATCHED_TABLES: &[&str] = &["assets", "findings", "import_operations"];
+
+impl SqliteDatabase {
+    /// Subscribes to live row-level changes on `assets`, `findings`, and
+    /// `import_operations`, driven by SQLite's update/commit hooks instead
+    /// of the `get_stale_assets` polling loop. Each committed write is
+    /// delivered once; a lagging subscriber sees
+    /// `RecvError::Lagged` rather than blocking writers.
+    pub fn subscribe(&self) -> broadcast::Receiver<DbChange> {
+        self.change_tx.subscribe()
+    }
+}
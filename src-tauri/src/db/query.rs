@@ -0,0 +1,259 @@
+use crate::db::{Asset, Badge, SqliteDatabase};
+use crate::error::Result;
+
+/// Fluent filter builder for the asset store, so a consumer composes
+/// `source`/`method`/`recursive`/depth-range/`host` predicates once and lets
+/// `SqliteDatabase::query_assets` turn them into a single `WHERE` clause,
+/// instead of pulling every row via `get_assets` and filtering in Rust.
+#[derive(Debug, Default, Clone)]
+pub struct Query {
+    source: Option<String>,
+    method: Option<String>,
+    recursive: Option<bool>,
+    min_depth: Option<i32>,
+    max_depth: Option<i32>,
+    host: Option<String>,
+}
+
+impl Query {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn source(mut self, source: &str) -> Self {
+        self.source = Some(source.to_string());
+        self
+    }
+
+    pub fn method(mut self, method: &str) -> Self {
+        self.method = Some(method.to_string());
+        self
+    }
+
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = Some(recursive);
+        self
+    }
+
+    pub fn min_depth(mut self, depth: i32) -> Self {
+        self.min_depth = Some(depth);
+        self
+    }
+
+    pub fn max_depth(mut self, depth: i32) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Restrict to assets whose URL host is `host` (or a subdomain of it).
+    pub fn host(mut self, host: &str) -> Self {
+        self.host = Some(host.to_string());
+        self
+    }
+}
+
+/// Scope concept distinguishing "all discovered" assets from a narrower
+/// "in-scope target" set -- hosts and path prefixes the operator has
+/// explicitly allowed -- so later auditing steps (reporting, exploitation)
+/// can restrict themselves to authorized targets the way `cargo-audit`
+/// restricts itself to public package scope.
+#[derive(Debug, Default, Clone)]
+pub struct Scope {
+    allowed_hosts: Vec<String>,
+    allowed_path_prefixes: Vec<String>,
+}
+
+impl Scope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn allow_host(mut self, host: &str) -> Self {
+        self.allowed_hosts.push(host.to_lowercase());
+        self
+    }
+
+    pub fn allow_path(mut self, path_prefix: &str) -> Self {
+        self.allowed_path_prefixes.push(path_prefix.to_string());
+        self
+    }
+
+    /// Whether `url` falls under an allowed host and, if any path prefixes
+    /// were configured, an allowed path too. An empty `Scope` (no hosts or
+    /// paths configured) allows everything -- "all discovered" is the
+    /// default, unscoped `Scope`.
+    pub fn contains(&self, url: &str) -> bool {
+        let Ok(parsed) = url::Url::parse(url) else {
+            return false;
+        };
+
+        let host_ok = self.allowed_hosts.is_empty()
+            || parsed
+                .host_str()
+                .map(|h| {
+                    self.allowed_hosts
+                        .iter()
+                        .any(|allowed| h == allowed || h.ends_with(&format!(".{}", allowed)))
+                })
+                .unwrap_or(false);
+
+        let path_ok = self.allowed_path_prefixes.is_empty()
+            || self
+                .allowed_path_prefixes
+                .iter()
+                .any(|prefix| parsed.path().starts_with(prefix.as_str()));
+
+        host_ok && path_ok
+    }
+}
+
+impl SqliteDatabase {
+    /// Filter the asset store by `query`'s predicates, building a single
+    /// SQL `WHERE` clause instead of filtering in Rust after a full-table
+    /// `get_assets`.
+    pub fn query_assets(&self, query: &Query) -> Result<Vec<Asset>> {
+        let conn = self.get_conn()?;
+
+        let mut clauses: Vec<String> = Vec::new();
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(source) = &query.source {
+            clauses.push("source = ?".to_string());
+            params.push(Box::new(source.clone()));
+        }
+        if let Some(method) = &query.method {
+            clauses.push("method = ?".to_string());
+            params.push(Box::new(method.clone()));
+        }
+        if let Some(recursive) = query.recursive {
+            clauses.push("recursive = ?".to_string());
+            params.push(Box::new(recursive));
+        }
+        if let Some(min_depth) = query.min_depth {
+            clauses.push("depth >= ?".to_string());
+            params.push(Box::new(min_depth));
+        }
+        if let Some(max_depth) = query.max_depth {
+            clauses.push("depth <= ?".to_string());
+            params.push(Box::new(max_depth));
+        }
+        if let Some(host) = &query.host {
+            // Host match is a URL-prefix LIKE, not a real scope check -- fine
+            // for filtering convenience, not a security boundary.
+            clauses.push("(url LIKE ? OR url LIKE ?)".to_string());
+            params.push(Box::new(format!("http://{}/%", host)));
+            params.push(Box::new(format!("https://{}/%", host)));
+        }
+
+        let where_clause = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+
+        let sql = format!(
+            "SELECT id, url, method, status, status_code, risk_score, findings, folder_id, response_headers, response_body, request_headers, request_body, created_at, updated_at, notes, triage_status, is_documented, source, recursive, is_workbench, depth, content_hash FROM assets {} ORDER BY id DESC",
+            where_clause
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+
+        let asset_iter = stmt.query_map(param_refs.as_slice(), |row| {
+            let findings_json: String = row.get(6)?;
+            let findings: Vec<Badge> = serde_json::from_str(&findings_json).unwrap_or_default();
+            Ok(Asset {
+                id: row.get(0)?,
+                url: row.get(1)?,
+                method: row.get(2)?,
+                status: row.get(3)?,
+                status_code: row.get(4)?,
+                risk_score: row.get(5)?,
+                findings,
+                folder_id: row.get(7)?,
+                response_headers: row.get(8)?,
+                response_body: row.get(9)?,
+                request_headers: row.get(10)?,
+                request_body: row.get(11)?,
+                created_at: row.get(12)?,
+                updated_at: row.get(13)?,
+                notes: row.get(14).unwrap_or_else(|_| "".to_string()),
+                triage_status: row.get(15).unwrap_or_else(|_| "Unreviewed".to_string()),
+                is_documented: row.get(16).unwrap_or(true),
+                source: row.get(17).unwrap_or_else(|_| "User".to_string()),
+                recursive: row.get(18).unwrap_or(false),
+                is_workbench: row.get(19).unwrap_or(false),
+                depth: row.get(20).unwrap_or(0),
+                content_hash: row.get(21).unwrap_or_else(|_| "".to_string()),
+            })
+        })?;
+
+        let mut assets = Vec::new();
+        for asset in asset_iter {
+            assets.push(asset?);
+        }
+        Ok(assets)
+    }
+
+    /// `query_assets` narrowed to `scope`'s allowed hosts/paths.
+    pub fn query_assets_in_scope(&self, query: &Query, scope: &Scope) -> Result<Vec<Asset>> {
+        Ok(self
+            .query_assets(query)?
+            .into_iter()
+            .filter(|asset| scope.contains(&asset.url))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_db() -> SqliteDatabase {
+        SqliteDatabase::new(":memory:").expect("Failed to create in-memory db")
+    }
+
+    #[test]
+    fn test_query_filters_by_source_and_method() {
+        let db = setup_db();
+        db.add_asset("http://api.com/a", "Discovery", Some("GET"), false, false, 0)
+            .unwrap();
+        db.add_asset("http://api.com/b", "Discovery", Some("POST"), false, false, 0)
+            .unwrap();
+        db.add_asset("http://api.com/c", "Import", Some("GET"), false, false, 0)
+            .unwrap();
+
+        let results = db
+            .query_assets(&Query::new().source("Discovery").method("POST"))
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].url, "http://api.com/b");
+    }
+
+    #[test]
+    fn test_query_filters_by_depth_range() {
+        let db = setup_db();
+        db.add_asset("http://api.com/a", "Discovery", Some("GET"), false, false, 0)
+            .unwrap();
+        db.add_asset("http://api.com/b", "Discovery", Some("GET"), false, false, 2)
+            .unwrap();
+
+        let results = db.query_assets(&Query::new().max_depth(1)).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].depth, 0);
+    }
+
+    #[test]
+    fn test_scope_allows_subdomains() {
+        let scope = Scope::new().allow_host("example.com");
+        assert!(scope.contains("https://example.com/foo"));
+        assert!(scope.contains("https://api.example.com/foo"));
+        assert!(!scope.contains("https://evil.com/foo"));
+    }
+
+    #[test]
+    fn test_empty_scope_allows_everything() {
+        let scope = Scope::new();
+        assert!(scope.contains("https://anything.example/path"));
+    }
+}
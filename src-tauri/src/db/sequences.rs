@@ -3,7 +3,7 @@ use crate::error::Result;
 
 impl SqliteDatabase {
     pub fn create_sequence(&self, name: &str, context_summary: Option<String>) -> Result<String> {
-        let conn = self.conn.lock().map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         let id = uuid::Uuid::new_v4().to_string();
         
@@ -16,7 +16,7 @@ impl SqliteDatabase {
     }
 
     pub fn add_step_to_sequence(&self, step: &crate::core::data::SequenceStep) -> Result<()> {
-        let conn = self.conn.lock().map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         conn.execute(
             "INSERT INTO sequence_steps (sequence_id, asset_id, method, url, status_code, request_body, response_body, request_headers, response_headers, captures)
@@ -39,7 +39,7 @@ impl SqliteDatabase {
     }
 
     pub fn get_sequence(&self, id: &str) -> Result<crate::core::data::RequestSequence> {
-        let conn = self.conn.lock().map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         let entry: (String, Option<String>, String, Option<String>) = conn.query_row(
             "SELECT id, name, created_at, context_summary FROM sequences WHERE id = ?1",
@@ -93,7 +93,7 @@ impl SqliteDatabase {
     }
 
     pub fn list_sequences(&self) -> Result<Vec<crate::core::data::RequestSequence>> {
-        let conn = self.conn.lock().map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
 
         let mut stmt = conn.prepare("SELECT id, name, created_at, context_summary FROM sequences ORDER BY created_at DESC")?;
         let seq_iter = stmt.query_map([], |row| {
@@ -113,7 +113,7 @@ impl SqliteDatabase {
         Ok(sequences)
     }
     pub fn delete_step_from_sequence(&self, step_id: i64) -> Result<()> {
-        let conn = self.conn.lock().map_err(|e| crate::error::Error::Internal(e.to_string()))?;
+        let conn = self.get_conn()?;
         conn.execute("DELETE FROM sequence_steps WHERE id = ?1", [step_id])?;
         Ok(())
     }
@@ -0,0 +1,202 @@
+use crate::db::error::recover_poison;
+use crate::db::{Asset, Badge, Folder, SqliteDatabase};
+use crate::error::{Error, Result};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Mutex;
+
+/// Narrow, command-facing abstraction over the asset/folder/scan-result
+/// operations the Tauri command layer actually calls. A deliberate subset of
+/// [`crate::db::traits::DatabaseTrait`] rather than a replacement for it --
+/// imports, sequences, settings, etc. still go through `DatabaseTrait`
+/// directly -- but object-safe and small enough that commands can run over
+/// SQLite, an in-memory map, or (eventually) a remote store without the call
+/// sites changing.
+pub trait Storage: Send + Sync {
+    fn get_assets(&self) -> Result<Vec<Asset>>;
+    fn add_folder(&self, name: &str, parent_id: Option<i64>) -> Result<i64>;
+    fn move_assets_to_folder(&self, asset_ids: Vec<i64>, folder_id: i64) -> Result<()>;
+    fn update_asset_documentation(
+        &self,
+        id: i64,
+        is_documented: bool,
+        actor: Option<&str>,
+        reason: Option<&str>,
+    ) -> Result<()>;
+    #[allow(clippy::too_many_arguments)]
+    fn update_scan_result(
+        &self,
+        id: i64,
+        status: &str,
+        status_code: i32,
+        risk_score: i32,
+        findings: Vec<Badge>,
+        resp_headers: &str,
+        resp_body: &str,
+        req_headers: &str,
+        req_body: &str,
+        content_hash: &str,
+    ) -> Result<()>;
+}
+
+impl Storage for SqliteDatabase {
+    fn get_assets(&self) -> Result<Vec<Asset>> {
+        SqliteDatabase::get_assets(self)
+    }
+    fn add_folder(&self, name: &str, parent_id: Option<i64>) -> Result<i64> {
+        SqliteDatabase::add_folder(self, name, parent_id)
+    }
+    fn move_assets_to_folder(&self, asset_ids: Vec<i64>, folder_id: i64) -> Result<()> {
+        SqliteDatabase::move_assets_to_folder(self, asset_ids, folder_id)
+    }
+    fn update_asset_documentation(
+        &self,
+        id: i64,
+        is_documented: bool,
+        actor: Option<&str>,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        SqliteDatabase::update_asset_documentation(self, id, is_documented, actor, reason)
+    }
+    fn update_scan_result(
+        &self,
+        id: i64,
+        status: &str,
+        status_code: i32,
+        risk_score: i32,
+        findings: Vec<Badge>,
+        resp_headers: &str,
+        resp_body: &str,
+        req_headers: &str,
+        req_body: &str,
+        content_hash: &str,
+    ) -> Result<()> {
+        SqliteDatabase::update_scan_result(
+            self,
+            id,
+            status,
+            status_code,
+            risk_score,
+            findings,
+            resp_headers,
+            resp_body,
+            req_headers,
+            req_body,
+            content_hash,
+        )
+    }
+}
+
+/// Pure in-memory `Storage`, for tests and ephemeral sessions where no file
+/// should be touched. IDs are assigned the same way `SqliteDatabase` hands
+/// out rowids: a monotonically increasing counter, never reused even after
+/// deletes (which this backend doesn't implement any of yet).
+#[derive(Default)]
+pub struct InMemoryStorage {
+    assets: Mutex<Vec<Asset>>,
+    folders: Mutex<Vec<Folder>>,
+    next_folder_id: AtomicI64,
+}
+
+impl InMemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for InMemoryStorage {
+    fn get_assets(&self) -> Result<Vec<Asset>> {
+        Ok(self.assets.lock().unwrap_or_else(recover_poison).clone())
+    }
+
+    fn add_folder(&self, name: &str, parent_id: Option<i64>) -> Result<i64> {
+        let id = self.next_folder_id.fetch_add(1, Ordering::SeqCst) + 1;
+        self.folders
+            .lock()
+            .unwrap_or_else(recover_poison)
+            .push(Folder {
+                id,
+                name: name.to_string(),
+                parent_id,
+                created_at: String::new(),
+            });
+        Ok(id)
+    }
+
+    fn move_assets_to_folder(&self, asset_ids: Vec<i64>, folder_id: i64) -> Result<()> {
+        let mut assets = self.assets.lock().unwrap_or_else(recover_poison);
+        for asset in assets.iter_mut() {
+            if asset_ids.contains(&asset.id) {
+                asset.folder_id = folder_id;
+            }
+        }
+        Ok(())
+    }
+
+    fn update_asset_documentation(
+        &self,
+        id: i64,
+        is_documented: bool,
+        _actor: Option<&str>,
+        _reason: Option<&str>,
+    ) -> Result<()> {
+        let mut assets = self.assets.lock().unwrap_or_else(recover_poison);
+        let asset = assets
+            .iter_mut()
+            .find(|a| a.id == id)
+            .ok_or_else(|| Error::NotFound(format!("asset {}", id)))?;
+        asset.is_documented = is_documented;
+        Ok(())
+    }
+
+    fn update_scan_result(
+        &self,
+        id: i64,
+        status: &str,
+        status_code: i32,
+        risk_score: i32,
+        findings: Vec<Badge>,
+        resp_headers: &str,
+        resp_body: &str,
+        req_headers: &str,
+        req_body: &str,
+        content_hash: &str,
+    ) -> Result<()> {
+        let mut assets = self.assets.lock().unwrap_or_else(recover_poison);
+        let asset = assets
+            .iter_mut()
+            .find(|a| a.id == id)
+            .ok_or_else(|| Error::NotFound(format!("asset {}", id)))?;
+        asset.status = status.to_string();
+        asset.status_code = status_code;
+        asset.risk_score = risk_score;
+        asset.findings = findings;
+        asset.response_headers = resp_headers.to_string();
+        asset.response_body = resp_body.to_string();
+        asset.request_headers = req_headers.to_string();
+        asset.request_body = req_body.to_string();
+        asset.content_hash = content_hash.to_string();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_folder_then_get_assets_round_trips() {
+        let storage = InMemoryStorage::new();
+        let folder_id = storage.add_folder("root", None).unwrap();
+        assert_eq!(folder_id, 1);
+        assert!(storage.get_assets().unwrap().is_empty());
+    }
+
+    #[test]
+    fn update_asset_documentation_on_missing_asset_is_not_found() {
+        let storage = InMemoryStorage::new();
+        let err = storage
+            .update_asset_documentation(404, true, None, None)
+            .unwrap_err();
+        assert!(matches!(err, Error::NotFound(_)));
+    }
+}
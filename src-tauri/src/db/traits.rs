@@ -26,6 +26,7 @@ pub trait DatabaseTrait: Send + Sync {
         resp_body: &str,
         req_headers: &str,
         req_body: &str,
+        content_hash: &str,
     ) -> Result<()>;
     fn delete_asset(&self, id: i64) -> Result<()>;
     fn get_asset_history(&self, asset_id: i64) -> Result<Vec<ScanHistoryEntry>>;
@@ -46,9 +47,21 @@ pub trait DatabaseTrait: Send + Sync {
     fn clear_all_assets(&self) -> Result<()>;
     fn purge_recursive_assets(&self) -> Result<usize>;
     fn sanitize_urls(&self) -> Result<usize>;
-    fn update_asset_documentation(&self, id: i64, is_documented: bool) -> Result<()>;
+    fn update_asset_documentation(
+        &self,
+        id: i64,
+        is_documented: bool,
+        actor: Option<&str>,
+        reason: Option<&str>,
+    ) -> Result<()>;
     fn update_asset_workbench_status(&self, id: i64, is_workbench: bool) -> Result<()>;
-    fn batch_mark_shadow_apis(&self, asset_ids: &[i64]) -> Result<usize>;
+    fn batch_mark_shadow_apis(
+        &self,
+        asset_ids: &[i64],
+        actor: Option<&str>,
+        reason: Option<&str>,
+    ) -> Result<usize>;
+    fn documentation_history(&self, asset_id: i64) -> Result<Vec<crate::db::DocumentationHistoryEntry>>;
 
     // Folders
     fn add_folder(&self, name: &str, parent_id: Option<i64>) -> Result<i64>;
@@ -0,0 +1,244 @@
+use crate::db::{Badge, Severity};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+
+/// Env var pointing at a serialized [`KnownBadFilter`] cascade on disk. When
+/// unset (or unreadable), path matching is simply skipped -- same
+/// fail-open-to-"not configured" posture as `RuleSet::load`.
+const KNOWN_BAD_CASCADE_ENV_VAR: &str = "APEX_KNOWN_BAD_CASCADE";
+
+/// False-positive rate each cascade level's Bloom filter is sized for.
+/// Lower values cost more bits per element but shrink every subsequent
+/// correction level, so the whole cascade converges in fewer levels.
+const TARGET_FP_RATE: f64 = 0.01;
+
+/// A fixed-size bitset Bloom filter using double hashing (Kirsch/Mitzenmacher)
+/// to derive its `k` probe positions from a single 256-bit digest instead of
+/// computing `k` independent hashes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BloomFilter {
+    bits: Vec<u64>,
+    num_bits: usize,
+    num_hashes: usize,
+}
+
+impl BloomFilter {
+    /// Size a filter for `n` elements at [`TARGET_FP_RATE`], using the
+    /// standard `m = -n*ln(p)/ln(2)^2`, `k = (m/n)*ln(2)` formulas.
+    fn with_capacity(n: usize) -> Self {
+        let n = n.max(1) as f64;
+        let num_bits = (-(n * TARGET_FP_RATE.ln()) / std::f64::consts::LN_2.powi(2)).ceil() as usize;
+        let num_bits = num_bits.max(64);
+        let num_hashes = ((num_bits as f64 / n) * std::f64::consts::LN_2).round().max(1.0) as usize;
+        Self {
+            bits: vec![0u64; num_bits.div_ceil(64)],
+            num_bits,
+            num_hashes,
+        }
+    }
+
+    fn build(digests: &[[u8; 32]]) -> Self {
+        let mut filter = Self::with_capacity(digests.len());
+        for digest in digests {
+            filter.insert(digest);
+        }
+        filter
+    }
+
+    /// Derive this digest's `num_hashes` bit positions as `h1 + i*h2 mod m`,
+    /// the standard substitute for `k` independent hash functions.
+    fn positions(&self, digest: &[u8; 32]) -> impl Iterator<Item = usize> + '_ {
+        let h1 = u64::from_le_bytes(digest[0..8].try_into().unwrap());
+        let h2 = u64::from_le_bytes(digest[8..16].try_into().unwrap());
+        (0..self.num_hashes).map(move |i| {
+            (h1.wrapping_add((i as u64).wrapping_mul(h2)) % self.num_bits as u64) as usize
+        })
+    }
+
+    fn insert(&mut self, digest: &[u8; 32]) {
+        for pos in self.positions(digest).collect::<Vec<_>>() {
+            self.bits[pos / 64] |= 1 << (pos % 64);
+        }
+    }
+
+    fn contains(&self, digest: &[u8; 32]) -> bool {
+        self.positions(digest).all(|pos| self.bits[pos / 64] & (1 << (pos % 64)) != 0)
+    }
+}
+
+/// Multi-level Bloom filter cascade giving exact membership (zero false
+/// positives) for a known-bad set `R`, the structure browsers use for
+/// certificate revocation lists. Built from `R` plus a set of benign decoy
+/// values: level 0 is a filter over `R`; its false positives against the
+/// decoys seed level 1; level 1's false positives against `R` seed level 2;
+/// and so on until a level produces no false positives. This ships a
+/// multi-megabyte blocklist in kilobytes, at the cost of needing both sides
+/// of the set at build time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownBadFilter {
+    levels: Vec<BloomFilter>,
+}
+
+impl KnownBadFilter {
+    /// Build a cascade from the known-bad set `bad` and a benign decoy set
+    /// `decoys` disjoint from it. Hashes are raw SHA-256 digests, not path
+    /// strings -- callers typically pass [`Self::hash_path`] output.
+    pub fn build(bad: &[[u8; 32]], decoys: &[[u8; 32]]) -> Self {
+        let mut levels: Vec<BloomFilter> = Vec::new();
+        let mut members: Vec<[u8; 32]> = bad.to_vec();
+
+        loop {
+            if members.is_empty() {
+                break;
+            }
+            let filter = BloomFilter::build(&members);
+            let level_index = levels.len();
+            // Even levels are built over (subsets of) `bad`, so the set to
+            // probe for false positives is `decoys`, and vice versa.
+            let probe_set = if level_index % 2 == 0 { decoys } else { bad };
+            let false_positives: Vec<[u8; 32]> = probe_set
+                .iter()
+                .filter(|d| filter.contains(d))
+                .copied()
+                .collect();
+            levels.push(filter);
+            if false_positives.is_empty() {
+                break;
+            }
+            members = false_positives;
+        }
+
+        Self { levels }
+    }
+
+    /// Hash a request path (or any other signature string) the same way
+    /// `build`'s inputs must be hashed, so a live query digest matches the
+    /// cascade it was built against.
+    pub fn hash_path(path: &str) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(path.as_bytes());
+        hasher.finalize().into()
+    }
+
+    /// Walk the cascade for `digest`: at the first level that does NOT
+    /// contain it, the element is "in R" iff that level's index is odd
+    /// (odd levels are built over decoy false positives, so excluding one
+    /// means this wasn't actually a genuine benign collision). If every
+    /// level contains it, the cascade was built with one more potential
+    /// level of zero-FP headroom than it needed, so the parity of the
+    /// level count itself settles it.
+    pub fn contains(&self, digest: &[u8; 32]) -> bool {
+        for (level_index, filter) in self.levels.iter().enumerate() {
+            if !filter.contains(digest) {
+                return level_index % 2 == 1;
+            }
+        }
+        self.levels.len() % 2 == 1
+    }
+
+    pub fn contains_path(&self, path: &str) -> bool {
+        self.contains(&Self::hash_path(path))
+    }
+
+    /// Load a cascade serialized by [`Self::build`] (via `serde_json`) from
+    /// `path`. Returns `None` on any read/parse failure so a missing or
+    /// malformed file simply disables known-bad matching rather than
+    /// blocking startup.
+    pub fn load(path: &std::path::Path) -> Option<Self> {
+        let text = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&text).ok()
+    }
+}
+
+/// The cascade loaded from [`KNOWN_BAD_CASCADE_ENV_VAR`], if any, compiled
+/// once and shared across every scan the way [`crate::rules::RuleSet`] is.
+fn loaded_cascade() -> Option<&'static KnownBadFilter> {
+    static CASCADE: OnceLock<Option<KnownBadFilter>> = OnceLock::new();
+    CASCADE
+        .get_or_init(|| {
+            let path = std::env::var(KNOWN_BAD_CASCADE_ENV_VAR).ok()?;
+            KnownBadFilter::load(std::path::Path::new(&path))
+        })
+        .as_ref()
+}
+
+/// Check `url`'s path against the loaded known-bad cascade (if any),
+/// returning a high-risk badge on a match. Used by `scanner::scan_url` to
+/// flag assets matching a curated blocklist without shipping it in full.
+pub fn check_known_bad_path(url: &str) -> Option<Badge> {
+    let cascade = loaded_cascade()?;
+    let path = url::Url::parse(url).ok()?.path().to_string();
+    if cascade.contains_path(&path) {
+        Some(Badge::new(
+            "🚫",
+            "KnownBad",
+            Severity::High,
+            "Path matches a known-vulnerable or known-malicious endpoint signature.",
+        ))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hashes(paths: &[&str]) -> Vec<[u8; 32]> {
+        paths.iter().map(|p| KnownBadFilter::hash_path(p)).collect()
+    }
+
+    #[test]
+    fn test_cascade_has_zero_false_positives_against_its_build_time_decoys() {
+        let bad: Vec<String> = (0..200).map(|i| format!("/api/v1/bad-{}", i)).collect();
+        let decoys: Vec<String> = (0..2000).map(|i| format!("/api/v1/safe-{}", i)).collect();
+
+        let bad_hashes = hashes(&bad.iter().map(String::as_str).collect::<Vec<_>>());
+        let decoy_hashes = hashes(&decoys.iter().map(String::as_str).collect::<Vec<_>>());
+
+        let cascade = KnownBadFilter::build(&bad_hashes, &decoy_hashes);
+
+        for h in &bad_hashes {
+            assert!(cascade.contains(h));
+        }
+        for h in &decoy_hashes {
+            assert!(!cascade.contains(h));
+        }
+    }
+
+    #[test]
+    fn test_cascade_builds_multiple_levels_when_level_zero_has_false_positives() {
+        // A tiny, deliberately under-sized `bad` set relative to the decoy
+        // pool all but guarantees level 0 alone won't be false-positive-free.
+        let bad_hashes = hashes(&["/admin/debug"]);
+        let decoys: Vec<String> = (0..5000).map(|i| format!("/page-{}", i)).collect();
+        let decoy_hashes = hashes(&decoys.iter().map(String::as_str).collect::<Vec<_>>());
+
+        let cascade = KnownBadFilter::build(&bad_hashes, &decoy_hashes);
+
+        assert!(cascade.contains(&bad_hashes[0]));
+        for h in &decoy_hashes {
+            assert!(!cascade.contains(h));
+        }
+    }
+
+    #[test]
+    fn test_check_known_bad_path_without_loaded_cascade_is_none() {
+        std::env::remove_var(KNOWN_BAD_CASCADE_ENV_VAR);
+        assert!(check_known_bad_path("https://api.example.com/anything").is_none());
+    }
+
+    #[test]
+    fn test_serde_roundtrip_preserves_membership() {
+        let bad_hashes = hashes(&["/wp-admin/setup-config.php"]);
+        let decoy_hashes = hashes(&["/", "/index.html", "/about"]);
+        let cascade = KnownBadFilter::build(&bad_hashes, &decoy_hashes);
+
+        let json = serde_json::to_string(&cascade).unwrap();
+        let restored: KnownBadFilter = serde_json::from_str(&json).unwrap();
+
+        assert!(restored.contains(&bad_hashes[0]));
+        assert!(!restored.contains(&decoy_hashes[0]));
+    }
+}
@@ -1,185 +1,44 @@
-use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 
 mod data;
 pub use data::{Badge, Severity};
 
+mod error;
+
 mod db;
-use db::{Asset, Database, Folder};
+use db::{SqliteDatabase, Storage};
 
 mod detectors;
-use detectors::classify_vulnerability;
 
 mod scanner;
-use scanner::scan_url;
-
-mod ui;
-
-mod ai;
+pub(crate) use scanner::scan_url;
 
-mod openapi_parser;
-pub use openapi_parser::*;
+mod metrics;
 
-// ============================================
-// SHADOW API DETECTION DATA STRUCTURES
-// ============================================
+mod benchmark;
 
-/// Report generated after comparing assets against an OpenAPI spec
-#[derive(Serialize, Clone)]
-pub struct ShadowApiReport {
-    /// Title from the OpenAPI spec
-    pub spec_title: String,
-    /// Version from the OpenAPI spec
-    pub spec_version: String,
-    /// Total number of endpoints in the spec
-    pub total_endpoints: usize,
-    /// Total number of assets checked
-    pub total_assets_checked: usize,
-    /// Number of assets that match documented endpoints
-    pub documented_count: usize,
-    /// Number of Shadow APIs detected
-    pub shadow_api_count: usize,
-    /// List of Shadow API assets
-    pub shadow_apis: Vec<ShadowApiAsset>,
-}
+mod admin_api;
 
-/// A single Shadow API asset
-#[derive(Serialize, Clone)]
-pub struct ShadowApiAsset {
-    /// Asset ID
-    pub id: i64,
-    /// Full URL of the asset
-    pub url: String,
-    /// HTTP method
-    pub method: String,
-    /// Risk level (always "Medium" for Shadow APIs)
-    pub risk_level: String,
-}
-
-// ============================================
-// DEBUG LOGGING INFRASTRUCTURE
-// ============================================
-
-/// Log levels for debug console
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum LogLevel {
-    Info,
-    Warn,
-    Error,
-    Success,
-}
-
-/// Debug log entry sent to frontend
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DebugLogEntry {
-    pub id: String,
-    pub timestamp: String,
-    pub level: LogLevel,
-    pub source: String,
-    pub message: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub details: Option<serde_json::Value>,
-}
+mod ui;
 
-/// Generate a unique log ID
-fn generate_log_id() -> String {
-    format!(
-        "log_{}_{}",
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis())
-            .unwrap_or(0),
-        rand::random::<u32>()
-    )
-}
+mod ai;
 
-/// Emit a debug log event to the frontend
-pub fn emit_log(
-    app: &AppHandle,
-    level: LogLevel,
-    source: &str,
-    message: &str,
-    details: Option<serde_json::Value>,
-) {
-    let entry = DebugLogEntry {
-        id: generate_log_id(),
-        timestamp: chrono::Utc::now().to_rfc3339(),
-        level,
-        source: source.to_string(),
-        message: message.to_string(),
-        details,
-    };
+mod core;
 
-    // Also print to backend console for debugging
-    match entry.level {
-        LogLevel::Info => println!("[INFO] [{}] {}", source, message),
-        LogLevel::Warn => eprintln!("[WARN] [{}] {}", source, message),
-        LogLevel::Error => eprintln!("[ERROR] [{}] {}", source, message),
-        LogLevel::Success => println!("[SUCCESS] [{}] {}", source, message),
-    }
+mod commands;
 
-    if let Err(e) = app.emit("debug-log", &entry) {
-        eprintln!("Failed to emit debug log: {}", e);
-    }
-}
+mod services;
 
-/// Tauri command for frontend to emit debug logs
-#[tauri::command]
-fn log_debug(
-    app: AppHandle,
-    level: String,
-    source: String,
-    message: String,
-    details: Option<serde_json::Value>,
-) -> Result<(), String> {
-    let log_level = match level.to_lowercase().as_str() {
-        "info" => LogLevel::Info,
-        "warn" => LogLevel::Warn,
-        "error" => LogLevel::Error,
-        "success" => LogLevel::Success,
-        _ => LogLevel::Info,
-    };
+mod utils;
 
-    emit_log(&app, log_level, &source, &message, details);
-    Ok(())
-}
+mod openapi_parser;
+pub use openapi_parser::*;
 
-// ============================================
-// OPENAPI PARSING & SHADOW API DETECTION
-// ============================================
+mod rules;
 
-/// Extract the path portion from a full URL
-/// Handles URLs like `https://api.example.com/users/123?foo=bar` → `/users/123`
-fn extract_path_from_url(url: &str) -> String {
-    // Try to parse as a full URL
-    if let Ok(parsed) = url::Url::parse(url) {
-        let path = parsed.path();
-        return path.to_string();
-    }
-    
-    // Fallback: manual extraction for malformed URLs
-    // Remove protocol if present
-    let without_protocol = url
-        .strip_prefix("https://")
-        .or_else(|| url.strip_prefix("http://"))
-        .unwrap_or(url);
-    
-    // Find the start of the path (after the first '/')
-    if let Some(slash_pos) = without_protocol.find('/') {
-        let path_and_query = &without_protocol[slash_pos..];
-        // Remove query string if present
-        if let Some(query_pos) = path_and_query.find('?') {
-            path_and_query[..query_pos].to_string()
-        } else {
-            path_and_query.to_string()
-        }
-    } else {
-        // No path found, return root
-        "/".to_string()
-    }
-}
+mod known_bad;
 
 /// Parse an OpenAPI specification (auto-detects JSON/YAML)
 /// Returns the parsed OpenApiSpec with all documented endpoints
@@ -188,344 +47,6 @@ fn parse_openapi_spec(content: String) -> Result<OpenApiSpec, String> {
     parse_openapi_auto(&content).map_err(|e| e.to_string())
 }
 
-/// Import an OpenAPI spec and detect Shadow APIs
-/// Compares all assets in the database against the spec
-/// Marks assets not in the spec as Shadow APIs (sets is_documented = false)
-#[tauri::command]
-async fn import_openapi_spec_and_detect_shadow_apis(
-    app: AppHandle,
-    content: String,
-) -> Result<ShadowApiReport, String> {
-    // Parse the OpenAPI spec
-    let spec = parse_openapi_auto(&content).map_err(|e| e.to_string())?;
-    
-    println!(
-        "OpenAPI Spec parsed: {} v{} with {} endpoints",
-        spec.title,
-        spec.version,
-        spec.endpoints.len()
-    );
-    
-    // Get all assets from the database
-    let db = app.state::<Database>();
-    let assets = db.get_assets().map_err(|e| e.to_string())?;
-    
-    let total_assets = assets.len();
-    let mut shadow_api_ids = Vec::new();
-    let mut shadow_apis = Vec::new();
-    let mut documented_count = 0;
-    
-    for asset in &assets {
-        // Extract the path from the asset URL
-        let path = extract_path_from_url(&asset.url);
-        
-        // Check if this asset matches any documented endpoint
-        let is_documented = spec.matches_endpoint(&path, &asset.method);
-        
-        if is_documented {
-            documented_count += 1;
-        } else {
-            // This is a Shadow API
-            shadow_api_ids.push(asset.id);
-            shadow_apis.push(ShadowApiAsset {
-                id: asset.id,
-                url: asset.url.clone(),
-                method: asset.method.clone(),
-                risk_level: "Medium".to_string(),
-            });
-        }
-    }
-    
-    // Mark Shadow APIs in the database
-    if !shadow_api_ids.is_empty() {
-        db.batch_mark_shadow_apis(&shadow_api_ids)
-            .map_err(|e| e.to_string())?;
-        
-        println!(
-            "Marked {} assets as Shadow APIs",
-            shadow_api_ids.len()
-        );
-    }
-    
-    let shadow_count = shadow_apis.len();
-    
-    Ok(ShadowApiReport {
-        spec_title: spec.title,
-        spec_version: spec.version,
-        total_endpoints: spec.endpoints.len(),
-        total_assets_checked: total_assets,
-        documented_count,
-        shadow_api_count: shadow_count,
-        shadow_apis,
-    })
-}
-
-// ============================================
-// ASSET MANAGEMENT COMMANDS
-// ============================================
-
-// Command: Import Assets
-// Takes raw text, extracts URLs, saves to DB, and triggers background scan.
-#[tauri::command]
-async fn import_assets(app: AppHandle, content: String) -> Result<Vec<i64>, String> {
-    println!(
-        "Importing assets using strict line parser (content length: {})",
-        content.len()
-    );
-    let db = app.state::<Database>();
-
-    let mut ids = Vec::new();
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-
-        // Extremely aggressive splitting to isolate the URL from any CSV/metadata
-        // We take the first part when splitting by common delimiters, and trim again.
-        let raw_url = trimmed
-            .split(|c| c == ',' || c == ';' || c == '\t' || c == ' ' || c == '|')
-            .next()
-            .unwrap_or("")
-            .trim();
-
-        if raw_url.is_empty() {
-            continue;
-        }
-
-        let mut url = raw_url.to_string();
-
-        // Strict Validation: If it doesn't look like a URL or starts with http, discard or fix
-        if !url.to_lowercase().starts_with("http") && !url.contains('.') {
-            continue;
-        }
-
-        // Ensure protocol
-        if !url.starts_with("http://") && !url.starts_with("https://") {
-            url = format!("https://{}", url);
-        }
-
-        // Insert into DB and trigger immediate scan
-        match db.add_asset(&url) {
-            Ok(id) => {
-                ids.push(id);
-                let app_handle = app.clone();
-                let url_clone = url.clone();
-
-                tauri::async_runtime::spawn(async move {
-                    let db_state = app_handle.state::<Database>();
-                    let result = scan_url(&db_state.client, &url_clone, "GET").await;
-                    let _ = db_state.update_scan_result(
-                        id,
-                        &result.status,
-                        result.status_code,
-                        result.risk_score,
-                        result.findings,
-                        &result.response_headers,
-                        &result.response_body,
-                        &result.request_headers,
-                        &result.request_body,
-                    );
-                    let _ = app_handle.emit("scan-update", id);
-                });
-            }
-            Err(e) => eprintln!("Failed to add asset {}: {}", url, e),
-        }
-    }
-
-    println!(
-        "Import completed. {} assets processing immediately.",
-        ids.len()
-    );
-    Ok(ids)
-}
-
-// Command: Get All Assets
-#[tauri::command]
-fn get_assets(state: tauri::State<Database>) -> Result<Vec<Asset>, String> {
-    state.get_assets().map_err(|e| e.to_string())
-}
-
-#[tauri::command]
-async fn get_asset_history(
-    state: tauri::State<'_, Database>,
-    asset_id: i64,
-) -> Result<Vec<db::ScanHistoryEntry>, String> {
-    state.get_asset_history(asset_id).map_err(|e| e.to_string())
-}
-
-#[tauri::command]
-async fn generate_audit_report(state: tauri::State<'_, Database>) -> Result<String, String> {
-    let suspects = state.get_suspect_assets().map_err(|e| e.to_string())?;
-
-    if suspects.is_empty() {
-        return Ok("# No Findings to Report\n\nMark assets as 'Suspect' or run full scans to generate a report.".to_string());
-    }
-
-    let mut report = String::from("# APEX API Security Audit Report\n\n");
-    report.push_str(&format!(
-        "*Generated on: {}*\n\n",
-        chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
-    ));
-
-    for asset in suspects {
-        report.push_str(&format!("## 🔍 Asset: {}\n", asset.url));
-        report.push_str(&format!("**Method:** {}\n", asset.method));
-        report.push_str(&format!("**Risk Score:** {}\n", asset.risk_score));
-        report.push_str(&format!("**Triage Status:** {}\n\n", asset.triage_status));
-
-        if !asset.findings.is_empty() {
-            report.push_str("### 🚨 Findings\n");
-            for finding in asset.findings {
-                report.push_str(&format!(
-                    "- **{}**: {}\n",
-                    finding.short, finding.description
-                ));
-            }
-            report.push_str("\n");
-        }
-
-        if !asset.notes.is_empty() {
-            report.push_str("### 📝 Auditor Notes\n");
-            report.push_str(&format!("{}\n\n", asset.notes));
-        }
-
-        report.push_str("### 🔗 Request Details\n");
-        report.push_str("```http\n");
-        report.push_str(&asset.request_headers);
-        report.push_str("\n\n");
-        report.push_str(&asset.request_body);
-        report.push_str("\n```\n\n");
-
-        report.push_str("---\n\n");
-    }
-
-    Ok(report)
-}
-
-#[tauri::command]
-async fn export_to_csv_final_v5(state: tauri::State<'_, Database>) -> Result<String, String> {
-    let suspects = state.get_suspect_assets().map_err(|e| e.to_string())?;
-
-    let mut csv = String::from("URL,Method,Status,Risk Score,FindingsCount,Triage Status,Notes\n");
-    for asset in suspects {
-        let findings_count = asset.findings.len();
-        let safe_url = asset.url.replace(',', ";");
-        let safe_notes = asset.notes.replace(',', ";").replace('\n', " ");
-
-        csv.push_str(&format!(
-            "{},{},{},{},{},{},{}\n",
-            safe_url,
-            asset.method,
-            asset.status,
-            asset.risk_score,
-            findings_count,
-            asset.triage_status,
-            safe_notes
-        ));
-    }
-
-    Ok(csv)
-}
-
-// Command: Delete Asset
-#[tauri::command]
-fn delete_asset(state: tauri::State<Database>, id: i64) -> Result<(), String> {
-    state.delete_asset(id).map_err(|e| e.to_string())
-}
-
-// Command: Clear All Assets
-#[tauri::command]
-fn clear_database(state: tauri::State<Database>) -> Result<(), String> {
-    state.clear_all_assets().map_err(|e| e.to_string())
-}
-
-// Command: Sanitize URLs
-#[tauri::command]
-fn sanitize_database(state: tauri::State<Database>) -> Result<usize, String> {
-    state.sanitize_urls().map_err(|e| e.to_string())
-}
-
-// Command: Folders API
-#[tauri::command]
-fn get_folders(state: tauri::State<Database>) -> Result<Vec<Folder>, String> {
-    state.get_folders().map_err(|e| e.to_string())
-}
-
-#[tauri::command]
-fn add_folder(
-    state: tauri::State<Database>,
-    name: String,
-    parent_id: Option<i64>,
-) -> Result<i64, String> {
-    state
-        .add_folder(&name, parent_id)
-        .map_err(|e| e.to_string())
-}
-
-#[tauri::command]
-fn move_assets_to_folder(
-    state: tauri::State<Database>,
-    ids: Vec<i64>,
-    folder_id: i64,
-) -> Result<(), String> {
-    state
-        .move_assets_to_folder(ids, folder_id)
-        .map_err(|e| e.to_string())
-}
-
-#[tauri::command]
-fn delete_folder(state: tauri::State<Database>, id: i64) -> Result<(), String> {
-    state.delete_folder(id).map_err(|e| e.to_string())
-}
-
-#[tauri::command]
-fn get_vulnerability_badge(finding_code: String) -> Option<db::Badge> {
-    classify_vulnerability(&finding_code)
-}
-
-// Command: Re-Scan Asset
-#[tauri::command]
-async fn rescan_asset(app: AppHandle, id: i64) -> Result<(), String> {
-    let db = app.state::<Database>();
-    let assets = db.get_assets().map_err(|e| e.to_string())?;
-    if let Some(asset) = assets.iter().find(|a| a.id == id) {
-        let url = asset.url.clone();
-        let method = asset.method.clone();
-        let app_handle = app.clone();
-
-        tauri::async_runtime::spawn(async move {
-            let db_state = app_handle.state::<Database>();
-            let result = scan_url(&db_state.client, &url, &method).await;
-            let _ = db_state.update_scan_result(
-                id,
-                &result.status,
-                result.status_code,
-                result.risk_score,
-                result.findings,
-                &result.response_headers,
-                &result.response_body,
-                &result.request_headers,
-                &result.request_body,
-            );
-            let _ = app_handle.emit("scan-update", id);
-        });
-    }
-    Ok(())
-}
-
-#[tauri::command]
-fn update_asset_triage(
-    state: tauri::State<Database>,
-    id: i64,
-    triage_status: String,
-    notes: String,
-) -> Result<(), String> {
-    state
-        .update_asset_triage(id, &triage_status, &notes)
-        .map_err(|e| e.to_string())
-}
-
 fn start_background_monitor(app_handle: AppHandle) {
     tauri::async_runtime::spawn(async move {
         println!("Background Monitor: Initializing specialized security loop...");
@@ -533,10 +54,11 @@ fn start_background_monitor(app_handle: AppHandle) {
             // Check every 10 seconds for more responsive queue processing
             tokio::time::sleep(Duration::from_secs(10)).await;
 
-            let db = app_handle.state::<Database>();
+            let db = app_handle.state::<SqliteDatabase>();
             // Fetch up to 10 stale/pending assets
             match db.get_stale_assets(10, 5) {
                 Ok(stale_assets) => {
+                    metrics::set_queue_depth(stale_assets.len() as i64);
                     if !stale_assets.is_empty() {
                         println!(
                             "Background Monitor: Processing {} assets.",
@@ -550,8 +72,10 @@ fn start_background_monitor(app_handle: AppHandle) {
                         let handle = app_handle.clone();
 
                         tauri::async_runtime::spawn(async move {
-                            let db_state = handle.state::<Database>();
-                            let result = scan_url(&db_state.client, &url, &method).await;
+                            let db_state = handle.state::<SqliteDatabase>();
+                            let result =
+                                scan_url(&db_state.client, &url, &method, &db_state.rate_limiter)
+                                    .await;
                             let _ = db_state.update_scan_result(
                                 id,
                                 &result.status,
@@ -562,6 +86,7 @@ fn start_background_monitor(app_handle: AppHandle) {
                                 &result.response_body,
                                 &result.request_headers,
                                 &result.request_body,
+                                &result.content_hash,
                             );
                             let _ = handle.emit("scan-update", id);
                         });
@@ -578,13 +103,20 @@ fn start_background_monitor(app_handle: AppHandle) {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let db = Database::new("apex.db").expect("Failed to initialize database");
+    let db = SqliteDatabase::new("apex.db").expect("Failed to initialize database");
+    let storage: Box<dyn Storage> = Box::new(db.clone());
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .manage(db) // Manage state
+        .manage(storage) // Narrow Storage-trait view onto the same database, for commands that only need it
+        .manage(utils::crypto::CryptoManager::new())
+        .manage(core::jobs::JobManager::default())
+        .manage(core::discovery_telemetry::TelemetryStore::default())
+        .manage(core::sequence_state::SequenceJarStore::default())
+        .manage(services::proxy::ProxyService::new())
         .setup(|app| {
             let window = app.get_webview_window("main").expect(
                 "Failed to get main window - application may not have initialized properly",
@@ -595,37 +127,134 @@ pub fn run() {
             }));
             start_background_monitor(app.handle().clone());
             ai::auto_initialize_ai(app.handle().clone());
+
+            let metrics_db = app.state::<SqliteDatabase>();
+            let metrics_enabled = metrics_db
+                .get_setting("metrics_enabled")
+                .ok()
+                .flatten()
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false);
+            if metrics_enabled {
+                let bind_addr: SocketAddr = metrics_db
+                    .get_setting("metrics_bind_addr")
+                    .ok()
+                    .flatten()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or_else(|| ([127, 0, 0, 1], 9898).into());
+                tauri::async_runtime::spawn(metrics::serve(bind_addr));
+            }
+
+            let admin_api_config = admin_api::AdminApiConfig::from_env();
+            if admin_api_config.enabled {
+                if admin_api_config.token.is_empty() {
+                    eprintln!(
+                        "Admin API: APEX_ADMIN_API_ENABLED is set but APEX_ADMIN_API_TOKEN is empty; refusing to start the admin API unauthenticated."
+                    );
+                } else {
+                    let addr = ([127, 0, 0, 1], admin_api_config.port).into();
+                    let handle = app.handle().clone();
+                    tauri::async_runtime::spawn(admin_api::serve(
+                        handle,
+                        addr,
+                        admin_api_config.token,
+                    ));
+                }
+            }
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
-            import_assets,
-            get_assets,
-            get_asset_history,
-            generate_audit_report,
-            export_to_csv_final_v5,
-            delete_asset,
-            rescan_asset,
-            clear_database,
-            get_folders,
-            add_folder,
-            move_assets_to_folder,
-            delete_folder,
-            get_vulnerability_badge,
-            sanitize_database,
+            parse_openapi_spec,
+            benchmark::run_benchmark,
             ai::analyze_logic_flaws,
             ui::inspector::sign_jwt,
             ui::inspector::decode_jwt,
+            ui::inspector::audit_jwt,
             ui::inspector::generate_curl,
-            update_asset_triage,
+            ui::inspector::sign_aws_sigv4,
             ai::analyze_finding,
             ai::analyze_asset_summary,
             ai::get_llm_config,
             ai::update_llm_config,
+            ai::list_providers,
+            ai::add_provider,
+            ai::set_active_provider,
+            ai::remove_provider,
             ai::check_local_model_status,
             ai::pull_local_model,
-            log_debug,
-            parse_openapi_spec,
-            import_openapi_spec_and_detect_shadow_apis
+            commands::debug::log_debug,
+            commands::active_scan::execute_active_scan,
+            commands::assets::import_assets,
+            commands::assets::get_assets,
+            commands::assets::get_asset_history,
+            commands::assets::delete_asset,
+            commands::assets::clear_database,
+            commands::assets::sanitize_database,
+            commands::assets::update_asset_triage,
+            commands::assets::enhanced_import_assets,
+            commands::assets::resume_import,
+            commands::assets::abort_import,
+            commands::assets::get_import_status,
+            commands::assets::get_import_history,
+            commands::assets::reimport_assets,
+            commands::assets::clear_import_history,
+            commands::assets::import_staged_assets,
+            commands::assets::purge_recursive_assets,
+            commands::assets::validate_urls,
+            commands::assets::toggle_finding_fp,
+            commands::assets::add_asset,
+            commands::assets::update_asset_source,
+            commands::crypto::encrypt_api_key,
+            commands::crypto::decrypt_api_key,
+            commands::diff::compare_responses,
+            commands::discovery::discover_subdomains,
+            commands::discovery::get_discovery_stats,
+            commands::discovery::crawl_discovered_assets,
+            commands::discovery::promote_discovered_assets,
+            commands::discovery::fetch_wayback_urls,
+            commands::discovery::scan_ports,
+            commands::export::generate_audit_report,
+            commands::export::export_findings_to_csv,
+            commands::export::export_findings_to_sarif,
+            commands::export::generate_html_report,
+            commands::folders::get_folders,
+            commands::folders::add_folder,
+            commands::folders::move_assets_to_folder,
+            commands::folders::delete_folder,
+            commands::jobs::cancel_job,
+            commands::jobs::get_job,
+            commands::jobs::list_jobs,
+            commands::openapi_import::import_openapi_assets,
+            commands::proxy::start_proxy_service,
+            commands::proxy::stop_proxy_service,
+            commands::proxy::get_proxy_status,
+            commands::proxy::set_proxy_intercept,
+            commands::proxy::forward_intercepted_request,
+            commands::proxy::drop_intercepted_request,
+            commands::repeater::send_request,
+            commands::repeater::save_response,
+            commands::repeater::list_history,
+            commands::repeater::diff_responses,
+            commands::repeater::run_intruder_attack,
+            commands::repeater::poll_until_change,
+            commands::scan::get_vulnerability_badge,
+            commands::scan::rescan_asset,
+            commands::sequence::start_sequence,
+            commands::sequence::add_to_sequence,
+            commands::sequence::get_sequence,
+            commands::sequence::list_sequences,
+            commands::sequence::execute_sequence_step,
+            commands::sequence::execute_sequence_chain,
+            commands::sequence::delete_sequence_step,
+            commands::settings::get_setting,
+            commands::settings::set_setting,
+            commands::shadow_api::import_openapi_spec_and_detect_shadow_apis,
+            commands::shadow_api::clear_documentation_status,
+            commands::shadow_api::import_missing_endpoints,
+            commands::signatures::list_signatures,
+            commands::signatures::add_signature,
+            commands::signatures::delete_signature
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,259 @@
+//! Prometheus instrumentation for the audit pipeline: scan throughput,
+//! findings by detector/severity, shadow-API and queue-depth gauges, and
+//! latency histograms for outbound requests and rate-limiter backpressure.
+//! Mirrors the `PrometheusBuilder`/registry-and-scrape-endpoint pattern used
+//! by projects like pict-rs and Garage's `admin/metrics.rs`, giving
+//! operators a `/metrics` endpoint instead of having to tail the debug log.
+
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, IntCounterVec, IntGauge, Opts, Registry, TextEncoder,
+};
+use std::net::SocketAddr;
+use std::sync::OnceLock;
+
+struct Metrics {
+    registry: Registry,
+    scans_total: IntCounterVec,
+    findings_total: IntCounterVec,
+    shadow_api_count: IntGauge,
+    queue_depth: IntGauge,
+    request_latency_seconds: Histogram,
+    rate_limiter_wait_seconds: Histogram,
+    assets_imported_total: IntCounterVec,
+    scan_cache_total: IntCounterVec,
+    recursive_urls_total: IntCounterVec,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+
+        let scans_total = IntCounterVec::new(
+            Opts::new(
+                "apex_scans_total",
+                "Total scan_url invocations, labeled by method and status code bucket",
+            ),
+            &["method", "status_bucket"],
+        )
+        .expect("metric definition must be valid");
+        registry
+            .register(Box::new(scans_total.clone()))
+            .expect("metric must register exactly once");
+
+        let findings_total = IntCounterVec::new(
+            Opts::new(
+                "apex_findings_total",
+                "Total findings reported, labeled by detector and severity",
+            ),
+            &["detector", "severity"],
+        )
+        .expect("metric definition must be valid");
+        registry
+            .register(Box::new(findings_total.clone()))
+            .expect("metric must register exactly once");
+
+        let shadow_api_count = IntGauge::new(
+            "apex_shadow_api_count",
+            "Current count of endpoints detected as undocumented shadow APIs",
+        )
+        .expect("metric definition must be valid");
+        registry
+            .register(Box::new(shadow_api_count.clone()))
+            .expect("metric must register exactly once");
+
+        let queue_depth = IntGauge::new(
+            "apex_queue_depth",
+            "Depth of the background monitor's stale-asset rescan queue",
+        )
+        .expect("metric definition must be valid");
+        registry
+            .register(Box::new(queue_depth.clone()))
+            .expect("metric must register exactly once");
+
+        let request_latency_seconds = Histogram::with_opts(HistogramOpts::new(
+            "apex_request_latency_seconds",
+            "Per-request latency observed by scan_url",
+        ))
+        .expect("metric definition must be valid");
+        registry
+            .register(Box::new(request_latency_seconds.clone()))
+            .expect("metric must register exactly once");
+
+        let rate_limiter_wait_seconds = Histogram::with_opts(HistogramOpts::new(
+            "apex_rate_limiter_wait_seconds",
+            "Sleep duration spent in RateLimiter::wait backing off requests",
+        ))
+        .expect("metric definition must be valid");
+        registry
+            .register(Box::new(rate_limiter_wait_seconds.clone()))
+            .expect("metric must register exactly once");
+
+        let assets_imported_total = IntCounterVec::new(
+            Opts::new(
+                "apex_assets_imported_total",
+                "Staged assets processed by ImportService, labeled by outcome (imported/duplicate_skipped)",
+            ),
+            &["outcome"],
+        )
+        .expect("metric definition must be valid");
+        registry
+            .register(Box::new(assets_imported_total.clone()))
+            .expect("metric must register exactly once");
+
+        let scan_cache_total = IntCounterVec::new(
+            Opts::new(
+                "apex_scan_cache_total",
+                "Scans short-circuited by the is_asset_recently_scanned cache, labeled hit/miss",
+            ),
+            &["result"],
+        )
+        .expect("metric definition must be valid");
+        registry
+            .register(Box::new(scan_cache_total.clone()))
+            .expect("metric must register exactly once");
+
+        let recursive_urls_total = IntCounterVec::new(
+            Opts::new(
+                "apex_recursive_urls_total",
+                "URLs surfaced by recursive discovery, labeled by outcome (discovered/blocked)",
+            ),
+            &["outcome"],
+        )
+        .expect("metric definition must be valid");
+        registry
+            .register(Box::new(recursive_urls_total.clone()))
+            .expect("metric must register exactly once");
+
+        Self {
+            registry,
+            scans_total,
+            findings_total,
+            shadow_api_count,
+            queue_depth,
+            request_latency_seconds,
+            rate_limiter_wait_seconds,
+            assets_imported_total,
+            scan_cache_total,
+            recursive_urls_total,
+        }
+    }
+}
+
+fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::new)
+}
+
+/// Bucket an HTTP status code the way operators read dashboards: by
+/// hundreds digit, plus a `"none"` bucket for connection failures where
+/// `scan_url` never got a status code at all.
+fn status_bucket(status_code: i32) -> &'static str {
+    match status_code {
+        0 => "none",
+        100..=199 => "1xx",
+        200..=299 => "2xx",
+        300..=399 => "3xx",
+        400..=499 => "4xx",
+        500..=599 => "5xx",
+        _ => "other",
+    }
+}
+
+/// Record one `scan_url` invocation, labeled by HTTP method and status
+/// code bucket.
+pub fn record_scan(method: &str, status_code: i32) {
+    metrics()
+        .scans_total
+        .with_label_values(&[&method.to_uppercase(), status_bucket(status_code)])
+        .inc();
+}
+
+/// Record one finding, labeled by detector (a [`crate::db::Badge`]'s
+/// `short` code) and severity.
+pub fn record_finding(detector: &str, severity: &str) {
+    metrics()
+        .findings_total
+        .with_label_values(&[detector, severity])
+        .inc();
+}
+
+/// Set the current shadow-API gauge, refreshed by
+/// `import_openapi_spec_and_detect_shadow_apis` on every spec import.
+pub fn set_shadow_api_count(count: i64) {
+    metrics().shadow_api_count.set(count);
+}
+
+/// Set the current background-monitor queue-depth gauge.
+pub fn set_queue_depth(count: i64) {
+    metrics().queue_depth.set(count);
+}
+
+/// Observe one `scan_url` request's end-to-end latency in seconds.
+pub fn observe_request_latency(seconds: f64) {
+    metrics().request_latency_seconds.observe(seconds);
+}
+
+/// Observe one `RateLimiter::wait` sleep duration in seconds.
+pub fn observe_rate_limiter_wait(seconds: f64) {
+    metrics().rate_limiter_wait_seconds.observe(seconds);
+}
+
+/// Record one staged asset reaching `ImportService::process_staged_assets`,
+/// labeled by whether it was imported or skipped as a duplicate.
+pub fn record_asset_imported(skipped_duplicate: bool) {
+    let outcome = if skipped_duplicate {
+        "duplicate_skipped"
+    } else {
+        "imported"
+    };
+    metrics()
+        .assets_imported_total
+        .with_label_values(&[outcome])
+        .inc();
+}
+
+/// Record one `is_asset_recently_scanned` lookup, labeled by whether it hit
+/// the cache (scan skipped) or missed (scan actually ran).
+pub fn record_scan_cache(hit: bool) {
+    let result = if hit { "hit" } else { "miss" };
+    metrics().scan_cache_total.with_label_values(&[result]).inc();
+}
+
+/// Record one recursively-discovered URL, labeled by whether the SSRF guard
+/// cleared it to scan or blocked it.
+pub fn record_recursive_url(blocked: bool) {
+    let outcome = if blocked { "blocked" } else { "discovered" };
+    metrics()
+        .recursive_urls_total
+        .with_label_values(&[outcome])
+        .inc();
+}
+
+/// Render every registered metric in the Prometheus text exposition
+/// format, ready to hand back as the body of a scrape response.
+pub fn render() -> String {
+    let metric_families = metrics().registry.gather();
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&metric_families, &mut buffer)
+        .expect("text encoding of gathered metrics does not fail");
+    String::from_utf8(buffer).expect("prometheus text format is always valid UTF-8")
+}
+
+/// Serve the text exposition format on `addr` until the process exits.
+/// Callers bind this to `127.0.0.1` (see [`crate::run`]) so the scrape
+/// endpoint isn't reachable off the local machine.
+pub async fn serve(addr: SocketAddr) {
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Body, Response, Server};
+
+    let make_svc = make_service_fn(|_conn| async {
+        Ok::<_, std::convert::Infallible>(service_fn(|_req| async {
+            Ok::<_, std::convert::Infallible>(Response::new(Body::from(render())))
+        }))
+    });
+
+    if let Err(e) = Server::bind(&addr).serve(make_svc).await {
+        eprintln!("Metrics server error: {}", e);
+    }
+}
@@ -0,0 +1,226 @@
+use crate::db::Severity;
+use crate::detectors::Finding;
+use regex::Regex;
+use serde::Deserialize;
+
+/// A single externalized detection rule: what to match and the badge to
+/// emit when it does. Mirrors the hardcoded checks in `detectors::analyze`,
+/// but loaded from a TOML file at startup instead of compiled in, so a user
+/// can add or override a signature with `--rules custom.toml` instead of
+/// recompiling.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rule {
+    pub emoji: String,
+    pub short: String,
+    pub severity: Severity,
+    pub description: String,
+    #[serde(rename = "match")]
+    pub matcher: Matcher,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Matcher {
+    /// Case-insensitive match if the body contains any of `values`.
+    Literal { values: Vec<String> },
+    /// Case-insensitive regex match against the body.
+    Regex { pattern: String },
+    /// Match if the response status code satisfies `op`/`value`.
+    Status { op: StatusOp, value: u16 },
+    /// Match if a header (case-insensitive name) contains `contains`.
+    Header { name: String, contains: String },
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusOp {
+    Eq,
+    Gte,
+    Lte,
+}
+
+/// A `Rule` with its regex (if any) compiled once at load time, instead of
+/// recompiling a `Regex::new(...)` on every `analyze` call the way the
+/// hardcoded checks in `detectors.rs` do.
+struct CompiledRule {
+    rule: Rule,
+    regex: Option<Regex>,
+}
+
+/// The built-in rules shipped with the app, covering the same categories
+/// `detectors::analyze` hardcodes. Embedded at compile time so detection
+/// works out of the box; `RuleSet::load` swaps in a user-supplied file's
+/// rules in its place.
+const DEFAULT_RULES_TOML: &str = include_str!("rules/default_rules.toml");
+
+/// A compiled set of [`Rule`]s, ready to evaluate against a response.
+pub struct RuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl RuleSet {
+    /// Compile the embedded default rules.
+    pub fn default_rules() -> Self {
+        Self::from_toml(DEFAULT_RULES_TOML).expect("embedded default ruleset must parse")
+    }
+
+    /// Parse and compile a TOML ruleset (a top-level `rules = [...]` array
+    /// of rule tables). Each regex pattern is compiled once here rather
+    /// than per-call, and a bad pattern is reported as an `Err` instead of
+    /// panicking the way `Regex::new(...).unwrap()` would.
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        #[derive(Deserialize)]
+        struct RulesFile {
+            rules: Vec<Rule>,
+        }
+        let parsed: RulesFile = toml::from_str(text).map_err(|e| e.to_string())?;
+        Self::compile(parsed.rules)
+    }
+
+    fn compile(rules: Vec<Rule>) -> Result<Self, String> {
+        let mut compiled = Vec::with_capacity(rules.len());
+        for rule in rules {
+            let regex = match &rule.matcher {
+                Matcher::Regex { pattern } => {
+                    Some(Regex::new(&format!("(?i){}", pattern)).map_err(|e| e.to_string())?)
+                }
+                _ => None,
+            };
+            compiled.push(CompiledRule { rule, regex });
+        }
+        Ok(Self { rules: compiled })
+    }
+
+    /// Load a user-supplied ruleset file (e.g. `--rules custom.toml`),
+    /// falling back to the embedded defaults if it can't be read or
+    /// parsed, so a malformed override doesn't disable detection entirely.
+    #[allow(dead_code)]
+    pub fn load(path: &std::path::Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|text| Self::from_toml(&text).ok())
+            .unwrap_or_else(Self::default_rules)
+    }
+
+    /// Evaluate every rule against `body`/`status`/`headers`, returning one
+    /// [`Finding`] per match. A `Status`/`Header` match has no body offset,
+    /// so it's recorded as a zero-width finding at the start of the body.
+    pub fn evaluate(&self, body: &str, status: u16, headers: &str) -> Vec<Finding> {
+        self.rules
+            .iter()
+            .filter_map(|compiled| {
+                let (start, end) = match_offsets(compiled, body, status, headers)?;
+                Some(Finding::from_parts(
+                    &compiled.rule.emoji,
+                    &compiled.rule.short,
+                    compiled.rule.severity.clone(),
+                    &compiled.rule.description,
+                    start,
+                    end,
+                ))
+            })
+            .collect()
+    }
+}
+
+fn match_offsets(
+    compiled: &CompiledRule,
+    body: &str,
+    status: u16,
+    headers: &str,
+) -> Option<(usize, usize)> {
+    match &compiled.rule.matcher {
+        Matcher::Literal { values } => {
+            let lower_body = body.to_lowercase();
+            values.iter().find_map(|v| {
+                let needle = v.to_lowercase();
+                lower_body
+                    .find(&needle)
+                    .map(|start| (start, start + needle.len()))
+            })
+        }
+        Matcher::Regex { .. } => compiled
+            .regex
+            .as_ref()
+            .and_then(|re| re.find(body))
+            .map(|m| (m.start(), m.end())),
+        Matcher::Status { op, value } => {
+            let hit = match op {
+                StatusOp::Eq => status == *value,
+                StatusOp::Gte => status >= *value,
+                StatusOp::Lte => status <= *value,
+            };
+            hit.then_some((0, 0))
+        }
+        Matcher::Header { name, contains } => {
+            let name = format!("{}:", name.to_lowercase());
+            let contains = contains.to_lowercase();
+            headers
+                .lines()
+                .any(|line| {
+                    let lower_line = line.to_lowercase();
+                    lower_line.starts_with(&name) && lower_line.contains(&contains)
+                })
+                .then_some((0, 0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+rules = [
+  { emoji = "🧬", short = "XXE", severity = "High", description = "XML external entity reference detected.",
+    match = { type = "literal", values = ["<!entity", "system \"file://"] } },
+  { emoji = "🚦", short = "RateLimit", severity = "Low", description = "Endpoint is rate limited.",
+    match = { type = "status", op = "eq", value = 429 } },
+  { emoji = "🔓", short = "NoHSTS", severity = "Medium", description = "Missing HSTS header.",
+    match = { type = "header", name = "strict-transport-security", contains = "max-age" } },
+]
+"#;
+
+    #[test]
+    fn test_default_rules_parse() {
+        let ruleset = RuleSet::default_rules();
+        assert!(!ruleset.rules.is_empty());
+    }
+
+    #[test]
+    fn test_literal_matcher_finds_offset() {
+        let ruleset = RuleSet::from_toml(SAMPLE).unwrap();
+        let findings = ruleset.evaluate("<!ENTITY xxe SYSTEM \"file:///etc/passwd\">", 200, "");
+        assert!(findings.iter().any(|f| f.badge.short == "XXE"));
+    }
+
+    #[test]
+    fn test_status_matcher_matches_exact_code() {
+        let ruleset = RuleSet::from_toml(SAMPLE).unwrap();
+        let findings = ruleset.evaluate("too many requests", 429, "");
+        assert!(findings.iter().any(|f| f.badge.short == "RateLimit"));
+
+        let findings = ruleset.evaluate("ok", 200, "");
+        assert!(!findings.iter().any(|f| f.badge.short == "RateLimit"));
+    }
+
+    #[test]
+    fn test_header_matcher_requires_name_and_contains() {
+        let ruleset = RuleSet::from_toml(SAMPLE).unwrap();
+        let headers = "Strict-Transport-Security: max-age=31536000";
+        let findings = ruleset.evaluate("", 200, headers);
+        assert!(!findings.iter().any(|f| f.badge.short == "NoHSTS"));
+
+        let findings = ruleset.evaluate("", 200, "Content-Type: text/html");
+        assert!(!findings.iter().any(|f| f.badge.short == "NoHSTS"));
+    }
+
+    #[test]
+    fn test_malformed_override_falls_back_to_defaults() {
+        let dir = std::env::temp_dir().join(format!("bad-rules-{}.toml", std::process::id()));
+        std::fs::write(&dir, "not valid toml {{{").unwrap();
+        let ruleset = RuleSet::load(&dir);
+        std::fs::remove_file(&dir).ok();
+        assert!(!ruleset.rules.is_empty());
+    }
+}
@@ -1,6 +1,8 @@
 use crate::db::{Badge, Severity};
 use crate::detectors::analyze;
-use reqwest::{header::HeaderMap, Client};
+use futures::StreamExt;
+use reqwest::{header::HeaderMap, Client, Response};
+use sha2::{Digest, Sha256};
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ScanResult {
@@ -13,6 +15,28 @@ pub struct ScanResult {
     pub request_headers: String,
     pub request_body: String,
     pub discovered_urls: Vec<String>,
+    /// Hex SHA-256 digest of the raw response body, computed while the body
+    /// is streamed in rather than with a second pass over it afterwards. Lets
+    /// a rescan detect whether the endpoint's content actually changed with
+    /// an O(1) comparison instead of a full line-level diff every time.
+    pub content_hash: String,
+}
+
+/// Read `response`'s body, feeding each chunk through a SHA-256 hasher as it
+/// arrives, and return the decoded text alongside the hex digest of the
+/// whole body.
+async fn read_body_with_digest(response: Response) -> (String, String) {
+    let mut hasher = Sha256::new();
+    let mut buf: Vec<u8> = Vec::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let Ok(chunk) = chunk else { break };
+        hasher.update(&chunk);
+        buf.extend_from_slice(&chunk);
+    }
+
+    let text = String::from_utf8(buf).unwrap_or_else(|_| "[Incompatible Binary Content]".to_string());
+    (text, format!("{:x}", hasher.finalize()))
 }
 
 pub async fn scan_url(
@@ -22,7 +46,7 @@ pub async fn scan_url(
     rate_limiter: &crate::core::rate_limiter::RateLimiter,
 ) -> ScanResult {
     // Wait for the rate limit before making any request
-    rate_limiter.wait().await;
+    rate_limiter.wait(url).await;
     let method_type = match method.to_uppercase().as_str() {
         "POST" => reqwest::Method::POST,
         "PUT" => reqwest::Method::PUT,
@@ -37,9 +61,12 @@ pub async fn scan_url(
     );
     let request_body = "".to_string(); // Placeholder for future targeted scans with payloads
 
+    let request_started = std::time::Instant::now();
     let response = match client.request(method_type, url).send().await {
         Ok(resp) => resp,
         Err(e) => {
+            crate::metrics::record_scan(method, 0);
+            crate::metrics::observe_request_latency(request_started.elapsed().as_secs_f64());
             return ScanResult {
                 status: "Connection Failed".to_string(),
                 status_code: 0,
@@ -50,25 +77,38 @@ pub async fn scan_url(
                 request_headers,
                 request_body,
                 discovered_urls: vec![],
+                content_hash: String::new(),
             };
         }
     };
 
     let status_code = response.status();
     let u16_status = status_code.as_u16();
+    crate::metrics::record_scan(method, u16_status as i32);
+    crate::metrics::observe_request_latency(request_started.elapsed().as_secs_f64());
+    rate_limiter
+        .on_response(url, u16_status, retry_after(response.headers()))
+        .await;
 
     // Capture response headers
     let response_headers = format_headers(response.headers());
 
-    let response_body = match response.text().await {
-        Ok(text) => text,
-        Err(_) => "[Incompatible Binary Content]".to_string(),
-    };
+    let (response_body, content_hash) = read_body_with_digest(response).await;
 
     let combined_body = format!("{}\n{}", request_body, response_body);
     let combined_headers = format!("{}\n{}", request_headers, response_headers);
 
-    let badges = analyze(url, &combined_body, u16_status, method, &combined_headers);
+    let mut badges = analyze(url, &combined_body, u16_status, method, &combined_headers);
+    if let Some(known_bad_badge) = crate::known_bad::check_known_bad_path(url) {
+        badges.push(known_bad_badge);
+    }
+    // Run the enhanced-detector registry alongside the legacy hardcoded
+    // checks above -- it covers categories (BOLA, mass assignment, rate
+    // limiting, JWT/cert issues) `analyze` doesn't, deduped against its own
+    // overlapping findings before being folded into the same badge list.
+    for finding in crate::core::detector::run_enhanced_detectors(url, &combined_body, &combined_headers) {
+        badges.push(finding.badge);
+    }
     let mut risk_score = 0;
     for b in &badges {
         risk_score += match b.severity {
@@ -78,6 +118,7 @@ pub async fn scan_url(
             Severity::Low => 10,
             Severity::Info => 0,
         };
+        crate::metrics::record_finding(&b.short, &format!("{:?}", b.severity));
     }
 
     let final_status = if risk_score >= 100 {
@@ -102,6 +143,7 @@ pub async fn scan_url(
         request_headers,
         request_body,
         discovered_urls,
+        content_hash,
     }
 }
 
@@ -153,6 +195,17 @@ fn extract_urls(body: &str, base_url: &str) -> Vec<String> {
     urls
 }
 
+/// Parse a `Retry-After` header's delay-seconds form into a `Duration`.
+/// The HTTP-date form isn't handled; the rate limiter simply falls back to
+/// its normal backoff when the header is absent or isn't a plain integer.
+fn retry_after(headers: &HeaderMap) -> Option<std::time::Duration> {
+    headers
+        .get("retry-after")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .map(std::time::Duration::from_secs)
+}
+
 fn format_headers(headers: &HeaderMap) -> String {
     headers
         .iter()
@@ -0,0 +1,169 @@
+use crate::core::rate_limiter::RateLimiter;
+use crate::db::SqliteDatabase;
+use futures::stream::{self, StreamExt};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Status codes that `ContentDiscoveryEngine::run` treats as "a real
+/// resource exists here" and records via `add_asset`, matching the default
+/// match-status set recursive content-discovery tools (ffuf/gobuster/
+/// feroxbuster) ship with.
+pub const DEFAULT_HIT_STATUSES: &[u16] = &[200, 204, 301, 302, 307, 401, 403];
+
+/// Tunables for a single `ContentDiscoveryEngine::run` crawl.
+pub struct DiscoveryConfig {
+    pub wordlist: Vec<String>,
+    pub methods: Vec<String>,
+    pub max_depth: i32,
+    pub hit_statuses: Vec<u16>,
+    pub concurrency: usize,
+    pub rate_limit_ms: u64,
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            wordlist: Vec::new(),
+            methods: vec!["GET".to_string()],
+            max_depth: 2,
+            hit_statuses: DEFAULT_HIT_STATUSES.to_vec(),
+            concurrency: 10,
+            rate_limit_ms: 100,
+        }
+    }
+}
+
+/// Recursive wordlist-based content-discovery engine layered on the asset
+/// store. Brute-forces `base/word` across `config.methods` starting from a
+/// seed URL, re-enqueuing directory-like hits (trailing slash, or a 3xx
+/// pointing deeper under the same host) up to `config.max_depth`, and
+/// records every real resource via `SqliteDatabase::add_asset` with
+/// `source = "Discovery"`.
+///
+/// A `(url, method)` pair is only ever requested once per run -- the
+/// in-memory `visited` set in `run` mirrors the store's own URL+method
+/// uniqueness semantics (see `test_distinct_methods`), so re-running
+/// against a target that already has imported assets just upgrades their
+/// `recursive` flag via `add_asset` instead of re-crawling them. Per-host
+/// rate limiting is tracked in `rate_limiters`, keyed by host, so multiple
+/// seed URLs on different hosts don't throttle each other.
+pub struct ContentDiscoveryEngine {
+    client: reqwest::Client,
+    config: DiscoveryConfig,
+    rate_limiters: Mutex<HashMap<String, Arc<RateLimiter>>>,
+}
+
+impl ContentDiscoveryEngine {
+    pub fn new(client: reqwest::Client, config: DiscoveryConfig) -> Self {
+        Self {
+            client,
+            config,
+            rate_limiters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn rate_limiter_for_host(&self, host: &str) -> Arc<RateLimiter> {
+        let mut limiters = self.rate_limiters.lock().await;
+        limiters
+            .entry(host.to_string())
+            .or_insert_with(|| Arc::new(RateLimiter::new(self.config.rate_limit_ms)))
+            .clone()
+    }
+
+    /// Whether `url` looks like a directory worth descending into: either it
+    /// ends in `/`, or a 3xx redirected to a deeper path under the same host.
+    fn is_directory_like(url: &str, status: u16, location: Option<&str>) -> bool {
+        if url.ends_with('/') {
+            return true;
+        }
+        if (300..400).contains(&status) {
+            if let Some(loc) = location {
+                if let (Ok(base), Ok(target)) = (url::Url::parse(url), url::Url::parse(loc)) {
+                    return target.host() == base.host()
+                        && target.path().len() > base.path().len();
+                }
+            }
+        }
+        false
+    }
+
+    async fn probe(&self, url: String, method: String) -> Option<(String, String, u16, Option<String>)> {
+        let host = url::Url::parse(&url).ok()?.host_str()?.to_string();
+        self.rate_limiter_for_host(&host).await.wait().await;
+
+        let method_type = match method.to_uppercase().as_str() {
+            "POST" => reqwest::Method::POST,
+            "PUT" => reqwest::Method::PUT,
+            "DELETE" => reqwest::Method::DELETE,
+            "HEAD" => reqwest::Method::HEAD,
+            _ => reqwest::Method::GET,
+        };
+
+        let response = self.client.request(method_type, &url).send().await.ok()?;
+        let status = response.status().as_u16();
+        let location = response
+            .headers()
+            .get("location")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+
+        Some((url, method, status, location))
+    }
+
+    /// Run a full recursive crawl starting from `base_url`, inserting every
+    /// hit into `db`, and return the number of assets recorded.
+    pub async fn run(&self, db: &SqliteDatabase, base_url: &str) -> usize {
+        let mut visited: HashSet<(String, String)> = HashSet::new();
+        let mut frontier: VecDeque<(String, i32)> = VecDeque::new();
+        frontier.push_back((base_url.trim_end_matches('/').to_string(), 0));
+
+        let mut discovered = 0usize;
+
+        while let Some((base, depth)) = frontier.pop_front() {
+            let requests: Vec<(String, String)> = self
+                .config
+                .wordlist
+                .iter()
+                .flat_map(|word| {
+                    let target = format!("{}/{}", base, word);
+                    self.config
+                        .methods
+                        .iter()
+                        .map(move |method| (target.clone(), method.clone()))
+                })
+                .filter(|pair| !visited.contains(pair))
+                .collect();
+
+            for pair in &requests {
+                visited.insert(pair.clone());
+            }
+
+            let hits: Vec<(String, String, u16, Option<String>)> = stream::iter(requests)
+                .map(|(url, method)| self.probe(url, method))
+                .buffer_unordered(self.config.concurrency)
+                .filter_map(|result| async move { result })
+                .collect()
+                .await;
+
+            for (url, method, status, location) in hits {
+                if !self.config.hit_statuses.contains(&status) {
+                    continue;
+                }
+
+                let recursive = Self::is_directory_like(&url, status, location.as_deref());
+                if let Err(e) = db.add_asset(&url, "Discovery", Some(&method), recursive, false, depth) {
+                    eprintln!("Content Discovery: failed to add asset {}: {}", url, e);
+                    continue;
+                }
+                discovered += 1;
+
+                if recursive && depth < self.config.max_depth {
+                    frontier.push_back((url.trim_end_matches('/').to_string(), depth + 1));
+                }
+            }
+        }
+
+        discovered
+    }
+}
@@ -51,6 +51,7 @@ impl ImportService {
                     &format!("Skipping duplicate asset: {}", asset.url),
                     None,
                 );
+                crate::metrics::record_asset_imported(true);
                 continue;
             }
 
@@ -91,6 +92,7 @@ impl ImportService {
                         &format!("Asset successfully added/updated with ID: {}", id),
                         Some(serde_json::json!({ "id": id, "url": normalized })),
                     );
+                    crate::metrics::record_asset_imported(false);
                     id
                 }
                 Err(e) => {
@@ -129,9 +131,11 @@ impl ImportService {
                 .db
                 .is_asset_recently_scanned(&normalized, &asset.method, 10)
             {
+                crate::metrics::record_scan_cache(true);
                 let _ = app.emit("scan-update", asset_id);
                 true
             } else {
+                crate::metrics::record_scan_cache(false);
                 false
             };
 
@@ -197,14 +201,36 @@ impl ImportService {
                                         .any(|d| host == d || host.ends_with(&format!(".{}", d)));
 
                                     if !is_blacklisted && is_authorized {
-                                        let _ = db_state.add_asset(
+                                        let port = parsed.port_or_known_default().unwrap_or(443);
+                                        let pinned = crate::services::ssrf_guard::guard_and_resolve(
+                                            db_state.inner(),
+                                            &app_handle,
+                                            host,
+                                            port,
                                             &discovered_url,
-                                            "Recursive",
-                                            None,
-                                            true,
-                                            false,
-                                            0,
-                                        );
+                                        )
+                                        .await;
+
+                                        crate::metrics::record_recursive_url(pinned.is_none());
+
+                                        // Store the asset under its original hostname-based URL, not
+                                        // the resolved IP -- `guard_and_resolve` already called
+                                        // `pin_host` on a successful clear, which binds every future
+                                        // resolution of `host` (the eventual rescan included, since
+                                        // `SqliteDatabase`'s client resolves through
+                                        // `PinnedHostResolver`) to this exact address. So the
+                                        // DNS-rebinding window this guard exists to close is shut
+                                        // regardless of which form of the URL we persist here.
+                                        if pinned.is_some() {
+                                            let _ = db_state.add_asset(
+                                                &discovered_url,
+                                                "Recursive",
+                                                None,
+                                                true,
+                                                false,
+                                                0,
+                                            );
+                                        }
                                     }
                                 }
                             }
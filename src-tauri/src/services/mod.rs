@@ -0,0 +1,7 @@
+pub mod content_discovery;
+pub mod import;
+pub mod monitor;
+pub mod proxy;
+pub mod scan;
+pub mod ssrf_guard;
+pub mod subdomain_enum;
@@ -3,11 +3,26 @@ use crate::db::SqliteDatabase;
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 
+// Passive subdomain enumeration hits crt.sh, a shared public resource, so it
+// runs far less often than the main scan tick (every 30th tick == 5 minutes).
+const SUBDOMAIN_ENUM_EVERY_N_TICKS: u64 = 30;
+
 pub fn start_background_monitor(app_handle: AppHandle) {
     tauri::async_runtime::spawn(async move {
         println!("Background Monitor: Initializing specialized security loop...");
+        let mut tick: u64 = 0;
         loop {
             tokio::time::sleep(Duration::from_secs(10)).await;
+            tick += 1;
+
+            if tick % SUBDOMAIN_ENUM_EVERY_N_TICKS == 0 {
+                let handle = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    let db_state = handle.state::<SqliteDatabase>();
+                    crate::services::subdomain_enum::run_passive_enumeration(db_state.inner())
+                        .await;
+                });
+            }
 
             let db = app_handle.state::<SqliteDatabase>();
 
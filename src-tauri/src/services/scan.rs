@@ -1,20 +1,110 @@
-use crate::core::data::{ScanResult, Severity};
+use crate::core::data::{Badge, ScanResult, Severity};
 use crate::core::detectors::analyze;
+use crate::data::{AuthDigest, AuthProfile, RequestSequence, SequenceStep};
 use crate::db::SqliteDatabase;
-use reqwest::{header::HeaderMap, Client};
+use crate::utils::sequence_engine::{extract_variables, substitute_variables};
+use base64::{engine::general_purpose, Engine as _};
+use hmac::{Hmac, Mac};
+use reqwest::{header::HeaderMap, Client, RequestBuilder};
+use serde::Serialize;
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
 use url::Url;
 
+/// Result of replaying a single [`SequenceStep`]: the resolved request that
+/// was actually sent (after `{{name}}` substitution) plus whatever `analyze`
+/// found in the response.
+#[derive(Debug, Clone)]
+pub struct SequenceStepReplay {
+    pub step_id: i64,
+    pub url: String,
+    pub status_code: u16,
+    pub response_body: String,
+    pub response_headers: String,
+    pub findings: Vec<Badge>,
+}
+
+/// Outcome of replaying a sequence twice -- once under the original
+/// identity, once with `overrides` swapped in for a second identity -- to
+/// look for broken object/function-level authorization: a step the second
+/// identity should not be able to reach, but which still returned 2xx with
+/// the same resource data the first identity saw.
+#[derive(Debug, Clone)]
+pub struct AuthorizationReplayFinding {
+    pub step_id: i64,
+    pub url: String,
+    pub status_code: u16,
+    pub description: String,
+}
+
+/// Scope and limits for [`ScanService::crawl`]: how many hops from the seed
+/// URLs to follow, how many requests the whole crawl may spend, and which
+/// URLs are in bounds. `allow_globs`/`deny_globs` are `*`/`?` shell-style
+/// globs matched against the full URL (e.g. `https://api.example.com/admin/*`);
+/// an empty `allow_globs` allows everything not explicitly denied.
+#[derive(Debug, Clone)]
+pub struct CrawlConfig {
+    pub max_depth: usize,
+    pub max_requests: usize,
+    pub allow_globs: Vec<String>,
+    pub deny_globs: Vec<String>,
+}
+
+impl Default for CrawlConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            max_requests: 100,
+            allow_globs: Vec::new(),
+            deny_globs: Vec::new(),
+        }
+    }
+}
+
+/// A parent -> child discovery link recorded while crawling, so reports can
+/// show how each endpoint was reached. `parent_url` is `None` for a seed URL.
+#[derive(Debug, Clone, Serialize)]
+pub struct CrawlEdge {
+    pub parent_url: Option<String>,
+    pub child_url: String,
+    pub depth: usize,
+}
+
+/// Progress payload emitted as the `"crawl-progress"` event after every scan,
+/// so the Tauri frontend can render a live crawl tree as the crawl runs.
+#[derive(Debug, Clone, Serialize)]
+pub struct CrawlProgress {
+    pub url: String,
+    pub depth: usize,
+    pub scanned: usize,
+    pub queued: usize,
+}
+
+/// Everything a crawl produced: every URL scanned (with its [`ScanResult`])
+/// and the parent -> child edges that explain how each one was reached.
+#[derive(Debug, Clone)]
+pub struct CrawlResult {
+    pub scans: Vec<(String, ScanResult)>,
+    pub edges: Vec<CrawlEdge>,
+}
+
 pub struct ScanService {
     client: Client,
     rate_limiter: Arc<crate::core::rate_limiter::RateLimiter>,
+    db: SqliteDatabase,
+    hmac_nonce: AtomicU64,
 }
 
 impl ScanService {
     pub fn new(db: SqliteDatabase) -> Self {
         Self {
-            client: db.client,
-            rate_limiter: db.rate_limiter,
+            client: db.client.clone(),
+            rate_limiter: db.rate_limiter.clone(),
+            db,
+            hmac_nonce: AtomicU64::new(1),
         }
     }
 
@@ -34,7 +124,10 @@ impl ScanService {
             _ => reqwest::Method::GET,
         };
 
-        let response = match self.client.request(method_type, url).send().await {
+        let rb = self.client.request(method_type, url);
+        let rb = self.apply_auth_profile(rb, url, &request_body);
+
+        let response = match rb.send().await {
             Ok(resp) => resp,
             Err(e) => {
                 return ScanResult {
@@ -102,6 +195,262 @@ impl ScanService {
         }
     }
 
+    /// Re-issue every step of `seq` in recorded (`timestamp`) order, seeding
+    /// the substitution context with `overrides` and growing it with each
+    /// step's `captures` as responses come back -- so a later step's
+    /// `{{name}}` placeholders resolve to values the earlier steps actually
+    /// returned, the same way the sequence was originally recorded.
+    pub async fn replay_sequence(
+        &self,
+        seq: &RequestSequence,
+        overrides: HashMap<String, String>,
+    ) -> Vec<SequenceStepReplay> {
+        let mut context = overrides;
+        let mut results = Vec::new();
+
+        for step in &seq.steps {
+            let (replay, captured) = self.replay_step(step, &context).await;
+            context.extend(captured);
+            results.push(replay);
+        }
+
+        results
+    }
+
+    /// Replay `seq` twice -- once as recorded, once with `alternate_identity`
+    /// overriding the captured auth context (e.g. swapping in a second
+    /// account's session token/cookie) -- and flag any step where the
+    /// alternate identity still gets a 2xx response whose body matches the
+    /// original identity's resource data. That's a broken object/function
+    /// level authorization finding: the server didn't check who was asking.
+    pub async fn replay_with_identity_swap(
+        &self,
+        seq: &RequestSequence,
+        alternate_identity: HashMap<String, String>,
+    ) -> Vec<AuthorizationReplayFinding> {
+        let baseline = self.replay_sequence(seq, HashMap::new()).await;
+
+        let mut context = alternate_identity;
+        let mut findings = Vec::new();
+
+        for (step, baseline_replay) in seq.steps.iter().zip(baseline.iter()) {
+            let (replay, captured) = self.replay_step(step, &context).await;
+            context.extend(captured);
+
+            let is_success = (200..300).contains(&replay.status_code);
+            let matches_baseline = !baseline_replay.response_body.is_empty()
+                && replay.response_body == baseline_replay.response_body;
+
+            if is_success && matches_baseline {
+                findings.push(AuthorizationReplayFinding {
+                    step_id: step.id,
+                    url: replay.url.clone(),
+                    status_code: replay.status_code,
+                    description: format!(
+                        "Step returned {} with the original identity's resource data under a different identity -- possible broken object/function level authorization.",
+                        replay.status_code
+                    ),
+                });
+            }
+        }
+
+        findings
+    }
+
+    /// Breadth-first crawl from `seed_urls`, driven entirely by `scan_url`'s
+    /// own `discovered_urls`: each URL popped off the work queue is scanned,
+    /// its in-scope discoveries are enqueued one hop deeper, and a
+    /// parent -> child [`CrawlEdge`] is recorded and persisted for every URL
+    /// reached. Stops once `config.max_depth` or `config.max_requests` is
+    /// exhausted. A URL already present in `SqliteDatabase` (from this or an
+    /// earlier crawl) is recorded as an edge but not re-scanned, so repeat
+    /// crawls of a partially known target are incremental. When `app_handle`
+    /// is given, emits a `"crawl-progress"` event after every scan so the
+    /// Tauri frontend can render the crawl tree live.
+    pub async fn crawl(
+        &self,
+        seed_urls: Vec<String>,
+        config: CrawlConfig,
+        app_handle: Option<AppHandle>,
+    ) -> CrawlResult {
+        let allow_globs: Vec<_> = config.allow_globs.iter().map(|g| glob_to_regex(g)).collect();
+        let deny_globs: Vec<_> = config.deny_globs.iter().map(|g| glob_to_regex(g)).collect();
+
+        let mut visited: HashSet<String> = HashSet::new();
+        let mut queue: VecDeque<(String, Option<String>, usize)> =
+            seed_urls.into_iter().map(|url| (url, None, 0)).collect();
+
+        let mut scans = Vec::new();
+        let mut edges = Vec::new();
+
+        while let Some((url, parent, depth)) = queue.pop_front() {
+            if scans.len() >= config.max_requests {
+                break;
+            }
+            if !visited.insert(url.clone()) {
+                continue;
+            }
+            if !is_in_scope(&url, &allow_globs, &deny_globs) {
+                continue;
+            }
+
+            let _ = self
+                .db
+                .save_discovery_edge(parent.as_deref(), &url, depth);
+            edges.push(CrawlEdge {
+                parent_url: parent,
+                child_url: url.clone(),
+                depth,
+            });
+
+            // Already scanned in an earlier crawl -- keep the edge for the
+            // tree, but don't spend another request re-scanning it.
+            if self.db.asset_exists_by_url_method(&url, "GET") {
+                continue;
+            }
+
+            let result = self.scan_url(&url, "GET").await;
+
+            if depth < config.max_depth {
+                for child in &result.discovered_urls {
+                    if !visited.contains(child) {
+                        queue.push_back((child.clone(), Some(url.clone()), depth + 1));
+                    }
+                }
+            }
+
+            scans.push((url.clone(), result));
+
+            if let Some(handle) = &app_handle {
+                let _ = handle.emit(
+                    "crawl-progress",
+                    CrawlProgress {
+                        url,
+                        depth,
+                        scanned: scans.len(),
+                        queued: queue.len(),
+                    },
+                );
+            }
+        }
+
+        CrawlResult { scans, edges }
+    }
+
+    /// Resolve `step`'s URL/body/headers against `context`, send the
+    /// request, score the response through `analyze` the same way
+    /// `scan_url` does, and extract this step's `captures` out of the
+    /// response for the caller to fold into the context for the next step.
+    async fn replay_step(
+        &self,
+        step: &SequenceStep,
+        context: &HashMap<String, String>,
+    ) -> (SequenceStepReplay, HashMap<String, String>) {
+        let url = substitute_variables(&step.url, context);
+        let body = step
+            .request_body
+            .as_deref()
+            .map(|b| substitute_variables(b, context));
+
+        self.rate_limiter.wait().await;
+
+        let method_type = match step.method.to_uppercase().as_str() {
+            "POST" => reqwest::Method::POST,
+            "PUT" => reqwest::Method::PUT,
+            "PATCH" => reqwest::Method::PATCH,
+            "DELETE" => reqwest::Method::DELETE,
+            _ => reqwest::Method::GET,
+        };
+
+        let mut rb = self.client.request(method_type, &url);
+        if let Some(headers) = &step.request_headers {
+            for line in substitute_variables(headers, context).lines() {
+                if let Some((key, value)) = line.split_once(':') {
+                    rb = rb.header(key.trim(), value.trim());
+                }
+            }
+        }
+        if let Some(body) = body.clone() {
+            rb = rb.body(body);
+        }
+
+        let (status_code, response_body, response_headers) = match rb.send().await {
+            Ok(resp) => {
+                let status_code = resp.status().as_u16();
+                let response_headers = self.format_headers(resp.headers());
+                let response_body = resp.text().await.unwrap_or_default();
+                (status_code, response_body, response_headers)
+            }
+            Err(e) => (0, String::new(), format!("Error: {}", e)),
+        };
+
+        let findings = analyze(
+            &url,
+            &response_body,
+            status_code,
+            &step.method,
+            &response_headers,
+        );
+
+        let captured = extract_variables(&step.captures, &response_body, &response_headers);
+
+        (
+            SequenceStepReplay {
+                step_id: step.id,
+                url,
+                status_code,
+                response_body,
+                response_headers,
+                findings,
+            },
+            captured,
+        )
+    }
+
+    /// Look up the best-matching [`AuthProfile`] for `url` (longest scope
+    /// prefix match, see [`SqliteDatabase::find_auth_profile_for_url`]) and
+    /// apply its credentials to `rb` -- a static header, the cookie jar, or
+    /// an HMAC signature computed over `body`. Requests to URLs with no
+    /// matching profile are sent unmodified.
+    fn apply_auth_profile(&self, rb: RequestBuilder, url: &str, body: &str) -> RequestBuilder {
+        let Ok(Some(scoped)) = self.db.find_auth_profile_for_url(url) else {
+            return rb;
+        };
+
+        match scoped.profile {
+            AuthProfile::StaticHeader {
+                header_name,
+                header_value,
+            } => rb.header(header_name, header_value),
+            AuthProfile::CookieJar { cookies } => {
+                let cookie_header = cookies
+                    .iter()
+                    .map(|(k, v)| format!("{}={}", k, v))
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                rb.header(reqwest::header::COOKIE, cookie_header)
+            }
+            AuthProfile::HmacSigned {
+                secret_key_base64,
+                digest,
+                signature_header,
+                nonce_header,
+            } => {
+                let Ok(path) = Url::parse(url).map(|u| u.path().to_string()) else {
+                    return rb;
+                };
+                let nonce = self.hmac_nonce.fetch_add(1, Ordering::SeqCst);
+                let Ok(secret) = general_purpose::STANDARD.decode(&secret_key_base64) else {
+                    return rb;
+                };
+                let signature =
+                    sign_hmac_request(&secret, &digest, path.as_bytes(), nonce, body.as_bytes());
+                rb.header(nonce_header, nonce.to_string())
+                    .header(signature_header, signature)
+            }
+        }
+    }
+
     fn extract_urls(&self, body: &str, base_url: &str) -> Vec<String> {
         let mut urls = Vec::new();
         let base = match Url::parse(base_url) {
@@ -159,3 +508,125 @@ impl ScanService {
             .join("\n")
     }
 }
+
+/// Common exchange-API HMAC request-signing scheme: sign
+/// `path || SHA256(nonce || body)` with the scope's secret key, base64-encode
+/// the result, using whichever digest the profile configures for the outer
+/// HMAC.
+fn sign_hmac_request(secret: &[u8], digest: &AuthDigest, path: &[u8], nonce: u64, body: &[u8]) -> String {
+    let mut inner_hasher = Sha256::new();
+    inner_hasher.update(nonce.to_string().as_bytes());
+    inner_hasher.update(body);
+    let inner_digest = inner_hasher.finalize();
+
+    let mut message = Vec::with_capacity(path.len() + inner_digest.len());
+    message.extend_from_slice(path);
+    message.extend_from_slice(&inner_digest);
+
+    match digest {
+        AuthDigest::Sha256 => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+            mac.update(&message);
+            general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+        }
+        AuthDigest::Sha512 => {
+            let mut mac = Hmac::<Sha512>::new_from_slice(secret).expect("HMAC accepts any key length");
+            mac.update(&message);
+            general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+        }
+    }
+}
+
+/// Translate a `*`/`?` shell-style glob into an anchored [`regex::Regex`] --
+/// `*` matches any run of characters, `?` matches exactly one, everything
+/// else is matched literally. No glob-matching crate exists elsewhere in
+/// this tree, so [`CrawlConfig`]'s allow/deny scoping builds its own here.
+fn glob_to_regex(glob: &str) -> regex::Regex {
+    let mut pattern = String::from("^");
+    for ch in glob.chars() {
+        match ch {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+    regex::Regex::new(&pattern).unwrap_or_else(|_| regex::Regex::new("$^").expect("static regex"))
+}
+
+/// A URL is in scope for a crawl if it matches no `deny` glob and either
+/// `allow` is empty (allow everything not denied) or it matches at least one
+/// `allow` glob.
+fn is_in_scope(url: &str, allow: &[regex::Regex], deny: &[regex::Regex]) -> bool {
+    if deny.iter().any(|re| re.is_match(url)) {
+        return false;
+    }
+    allow.is_empty() || allow.iter().any(|re| re.is_match(url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_in_scope_allows_everything_when_allow_globs_empty() {
+        let deny = vec![glob_to_regex("*/logout")];
+        assert!(is_in_scope("https://api.example.com/orders", &[], &deny));
+        assert!(!is_in_scope("https://api.example.com/logout", &[], &deny));
+    }
+
+    #[test]
+    fn test_is_in_scope_requires_an_allow_glob_match_when_present() {
+        let allow = vec![glob_to_regex("https://api.example.com/v1/*")];
+        assert!(is_in_scope(
+            "https://api.example.com/v1/orders",
+            &allow,
+            &[]
+        ));
+        assert!(!is_in_scope(
+            "https://api.example.com/v2/orders",
+            &allow,
+            &[]
+        ));
+    }
+
+    #[test]
+    fn test_is_in_scope_deny_wins_over_allow() {
+        let allow = vec![glob_to_regex("https://api.example.com/*")];
+        let deny = vec![glob_to_regex("https://api.example.com/admin/*")];
+        assert!(is_in_scope(
+            "https://api.example.com/orders",
+            &allow,
+            &deny
+        ));
+        assert!(!is_in_scope(
+            "https://api.example.com/admin/users",
+            &allow,
+            &deny
+        ));
+    }
+
+    #[test]
+    fn test_sign_hmac_request_is_deterministic_for_same_inputs() {
+        let secret = b"scope-secret";
+        let a = sign_hmac_request(secret, &AuthDigest::Sha256, b"/api/v1/orders", 42, b"{}");
+        let b = sign_hmac_request(secret, &AuthDigest::Sha256, b"/api/v1/orders", 42, b"{}");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_sign_hmac_request_changes_with_nonce() {
+        let secret = b"scope-secret";
+        let a = sign_hmac_request(secret, &AuthDigest::Sha256, b"/api/v1/orders", 1, b"{}");
+        let b = sign_hmac_request(secret, &AuthDigest::Sha256, b"/api/v1/orders", 2, b"{}");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_sign_hmac_request_differs_between_digests() {
+        let secret = b"scope-secret";
+        let sha256 = sign_hmac_request(secret, &AuthDigest::Sha256, b"/api/v1/orders", 1, b"{}");
+        let sha512 = sign_hmac_request(secret, &AuthDigest::Sha512, b"/api/v1/orders", 1, b"{}");
+        assert_ne!(sha256, sha512);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::commands::debug::{emit_log, LogLevel};
+use crate::core::dns_guard::{is_blocked_ip, pin_host};
+use crate::db::{Badge, Severity, SqliteDatabase};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use tauri::AppHandle;
+
+/// Setting holding extra CIDR ranges (comma-separated, IPv4 only) to allow
+/// even though they'd otherwise be blocked as private/internal -- e.g. a
+/// staging VPC an authorized engagement is allowed to recurse into.
+const ALLOW_RANGES_SETTING: &str = "ssrf_guard_allow_cidrs";
+/// Setting holding extra CIDR ranges (comma-separated, IPv4 only) to block
+/// on top of the built-in loopback/RFC1918/link-local/metadata set.
+const DENY_RANGES_SETTING: &str = "ssrf_guard_deny_cidrs";
+
+/// A host that's been resolved and cleared by the guard, carrying the exact
+/// socket address the subsequent scan must connect to. The pin is made
+/// binding (not just advisory) via `dns_guard::pin_host` -- `SqliteDatabase`'s
+/// HTTP client resolves through `dns_guard::PinnedHostResolver`, which serves
+/// this exact address back instead of re-resolving the hostname, closing the
+/// DNS-rebinding window between this check and the actual scan.
+pub struct PinnedHost {
+    pub addr: SocketAddr,
+}
+
+/// Resolve `host:port`, reject it if every resolved address falls in a
+/// blocked range, and return the one address cleared to connect to
+/// otherwise. A blocked or unresolvable host is logged under
+/// `backend:ssrf-guard` and (when blocked) recorded against `discovered_url`
+/// so it still shows up in scan history.
+pub async fn guard_and_resolve(
+    db: &SqliteDatabase,
+    app: &AppHandle,
+    host: &str,
+    port: u16,
+    discovered_url: &str,
+) -> Option<PinnedHost> {
+    let allow_cidrs = load_cidrs(db, ALLOW_RANGES_SETTING);
+    let deny_cidrs = load_cidrs(db, DENY_RANGES_SETTING);
+
+    let resolved = match tokio::net::lookup_host((host, port)).await {
+        Ok(addrs) => addrs.collect::<Vec<_>>(),
+        Err(e) => {
+            emit_log(
+                app,
+                LogLevel::Warn,
+                "backend:ssrf-guard",
+                &format!("DNS resolution failed for {}: {}", host, e),
+                None,
+            );
+            return None;
+        }
+    };
+
+    let pinned = resolved
+        .into_iter()
+        .find(|addr| !is_range_blocked(&addr.ip(), &allow_cidrs, &deny_cidrs));
+
+    match pinned {
+        Some(addr) => {
+            emit_log(
+                app,
+                LogLevel::Info,
+                "backend:ssrf-guard",
+                &format!("Resolved and pinned {} to {}", host, addr.ip()),
+                Some(serde_json::json!({ "host": host, "pinned_ip": addr.ip().to_string() })),
+            );
+            // Make the pin binding, not just advisory -- `PinnedHostResolver`
+            // (installed on `SqliteDatabase::client`) serves this exact
+            // address back when the recursive asset is actually scanned.
+            pin_host(host, addr.ip());
+            Some(PinnedHost { addr })
+        }
+        None => {
+            emit_log(
+                app,
+                LogLevel::Error,
+                "backend:ssrf-guard",
+                &format!(
+                    "Blocked recursive discovery of {}: every resolved address is loopback/internal/metadata",
+                    host
+                ),
+                Some(serde_json::json!({ "host": host, "url": discovered_url })),
+            );
+            record_blocked_host(db, discovered_url);
+            None
+        }
+    }
+}
+
+/// Record a blocked host as an asset carrying an SSRF-guard finding, reusing
+/// the same `update_scan_result` path every other scan result goes through
+/// so the block still surfaces in scan history.
+fn record_blocked_host(db: &SqliteDatabase, discovered_url: &str) {
+    if let Ok(asset_id) = db.add_asset(discovered_url, "Recursive", None, true, false, 0) {
+        let badge = Badge::new(
+            "🚫",
+            "SSRF-guard blocked",
+            Severity::High,
+            "Recursive discovery resolved this host to a loopback, private, link-local, or metadata address and refused to scan it.",
+        );
+        let _ = db.update_scan_result(asset_id, "Blocked (SSRF)", 0, 0, vec![badge], "", "", "", "", "");
+    }
+}
+
+fn is_range_blocked(ip: &IpAddr, allow: &[(Ipv4Addr, u8)], deny: &[(Ipv4Addr, u8)]) -> bool {
+    if let IpAddr::V4(v4) = ip {
+        if allow.iter().any(|(net, bits)| ipv4_in_cidr(*v4, *net, *bits)) {
+            return false;
+        }
+        if deny.iter().any(|(net, bits)| ipv4_in_cidr(*v4, *net, *bits)) {
+            return true;
+        }
+    }
+    is_blocked_ip(ip)
+}
+
+fn ipv4_in_cidr(ip: Ipv4Addr, net: Ipv4Addr, prefix_bits: u8) -> bool {
+    if prefix_bits == 0 {
+        return true;
+    }
+    let mask = u32::MAX.checked_shl(32 - prefix_bits as u32).unwrap_or(0);
+    u32::from(ip) & mask == u32::from(net) & mask
+}
+
+fn load_cidrs(db: &SqliteDatabase, setting: &str) -> Vec<(Ipv4Addr, u8)> {
+    db.get_setting(setting)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|entry| parse_cidr(entry.trim()))
+        .collect()
+}
+
+fn parse_cidr(entry: &str) -> Option<(Ipv4Addr, u8)> {
+    if entry.is_empty() {
+        return None;
+    }
+    let (addr, bits) = match entry.split_once('/') {
+        Some((addr, bits)) => (addr, bits.parse().ok()?),
+        None => (entry, 32),
+    };
+    Some((addr.parse().ok()?, bits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ipv4_in_cidr_matches_containing_block() {
+        let net = "10.0.0.0".parse().unwrap();
+        assert!(ipv4_in_cidr("10.1.2.3".parse().unwrap(), net, 8));
+        assert!(!ipv4_in_cidr("11.1.2.3".parse().unwrap(), net, 8));
+    }
+
+    #[test]
+    fn test_parse_cidr_defaults_to_single_host() {
+        let (addr, bits) = parse_cidr("203.0.113.5").unwrap();
+        assert_eq!(addr, "203.0.113.5".parse::<Ipv4Addr>().unwrap());
+        assert_eq!(bits, 32);
+    }
+
+    #[test]
+    fn test_parse_cidr_reads_prefix() {
+        let (addr, bits) = parse_cidr("203.0.113.0/24").unwrap();
+        assert_eq!(addr, "203.0.113.0".parse::<Ipv4Addr>().unwrap());
+        assert_eq!(bits, 24);
+    }
+
+    #[test]
+    fn test_is_range_blocked_allow_overrides_deny() {
+        let allow = vec![("10.0.0.0".parse().unwrap(), 8)];
+        let deny = vec![("10.0.0.0".parse().unwrap(), 8)];
+        assert!(!is_range_blocked(&"10.1.2.3".parse().unwrap(), &allow, &deny));
+    }
+
+    #[test]
+    fn test_is_range_blocked_deny_extends_builtin_ranges() {
+        let deny = vec![("203.0.113.0".parse().unwrap(), 24)];
+        assert!(is_range_blocked(&"203.0.113.9".parse().unwrap(), &[], &deny));
+        assert!(!is_range_blocked(&"203.0.114.9".parse().unwrap(), &[], &deny));
+    }
+}
@@ -0,0 +1,96 @@
+use crate::db::SqliteDatabase;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+#[derive(Debug, Deserialize)]
+struct CrtShEntry {
+    name_value: String,
+}
+
+/// Query crt.sh's certificate-transparency log search for every name ever
+/// issued a certificate under `domain`, splitting multi-line `name_value`
+/// entries and stripping leading wildcard labels.
+async fn query_crtsh(client: &reqwest::Client, domain: &str) -> Vec<String> {
+    let url = format!("https://crt.sh/?q=%25.{}&output=json", domain);
+    let entries: Vec<CrtShEntry> = match client.get(&url).send().await {
+        Ok(resp) => match resp.json().await {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!(
+                    "Subdomain Enum: failed to parse crt.sh response for {}: {}",
+                    domain, e
+                );
+                return Vec::new();
+            }
+        },
+        Err(e) => {
+            eprintln!("Subdomain Enum: crt.sh request failed for {}: {}", domain, e);
+            return Vec::new();
+        }
+    };
+
+    let mut names = HashSet::new();
+    for entry in entries {
+        for line in entry.name_value.split('\n') {
+            let cleaned = line.trim().trim_start_matches("*.").to_lowercase();
+            if !cleaned.is_empty() {
+                names.insert(cleaned);
+            }
+        }
+    }
+    names.into_iter().collect()
+}
+
+/// Confirm a CT-log candidate actually resolves before it gets seeded as an
+/// asset, so stale or decommissioned certificate entries don't pollute scope.
+async fn resolves(host: &str) -> bool {
+    tokio::net::lookup_host((host, 0))
+        .await
+        .map(|mut addrs| addrs.next().is_some())
+        .unwrap_or(false)
+}
+
+/// Passively enumerate subdomains for every authorized domain via
+/// certificate-transparency logs, seeding confirmed, in-scope, resolvable
+/// hosts as `Subdomain` assets. This is the passive counterpart to the
+/// recursive monitor's link-scraping discovery: it finds hosts that were
+/// never linked from a crawled page body. Run on a slower cadence than the
+/// main scan tick since crt.sh is a shared, rate-sensitive public resource.
+pub async fn run_passive_enumeration(db: &SqliteDatabase) {
+    let authorized_domains = match db.get_authorized_domains() {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("Subdomain Enum: failed to load authorized domains: {}", e);
+            return;
+        }
+    };
+
+    for domain in &authorized_domains {
+        let candidates = query_crtsh(&db.client, domain).await;
+        if candidates.is_empty() {
+            continue;
+        }
+        println!(
+            "Subdomain Enum: {} candidate(s) for {}",
+            candidates.len(),
+            domain
+        );
+
+        for host in candidates {
+            let is_authorized = authorized_domains
+                .iter()
+                .any(|d| &host == d || host.ends_with(&format!(".{}", d)));
+            if !is_authorized {
+                continue;
+            }
+            if !resolves(&host).await {
+                continue;
+            }
+
+            let url = format!("https://{}", host);
+            if let Err(e) = db.add_asset(&url, "Subdomain", None, true, false, 0) {
+                eprintln!("Subdomain Enum: failed to add asset {}: {}", url, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use futures::StreamExt;
+use reqwest::Response;
+
+/// Default cap on response bodies read by discovery/replay code paths, to
+/// keep a single large or hostile endpoint from exhausting memory while
+/// scanning hundreds of subdomains.
+pub const DEFAULT_MAX_BODY_BYTES: usize = 5 * 1024 * 1024;
+
+/// Result of a capped body read: the text decoded so far (best-effort,
+/// lossy) and whether the cap was hit before the stream ended.
+pub struct BoundedBody {
+    pub text: String,
+    pub truncated: bool,
+}
+
+/// Read `resp`'s body up to `max_bytes`, short-circuiting on `Content-Length`
+/// when it already exceeds the cap, and otherwise aborting the byte stream
+/// the moment the accumulated length crosses the cap.
+pub async fn read_bounded(resp: Response, max_bytes: usize) -> BoundedBody {
+    if let Some(len) = resp.content_length() {
+        if len as usize > max_bytes {
+            return BoundedBody {
+                text: String::new(),
+                truncated: true,
+            };
+        }
+    }
+
+    let mut buf: Vec<u8> = Vec::new();
+    let mut truncated = false;
+    let mut stream = resp.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let Ok(chunk) = chunk else { break };
+        buf.extend_from_slice(&chunk);
+        if buf.len() > max_bytes {
+            truncated = true;
+            break;
+        }
+    }
+
+    BoundedBody {
+        text: String::from_utf8_lossy(&buf).into_owned(),
+        truncated,
+    }
+}
@@ -2,16 +2,79 @@ use aes_gcm::{
     aead::{Aead, KeyInit},
     Aes256Gcm, Key, Nonce,
 };
+use argon2::{Algorithm, Argon2, Params, Version};
 use base64::{engine::general_purpose, Engine as _};
+use bip39::Mnemonic;
 use keyring::Entry;
 use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::env;
+use zeroize::Zeroize;
 
 const SERVICE_NAME: &str = "apex-security-auditor";
 const USER_NAME: &str = "encryption-key";
+/// Env var holding a base64-encoded 32-byte key, checked before the
+/// keyring so CI and other headless runs can supply one without a real
+/// OS keyring available.
+const MASTER_KEY_ENV_VAR: &str = "APEX_MASTER_KEY";
+/// Keyring entry whose mere presence marks the legacy hardcoded key as
+/// retired, set by [`CryptoManager::retire_legacy_key`] once a migration has
+/// confirmed nothing is encrypted under it anymore.
+const LEGACY_KEY_RETIRED_ENTRY: &str = "legacy-key-retired";
+
+/// Which key a [`CryptoManager::decrypt_key_source`] call actually used to
+/// recover the plaintext, so callers migrating old data can tell a
+/// still-legacy row from an already-current one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeySource {
+    Primary,
+    Legacy,
+}
+
+/// 32 bytes of key material that overwrite themselves with zeros on drop,
+/// so a `CryptoManager` going out of scope (or an early return mid-derive)
+/// doesn't leave the raw key sitting in freed memory for a later allocation
+/// to stumble onto.
+struct SafeKey([u8; 32]);
+
+impl Drop for SafeKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Keyring entry holding the [`PassphraseParams`] blob when the vault is in
+/// passphrase mode. Deliberately a separate entry from `USER_NAME` so a
+/// passphrase-mode vault never has a raw key sitting in the keyring at all.
+const PASSPHRASE_PARAMS_USER_NAME: &str = "vault-passphrase-params";
+
+/// Fixed plaintext encrypted under a candidate key and compared on
+/// `unlock`: if it doesn't decrypt back to this, the passphrase was wrong.
+const SENTINEL_PLAINTEXT: &str = "apex-vault-sentinel-v1";
+
+/// Argon2id cost parameters and the salt they were run with, persisted
+/// alongside an encrypted sentinel so the key itself never has to be
+/// stored -- only what's needed to re-derive and verify it from the
+/// passphrase.
+#[derive(Serialize, Deserialize)]
+struct PassphraseParams {
+    salt: String, // base64
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    sentinel: String, // base64 AES-256-GCM(derived_key, SENTINEL_PLAINTEXT)
+}
+
+const ARGON2_M_COST: u32 = 19 * 1024; // 19 MiB, the OWASP-recommended minimum
+const ARGON2_T_COST: u32 = 2;
+const ARGON2_P_COST: u32 = 1;
 
 pub struct CryptoManager {
-    key: Key<Aes256Gcm>,
-    legacy_key: Key<Aes256Gcm>,
+    key: SafeKey,
+    /// `None` once [`Self::retire_legacy_key`] has been called -- at that
+    /// point `decrypt` no longer falls back to it, so the weak hardcoded key
+    /// doesn't have to live in memory indefinitely.
+    legacy_key: Option<SafeKey>,
 }
 
 impl Default for CryptoManager {
@@ -21,15 +84,31 @@ impl Default for CryptoManager {
 }
 
 impl CryptoManager {
+    /// Keyring-random-key mode: the default when no passphrase has been
+    /// set via [`Self::set_passphrase`]. Use [`Self::unlock`] instead when
+    /// the vault is in passphrase mode.
     pub fn new() -> Self {
-        // 1. Setup Legacy Key (for migration/fallback)
-        let legacy_src = b"apex-security-auditor-secret-key-32b";
-        let legacy_key = Key::<Aes256Gcm>::from_slice(&legacy_src[..32]);
+        // Bootstrapping from an env var takes priority over the keyring, so
+        // CI and other headless runs can supply a key without a real OS
+        // keyring backing it.
+        if let Ok(mut encoded) = env::var(MASTER_KEY_ENV_VAR) {
+            let decoded = general_purpose::STANDARD.decode(&encoded).ok();
+            encoded.zeroize();
+            if let Some(mut bytes) = decoded {
+                if bytes.len() == 32 {
+                    let mut key_bytes = [0u8; 32];
+                    key_bytes.copy_from_slice(&bytes);
+                    bytes.zeroize();
+                    return Self::from_key(key_bytes);
+                }
+                bytes.zeroize();
+            }
+        }
 
-        // 2. Setup Secure Key (from OS Keyring)
+        // Setup Secure Key (from OS Keyring)
         let entry = Entry::new(SERVICE_NAME, USER_NAME).ok();
 
-        let key_bytes = if let Some(entry) = &entry {
+        let mut key_vec = if let Some(entry) = &entry {
             match entry.get_password() {
                 Ok(password) => {
                     // Start with decoding
@@ -51,14 +130,146 @@ impl CryptoManager {
             key.to_vec()
         };
 
-        let key = Key::<Aes256Gcm>::from_slice(&key_bytes);
+        let mut key_bytes = [0u8; 32];
+        key_bytes.copy_from_slice(&key_vec);
+        key_vec.zeroize();
+        Self::from_key(key_bytes)
+    }
+
+    /// Switch the vault to passphrase mode: derive a fresh key from
+    /// `passphrase` via Argon2id under a new random salt, store the salt,
+    /// cost parameters, and a sentinel ciphertext in the keyring (never the
+    /// key itself), and return a `CryptoManager` using the derived key.
+    /// Existing data encrypted under the previous keyring-random key is
+    /// unreadable until re-encrypted under the new one.
+    pub fn set_passphrase(passphrase: &str) -> Result<Self, String> {
+        let mut salt = [0u8; 16];
+        thread_rng().fill_bytes(&mut salt);
+
+        let key_bytes = derive_key(
+            passphrase,
+            &salt,
+            ARGON2_M_COST,
+            ARGON2_T_COST,
+            ARGON2_P_COST,
+        )?;
+
+        let manager = Self::from_key(key_bytes);
+        let sentinel = manager.encrypt(SENTINEL_PLAINTEXT)?;
+
+        let params = PassphraseParams {
+            salt: general_purpose::STANDARD.encode(salt),
+            m_cost: ARGON2_M_COST,
+            t_cost: ARGON2_T_COST,
+            p_cost: ARGON2_P_COST,
+            sentinel,
+        };
+        let serialized =
+            serde_json::to_string(&params).map_err(|e| format!("Failed to serialize passphrase params: {}", e))?;
+
+        let entry = Entry::new(SERVICE_NAME, PASSPHRASE_PARAMS_USER_NAME)
+            .map_err(|e| format!("Failed to open keyring entry: {}", e))?;
+        entry
+            .set_password(&serialized)
+            .map_err(|e| format!("Failed to persist passphrase params: {}", e))?;
+
+        Ok(manager)
+    }
+
+    /// Re-derive the passphrase-mode key and verify it against the stored
+    /// sentinel. Returns `Err` on a wrong passphrase or if the vault isn't
+    /// in passphrase mode, rather than silently returning a `CryptoManager`
+    /// that would just fail to decrypt every row.
+    pub fn unlock(passphrase: &str) -> Result<Self, String> {
+        let entry = Entry::new(SERVICE_NAME, PASSPHRASE_PARAMS_USER_NAME)
+            .map_err(|e| format!("Failed to open keyring entry: {}", e))?;
+        let serialized = entry
+            .get_password()
+            .map_err(|_| "Vault is not in passphrase mode".to_string())?;
+        let params: PassphraseParams = serde_json::from_str(&serialized)
+            .map_err(|e| format!("Failed to parse stored passphrase params: {}", e))?;
+
+        let salt = general_purpose::STANDARD
+            .decode(&params.salt)
+            .map_err(|e| format!("Failed to decode stored salt: {}", e))?;
+        let key_bytes = derive_key(passphrase, &salt, params.m_cost, params.t_cost, params.p_cost)?;
 
+        let manager = Self::from_key(key_bytes);
+        match manager.decrypt(&params.sentinel) {
+            Ok(plaintext) if plaintext == SENTINEL_PLAINTEXT => Ok(manager),
+            _ => Err("Incorrect passphrase".to_string()),
+        }
+    }
+
+    /// Encode the primary key as a 24-word BIP39 mnemonic (11 bits per word
+    /// over the 256-bit key plus an 8-bit checksum group), so it can be
+    /// written down and restored on a machine with no access to the
+    /// original OS keyring entry.
+    pub fn export_recovery_phrase(&self) -> Result<String, String> {
+        Mnemonic::from_entropy(&self.key.0)
+            .map(|m| m.to_string())
+            .map_err(|e| format!("Failed to encode recovery phrase: {}", e))
+    }
+
+    /// Parse a recovery phrase produced by [`Self::export_recovery_phrase`],
+    /// validating its checksum, and restore the vault to the key it encodes.
+    /// The recovered key is written back into the keyring so the vault
+    /// reverts to keyring-random-key mode using this key going forward.
+    pub fn restore_from_phrase(words: &str) -> Result<Self, String> {
+        let mnemonic = Mnemonic::parse_normalized(words)
+            .map_err(|e| format!("Invalid recovery phrase: {}", e))?;
+        let entropy = mnemonic.to_entropy();
+        if entropy.len() != 32 {
+            return Err("Recovery phrase does not encode a 256-bit key".to_string());
+        }
+        let mut key_bytes = [0u8; 32];
+        key_bytes.copy_from_slice(&entropy);
+
+        let entry = Entry::new(SERVICE_NAME, USER_NAME)
+            .map_err(|e| format!("Failed to open keyring entry: {}", e))?;
+        entry
+            .set_password(&general_purpose::STANDARD.encode(key_bytes))
+            .map_err(|e| format!("Failed to persist recovered key: {}", e))?;
+
+        Ok(Self::from_key(key_bytes))
+    }
+
+    fn from_key(key: [u8; 32]) -> Self {
+        let mut legacy_bytes = *b"apex-security-auditor-secret-key-32b";
+        let mut legacy_key_bytes = [0u8; 32];
+        legacy_key_bytes.copy_from_slice(&legacy_bytes[..32]);
+        legacy_bytes.zeroize();
+        let legacy_key = if Self::legacy_key_retired() {
+            None
+        } else {
+            Some(SafeKey(legacy_key_bytes))
+        };
         Self {
-            key: *key,
-            legacy_key: *legacy_key,
+            key: SafeKey(key),
+            legacy_key,
         }
     }
 
+    /// Whether [`Self::retire_legacy_key`] has previously confirmed every
+    /// row is migrated off the legacy key.
+    fn legacy_key_retired() -> bool {
+        Entry::new(SERVICE_NAME, LEGACY_KEY_RETIRED_ENTRY)
+            .and_then(|e| e.get_password())
+            .is_ok()
+    }
+
+    /// Stop loading the legacy key at startup. Callers must only invoke this
+    /// after confirming (e.g. via a `migrate_to_current_key`-style routine)
+    /// that no stored data still decrypts under it -- once retired,
+    /// [`Self::decrypt`] can no longer read such rows at all.
+    pub fn retire_legacy_key() -> Result<(), String> {
+        let entry = Entry::new(SERVICE_NAME, LEGACY_KEY_RETIRED_ENTRY)
+            .map_err(|e| format!("Failed to open keyring entry: {}", e))?;
+        entry
+            .set_password("true")
+            .map_err(|e| format!("Failed to persist retirement flag: {}", e))
+    }
+
     fn generate_and_store_key(entry: &Entry) -> Vec<u8> {
         let mut key = [0u8; 32];
         thread_rng().fill_bytes(&mut key);
@@ -74,11 +285,11 @@ impl CryptoManager {
     }
 
     pub fn encrypt(&self, data: &str) -> Result<String, String> {
-        self.encrypt_with_key(&self.key, data)
+        self.encrypt_with_key(&self.key.0, data)
     }
 
-    fn encrypt_with_key(&self, key: &Key<Aes256Gcm>, data: &str) -> Result<String, String> {
-        let cipher = Aes256Gcm::new(key);
+    fn encrypt_with_key(&self, key: &[u8; 32], data: &str) -> Result<String, String> {
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
         let mut nonce_bytes = [0u8; 12];
         thread_rng().fill_bytes(&mut nonce_bytes);
         let nonce = Nonce::from_slice(&nonce_bytes);
@@ -95,6 +306,14 @@ impl CryptoManager {
     }
 
     pub fn decrypt(&self, encoded_data: &str) -> Result<String, String> {
+        self.decrypt_key_source(encoded_data).map(|(plaintext, _)| plaintext)
+    }
+
+    /// Like [`Self::decrypt`], but also reports which key recovered the
+    /// plaintext -- used by a migration pass to find rows still encrypted
+    /// under the legacy key so they can be re-encrypted under the primary
+    /// one.
+    pub fn decrypt_key_source(&self, encoded_data: &str) -> Result<(String, KeySource), String> {
         let combined = general_purpose::STANDARD
             .decode(encoded_data)
             .map_err(|e| format!("Base64 decode failed: {}", e))?;
@@ -107,21 +326,45 @@ impl CryptoManager {
         let nonce = Nonce::from_slice(nonce_bytes);
 
         // Try primary key first
-        let cipher_primary = Aes256Gcm::new(&self.key);
-        match cipher_primary.decrypt(nonce, ciphertext) {
-            Ok(plaintext) => {
-                String::from_utf8(plaintext).map_err(|e| format!("UTF-8 conversion failed: {}", e))
-            }
-            Err(_) => {
-                // Try legacy key
-                let cipher_legacy = Aes256Gcm::new(&self.legacy_key);
-                let decrypted_bytes = cipher_legacy
-                    .decrypt(nonce, ciphertext)
-                    .map_err(|e| format!("Decryption failed (tried both keys): {}", e))?;
-
-                String::from_utf8(decrypted_bytes)
-                    .map_err(|e| format!("UTF-8 conversion failed: {}", e))
-            }
+        let cipher_primary = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.key.0));
+        if let Ok(plaintext) = cipher_primary.decrypt(nonce, ciphertext) {
+            let plaintext = String::from_utf8(plaintext)
+                .map_err(|e| format!("UTF-8 conversion failed: {}", e))?;
+            return Ok((plaintext, KeySource::Primary));
         }
+
+        // Fall back to the legacy key, if it hasn't been retired.
+        let legacy_key = self
+            .legacy_key
+            .as_ref()
+            .ok_or_else(|| "Decryption failed: legacy key has been retired".to_string())?;
+        let cipher_legacy = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&legacy_key.0));
+        let decrypted_bytes = cipher_legacy
+            .decrypt(nonce, ciphertext)
+            .map_err(|e| format!("Decryption failed (tried both keys): {}", e))?;
+
+        let plaintext = String::from_utf8(decrypted_bytes)
+            .map_err(|e| format!("UTF-8 conversion failed: {}", e))?;
+        Ok((plaintext, KeySource::Legacy))
     }
 }
+
+/// Run Argon2id over `passphrase` with the given cost parameters, producing
+/// a 32-byte AES-256 key.
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+) -> Result<[u8; 32], String> {
+    let params = Params::new(m_cost, t_cost, p_cost, Some(32))
+        .map_err(|e| format!("Invalid Argon2 parameters: {}", e))?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+
+    let mut key_bytes = [0u8; 32];
+    argon2
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key_bytes)
+        .map_err(|e| format!("Key derivation failed: {}", e))?;
+    Ok(key_bytes)
+}
@@ -0,0 +1,6 @@
+pub mod bounded_body;
+pub mod crypto;
+pub mod openapi_parser;
+pub mod redaction;
+pub mod sequence_engine;
+pub mod url_utils;
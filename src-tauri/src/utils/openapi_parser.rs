@@ -4,6 +4,7 @@
 //! documented API endpoints for Shadow API Detection. Endpoints not found in
 //! the spec will be flagged as "Shadow API".
 
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -58,6 +59,31 @@ pub struct DocumentedEndpoint {
     pub method: String,
     /// Optional description/summary from the spec
     pub summary: Option<String>,
+    /// Resolved security scheme names that apply to this operation (an
+    /// operation-level `security` overrides the document-level default; an
+    /// explicit `security: []` opts the operation out of auth entirely).
+    /// Empty means the endpoint requires no authentication.
+    #[serde(default)]
+    pub security: Vec<String>,
+    /// Declared constraint for each `{name}` path parameter's schema, used
+    /// only by [`OpenApiSpec::matches_endpoint_strict`]/`find_endpoint_strict`
+    /// -- loose matching (`matches_endpoint`/`find_endpoint`) ignores this
+    /// and keeps treating any non-empty segment as a match.
+    #[serde(default)]
+    pub path_params: Vec<(String, ParamConstraint)>,
+}
+
+/// A path parameter's declared schema constraint, used only in strict mode.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ParamConstraint {
+    /// Schema `type: integer` -- segment must parse as an integer.
+    Integer,
+    /// Schema `type: number` -- segment must parse as a number.
+    Number,
+    /// Schema `pattern: <regex>` -- segment must match the regex.
+    Pattern(String),
+    /// Schema `type: string` or no constraint -- any non-empty segment matches.
+    String,
 }
 
 /// Holds parsed OpenAPI specification information
@@ -69,6 +95,12 @@ pub struct OpenApiSpec {
     pub title: String,
     /// API version
     pub version: String,
+    /// Segment trie over `endpoints`, built once after parsing so repeated
+    /// lookups (every intercepted proxy request) are O(S) instead of
+    /// O(N*S) linear scans. Not part of the spec's own data -- an index
+    /// derived from `endpoints` -- so it's excluded from serialization.
+    #[serde(skip)]
+    trie: PathTrieNode,
 }
 
 impl OpenApiSpec {
@@ -78,33 +110,180 @@ impl OpenApiSpec {
             endpoints: Vec::new(),
             title,
             version,
+            trie: PathTrieNode::default(),
+        }
+    }
+
+    /// (Re)build `trie` from `endpoints`. Called once by
+    /// [`validate_and_parse_root`] right after parsing; exposed so callers
+    /// that build an `OpenApiSpec` by hand (e.g. tests) can opt in too.
+    pub fn rebuild_trie(&mut self) {
+        let mut trie = PathTrieNode::default();
+        for endpoint in &self.endpoints {
+            trie.insert(&endpoint.path, &endpoint.method, endpoint.clone());
         }
+        self.trie = trie;
     }
 
     /// Check if a given path and method matches any documented endpoint
     /// Handles path parameters (e.g., `/users/{id}` matches `/users/123`)
     pub fn matches_endpoint(&self, path: &str, method: &str) -> bool {
-        let normalized_method = method.to_uppercase();
-        self.endpoints.iter().any(|endpoint| {
-            endpoint.method == normalized_method && paths_match(&endpoint.path, path)
-        })
+        self.find_endpoint(path, method).is_some()
     }
 
     /// Find all endpoints that match a given path (regardless of method)
     pub fn find_endpoints_by_path(&self, path: &str) -> Vec<&DocumentedEndpoint> {
-        self.endpoints
-            .iter()
-            .filter(|endpoint| paths_match(&endpoint.path, path))
-            .collect()
+        let segments: Vec<&str> = path.split('/').collect();
+        let mut out = Vec::new();
+        self.trie.collect_by_path(&segments, &mut out);
+        out
     }
 
     /// Find endpoint by exact path and method
     pub fn find_endpoint(&self, path: &str, method: &str) -> Option<&DocumentedEndpoint> {
+        let normalized_method = method.to_uppercase();
+        self.trie.lookup(path, &normalized_method)
+    }
+
+    /// Like [`Self::matches_endpoint`], but additionally requires every
+    /// `{name}` path parameter's concrete segment to satisfy its declared
+    /// schema constraint (`integer`/`number`/`pattern`) -- opt-in, since
+    /// loose matching is the default and remains unaffected.
+    pub fn matches_endpoint_strict(&self, path: &str, method: &str) -> bool {
+        self.find_endpoint_strict(path, method).is_some()
+    }
+
+    /// Like [`Self::find_endpoint`], but constraint-checked -- see
+    /// [`Self::matches_endpoint_strict`]. Falls back to a linear scan rather
+    /// than the trie since constraint checking isn't part of the hot
+    /// shadow-API path the trie was built to speed up.
+    pub fn find_endpoint_strict(&self, path: &str, method: &str) -> Option<&DocumentedEndpoint> {
         let normalized_method = method.to_uppercase();
         self.endpoints.iter().find(|endpoint| {
-            endpoint.method == normalized_method && paths_match(&endpoint.path, path)
+            endpoint.method == normalized_method
+                && paths_match_strict(&endpoint.path, path, &endpoint.path_params)
         })
     }
+
+    /// Documented endpoints whose effective `security` is empty -- likely
+    /// Broken Object/Function Level Authorization candidates (e.g. a
+    /// `DELETE /users/{id}` with no security requirement at all).
+    pub fn unauthenticated_endpoints(&self) -> Vec<&DocumentedEndpoint> {
+        self.endpoints
+            .iter()
+            .filter(|endpoint| endpoint.security.is_empty())
+            .collect()
+    }
+}
+
+/// Prefix trie over documented path segments, keyed on literal segments plus
+/// at most one special "parameter" child representing any `{...}` segment,
+/// plus an optional catch-all bucket for a terminal `{name:.*}`/`{name+}`
+/// segment. Each terminal node holds a `method -> DocumentedEndpoint` map.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct PathTrieNode {
+    literal_children: HashMap<String, PathTrieNode>,
+    param_child: Option<Box<PathTrieNode>>,
+    catchall_methods: Option<HashMap<String, DocumentedEndpoint>>,
+    methods: HashMap<String, DocumentedEndpoint>,
+}
+
+impl PathTrieNode {
+    /// Insert `endpoint`, splitting `path` on `/` and walking/creating nodes;
+    /// a `{...}` segment routes to `param_child`, a catch-all segment (only
+    /// meaningful as the last one) is stored on `catchall_methods` instead
+    /// of creating a further child.
+    fn insert(&mut self, path: &str, method: &str, endpoint: DocumentedEndpoint) {
+        let segments: Vec<&str> = path.split('/').collect();
+        self.insert_segments(&segments, method, endpoint);
+    }
+
+    fn insert_segments(&mut self, segments: &[&str], method: &str, endpoint: DocumentedEndpoint) {
+        let Some((seg, rest)) = segments.split_first() else {
+            self.methods.insert(method.to_string(), endpoint);
+            return;
+        };
+
+        if is_catchall_segment(seg) {
+            self.catchall_methods
+                .get_or_insert_with(HashMap::new)
+                .insert(method.to_string(), endpoint);
+            return;
+        }
+
+        if seg.starts_with('{') && seg.ends_with('}') {
+            self.param_child
+                .get_or_insert_with(|| Box::new(PathTrieNode::default()))
+                .insert_segments(rest, method, endpoint);
+        } else {
+            self.literal_children
+                .entry((*seg).to_string())
+                .or_default()
+                .insert_segments(rest, method, endpoint);
+        }
+    }
+
+    /// O(S) lookup: split `path` on `/` and walk the trie, trying the
+    /// literal child first, falling back to the parameter child, and
+    /// finally to a catch-all bucket at whichever node has one.
+    fn lookup(&self, path: &str, method: &str) -> Option<&DocumentedEndpoint> {
+        let segments: Vec<&str> = path.split('/').collect();
+        self.lookup_segments(&segments, method)
+    }
+
+    fn lookup_segments(&self, segments: &[&str], method: &str) -> Option<&DocumentedEndpoint> {
+        let Some((seg, rest)) = segments.split_first() else {
+            return self
+                .methods
+                .get(method)
+                .or_else(|| self.catchall_methods.as_ref().and_then(|m| m.get(method)));
+        };
+
+        if let Some(child) = self.literal_children.get(*seg) {
+            if let Some(ep) = child.lookup_segments(rest, method) {
+                return Some(ep);
+            }
+        }
+
+        if !seg.is_empty() {
+            if let Some(child) = &self.param_child {
+                if let Some(ep) = child.lookup_segments(rest, method) {
+                    return Some(ep);
+                }
+            }
+        }
+
+        self.catchall_methods.as_ref().and_then(|m| m.get(method))
+    }
+
+    /// Collect every endpoint reachable by `segments`, unlike [`Self::lookup_segments`]
+    /// this explores the literal child *and* the parameter child (both can
+    /// legitimately match the same concrete path, e.g. a literal `/users/list`
+    /// alongside a parameterized `/users/{id}`), since there's no method to
+    /// disambiguate on here.
+    fn collect_by_path<'a>(&'a self, segments: &[&str], out: &mut Vec<&'a DocumentedEndpoint>) {
+        let Some((seg, rest)) = segments.split_first() else {
+            out.extend(self.methods.values());
+            if let Some(catchall) = &self.catchall_methods {
+                out.extend(catchall.values());
+            }
+            return;
+        };
+
+        if let Some(child) = self.literal_children.get(*seg) {
+            child.collect_by_path(rest, out);
+        }
+
+        if !seg.is_empty() {
+            if let Some(child) = &self.param_child {
+                child.collect_by_path(rest, out);
+            }
+        }
+
+        if let Some(catchall) = &self.catchall_methods {
+            out.extend(catchall.values());
+        }
+    }
 }
 
 // ============================================
@@ -118,6 +297,17 @@ struct OpenApiRoot {
     swagger: Option<String>, // For Swagger 2.x detection
     info: Option<Info>,
     paths: Option<HashMap<String, PathItem>>,
+    /// Swagger 2.0's document-wide path prefix, prepended to every path key
+    /// when normalizing a 2.0 spec onto [`OpenApiSpec`]. Absent in OpenAPI 3.x.
+    #[serde(rename = "basePath")]
+    base_path: Option<String>,
+    /// Parsed for schema completeness (`type: apiKey/http/oauth2/openIdConnect`
+    /// scheme definitions); resolution only needs the scheme *names* that
+    /// `security` requirements reference, not these definitions.
+    #[allow(dead_code)]
+    components: Option<Components>,
+    /// Document-level default security requirements; overridden per-operation.
+    security: Option<Vec<SecurityRequirement>>,
 }
 
 /// Info section of OpenAPI spec
@@ -127,6 +317,29 @@ struct Info {
     version: Option<String>,
 }
 
+/// `components` section -- only `securitySchemes` is consumed today.
+#[derive(Debug, Deserialize)]
+struct Components {
+    #[serde(rename = "securitySchemes")]
+    #[allow(dead_code)]
+    security_schemes: Option<HashMap<String, SecurityScheme>>,
+}
+
+/// One entry under `components.securitySchemes`. The `scheme_type` field is
+/// kept for completeness/debugging even though resolution only needs the
+/// scheme's *name* (the map key) to decide whether an endpoint is protected.
+#[derive(Debug, Deserialize)]
+struct SecurityScheme {
+    #[serde(rename = "type")]
+    #[allow(dead_code)]
+    scheme_type: Option<String>,
+}
+
+/// A single security requirement alternative: scheme name -> required scopes
+/// (empty for non-OAuth2 schemes). `security: [ { "oauth2": ["read"] }, {} ]`
+/// means "oauth2 with the read scope, OR no auth at all".
+type SecurityRequirement = HashMap<String, Vec<String>>;
+
 /// Path item containing HTTP methods
 #[derive(Debug, Deserialize)]
 struct PathItem {
@@ -138,6 +351,10 @@ struct PathItem {
     options: Option<Operation>,
     head: Option<Operation>,
     trace: Option<Operation>,
+    /// Path-level parameters, shared by every operation on this path item
+    /// unless an operation redeclares the same name.
+    #[serde(default)]
+    parameters: Vec<Parameter>,
 }
 
 /// Operation details for an HTTP method
@@ -149,6 +366,103 @@ struct Operation {
     operation_id: Option<String>,
     #[allow(dead_code)]
     tags: Option<Vec<String>>,
+    /// Operation-level override of the document's default `security`. When
+    /// present (including an explicit empty array) it wins outright.
+    security: Option<Vec<SecurityRequirement>>,
+    #[serde(default)]
+    parameters: Vec<Parameter>,
+}
+
+/// An OpenAPI 3.x `Parameter Object`/Swagger 2.0 parameter. 3.x nests the
+/// type/pattern under `schema`; 2.0 puts them directly on the parameter, so
+/// both `schema` and the top-level `type`/`pattern` fields are read, with
+/// `schema` preferred when both are present.
+#[derive(Debug, Deserialize)]
+struct Parameter {
+    name: String,
+    #[serde(rename = "in")]
+    location: Option<String>,
+    schema: Option<ParamSchema>,
+    #[serde(rename = "type")]
+    param_type: Option<String>,
+    pattern: Option<String>,
+}
+
+/// The subset of a 3.x `schema` object needed to classify a path parameter.
+#[derive(Debug, Deserialize)]
+struct ParamSchema {
+    #[serde(rename = "type")]
+    schema_type: Option<String>,
+    pattern: Option<String>,
+}
+
+impl Parameter {
+    fn is_path_param(&self) -> bool {
+        self.location.as_deref() == Some("path")
+    }
+
+    /// Resolve this parameter's declared schema into a [`ParamConstraint`],
+    /// preferring 3.x's nested `schema` and falling back to 2.0's top-level
+    /// `type`/`pattern`. A `pattern` always wins over a bare `type`.
+    fn constraint(&self) -> ParamConstraint {
+        let (param_type, pattern) = match &self.schema {
+            Some(schema) => (schema.schema_type.clone(), schema.pattern.clone()),
+            None => (self.param_type.clone(), self.pattern.clone()),
+        };
+
+        if let Some(pattern) = pattern {
+            return ParamConstraint::Pattern(pattern);
+        }
+        match param_type.as_deref() {
+            Some("integer") => ParamConstraint::Integer,
+            Some("number") => ParamConstraint::Number,
+            _ => ParamConstraint::String,
+        }
+    }
+}
+
+/// Merge path-level and operation-level parameters (operation overrides
+/// path-level on a name collision, per the OpenAPI spec) and resolve each
+/// declared `path`-location parameter into a `(name, constraint)` pair.
+fn path_param_constraints(
+    path_level: &[Parameter],
+    operation_level: &[Parameter],
+) -> Vec<(String, ParamConstraint)> {
+    let mut by_name: HashMap<&str, &Parameter> = HashMap::new();
+    for param in path_level.iter().filter(|p| p.is_path_param()) {
+        by_name.insert(&param.name, param);
+    }
+    for param in operation_level.iter().filter(|p| p.is_path_param()) {
+        by_name.insert(&param.name, param);
+    }
+
+    let mut constraints: Vec<(String, ParamConstraint)> = by_name
+        .into_iter()
+        .map(|(name, param)| (name.to_string(), param.constraint()))
+        .collect();
+    constraints.sort_by(|a, b| a.0.cmp(&b.0));
+    constraints
+}
+
+/// Resolve the effective list of security scheme names for an operation:
+/// an operation's own `security` (even `Some(vec![])`) overrides the
+/// document-level default entirely.
+fn resolve_security(
+    operation_security: &Option<Vec<SecurityRequirement>>,
+    document_default: &[SecurityRequirement],
+) -> Vec<String> {
+    let requirements: &[SecurityRequirement] = match operation_security {
+        Some(reqs) => reqs,
+        None => document_default,
+    };
+
+    let mut names: Vec<String> = requirements
+        .iter()
+        .flat_map(|requirement| requirement.keys().cloned())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
 }
 
 // ============================================
@@ -179,8 +493,11 @@ struct Operation {
 /// assert_eq!(spec.title, "My API");
 /// ```
 pub fn parse_openapi_json(content: &str) -> OpenApiResult<OpenApiSpec> {
-    let root: OpenApiRoot =
+    let value: serde_json::Value =
         serde_json::from_str(content).map_err(|e| OpenApiError::InvalidJson(e.to_string()))?;
+    let resolved = resolve_refs(value)?;
+    let root: OpenApiRoot =
+        serde_json::from_value(resolved).map_err(|e| OpenApiError::InvalidJson(e.to_string()))?;
 
     validate_and_parse_root(root)
 }
@@ -210,8 +527,16 @@ pub fn parse_openapi_json(content: &str) -> OpenApiResult<OpenApiSpec> {
 /// assert_eq!(spec.title, "My API");
 /// ```
 pub fn parse_openapi_yaml(content: &str) -> OpenApiResult<OpenApiSpec> {
-    let root: OpenApiRoot =
+    let yaml_value: serde_yaml::Value =
         serde_yaml::from_str(content).map_err(|e| OpenApiError::InvalidYaml(e.to_string()))?;
+    // Transcode through serde's data model into a `serde_json::Value` so the
+    // same `$ref`-resolution pass (written against JSON pointers) works for
+    // both formats.
+    let json_value: serde_json::Value = serde_json::to_value(&yaml_value)
+        .map_err(|e| OpenApiError::InvalidYaml(e.to_string()))?;
+    let resolved = resolve_refs(json_value)?;
+    let root: OpenApiRoot =
+        serde_json::from_value(resolved).map_err(|e| OpenApiError::InvalidYaml(e.to_string()))?;
 
     validate_and_parse_root(root)
 }
@@ -243,18 +568,88 @@ pub fn parse_openapi_auto(content: &str) -> OpenApiResult<OpenApiSpec> {
     parse_openapi_yaml(content)
 }
 
+// ============================================
+// $REF RESOLUTION
+// ============================================
+
+/// Resolve every local `$ref` (a JSON pointer like `#/components/pathItems/Foo`)
+/// found anywhere in the document -- path items, operations, parameters, etc.
+/// all share the same `{ "$ref": "..." }` shape, so this walks the raw value
+/// generically rather than needing per-struct-aware resolution. Splices the
+/// pointed-to value in place of the `$ref` object; a multi-hop chain
+/// (ref -> ref -> ...) is followed via recursion with a visited-set to catch
+/// cycles.
+fn resolve_refs(document: serde_json::Value) -> OpenApiResult<serde_json::Value> {
+    let snapshot = document.clone();
+    let mut resolved = document;
+    let mut visiting = Vec::new();
+    resolve_value(&mut resolved, &snapshot, &mut visiting)?;
+    Ok(resolved)
+}
+
+fn resolve_value(
+    value: &mut serde_json::Value,
+    document: &serde_json::Value,
+    visiting: &mut Vec<String>,
+) -> OpenApiResult<()> {
+    if let serde_json::Value::Object(map) = &*value {
+        if let Some(serde_json::Value::String(ref_path)) = map.get("$ref") {
+            let ref_path = ref_path.clone();
+            if visiting.contains(&ref_path) {
+                return Err(OpenApiError::ParseError(format!(
+                    "Cyclic $ref detected: {}",
+                    ref_path
+                )));
+            }
+
+            let pointer = ref_path.strip_prefix('#').ok_or_else(|| {
+                OpenApiError::ParseError(format!(
+                    "Unresolvable $ref (only local pointers are supported): {}",
+                    ref_path
+                ))
+            })?;
+            let mut target = document
+                .pointer(pointer)
+                .cloned()
+                .ok_or_else(|| OpenApiError::ParseError(format!("Unresolvable $ref: {}", ref_path)))?;
+
+            visiting.push(ref_path);
+            resolve_value(&mut target, document, visiting)?;
+            visiting.pop();
+
+            *value = target;
+            return Ok(());
+        }
+    }
+
+    match value {
+        serde_json::Value::Object(map) => {
+            for v in map.values_mut() {
+                resolve_value(v, document, visiting)?;
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for v in items.iter_mut() {
+                resolve_value(v, document, visiting)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
 // ============================================
 // HELPER FUNCTIONS
 // ============================================
 
-/// Validate the OpenAPI root and extract endpoints
+/// Validate the OpenAPI root and extract endpoints. Branches on whether
+/// `swagger` or `openapi` is present; OpenAPI 3.x remains the primary path,
+/// with Swagger 2.0 normalized onto the same [`OpenApiSpec`] so the trie
+/// matcher and shadow-API logic work unchanged regardless of spec version.
 fn validate_and_parse_root(root: OpenApiRoot) -> OpenApiResult<OpenApiSpec> {
-    // Check version
-    if let Some(ref swagger) = root.swagger {
-        return Err(OpenApiError::InvalidVersion(format!(
-            "Swagger 2.x ({}) is not supported. Please convert to OpenAPI 3.x format.",
-            swagger
-        )));
+    if let Some(swagger_version) = root.swagger.clone() {
+        return parse_swagger2_root(root, &swagger_version);
     }
 
     let openapi_version = root
@@ -279,26 +674,81 @@ fn validate_and_parse_root(root: OpenApiRoot) -> OpenApiResult<OpenApiSpec> {
         .and_then(|i| i.version.clone())
         .unwrap_or_else(|| "Unknown".to_string());
 
+    // Document-level default security requirements, overridden per-operation
+    // by `resolve_security` below.
+    let document_default_security = root.security.unwrap_or_default();
+
     // Extract endpoints from paths
     let mut endpoints = Vec::new();
 
     if let Some(paths) = root.paths {
         for (path, path_item) in paths.iter() {
-            extract_endpoints_from_path(path, path_item, &mut endpoints);
+            extract_endpoints_from_path(path, path_item, &document_default_security, &mut endpoints);
+        }
+    }
+
+    let mut spec = OpenApiSpec {
+        endpoints,
+        title,
+        version,
+        trie: PathTrieNode::default(),
+    };
+    spec.rebuild_trie();
+    Ok(spec)
+}
+
+/// Normalize a Swagger 2.0 document onto the same [`OpenApiSpec`] shape as
+/// OpenAPI 3.x: `basePath` is prepended to every path key, `paths` is walked
+/// with the same method set via [`extract_endpoints_from_path`], and
+/// `info.title`/`info.version` are read the same way as the 3.x path.
+fn parse_swagger2_root(root: OpenApiRoot, swagger_version: &str) -> OpenApiResult<OpenApiSpec> {
+    if !swagger_version.starts_with("2.") {
+        return Err(OpenApiError::InvalidVersion(format!(
+            "Expected Swagger 2.x, got {}",
+            swagger_version
+        )));
+    }
+
+    let info = root.info.as_ref();
+    let title = info
+        .and_then(|i| i.title.clone())
+        .unwrap_or_else(|| "Unknown API".to_string());
+    let version = info
+        .and_then(|i| i.version.clone())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let base_path = root.base_path.clone().unwrap_or_default();
+    let base_path = base_path.trim_end_matches('/');
+    let document_default_security = root.security.unwrap_or_default();
+
+    let mut endpoints = Vec::new();
+    if let Some(paths) = root.paths {
+        for (path, path_item) in paths.iter() {
+            let full_path = format!("{}{}", base_path, path);
+            extract_endpoints_from_path(
+                &full_path,
+                path_item,
+                &document_default_security,
+                &mut endpoints,
+            );
         }
     }
 
-    Ok(OpenApiSpec {
+    let mut spec = OpenApiSpec {
         endpoints,
         title,
         version,
-    })
+        trie: PathTrieNode::default(),
+    };
+    spec.rebuild_trie();
+    Ok(spec)
 }
 
 /// Extract all endpoints from a path item
 fn extract_endpoints_from_path(
     path: &str,
     path_item: &PathItem,
+    document_default_security: &[SecurityRequirement],
     endpoints: &mut Vec<DocumentedEndpoint>,
 ) {
     let methods = [
@@ -315,18 +765,40 @@ fn extract_endpoints_from_path(
     for (method, operation) in methods.iter() {
         if let Some(op) = operation {
             let summary = op.summary.clone().or(op.description.clone());
+            let security = resolve_security(&op.security, document_default_security);
+            let path_params = path_param_constraints(&path_item.parameters, &op.parameters);
             endpoints.push(DocumentedEndpoint {
                 path: path.to_string(),
                 method: method.to_string(),
                 summary,
+                security,
+                path_params,
             });
         }
     }
 }
 
+/// Is `segment` a catch-all/wildcard parameter, i.e. `{name:.*}` or `{name+}`?
+///
+/// Only valid as the *last* segment of a pattern -- a greedy catch-all
+/// anywhere else would swallow segments meant for later literals/params.
+fn is_catchall_segment(segment: &str) -> bool {
+    segment.starts_with('{') && segment.ends_with('}') && {
+        let inner = &segment[1..segment.len() - 1];
+        inner.ends_with(":.*") || inner.ends_with('+')
+    }
+}
+
 /// Check if a documented path pattern matches an actual request path
 ///
-/// Handles OpenAPI path parameters like `{id}`, `{name}`, etc.
+/// Handles OpenAPI path parameters like `{id}`, `{name}`, etc., plus a
+/// terminal catch-all segment (`{rest:.*}` or `{proxy+}`) that greedily
+/// matches one-or-more remaining actual segments joined by `/`. Catch-all
+/// semantics here are greedy-optional: `{rest:.*}` also matches when the
+/// tail is empty (the segment itself is present but nothing follows it),
+/// since `.*` documents "zero or more characters" and specs that mean to
+/// require at least one character should use a `pattern`/`+` constraint
+/// instead.
 ///
 /// # Examples
 /// ```
@@ -334,34 +806,91 @@ fn extract_endpoints_from_path(
 /// assert!(paths_match("/users/{id}", "/users/123"));
 /// assert!(paths_match("/users/{id}/posts/{postId}", "/users/456/posts/789"));
 /// assert!(!paths_match("/users/{id}", "/posts/123"));
+/// assert!(paths_match("/files/{path:.*}", "/files/a/b/c"));
 /// ```
 pub fn paths_match(pattern: &str, actual: &str) -> bool {
     let pattern_parts: Vec<&str> = pattern.split('/').collect();
     let actual_parts: Vec<&str> = actual.split('/').collect();
 
+    if let Some((last, prefix)) = pattern_parts.split_last() {
+        if is_catchall_segment(last) {
+            if actual_parts.len() < prefix.len() {
+                return false;
+            }
+            let actual_prefix = &actual_parts[..prefix.len()];
+            return prefix
+                .iter()
+                .zip(actual_prefix.iter())
+                .all(|(p, a)| segment_matches(p, a));
+        }
+    }
+
     if pattern_parts.len() != actual_parts.len() {
         return false;
     }
 
-    for (p, a) in pattern_parts.iter().zip(actual_parts.iter()) {
-        // Check if this is a path parameter (e.g., {id}, {name})
-        if p.starts_with('{') && p.ends_with('}') {
-            // Path parameter matches any non-empty value
-            if a.is_empty() {
-                return false;
-            }
-        } else if p != a {
-            // Literal path segment must match exactly
-            return false;
-        }
+    pattern_parts
+        .iter()
+        .zip(actual_parts.iter())
+        .all(|(p, a)| segment_matches(p, a))
+}
+
+/// Match one non-catch-all pattern segment against one actual segment:
+/// `{id}`-style parameters match any non-empty value, literals match exactly.
+fn segment_matches(pattern_segment: &str, actual_segment: &str) -> bool {
+    if pattern_segment.starts_with('{') && pattern_segment.ends_with('}') {
+        !actual_segment.is_empty()
+    } else {
+        pattern_segment == actual_segment
+    }
+}
+
+/// Strict variant of [`paths_match`]: a concrete path shape can match while
+/// violating a parameter's declared schema type, e.g. `/users/abc` shape-matches
+/// `/users/{id}` even when the spec declares `id` as an integer. This additionally
+/// checks every non-catch-all `{name}` segment against its `constraints` entry
+/// (by parameter name), if one is declared; an undeclared parameter stays
+/// permissive, matching any non-empty segment just like loose mode.
+pub fn paths_match_strict(pattern: &str, actual: &str, constraints: &[(String, ParamConstraint)]) -> bool {
+    if !paths_match(pattern, actual) {
+        return false;
     }
 
-    true
+    let pattern_parts: Vec<&str> = pattern.split('/').collect();
+    let actual_parts: Vec<&str> = actual.split('/').collect();
+
+    pattern_parts
+        .iter()
+        .zip(actual_parts.iter())
+        .all(|(p, a)| {
+            if is_catchall_segment(p) || !(p.starts_with('{') && p.ends_with('}')) {
+                return true;
+            }
+            let name = &p[1..p.len() - 1];
+            match constraints.iter().find(|(n, _)| n == name) {
+                Some((_, constraint)) => constraint_matches(constraint, a),
+                None => true,
+            }
+        })
+}
+
+/// Does `segment` satisfy `constraint`'s declared schema type?
+fn constraint_matches(constraint: &ParamConstraint, segment: &str) -> bool {
+    match constraint {
+        ParamConstraint::Integer => segment.parse::<i64>().is_ok(),
+        ParamConstraint::Number => segment.parse::<f64>().is_ok(),
+        ParamConstraint::Pattern(pattern) => Regex::new(pattern)
+            .map(|re| re.is_match(segment))
+            .unwrap_or(true),
+        ParamConstraint::String => true,
+    }
 }
 
 /// Convert a path pattern to a regex string for more complex matching
 ///
 /// This is useful when you need regex-based matching instead of simple pattern matching.
+/// A terminal catch-all segment (`{name:.*}` or `{name+}`) emits `.*` instead
+/// of `[^/]+` and is not bound by the usual one-segment-per-slash assumption.
 pub fn path_pattern_to_regex(pattern: &str) -> String {
     let mut regex = String::from("^");
     let parts: Vec<&str> = pattern.split('/').collect();
@@ -370,7 +899,9 @@ pub fn path_pattern_to_regex(pattern: &str) -> String {
         if i > 0 {
             regex.push('/');
         }
-        if part.starts_with('{') && part.ends_with('}') {
+        if i == parts.len() - 1 && is_catchall_segment(part) {
+            regex.push_str(".*");
+        } else if part.starts_with('{') && part.ends_with('}') {
             // Match any non-slash characters for path parameters
             regex.push_str("[^/]+");
         } else {
@@ -516,22 +1047,28 @@ paths: {}
 
     #[test]
     fn test_matches_endpoint() {
-        let spec = OpenApiSpec {
+        let mut spec = OpenApiSpec {
             endpoints: vec![
                 DocumentedEndpoint {
                     path: "/users".to_string(),
                     method: "GET".to_string(),
                     summary: Some("List users".to_string()),
+                    security: Vec::new(),
+                    path_params: Vec::new(),
                 },
                 DocumentedEndpoint {
                     path: "/users/{id}".to_string(),
                     method: "GET".to_string(),
                     summary: Some("Get user".to_string()),
+                    security: Vec::new(),
+                    path_params: Vec::new(),
                 },
             ],
             title: "Test".to_string(),
             version: "1.0".to_string(),
+            trie: PathTrieNode::default(),
         };
+        spec.rebuild_trie();
 
         assert!(spec.matches_endpoint("/users", "GET"));
         assert!(spec.matches_endpoint("/users/123", "GET"));
@@ -539,6 +1076,79 @@ paths: {}
         assert!(!spec.matches_endpoint("/posts", "GET"));
     }
 
+    #[test]
+    fn test_trie_lookup_matches_literal_before_param() {
+        let mut spec = OpenApiSpec {
+            endpoints: vec![
+                DocumentedEndpoint {
+                    path: "/users/list".to_string(),
+                    method: "GET".to_string(),
+                    summary: Some("Literal route".to_string()),
+                    security: Vec::new(),
+                    path_params: Vec::new(),
+                },
+                DocumentedEndpoint {
+                    path: "/users/{id}".to_string(),
+                    method: "GET".to_string(),
+                    summary: Some("Param route".to_string()),
+                    security: Vec::new(),
+                    path_params: Vec::new(),
+                },
+            ],
+            title: "Test".to_string(),
+            version: "1.0".to_string(),
+            trie: PathTrieNode::default(),
+        };
+        spec.rebuild_trie();
+
+        assert_eq!(
+            spec.find_endpoint("/users/list", "GET").unwrap().summary,
+            Some("Literal route".to_string())
+        );
+        assert_eq!(
+            spec.find_endpoint("/users/42", "GET").unwrap().summary,
+            Some("Param route".to_string())
+        );
+    }
+
+    #[test]
+    fn test_trie_lookup_matches_catchall() {
+        let mut spec = OpenApiSpec::new("Test".to_string(), "1.0".to_string());
+        spec.endpoints.push(DocumentedEndpoint {
+            path: "/static/{rest:.*}".to_string(),
+            method: "GET".to_string(),
+            summary: None,
+            security: Vec::new(),
+            path_params: Vec::new(),
+        });
+        spec.rebuild_trie();
+
+        assert!(spec.matches_endpoint("/static/a/b/c", "GET"));
+        assert!(!spec.matches_endpoint("/static/a/b/c", "POST"));
+    }
+
+    #[test]
+    fn test_find_endpoints_by_path_returns_both_literal_and_param_matches() {
+        let mut spec = OpenApiSpec::new("Test".to_string(), "1.0".to_string());
+        spec.endpoints.push(DocumentedEndpoint {
+            path: "/users/list".to_string(),
+            method: "GET".to_string(),
+            summary: None,
+            security: Vec::new(),
+            path_params: Vec::new(),
+        });
+        spec.endpoints.push(DocumentedEndpoint {
+            path: "/users/{id}".to_string(),
+            method: "DELETE".to_string(),
+            summary: None,
+            security: Vec::new(),
+            path_params: Vec::new(),
+        });
+        spec.rebuild_trie();
+
+        assert_eq!(spec.find_endpoints_by_path("/users/list").len(), 2);
+    }
+
     #[test]
     fn test_path_pattern_to_regex() {
         let regex = path_pattern_to_regex("/users/{id}");
@@ -568,9 +1178,39 @@ paths: {}
     }
 
     #[test]
-    fn test_swagger_2_rejected() {
+    fn test_swagger_2_normalizes_onto_openapi_spec() {
+        let json = r#"{
+            "swagger": "2.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "basePath": "/api/v1",
+            "paths": {
+                "/users": {"get": {"summary": "List users"}},
+                "/users/{id}": {"delete": {}}
+            }
+        }"#;
+        let spec = parse_openapi_json(json).unwrap();
+        assert_eq!(spec.title, "Test");
+        assert_eq!(spec.version, "1.0");
+        assert!(spec.matches_endpoint("/api/v1/users", "GET"));
+        assert!(spec.matches_endpoint("/api/v1/users/42", "DELETE"));
+        assert!(!spec.matches_endpoint("/users", "GET"));
+    }
+
+    #[test]
+    fn test_swagger_2_without_base_path() {
+        let json = r#"{
+            "swagger": "2.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {"/users": {"get": {}}}
+        }"#;
+        let spec = parse_openapi_json(json).unwrap();
+        assert!(spec.matches_endpoint("/users", "GET"));
+    }
+
+    #[test]
+    fn test_swagger_unsupported_major_version_rejected() {
         let json =
-            r#"{"swagger": "2.0", "info": {"title": "Test", "version": "1.0"}, "paths": {}}"#;
+            r#"{"swagger": "1.2", "info": {"title": "Test", "version": "1.0"}, "paths": {}}"#;
         let result = parse_openapi_json(json);
         assert!(matches!(result, Err(OpenApiError::InvalidVersion(_))));
     }
@@ -591,6 +1231,31 @@ paths: {}
         assert_eq!(spec.version, "Unknown");
     }
 
+    #[test]
+    fn test_paths_match_catchall_matches_multiple_segments() {
+        assert!(paths_match("/files/{path:.*}", "/files/a/b/c"));
+        assert!(paths_match("/static/{proxy+}", "/static/css/app.css"));
+    }
+
+    #[test]
+    fn test_paths_match_catchall_matches_single_and_zero_segments() {
+        assert!(paths_match("/files/{path:.*}", "/files/a"));
+        // Greedy-optional: the spec author wrote `.*`, which also covers
+        // the zero-length tail.
+        assert!(paths_match("/files/{path:.*}", "/files"));
+    }
+
+    #[test]
+    fn test_paths_match_catchall_still_requires_prefix_literals() {
+        assert!(!paths_match("/files/{path:.*}", "/other/a/b"));
+    }
+
+    #[test]
+    fn test_path_pattern_to_regex_catchall() {
+        let regex = path_pattern_to_regex("/files/{path:.*}");
+        assert_eq!(regex, r"^/files/.*$");
+    }
+
     #[test]
     fn test_all_http_methods() {
         let json = r#"{
@@ -623,4 +1288,211 @@ paths: {}
         assert!(methods.contains(&"HEAD"));
         assert!(methods.contains(&"TRACE"));
     }
+
+    #[test]
+    fn test_operation_security_overrides_document_default() {
+        let json = r#"{
+            "openapi": "3.0.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "security": [{"apiKeyAuth": []}],
+            "components": {
+                "securitySchemes": {
+                    "apiKeyAuth": {"type": "apiKey"}
+                }
+            },
+            "paths": {
+                "/users": {
+                    "get": {}
+                },
+                "/users/{id}": {
+                    "delete": {"security": []}
+                }
+            }
+        }"#;
+
+        let spec = parse_openapi_json(json).unwrap();
+        let get_users = spec.find_endpoint("/users", "GET").unwrap();
+        assert_eq!(get_users.security, vec!["apiKeyAuth".to_string()]);
+
+        let delete_user = spec.find_endpoint("/users/1", "DELETE").unwrap();
+        assert!(delete_user.security.is_empty());
+    }
+
+    #[test]
+    fn test_unauthenticated_endpoints_surfaces_no_auth_operations() {
+        let json = r#"{
+            "openapi": "3.0.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {
+                "/users": {"get": {}},
+                "/admin/reset": {"post": {"security": [{"oauth2": ["admin"]}]}}
+            }
+        }"#;
+
+        let spec = parse_openapi_json(json).unwrap();
+        let unauthenticated = spec.unauthenticated_endpoints();
+        assert_eq!(unauthenticated.len(), 1);
+        assert_eq!(unauthenticated[0].path, "/users");
+    }
+
+    #[test]
+    fn test_parse_resolves_path_item_ref() {
+        let json = r#"{
+            "openapi": "3.0.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {
+                "/widgets": {"$ref": "#/components/pathItems/Widgets"}
+            },
+            "components": {
+                "pathItems": {
+                    "Widgets": {
+                        "get": {"summary": "List widgets"}
+                    }
+                }
+            }
+        }"#;
+
+        let spec = parse_openapi_json(json).unwrap();
+        assert!(spec.matches_endpoint("/widgets", "GET"));
+    }
+
+    #[test]
+    fn test_parse_resolves_operation_ref() {
+        let json = r#"{
+            "openapi": "3.0.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {
+                "/widgets": {
+                    "get": {"$ref": "#/components/operations/ListWidgets"}
+                }
+            },
+            "components": {
+                "operations": {
+                    "ListWidgets": {"summary": "List widgets"}
+                }
+            }
+        }"#;
+
+        let spec = parse_openapi_json(json).unwrap();
+        let endpoint = spec.find_endpoint("/widgets", "GET").unwrap();
+        assert_eq!(endpoint.summary, Some("List widgets".to_string()));
+    }
+
+    #[test]
+    fn test_parse_unresolvable_ref_surfaces_parse_error() {
+        let json = r#"{
+            "openapi": "3.0.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {
+                "/widgets": {"$ref": "#/components/pathItems/DoesNotExist"}
+            }
+        }"#;
+
+        let result = parse_openapi_json(json);
+        assert!(matches!(result, Err(OpenApiError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_parse_cyclic_ref_surfaces_parse_error() {
+        let json = r#"{
+            "openapi": "3.0.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {
+                "/widgets": {"$ref": "#/components/pathItems/A"}
+            },
+            "components": {
+                "pathItems": {
+                    "A": {"$ref": "#/components/pathItems/B"},
+                    "B": {"$ref": "#/components/pathItems/A"}
+                }
+            }
+        }"#;
+
+        let result = parse_openapi_json(json);
+        assert!(matches!(result, Err(OpenApiError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_non_integer_segment_for_integer_param() {
+        let json = r#"{
+            "openapi": "3.0.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {
+                "/users/{id}": {
+                    "get": {
+                        "parameters": [
+                            {"name": "id", "in": "path", "schema": {"type": "integer"}}
+                        ]
+                    }
+                }
+            }
+        }"#;
+
+        let spec = parse_openapi_json(json).unwrap();
+        // Loose matching stays permissive.
+        assert!(spec.matches_endpoint("/users/abc", "GET"));
+        // Strict matching enforces the declared integer type.
+        assert!(spec.matches_endpoint_strict("/users/42", "GET"));
+        assert!(!spec.matches_endpoint_strict("/users/abc", "GET"));
+    }
+
+    #[test]
+    fn test_strict_mode_enforces_pattern_constraint() {
+        let json = r#"{
+            "openapi": "3.0.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {
+                "/orders/{code}": {
+                    "get": {
+                        "parameters": [
+                            {"name": "code", "in": "path", "schema": {"pattern": "^ORD-[0-9]+$"}}
+                        ]
+                    }
+                }
+            }
+        }"#;
+
+        let spec = parse_openapi_json(json).unwrap();
+        assert!(spec.matches_endpoint_strict("/orders/ORD-123", "GET"));
+        assert!(!spec.matches_endpoint_strict("/orders/whatever", "GET"));
+    }
+
+    #[test]
+    fn test_strict_mode_stays_permissive_for_undeclared_params() {
+        let json = r#"{
+            "openapi": "3.0.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {
+                "/users/{id}": {"get": {}}
+            }
+        }"#;
+
+        let spec = parse_openapi_json(json).unwrap();
+        assert!(spec.matches_endpoint_strict("/users/anything", "GET"));
+    }
+
+    #[test]
+    fn test_path_level_parameter_applies_and_operation_level_overrides() {
+        let json = r#"{
+            "openapi": "3.0.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {
+                "/items/{id}": {
+                    "parameters": [
+                        {"name": "id", "in": "path", "schema": {"type": "integer"}}
+                    ],
+                    "get": {},
+                    "delete": {
+                        "parameters": [
+                            {"name": "id", "in": "path", "schema": {"type": "string"}}
+                        ]
+                    }
+                }
+            }
+        }"#;
+
+        let spec = parse_openapi_json(json).unwrap();
+        assert!(!spec.matches_endpoint_strict("/items/abc", "GET"));
+        assert!(spec.matches_endpoint_strict("/items/abc", "DELETE"));
+    }
 }
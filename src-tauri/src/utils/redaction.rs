@@ -0,0 +1,124 @@
+/// Char-boundary-safe redaction and byte-offset-to-line/column conversion
+/// for values pulled out of arbitrary response bodies. `detect_secrets`
+/// historically byte-sliced `matched_value` directly (`&matched[..4]`),
+/// which panics the instant a multibyte character (an emoji, accented
+/// name, non-Latin script) lands inside the slice boundary -- every
+/// function here works in `char`s instead so that can't happen.
+
+/// 1-indexed line/column position, the form editor integrations expect
+/// (most editors number both lines and columns starting at 1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineCol {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Keep the first and last `keep_chars` characters of `s`, replacing
+/// everything between with `...`. Falls back to `"***"` (matching the
+/// all-masked behavior callers already relied on) when `s` is too short to
+/// show any context without revealing the whole value.
+pub fn redact_middle(s: &str, keep_chars: usize) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.len() <= keep_chars * 2 {
+        return "***".to_string();
+    }
+    let prefix: String = chars[..keep_chars].iter().collect();
+    let suffix: String = chars[chars.len() - keep_chars..].iter().collect();
+    format!("{}...{}", prefix, suffix)
+}
+
+/// Keep only the first `keep_chars` characters of `s`, appending `...` to
+/// signal truncation. Used where a caller wants a short preview rather than
+/// a first/last masked pair (e.g. a high-entropy string whose value isn't a
+/// known secret format, just a candidate worth a glance).
+pub fn redact_prefix(s: &str, keep_chars: usize) -> String {
+    let prefix: String = s.chars().take(keep_chars).collect();
+    format!("{}...", prefix)
+}
+
+/// Convert a byte offset into `content` (as produced by `regex`'s
+/// `Match::start`/`end`, which are always byte offsets) into a 1-indexed
+/// line/column position. `byte_offset` is clamped to `content.len()` and
+/// then walked back to the nearest char boundary at or before it, so an
+/// offset that lands mid-character (which should never happen from a
+/// well-formed regex match, but may from hand-constructed offsets) can't
+/// panic.
+pub fn byte_offset_to_line_col(content: &str, byte_offset: usize) -> LineCol {
+    let mut boundary = byte_offset.min(content.len());
+    while boundary > 0 && !content.is_char_boundary(boundary) {
+        boundary -= 1;
+    }
+
+    let mut line = 1;
+    let mut column = 1;
+    for ch in content[..boundary].chars() {
+        if ch == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+
+    LineCol { line, column }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_middle_keeps_first_and_last_chars() {
+        assert_eq!(redact_middle("AKIA1234567890123456", 4), "AKIA...3456");
+    }
+
+    #[test]
+    fn test_redact_middle_masks_short_values_entirely() {
+        assert_eq!(redact_middle("short", 4), "***");
+    }
+
+    #[test]
+    fn test_redact_middle_is_char_boundary_safe_on_multibyte_input() {
+        // Each "é" is 2 bytes in UTF-8; byte-slicing at a fixed offset
+        // would either panic or cut a character in half.
+        let value = "éééé1234567890éééé";
+        let redacted = redact_middle(value, 4);
+        assert_eq!(redacted, "éééé...éééé");
+    }
+
+    #[test]
+    fn test_redact_prefix_truncates_by_char_not_byte() {
+        let value = "日本語のテキストは長いです";
+        let redacted = redact_prefix(value, 3);
+        assert_eq!(redacted, "日本語...");
+    }
+
+    #[test]
+    fn test_byte_offset_to_line_col_first_line() {
+        let content = "hello world";
+        let pos = byte_offset_to_line_col(content, 6);
+        assert_eq!(pos, LineCol { line: 1, column: 7 });
+    }
+
+    #[test]
+    fn test_byte_offset_to_line_col_crosses_newline() {
+        let content = "line one\nline two\nline three";
+        let pos = byte_offset_to_line_col(content, 14); // 't' in "two"
+        assert_eq!(pos, LineCol { line: 2, column: 6 });
+    }
+
+    #[test]
+    fn test_byte_offset_to_line_col_handles_multibyte_prefix() {
+        let content = "café\nrest of line";
+        // Offset just after "café" (4 chars, 5 bytes since é is 2 bytes).
+        let pos = byte_offset_to_line_col(content, 5);
+        assert_eq!(pos, LineCol { line: 1, column: 5 });
+    }
+
+    #[test]
+    fn test_byte_offset_to_line_col_clamps_out_of_range_offset() {
+        let content = "short";
+        let pos = byte_offset_to_line_col(content, 9999);
+        assert_eq!(pos, LineCol { line: 1, column: 6 });
+    }
+}
@@ -45,6 +45,10 @@ fn extract_single_variable(
             let json: serde_json::Value = serde_json::from_str(response_body).ok()?;
             extract_json_value(&json, source_path)
         }
+        "jsonpath" => {
+            let json: serde_json::Value = serde_json::from_str(response_body).ok()?;
+            extract_jsonpath_value(&json, source_path)
+        }
         "header" => {
             for line in response_headers.lines() {
                 if let Some((key, val)) = line.split_once(':') {
@@ -65,6 +69,47 @@ fn extract_single_variable(
     }
 }
 
+/// Automatically harvest CSRF defenses from a response so multi-step flows
+/// don't require the user to hand-author a `VariableCapture` for them:
+/// `<meta name="csrf-token" content="...">`, hidden form inputs whose name
+/// looks like a CSRF/anti-forgery field, and `Set-Cookie` headers. Mirrors
+/// how CSRF middleware round-trips a token between request and response.
+pub fn capture_csrf_defenses(
+    response_body: &str,
+    response_headers: &str,
+) -> HashMap<String, String> {
+    let mut captured = HashMap::new();
+
+    if let Ok(re) = Regex::new(
+        r#"(?i)<meta\s+name=["']csrf-token["']\s+content=["']([^"']+)["']"#,
+    ) {
+        if let Some(caps) = re.captures(response_body) {
+            captured.insert("csrf-token".to_string(), caps[1].to_string());
+        }
+    }
+
+    if let Ok(re) = Regex::new(
+        r#"(?i)<input[^>]+name=["'](csrf[\w-]*|_token|authenticity_token|__RequestVerificationToken)["'][^>]+value=["']([^"']*)["']"#,
+    ) {
+        for caps in re.captures_iter(response_body) {
+            captured.insert(caps[1].to_string(), caps[2].to_string());
+        }
+    }
+
+    for line in response_headers.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            if key.trim().eq_ignore_ascii_case("set-cookie") {
+                if let Some((name, rest)) = value.trim().split_once('=') {
+                    let cookie_value = rest.split(';').next().unwrap_or("").trim();
+                    captured.insert(name.trim().to_string(), cookie_value.to_string());
+                }
+            }
+        }
+    }
+
+    captured
+}
+
 fn extract_json_value(json: &serde_json::Value, path: &str) -> Option<String> {
     let mut current = json;
     for part in path.split('.') {
@@ -74,11 +119,56 @@ fn extract_json_value(json: &serde_json::Value, path: &str) -> Option<String> {
         current = current.get(part)?;
     }
 
-    match current {
+    json_value_to_string(current)
+}
+
+/// Like [`extract_json_value`], but each dotted segment may carry one or
+/// more `[N]` array indices (e.g. `data.items[0].id`), which plain object-key
+/// traversal can't reach.
+fn extract_jsonpath_value(json: &serde_json::Value, path: &str) -> Option<String> {
+    let mut current = json;
+    for segment in path.split('.') {
+        if segment.is_empty() {
+            continue;
+        }
+        let (key, indices) = split_key_and_indices(segment);
+        if !key.is_empty() {
+            current = current.get(key)?;
+        }
+        for index in indices {
+            current = current.get(index)?;
+        }
+    }
+
+    json_value_to_string(current)
+}
+
+/// Split a jsonpath segment like `items[0][1]` into its leading object key
+/// (`"items"`, possibly empty for a bare `[0]`) and its array indices in order.
+fn split_key_and_indices(segment: &str) -> (&str, Vec<usize>) {
+    let key_end = segment.find('[').unwrap_or(segment.len());
+    let (key, mut rest) = segment.split_at(key_end);
+
+    let mut indices = Vec::new();
+    while let Some(after_bracket) = rest.strip_prefix('[') {
+        let Some(close) = after_bracket.find(']') else {
+            break;
+        };
+        if let Ok(index) = after_bracket[..close].parse::<usize>() {
+            indices.push(index);
+        }
+        rest = &after_bracket[close + 1..];
+    }
+
+    (key, indices)
+}
+
+fn json_value_to_string(value: &serde_json::Value) -> Option<String> {
+    match value {
         serde_json::Value::String(s) => Some(s.clone()),
         serde_json::Value::Number(n) => Some(n.to_string()),
         serde_json::Value::Bool(b) => Some(b.to_string()),
-        _ => Some(current.to_string()),
+        _ => Some(value.to_string()),
     }
 }
 
@@ -105,4 +195,28 @@ mod tests {
         let val = extract_single_variable(&cap, body, "");
         assert_eq!(val, Some("123".to_string()));
     }
+
+    #[test]
+    fn test_jsonpath_extraction_with_array_index() {
+        let body = r#"{"data": {"items": [{"id": 1}, {"id": 2}]}}"#;
+        let cap = VariableCapture {
+            name: "second_id".to_string(),
+            source: "jsonpath:data.items[1].id".to_string(),
+            regex: None,
+        };
+        let val = extract_single_variable(&cap, body, "");
+        assert_eq!(val, Some("2".to_string()));
+    }
+
+    #[test]
+    fn test_jsonpath_extraction_bare_array_root() {
+        let body = r#"[{"token": "abc"}, {"token": "xyz"}]"#;
+        let cap = VariableCapture {
+            name: "token".to_string(),
+            source: "jsonpath:[0].token".to_string(),
+            regex: None,
+        };
+        let val = extract_single_variable(&cap, body, "");
+        assert_eq!(val, Some("abc".to_string()));
+    }
 }